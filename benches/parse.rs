@@ -0,0 +1,16 @@
+use citation::{Citation, CitationRef};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+fn parse(c: &mut Criterion) {
+    for name in ["CITATION.cff", "references.cff", "CITATION.json"] {
+        let content = std::fs::read_to_string(format!("./tests/{name}")).unwrap();
+
+        let mut group = c.benchmark_group(name);
+        group.bench_function("Citation", |b| b.iter(|| black_box(&content).parse::<Citation>().unwrap()));
+        group.bench_function("CitationRef", |b| b.iter(|| CitationRef::parse(black_box(&content)).unwrap()));
+        group.finish();
+    }
+}
+
+criterion_group!(benches, parse);
+criterion_main!(benches);
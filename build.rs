@@ -0,0 +1,155 @@
+//! Generates the `License` enum from the SPDX License List in `spdx/licenses.json`.
+//!
+//! The file follows the format of `json/licenses.json` from
+//! https://github.com/spdx/license-list-data and can be replaced by a newer release.
+
+use std::{collections::HashSet, env, fmt::Write, fs, path::Path};
+
+use serde_json::Value;
+
+const LICENSE_LIST: &str = "spdx/licenses.json";
+
+struct License {
+    id: String,
+    name: String,
+    variant: String,
+    deprecated: bool,
+    osi_approved: bool,
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed={LICENSE_LIST}");
+
+    let content = fs::read_to_string(LICENSE_LIST).expect("failed to read SPDX license list");
+    let list: Value = serde_json::from_str(&content).expect("failed to parse SPDX license list");
+
+    let version = list["licenseListVersion"].as_str().expect("missing `licenseListVersion`");
+    let mut variants = HashSet::new();
+    let licenses: Vec<License> = list["licenses"]
+        .as_array()
+        .expect("missing `licenses`")
+        .iter()
+        .map(|license| {
+            let id = license["licenseId"].as_str().expect("missing `licenseId`").to_string();
+            let variant = variant_name(&id);
+            assert!(variants.insert(variant.clone()), "duplicate variant `{variant}` for `{id}`");
+
+            License {
+                name: license["name"].as_str().expect("missing `name`").to_string(),
+                deprecated: license["isDeprecatedLicenseId"].as_bool().unwrap_or(false),
+                osi_approved: license["isOsiApproved"].as_bool().unwrap_or(false),
+                id,
+                variant,
+            }
+        })
+        .collect();
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("licenses.rs");
+    fs::write(out, generate(version, &licenses)).expect("failed to write generated licenses");
+}
+
+/// Turns an SPDX identifier into a valid Rust identifier, e.g. `GPL-2.0+` into `GPL_2_0_plus`.
+fn variant_name(id: &str) -> String {
+    let mut name = String::new();
+
+    if id.starts_with(|c: char| c.is_ascii_digit()) {
+        name.push('_');
+    }
+
+    for c in id.chars() {
+        match c {
+            '+' => name.push_str("_plus"),
+            c if c.is_ascii_alphanumeric() => name.push(c),
+            _ => name.push('_'),
+        }
+    }
+
+    name
+}
+
+fn generate(version: &str, licenses: &[License]) -> String {
+    let mut code = String::new();
+
+    writeln!(code, "/// A license identifier from the [SPDX License List](https://spdx.org/licenses/) (version {version}).").unwrap();
+    writeln!(code, "///").unwrap();
+    writeln!(code, "/// Identifiers that are not part of the list are kept in [`License::Other`].").unwrap();
+    writeln!(code, "#[allow(non_camel_case_types)]").unwrap();
+    writeln!(code, "#[derive(Debug, Clone, PartialEq, Eq, Hash)]").unwrap();
+    writeln!(code, "pub enum License {{").unwrap();
+    for license in licenses {
+        writeln!(code, "    /// {}", license.name.replace('[', "\\[").replace(']', "\\]")).unwrap();
+        if license.deprecated {
+            writeln!(code, "    #[deprecated(note = \"`{}` is a deprecated SPDX license identifier\")]", license.id).unwrap();
+        }
+        writeln!(code, "    {},", license.variant).unwrap();
+    }
+    writeln!(code, "    /// A license identifier that is not part of the SPDX License List.").unwrap();
+    writeln!(code, "    Other(String),").unwrap();
+    writeln!(code, "}}").unwrap();
+    writeln!(code).unwrap();
+
+    writeln!(code, "#[allow(deprecated)]").unwrap();
+    writeln!(code, "impl License {{").unwrap();
+    writeln!(code, "    /// The version of the SPDX License List the identifiers are taken from.").unwrap();
+    writeln!(code, "    pub const LIST_VERSION: &'static str = {version:?};").unwrap();
+    writeln!(code).unwrap();
+    writeln!(code, "    /// All licenses of the SPDX License List, including deprecated ones.").unwrap();
+    writeln!(code, "    pub const ALL: &'static [License] = &[").unwrap();
+    for license in licenses {
+        writeln!(code, "        License::{},", license.variant).unwrap();
+    }
+    writeln!(code, "    ];").unwrap();
+    writeln!(code).unwrap();
+
+    writeln!(code, "    /// Returns the SPDX identifier of the license, e.g. `MIT`.").unwrap();
+    writeln!(code, "    pub fn id(&self) -> &str {{").unwrap();
+    writeln!(code, "        match self {{").unwrap();
+    write_arms(&mut code, licenses, |license| format!("{:?}", license.id), "License::Other(id) => id");
+    writeln!(code, "        }}").unwrap();
+    writeln!(code, "    }}").unwrap();
+    writeln!(code).unwrap();
+
+    writeln!(code, "    /// Returns the full name of the license, or `None` if it is not part of the SPDX License List.").unwrap();
+    writeln!(code, "    pub fn name(&self) -> Option<&'static str> {{").unwrap();
+    writeln!(code, "        match self {{").unwrap();
+    write_arms(&mut code, licenses, |license| format!("Some({:?})", license.name), "License::Other(_) => None");
+    writeln!(code, "        }}").unwrap();
+    writeln!(code, "    }}").unwrap();
+    writeln!(code).unwrap();
+
+    writeln!(code, "    /// Returns `true` if the identifier is deprecated by the SPDX License List.").unwrap();
+    writeln!(code, "    pub fn is_deprecated(&self) -> bool {{").unwrap();
+    writeln!(code, "        match self {{").unwrap();
+    write_arms(&mut code, licenses, |license| license.deprecated.to_string(), "License::Other(_) => false");
+    writeln!(code, "        }}").unwrap();
+    writeln!(code, "    }}").unwrap();
+    writeln!(code).unwrap();
+
+    writeln!(code, "    /// Returns `true` if the license is approved by the Open Source Initiative.").unwrap();
+    writeln!(code, "    pub fn is_osi_approved(&self) -> bool {{").unwrap();
+    writeln!(code, "        match self {{").unwrap();
+    write_arms(&mut code, licenses, |license| license.osi_approved.to_string(), "License::Other(_) => false");
+    writeln!(code, "        }}").unwrap();
+    writeln!(code, "    }}").unwrap();
+    writeln!(code).unwrap();
+
+    writeln!(code, "    fn from_known_id(id: &str) -> Option<Self> {{").unwrap();
+    writeln!(code, "        match id {{").unwrap();
+    for license in licenses {
+        writeln!(code, "            {:?} => Some(License::{}),", license.id, license.variant).unwrap();
+    }
+    writeln!(code, "            _ => None,").unwrap();
+    writeln!(code, "        }}").unwrap();
+    writeln!(code, "    }}").unwrap();
+    writeln!(code, "}}").unwrap();
+
+    code
+}
+
+/// Writes one match arm per license, followed by the arm for `License::Other`.
+fn write_arms(code: &mut String, licenses: &[License], value: impl Fn(&License) -> String, other: &str) {
+    for license in licenses {
+        writeln!(code, "            License::{} => {},", license.variant, value(license)).unwrap();
+    }
+    writeln!(code, "            {other},").unwrap();
+}
@@ -0,0 +1,91 @@
+#ifndef CITATION_H
+#define CITATION_H
+
+/* Generated by cbindgen from src/capi.rs, do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// A parsed `CITATION.cff` file, created by `citation_parse` and released by `citation_free`.
+typedef struct CitationHandle CitationHandle;
+
+
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Parses the content of a `CITATION.cff` file, in YAML or JSON, from a buffer of `length` bytes of UTF-8, which
+// does not need to end with a null byte.
+//
+// Returns `NULL` if the content cannot be parsed, or if parsing fails unexpectedly, and stores a description of the
+// problem in `*error` if `error` is not `NULL`.
+//
+// # Safety
+//
+// `buffer` must point to `length` readable bytes, and `error` must be `NULL` or point to a writable `char *`.
+struct CitationHandle *citation_parse(const uint8_t *buffer,
+                                      size_t length,
+                                      char **error);
+
+// Validates the citation and returns the report as JSON, with whether the citation is valid, the number of issues
+// per severity and every issue with its code, path, message, help and location. Returns `NULL` if `citation` is
+// `NULL`.
+//
+// # Safety
+//
+// `citation` must be `NULL` or a citation returned by `citation_parse` that has not been released.
+char *citation_validate(const struct CitationHandle *citation);
+
+// Converts the citation to a BibTeX entry, or returns `NULL` if `citation` is `NULL` or the conversion fails
+// unexpectedly.
+//
+// # Safety
+//
+// `citation` must be `NULL` or a citation returned by `citation_parse` that has not been released.
+char *citation_to_bibtex(const struct CitationHandle *citation);
+
+// Formats the citation in a style, one of `apa`, `ieee`, `chicago`, `mla`, `harvard` and `vancouver`, as `plain`
+// text, `markdown`, `html` or `latex`. Returns `NULL` if there is no such style or output format.
+//
+// # Safety
+//
+// `citation` must be `NULL` or a citation returned by `citation_parse` that has not been released, and `style` and
+// `output` must be `NULL` or null-terminated strings.
+char *citation_format(const struct CitationHandle *citation,
+                      const char *style,
+                      const char *output);
+
+// Converts the citation to another format, named like the formats of `citation convert`: `cff`, `bibtex`,
+// `biblatex`, `ris`, `csl-json`, `codemeta`, `zenodo`, `json-ld`, `datacite-xml`, `datacite-json`,
+// `dublin-core` or `endnote`. Returns `NULL` if there is no such format or the conversion fails.
+//
+// # Safety
+//
+// `citation` must be `NULL` or a citation returned by `citation_parse` that has not been released, and `to` must
+// be `NULL` or a null-terminated string.
+char *citation_convert(const struct CitationHandle *citation,
+                       const char *to);
+
+// Releases a citation returned by `citation_parse`. Does nothing if `citation` is `NULL`.
+//
+// # Safety
+//
+// `citation` must be `NULL` or a citation returned by `citation_parse` that has not been released.
+void citation_free(struct CitationHandle *citation);
+
+// Releases a string returned by one of the functions. Does nothing if `string` is `NULL`.
+//
+// # Safety
+//
+// `string` must be `NULL` or a string returned by one of the functions that has not been released.
+void citation_string_free(char *string);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* CITATION_H */
@@ -0,0 +1,332 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::Date;
+
+/// A natural person, as defined by `definitions.person` in the schema.
+///
+/// None of the name parts are required, so mononymous persons can be described
+/// with only `given-names` or only `family-names`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[serde(rename_all = "kebab-case")]
+pub struct Person {
+    /// The person's address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+
+    /// The person's affiliation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub affiliation: Option<String>,
+
+    /// The person's alias.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+
+    /// The person's city.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+
+    /// The person's country (ISO 3166-1 alpha-2 code).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+
+    /// The person's email address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+
+    /// The person's family names.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub family_names: Option<String>,
+
+    /// The person's fax number.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fax: Option<String>,
+
+    /// The person's given names.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub given_names: Option<String>,
+
+    /// The person's name particle, e.g., a nobiliary particle or a preposition meaning 'of' or 'from' (for example 'von' in 'Alexander von Humboldt').
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name_particle: Option<String>,
+
+    /// The person's name-suffix, e.g. 'Jr.' for Sammy Davis Jr. or 'III' for Frank Edwin Wright III.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name_suffix: Option<String>,
+
+    /// The person's ORCID identifier, which is serialized as a URL if it is valid.
+    #[serde(skip_serializing_if = "Option::is_none", serialize_with = "crate::orcid::canonical")]
+    pub orcid: Option<String>,
+
+    /// The person's post-code.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_code: Option<String>,
+
+    /// The person's region.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+
+    /// The person's phone number.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tel: Option<String>,
+
+    /// The person's website.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub website: Option<String>,
+
+    /// Keys that are not defined by the schema.
+    #[serde(flatten)]
+    #[cfg_attr(feature = "schemars", schemars(skip))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    pub extra: BTreeMap<String, serde_yaml::Value>,
+}
+
+/// An entity, i.e., an institution, team, research group, company, conference, etc., as opposed to a single natural person.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[serde(rename_all = "kebab-case")]
+pub struct Entity {
+    /// The name of the entity.
+    pub name: String,
+
+    /// The entity's address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+
+    /// The entity's alias.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+
+    /// The entity's city.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+
+    /// The entity's country (ISO 3166-1 alpha-2 code).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+
+    /// The entity's ending date, e.g., when the entity is a conference.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_end: Option<Date>,
+
+    /// The entity's starting date, e.g., when the entity is a conference.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_start: Option<Date>,
+
+    /// The entity's email address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+
+    /// The entity's fax number.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fax: Option<String>,
+
+    /// The entity's location, e.g., when the entity is a conference.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+
+    /// The entity's ORCID identifier, which is serialized as a URL if it is valid.
+    #[serde(skip_serializing_if = "Option::is_none", serialize_with = "crate::orcid::canonical")]
+    pub orcid: Option<String>,
+
+    /// The entity's post code.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_code: Option<String>,
+
+    /// The entity's region.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+
+    /// The entity's telephone number.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tel: Option<String>,
+
+    /// The entity's website.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub website: Option<String>,
+
+    /// Keys that are not defined by the schema.
+    #[serde(flatten)]
+    #[cfg_attr(feature = "schemars", schemars(skip))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    pub extra: BTreeMap<String, serde_yaml::Value>,
+}
+
+impl Entity {
+    /// Returns the name of the entity.
+    pub fn display_name(&self) -> String {
+        self.name.clone()
+    }
+}
+
+impl Person {
+    /// Creates a person from a full name such as `Luca Lewin`, treating the last word as the family name.
+    ///
+    /// Single names are taken as given names, as the schema suggests for mononyms.
+    pub fn from_name(name: &str) -> Self {
+        let name = name.trim();
+        let (given_names, family_names) = match name.rsplit_once(' ') {
+            Some((given, family)) => (Some(given.trim_end().to_string()), Some(family.to_string())),
+            None if name.is_empty() => (None, None),
+            None => (Some(name.to_string()), None),
+        };
+
+        Person { given_names, family_names, ..Default::default() }
+    }
+
+    /// Assembles the full name of the person, e.g. `Alexander von Humboldt Jr.`
+    ///
+    /// Falls back to the alias if the person has no name parts at all.
+    pub fn display_name(&self) -> String {
+        let parts = [&self.given_names, &self.name_particle, &self.family_names, &self.name_suffix];
+        let name = parts
+            .into_iter()
+            .flatten()
+            .map(|part| part.trim())
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        match (&self.alias, name.is_empty()) {
+            (Some(alias), true) => alias.clone(),
+            _ => name,
+        }
+    }
+}
+
+/// An author of a work, which is either a natural person or an entity such as a research group.
+///
+/// Like in the schema, authors with a `name` key are entities and all others are persons.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[serde(untagged)]
+pub enum Author {
+    Entity(Entity),
+    Person(Person)
+}
+
+impl Author {
+    /// Returns the name under which the author should be displayed.
+    pub fn display_name(&self) -> String {
+        match self {
+            Author::Entity(entity) => entity.display_name(),
+            Author::Person(person) => person.display_name(),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Author {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Fields::deserialize(deserializer).map(Author::from)
+    }
+}
+
+/// The keys of persons and entities, which are read in one pass (instead of trying each variant on buffered
+/// content) so that errors keep the path and location of the offending key, e.g. `authors[2].date-start`.
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case", expecting = "a person or an entity")]
+struct Fields {
+    #[serde(default, deserialize_with = "required")]
+    name: Option<String>,
+    address: Option<String>,
+    affiliation: Option<String>,
+    alias: Option<String>,
+    city: Option<String>,
+    country: Option<String>,
+    date_end: Option<Date>,
+    date_start: Option<Date>,
+    email: Option<String>,
+    family_names: Option<String>,
+    fax: Option<String>,
+    given_names: Option<String>,
+    location: Option<String>,
+    name_particle: Option<String>,
+    name_suffix: Option<String>,
+    orcid: Option<String>,
+    post_code: Option<String>,
+    region: Option<String>,
+    tel: Option<String>,
+    website: Option<String>,
+    #[serde(flatten)]
+    extra: BTreeMap<String, serde_yaml::Value>,
+}
+
+/// Deserializes the `name` of an entity without treating `null` as missing, as its presence makes the author an entity.
+fn required<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    String::deserialize(deserializer).map(Some)
+}
+
+/// Keeps a key that is not defined for the kind of author with the keys that are not defined by the schema.
+fn unknown<T: Serialize>(extra: &mut BTreeMap<String, serde_yaml::Value>, key: &str, value: Option<T>) {
+    if let Some(value) = value.and_then(|value| serde_yaml::to_value(value).ok()) {
+        extra.insert(key.to_string(), value);
+    }
+}
+
+impl From<Fields> for Author {
+    fn from(fields: Fields) -> Self {
+        let mut extra = fields.extra;
+
+        match fields.name {
+            Some(name) => {
+                unknown(&mut extra, "affiliation", fields.affiliation);
+                unknown(&mut extra, "family-names", fields.family_names);
+                unknown(&mut extra, "given-names", fields.given_names);
+                unknown(&mut extra, "name-particle", fields.name_particle);
+                unknown(&mut extra, "name-suffix", fields.name_suffix);
+
+                Author::Entity(Entity {
+                    name,
+                    address: fields.address,
+                    alias: fields.alias,
+                    city: fields.city,
+                    country: fields.country,
+                    date_end: fields.date_end,
+                    date_start: fields.date_start,
+                    email: fields.email,
+                    fax: fields.fax,
+                    location: fields.location,
+                    orcid: fields.orcid,
+                    post_code: fields.post_code,
+                    region: fields.region,
+                    tel: fields.tel,
+                    website: fields.website,
+                    extra,
+                })
+            }
+            None => {
+                unknown(&mut extra, "date-end", fields.date_end);
+                unknown(&mut extra, "date-start", fields.date_start);
+                unknown(&mut extra, "location", fields.location);
+
+                Author::Person(Person {
+                    address: fields.address,
+                    affiliation: fields.affiliation,
+                    alias: fields.alias,
+                    city: fields.city,
+                    country: fields.country,
+                    email: fields.email,
+                    family_names: fields.family_names,
+                    fax: fields.fax,
+                    given_names: fields.given_names,
+                    name_particle: fields.name_particle,
+                    name_suffix: fields.name_suffix,
+                    orcid: fields.orcid,
+                    post_code: fields.post_code,
+                    region: fields.region,
+                    tel: fields.tel,
+                    website: fields.website,
+                    extra,
+                })
+            }
+        }
+    }
+}
+
+/// A contact person or entity for the work. The schema uses the same definitions as for authors.
+pub type Contact = Author;
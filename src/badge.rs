@@ -0,0 +1,144 @@
+use crate::{xml::escape, Citation};
+
+/// The URL of the static badges of shields.io.
+const SHIELDS_URL: &str = "https://img.shields.io/badge";
+
+/// A badge for a README or a website that shows the DOI of a work or asks to cite it, see [`Citation::badge`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Badge {
+    /// The text on the left, e.g. `DOI`.
+    pub label: String,
+
+    /// The text on the right, e.g. `10.5281/zenodo.1003150`.
+    pub message: String,
+
+    /// The color of the right side, either a named color of shields.io such as `blue` or a hex color such as
+    /// `007ec6`.
+    pub color: String,
+
+    /// The page the badge links to, e.g. the landing page of the DOI.
+    pub link: Option<String>,
+}
+
+/// Escapes a text for the path of a static badge, where `-` and `_` separate the parts and stand for spaces.
+fn escape_path(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '-' => escaped.push_str("--"),
+            '_' => escaped.push_str("__"),
+            ' ' => escaped.push('_'),
+            c if c.is_ascii_alphanumeric() || matches!(c, '.' | '~') => escaped.push(c),
+            c => {
+                let mut buffer = [0; 4];
+                for byte in c.encode_utf8(&mut buffer).bytes() {
+                    escaped.push_str(&format!("%{byte:02X}"));
+                }
+            }
+        }
+    }
+
+    escaped
+}
+
+/// Returns the hex code of a color, resolving the named colors of shields.io.
+fn hex(color: &str) -> String {
+    let hex = match color {
+        "brightgreen" | "success" => "4c1",
+        "green" => "97ca00",
+        "yellowgreen" => "a4a61d",
+        "yellow" => "dfb317",
+        "orange" | "important" => "fe7d37",
+        "red" | "critical" => "e05d44",
+        "blue" | "informational" => "007ec6",
+        "lightgrey" | "lightgray" | "inactive" => "9f9f9f",
+        "grey" | "gray" => "555",
+        color => color.trim_start_matches('#'),
+    };
+
+    format!("#{hex}")
+}
+
+/// Estimates the width of a text in Verdana at 11px, the font of the badges, from the widths of common characters.
+fn width(text: &str) -> u32 {
+    let width: f32 = text
+        .chars()
+        .map(|c| match c {
+            'i' | 'j' | 'l' | '.' | ',' | ':' | ';' | '!' | '|' | '\'' => 3.5,
+            ' ' | 'f' | 'r' | 't' | '/' | '(' | ')' | '[' | ']' | '-' => 4.5,
+            'm' | 'w' => 10.5,
+            'M' | 'W' => 11.5,
+            'A'..='Z' => 7.5,
+            _ => 7.0,
+        })
+        .sum();
+
+    width.ceil() as u32
+}
+
+impl Badge {
+    /// Returns the URL of the badge on [shields.io](https://shields.io), e.g.
+    /// `https://img.shields.io/badge/DOI-10.5281%2Fzenodo.1003150-blue`.
+    pub fn url(&self) -> String {
+        format!("{SHIELDS_URL}/{}-{}-{}", escape_path(&self.label), escape_path(&self.message), escape_path(&self.color))
+    }
+
+    /// Returns the badge as a Markdown image, linked to [`Badge::link`] if there is one.
+    pub fn to_markdown(&self) -> String {
+        let image = format!("![{}]({})", self.alt(), self.url());
+
+        match &self.link {
+            Some(link) => format!("[{image}]({link})"),
+            None => image,
+        }
+    }
+
+    /// Renders the badge as an SVG image in the flat style of shields.io, e.g. to embed it in a website without
+    /// requesting it from shields.io. The widths of the texts are estimated, so they may differ slightly from the
+    /// badge served by shields.io.
+    pub fn to_svg(&self) -> String {
+        let (label, message) = (width(&self.label) + 10, width(&self.message) + 10);
+        let total = label + message;
+        let alt = escape(&self.alt());
+        let color = escape(&hex(&self.color));
+
+        let mut svg = format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{total}\" height=\"20\" role=\"img\" aria-label=\"{alt}\">");
+        svg.push_str(&format!("<title>{alt}</title>"));
+        svg.push_str("<linearGradient id=\"s\" x2=\"0\" y2=\"100%\"><stop offset=\"0\" stop-color=\"#bbb\" stop-opacity=\".1\"/><stop offset=\"1\" stop-opacity=\".1\"/></linearGradient>");
+        svg.push_str(&format!("<clipPath id=\"r\"><rect width=\"{total}\" height=\"20\" rx=\"3\" fill=\"#fff\"/></clipPath>"));
+        svg.push_str(&format!(
+            "<g clip-path=\"url(#r)\"><rect width=\"{label}\" height=\"20\" fill=\"#555\"/><rect x=\"{label}\" width=\"{message}\" height=\"20\" fill=\"{color}\"/><rect width=\"{total}\" height=\"20\" fill=\"url(#s)\"/></g>"
+        ));
+        svg.push_str("<g fill=\"#fff\" text-anchor=\"middle\" font-family=\"Verdana,Geneva,DejaVu Sans,sans-serif\" font-size=\"11\">");
+        svg.push_str(&format!("<text x=\"{}\" y=\"14\">{}</text>", label as f32 / 2.0, escape(&self.label)));
+        svg.push_str(&format!("<text x=\"{}\" y=\"14\">{}</text>", label as f32 + message as f32 / 2.0, escape(&self.message)));
+        svg.push_str("</g></svg>");
+
+        match &self.link {
+            Some(link) => format!("<a href=\"{}\">{svg}</a>", escape(link)),
+            None => svg,
+        }
+    }
+
+    /// The text of the badge, used as alternative text, e.g. `DOI: 10.5281/zenodo.1003150`.
+    fn alt(&self) -> String {
+        format!("{}: {}", self.label, self.message)
+    }
+}
+
+impl Citation {
+    /// Creates a badge for the citation: `DOI | 10.5281/zenodo.1003150` linked to the landing page of the DOI if the
+    /// citation has one, from `doi` or from `identifiers`, or `cite | this software` linked to the repository or the
+    /// website of the work otherwise.
+    pub fn badge(&self) -> Badge {
+        match self.find_doi() {
+            Some(doi) => Badge { label: "DOI".to_string(), link: Some(format!("https://doi.org/{doi}")), message: doi, color: "blue".to_string() },
+            None => Badge {
+                label: "cite".to_string(),
+                message: "this software".to_string(),
+                color: "blue".to_string(),
+                link: self.repository_code.clone().or(self.url.clone()).or(self.repository.clone()),
+            },
+        }
+    }
+}
@@ -0,0 +1,56 @@
+//! Validation of many citation files in parallel, e.g. of every repository in a large tree.
+//!
+//! ```no_run
+//! let paths = ["a/CITATION.cff", "b/CITATION.cff"];
+//!
+//! for (path, outcome) in citation::batch::validate_all(paths) {
+//!     match outcome {
+//!         Ok(report) => println!("{}: {report}", path.display()),
+//!         Err(error) => println!("{}: error: {error}", path.display()),
+//!     }
+//! }
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use rayon::prelude::*;
+
+use crate::{lint::Linter, parse::parse_file, Error, ParseOptions, ValidationReport};
+
+/// Reads, parses and validates the files at the given paths in parallel.
+///
+/// The results are in the order of the paths, regardless of which file is validated first. Every report has its
+/// source attached, see [`ValidationReport::with_source`], and files that cannot be read or parsed have an error
+/// instead of a report.
+pub fn validate_all<I>(paths: I) -> Vec<(PathBuf, Result<ValidationReport, Error>)>
+where
+    I: IntoIterator,
+    I::Item: Into<PathBuf>,
+{
+    validate_all_with(paths, &ParseOptions::default(), &Linter::default())
+}
+
+/// Like [`validate_all`], but parses the files with the given options and applies the configuration of the linter,
+/// including its custom rules.
+pub fn validate_all_with<I>(paths: I, options: &ParseOptions, linter: &Linter) -> Vec<(PathBuf, Result<ValidationReport, Error>)>
+where
+    I: IntoIterator,
+    I::Item: Into<PathBuf>,
+{
+    let paths: Vec<PathBuf> = paths.into_iter().map(Into::into).collect();
+
+    paths
+        .into_par_iter()
+        .map(|path| {
+            let outcome = validate(&path, options, linter);
+            (path, outcome)
+        })
+        .collect()
+}
+
+fn validate(path: &Path, options: &ParseOptions, linter: &Linter) -> Result<ValidationReport, Error> {
+    let content = std::fs::read_to_string(path)?;
+    let citation = parse_file(path, &content, options)?;
+
+    Ok(linter.lint(&citation).with_source(path.display().to_string(), content))
+}
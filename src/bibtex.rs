@@ -0,0 +1,991 @@
+use std::{collections::HashMap, error::Error, fmt};
+
+use crate::{Author, Citation, Date, Entity, Identifier, IntOrString, License, Licenses, Person, Reference, ReferenceType, Type};
+
+/// A BibTeX entry, e.g. `@software{lewin2024, ...}`.
+pub(crate) struct Entry {
+    kind: &'static str,
+    key: String,
+    /// The fields with their values as written after the `=`, i.e. braced text or a macro.
+    fields: Vec<(&'static str, String)>,
+}
+
+impl Entry {
+    pub(crate) fn new(kind: &'static str, key: String) -> Self {
+        Entry { kind, key, fields: Vec::new() }
+    }
+
+    /// Adds a field with a value that is written verbatim between braces.
+    pub(crate) fn raw(&mut self, name: &'static str, value: Option<String>) {
+        if let Some(value) = value.filter(|value| !value.trim().is_empty()) {
+            self.fields.push((name, format!("{{{value}}}")));
+        }
+    }
+
+    /// Adds a field with plain text, escaping the characters that are special to LaTeX.
+    pub(crate) fn text(&mut self, name: &'static str, value: Option<&str>) {
+        self.raw(name, value.map(escape));
+    }
+
+    /// Adds a field with a list of names, see [`names`].
+    pub(crate) fn names(&mut self, name: &'static str, authors: &[Author]) {
+        self.raw(name, (!authors.is_empty()).then(|| names(authors)));
+    }
+
+    /// Adds a field with a value that is written without braces, e.g. the `jan` month macro.
+    pub(crate) fn bare(&mut self, name: &'static str, value: Option<String>) {
+        if let Some(value) = value {
+            self.fields.push((name, value));
+        }
+    }
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "@{}{{{},", self.kind, self.key)?;
+        for (name, value) in &self.fields {
+            writeln!(f, "  {name} = {value},")?;
+        }
+        writeln!(f, "}}")
+    }
+}
+
+impl Citation {
+    /// Formats the citation as a BibTeX entry.
+    ///
+    /// If the citation has a `preferred-citation`, that reference is formatted instead, see
+    /// [`Reference::to_bibtex`]. Otherwise a `@software` entry is created, or `@misc` for datasets.
+    pub fn to_bibtex(&self) -> String {
+        if let Some(reference) = &self.preferred_citation {
+            return reference.to_bibtex();
+        }
+
+        let kind = match self.r#type {
+            Some(Type::Dataset) => "misc",
+            _ => "software",
+        };
+
+        let mut entry = Entry::new(kind, key(&self.authors, self.date_released.map(|date| date.year().to_string()).as_deref()));
+        entry.names("author", &self.authors);
+        entry.raw("title", Some(protect(&self.title)));
+        entry.text("year", self.date_released.map(|date| date.year().to_string()).as_deref());
+        entry.bare("month", self.date_released.map(|date| month(date.month()).to_string()));
+        entry.text("version", self.version.as_deref());
+        entry.text("doi", self.find_doi().as_deref());
+        entry.text("url", self.repository_code.as_deref().or(self.url.as_deref()));
+
+        entry.to_string()
+    }
+
+    /// Formats the citation as a biblatex entry.
+    ///
+    /// Unlike [`Citation::to_bibtex`], this uses the entry types and fields of biblatex, e.g.
+    /// `@software` and `@dataset` with a full `date`. If the citation has a `preferred-citation`,
+    /// that reference is formatted instead, see [`Reference::to_biblatex`].
+    pub fn to_biblatex(&self) -> String {
+        if let Some(reference) = &self.preferred_citation {
+            return reference.to_biblatex();
+        }
+
+        let kind = match self.r#type {
+            Some(Type::Dataset) => "dataset",
+            _ => "software",
+        };
+
+        let mut entry = Entry::new(kind, key(&self.authors, self.date_released.map(|date| date.year().to_string()).as_deref()));
+        entry.names("author", &self.authors);
+        entry.raw("title", Some(protect(&self.title)));
+        entry.text("date", self.date_released.map(|date| date.to_string()).as_deref());
+        entry.text("version", self.version.as_deref());
+        entry.text("doi", self.find_doi().as_deref());
+        entry.text("url", self.repository_code.as_deref().or(self.url.as_deref()));
+        entry.text("keywords", (!self.keywords.is_empty()).then(|| self.keywords.join(", ")).as_deref());
+
+        entry.to_string()
+    }
+
+    /// Returns the DOI of the work, from `doi` or from the first DOI in `identifiers`.
+    pub(crate) fn find_doi(&self) -> Option<String> {
+        self.doi.as_ref().map(|doi| doi.to_string()).or_else(|| {
+            self.identifiers.iter().find_map(|identifier| match identifier {
+                Identifier::Doi { value, .. } => Some(value.clone()),
+                _ => None,
+            })
+        })
+    }
+}
+
+impl Reference {
+    /// Formats the reference as a BibTeX entry.
+    ///
+    /// The entry type is derived from the type of the reference, e.g. `@article` for articles and
+    /// `@inproceedings` for conference papers. Types without a BibTeX equivalent become `@misc`.
+    pub fn to_bibtex(&self) -> String {
+        let kind = match &self.r#type {
+            ReferenceType::Article | ReferenceType::MagazineArticle | ReferenceType::NewspaperArticle => "article",
+            ReferenceType::Book | ReferenceType::EditedWork => "book",
+            ReferenceType::ConferencePaper => "inproceedings",
+            ReferenceType::Proceedings => "proceedings",
+            ReferenceType::Manual => "manual",
+            ReferenceType::Pamphlet => "booklet",
+            ReferenceType::Report => "techreport",
+            ReferenceType::Thesis if self.thesis_type.as_deref().is_some_and(is_masters) => "mastersthesis",
+            ReferenceType::Thesis => "phdthesis",
+            ReferenceType::Unpublished => "unpublished",
+            ReferenceType::Software
+            | ReferenceType::SoftwareCode
+            | ReferenceType::SoftwareContainer
+            | ReferenceType::SoftwareExecutable
+            | ReferenceType::SoftwareVirtualMachine => "software",
+            _ => "misc",
+        };
+
+        let year = self.year.as_deref().map(String::from).or_else(|| self.date_published.map(|date| date.year().to_string()));
+        let month_number = self.month.as_deref().and_then(|month| month.parse().ok()).or(self.date_published.map(|date| date.month()));
+
+        let mut entry = Entry::new(kind, key(&self.authors, year.as_deref()));
+        entry.names("author", &self.authors);
+        entry.names("editor", &self.editors);
+        entry.raw("title", Some(protect(&self.title)));
+
+        let conference = self.conference.as_ref().map(|conference| conference.name.as_str());
+        match kind {
+            "article" => entry.text("journal", self.journal.as_deref()),
+            "inproceedings" => entry.raw("booktitle", self.collection_title.as_deref().or(conference).map(protect)),
+            "book" => entry.raw("series", self.collection_title.as_deref().map(protect)),
+            _ => {}
+        }
+
+        match kind {
+            "phdthesis" | "mastersthesis" => entry.text("school", self.institution.as_ref().map(|institution| institution.name.as_str())),
+            "techreport" => entry.text("institution", self.institution.as_ref().map(|institution| institution.name.as_str())),
+            _ => entry.text("publisher", self.publisher.as_ref().map(|publisher| publisher.name.as_str())),
+        }
+
+        entry.text("edition", self.edition.as_deref());
+        entry.text("volume", self.volume.as_deref());
+        entry.text("number", self.issue.as_deref().or(self.number.as_deref()));
+        entry.text("pages", pages(self).as_deref());
+        entry.text("year", year.as_deref());
+        entry.bare("month", month_number.filter(|month| (1..=12).contains(month)).map(|month| self::month(month).to_string()));
+        entry.text("isbn", self.isbn.as_deref());
+        entry.text("issn", self.issn.as_deref());
+        entry.text("version", self.version.as_deref());
+        entry.text("doi", self.doi.as_ref().map(|doi| doi.as_str()));
+        entry.text("url", self.url.as_deref().or(self.repository_code.as_deref()));
+        entry.text("note", self.notes.as_deref());
+
+        entry.to_string()
+    }
+
+    /// Formats the reference as a biblatex entry.
+    ///
+    /// The entry type is derived from the type of the reference, e.g. `@article`, `@thesis` or
+    /// `@software`. Types without a biblatex equivalent become `@misc`. Institutions of works other
+    /// than theses and reports are mapped to `organization`.
+    pub fn to_biblatex(&self) -> String {
+        let (kind, subtype) = match &self.r#type {
+            ReferenceType::Article | ReferenceType::MagazineArticle | ReferenceType::NewspaperArticle => ("article", None),
+            ReferenceType::Book | ReferenceType::EditedWork => ("book", None),
+            ReferenceType::ConferencePaper => ("inproceedings", None),
+            ReferenceType::Proceedings => ("proceedings", None),
+            ReferenceType::Manual => ("manual", None),
+            ReferenceType::Pamphlet => ("booklet", None),
+            ReferenceType::Report => ("report", Some("techreport")),
+            ReferenceType::Thesis if self.thesis_type.as_deref().is_some_and(is_masters) => ("thesis", Some("mathesis")),
+            ReferenceType::Thesis => ("thesis", Some("phdthesis")),
+            ReferenceType::Unpublished => ("unpublished", None),
+            ReferenceType::Patent => ("patent", None),
+            ReferenceType::Data | ReferenceType::Database => ("dataset", None),
+            ReferenceType::Website | ReferenceType::Blog => ("online", None),
+            ReferenceType::Software
+            | ReferenceType::SoftwareCode
+            | ReferenceType::SoftwareContainer
+            | ReferenceType::SoftwareExecutable
+            | ReferenceType::SoftwareVirtualMachine => ("software", None),
+            _ => ("misc", None),
+        };
+
+        let year = self.year.as_deref().map(String::from).or_else(|| self.date_published.map(|date| date.year().to_string()));
+        let month = self.month.as_deref().and_then(|month| month.parse::<u8>().ok()).filter(|month| (1..=12).contains(month));
+        let date = match (self.date_published, &self.year, month) {
+            (Some(date), None, None) => Some(date.to_string()),
+            (_, _, Some(month)) => year.as_ref().map(|year| format!("{year}-{month:02}")),
+            _ => year.clone(),
+        };
+
+        let name = |entity: &Option<Entity>| entity.as_ref().map(|entity| entity.name.clone());
+
+        let mut entry = Entry::new(kind, key(&self.authors, year.as_deref()));
+        entry.names("author", &self.authors);
+        entry.names("editor", &self.editors);
+        entry.names("translator", &self.translators);
+        entry.raw("title", Some(protect(&self.title)));
+        entry.text("type", subtype);
+
+        match kind {
+            "article" => entry.text("journaltitle", self.journal.as_deref()),
+            "inproceedings" => entry.raw("booktitle", self.collection_title.clone().or(name(&self.conference)).as_deref().map(protect)),
+            "book" => entry.raw("series", self.collection_title.as_deref().map(protect)),
+            _ => {}
+        }
+        if kind == "inproceedings" {
+            entry.text("eventtitle", name(&self.conference).as_deref());
+        }
+        match kind {
+            "thesis" | "report" => entry.text("institution", name(&self.institution).as_deref()),
+            _ => entry.text("organization", name(&self.institution).as_deref()),
+        }
+
+        entry.text("publisher", name(&self.publisher).as_deref());
+        entry.text("location", name(&self.location).as_deref());
+        entry.text("edition", self.edition.as_deref());
+        entry.text("volume", self.volume.as_deref());
+        entry.text("number", self.issue.as_deref().or(self.number.as_deref()));
+        entry.text("pages", pages(self).as_deref());
+        entry.text("date", date.as_deref());
+        entry.text("isbn", self.isbn.as_deref());
+        entry.text("issn", self.issn.as_deref());
+        entry.text("version", self.version.as_deref());
+        entry.text("doi", self.doi.as_ref().map(|doi| doi.as_str()));
+        entry.text("url", self.url.as_deref().or(self.repository_code.as_deref()));
+        entry.text("urldate", self.date_accessed.map(|date| date.to_string()).as_deref());
+        entry.text("keywords", (!self.keywords.is_empty()).then(|| self.keywords.join(", ")).as_deref());
+        entry.text("note", self.notes.as_deref());
+
+        entry.to_string()
+    }
+}
+
+/// Returns the page range of a reference, e.g. `12--17`.
+pub(crate) fn pages(reference: &Reference) -> Option<String> {
+    match (&reference.start, &reference.end) {
+        (Some(start), Some(end)) => Some(format!("{start}--{end}")),
+        (Some(start), None) => Some(start.to_string()),
+        _ => reference.pages.as_deref().map(String::from),
+    }
+}
+
+fn is_masters(thesis_type: &str) -> bool {
+    let thesis_type = thesis_type.to_lowercase();
+    thesis_type.contains("master") || thesis_type.contains("msc")
+}
+
+/// Returns the BibTeX macro for a month, e.g. `jan`.
+fn month(month: u8) -> &'static str {
+    const MONTHS: [&str; 12] = ["jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec"];
+
+    MONTHS[usize::from(month.clamp(1, 12)) - 1]
+}
+
+/// Creates a citation key from the family name of the first author and the year, e.g. `lewin2024`.
+pub(crate) fn key(authors: &[Author], year: Option<&str>) -> String {
+    let name = match authors.first() {
+        Some(Author::Person(person)) => person.family_names.clone().or(person.given_names.clone()).or(person.alias.clone()),
+        Some(Author::Entity(entity)) => Some(entity.name.clone()),
+        None => None,
+    };
+
+    let mut key: String = name.unwrap_or_default().chars().filter(char::is_ascii_alphanumeric).collect::<String>().to_lowercase();
+    if key.is_empty() {
+        key.push_str("citation");
+    }
+    key.push_str(year.unwrap_or_default());
+
+    key
+}
+
+/// Replaces the key of a formatted entry, e.g. `lewin2024` in `@software{lewin2024,`.
+pub(crate) fn with_key(entry: &str, key: &str) -> String {
+    match entry.split_once('{').zip(entry.split_once(',')) {
+        Some(((kind, _), (_, fields))) => format!("{kind}{{{key},{fields}"),
+        None => entry.to_string(),
+    }
+}
+
+/// Formats authors in the `von Last, Jr, First` format, separated by `and`.
+///
+/// Entities and persons with a single name are wrapped in braces, so that they are not split into name parts.
+pub(crate) fn names(authors: &[Author]) -> String {
+    let names: Vec<String> = authors
+        .iter()
+        .map(|author| match author {
+            Author::Entity(entity) => format!("{{{}}}", escape(&entity.name)),
+            Author::Person(person) => match &person.family_names {
+                Some(family) => {
+                    let last = match &person.name_particle {
+                        Some(particle) => format!("{particle} {family}"),
+                        None => family.clone(),
+                    };
+                    let parts: Vec<&str> = [Some(last.as_str()), person.name_suffix.as_deref(), person.given_names.as_deref()]
+                        .into_iter()
+                        .flatten()
+                        .collect();
+                    escape(&parts.join(", "))
+                }
+                None => format!("{{{}}}", escape(&author.display_name())),
+            },
+        })
+        .collect();
+
+    names.join(" and ")
+}
+
+/// Escapes the characters that are special to LaTeX.
+pub(crate) fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// Escapes a title and protects words with capital letters from being lowercased by BibTeX styles,
+/// e.g. `{QED}: mathematical proof assistant`.
+///
+/// The first word is only protected if it has capital letters after its first letter, as styles keep
+/// the first letter of a title capitalized anyway.
+pub(crate) fn protect(title: &str) -> String {
+    title
+        .split(' ')
+        .enumerate()
+        .map(|(index, word)| (word, word.chars().skip(usize::from(index == 0)).any(char::is_uppercase)))
+        .map(|(word, capitalized)| match capitalized {
+            true => {
+                let end = word.trim_end_matches([':', ',', '.', ';', '?', '!']).len();
+                format!("{{{}}}{}", escape(&word[..end]), escape(&word[end..]))
+            }
+            false => escape(word),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Error returned when BibTeX input cannot be parsed or converted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseBibtexError {
+    /// The line of the problem, starting at 1.
+    pub line: usize,
+
+    /// A description of the problem.
+    pub message: String,
+}
+
+impl fmt::Display for ParseBibtexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at line {}", self.message, self.line)
+    }
+}
+
+impl Error for ParseBibtexError {}
+
+impl Reference {
+    /// Parses all entries of a BibTeX (or biblatex) file into references.
+    ///
+    /// `@string` macros are expanded, `@comment` and `@preamble` entries are skipped, and common
+    /// LaTeX escapes and accents are decoded. Names are split into their parts, names in braces
+    /// such as `{The Rust Community}` become entities.
+    pub fn from_bibtex(input: &str) -> Result<Vec<Reference>, ParseBibtexError> {
+        Parser::new(input).entries()?.iter().map(RawEntry::to_reference).collect()
+    }
+}
+
+impl Citation {
+    /// Creates a citation from the first `@software` entry of a BibTeX (or biblatex) file.
+    ///
+    /// Other entries can be converted with [`Reference::from_bibtex`]. Fails if there is no
+    /// `@software` entry or if it has no authors or title.
+    pub fn from_bibtex(input: &str) -> Result<Citation, ParseBibtexError> {
+        let entries = Parser::new(input).entries()?;
+        let entry = entries
+            .iter()
+            .find(|entry| entry.kind == "software")
+            .ok_or_else(|| ParseBibtexError { line: 1, message: "no @software entry found".to_string() })?;
+
+        let error = |message: &str| ParseBibtexError { line: entry.line, message: format!("the @software entry {message}") };
+
+        entry.to_reference()?.into_citation().map_err(|_| error("has no authors"))
+    }
+}
+
+/// An entry as written in the input, with the values of its fields still in LaTeX.
+struct RawEntry {
+    kind: String,
+    key: String,
+    line: usize,
+    fields: Vec<(String, String)>,
+}
+
+impl RawEntry {
+    fn field(&self, name: &str) -> Option<&str> {
+        self.fields.iter().find(|(field, _)| field == name).map(|(_, value)| value.as_str()).filter(|value| !value.trim().is_empty())
+    }
+
+    fn text(&self, name: &str) -> Option<String> {
+        self.field(name).map(decode)
+    }
+
+    fn to_reference(&self) -> Result<Reference, ParseBibtexError> {
+        let error = |message: String| ParseBibtexError { line: self.line, message: format!("{message} in entry `{}`", self.key) };
+        let entity = |name: &str| self.text(name).map(|name| Entity { name, ..Default::default() });
+
+        let r#type = match self.kind.as_str() {
+            "article" => ReferenceType::Article,
+            "book" | "mvbook" | "inbook" | "incollection" | "bookinbook" | "collection" => ReferenceType::Book,
+            "inproceedings" | "conference" => ReferenceType::ConferencePaper,
+            "proceedings" | "mvproceedings" => ReferenceType::Proceedings,
+            "manual" => ReferenceType::Manual,
+            "booklet" => ReferenceType::Pamphlet,
+            "techreport" | "report" => ReferenceType::Report,
+            "phdthesis" | "mastersthesis" | "thesis" => ReferenceType::Thesis,
+            "unpublished" => ReferenceType::Unpublished,
+            "software" | "softwareversion" | "codefragment" => ReferenceType::Software,
+            "dataset" | "data" => ReferenceType::Data,
+            "online" | "electronic" | "www" => ReferenceType::Website,
+            "patent" => ReferenceType::Patent,
+            _ => ReferenceType::Generic,
+        };
+
+        let mut reference = Reference {
+            r#type,
+            title: self.text("title").ok_or_else(|| error("missing title".to_string()))?,
+            authors: self.field("author").map(parse_names).unwrap_or_default(),
+            editors: self.field("editor").map(parse_names).unwrap_or_default(),
+            translators: self.field("translator").map(parse_names).unwrap_or_default(),
+            journal: self.text("journal").or_else(|| self.text("journaltitle")),
+            collection_title: self.text("booktitle").or_else(|| self.text("series")),
+            publisher: entity("publisher"),
+            location: entity("address").or_else(|| entity("location")),
+            institution: entity("institution").or_else(|| entity("school")).or_else(|| entity("organization")),
+            conference: entity("eventtitle"),
+            volume: self.text("volume").map(IntOrString::from),
+            edition: self.text("edition"),
+            version: self.text("version"),
+            isbn: self.text("isbn"),
+            issn: self.text("issn"),
+            url: self.text("url"),
+            notes: self.text("note"),
+            r#abstract: self.text("abstract"),
+            keywords: self
+                .text("keywords")
+                .map(|keywords| keywords.split([',', ';']).map(str::trim).filter(|keyword| !keyword.is_empty()).map(String::from).collect())
+                .unwrap_or_default(),
+            ..Default::default()
+        };
+
+        match self.kind.as_str() {
+            "article" => reference.issue = self.text("number").map(IntOrString::from),
+            _ => reference.number = self.text("number").map(IntOrString::from),
+        }
+
+        reference.thesis_type = match self.kind.as_str() {
+            "phdthesis" => Some("PhD thesis".to_string()),
+            "mastersthesis" => Some("Master's thesis".to_string()),
+            _ => self.text("type").filter(|_| reference.r#type == ReferenceType::Thesis),
+        };
+
+        if let Some(pages) = self.field("pages") {
+            match pages.split_once('-') {
+                Some((start, end)) => {
+                    reference.start = Some(decode(start).into());
+                    reference.end = Some(decode(end.trim_start_matches('-')).into());
+                }
+                None => reference.start = Some(decode(pages).into()),
+            }
+        }
+
+        if let Some(date) = self.text("date") {
+            match date.parse::<Date>() {
+                Ok(date) => reference.date_published = Some(date),
+                Err(_) => {
+                    let mut parts = date.splitn(3, '-');
+                    reference.year = parts.next().map(IntOrString::from);
+                    reference.month = parts.next().map(|month| month.trim_start_matches('0').into());
+                }
+            }
+        }
+        if let Some(year) = self.text("year") {
+            reference.year = Some(year.into());
+        }
+        if let Some(month) = self.text("month") {
+            reference.month = Some(parse_month(&month).ok_or_else(|| error(format!("invalid month `{month}`")))?.into());
+        }
+        if let Some(date) = self.text("urldate") {
+            reference.date_accessed = Some(date.parse().map_err(|_| error(format!("invalid urldate `{date}`")))?);
+        }
+
+        if let Some(doi) = self.text("doi") {
+            let value = ["https://doi.org/", "http://doi.org/", "https://dx.doi.org/", "http://dx.doi.org/", "doi:"]
+                .iter()
+                .find_map(|prefix| doi.strip_prefix(prefix))
+                .unwrap_or(&doi);
+            reference.doi = Some(value.parse().map_err(|_| error(format!("invalid DOI `{doi}`")))?);
+        }
+        if let Some(license) = self.text("license") {
+            reference.license = Some(Licenses::Single(License::from_id(&license)));
+        }
+
+        Ok(reference)
+    }
+}
+
+/// Returns the number of a month given as a number, name or abbreviation, e.g. `3`, `March` or `mar`.
+fn parse_month(month: &str) -> Option<u8> {
+    const NAMES: [&str; 12] = ["jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec"];
+
+    if let Ok(number) = month.trim().parse::<u8>() {
+        return (1..=12).contains(&number).then_some(number);
+    }
+
+    let month = month.trim().to_lowercase();
+    let position = NAMES.iter().position(|name| month.starts_with(name))?;
+
+    Some(position as u8 + 1)
+}
+
+/// The full names of the months, which the predefined month macros such as `jan` expand to.
+const MONTH_NAMES: [&str; 12] =
+    ["January", "February", "March", "April", "May", "June", "July", "August", "September", "October", "November", "December"];
+
+struct Parser<'a> {
+    input: &'a str,
+    position: usize,
+    strings: HashMap<String, String>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Self {
+        let strings = MONTH_NAMES.iter().map(|name| (name[..3].to_lowercase(), name.to_string())).collect();
+
+        Parser { input, position: 0, strings }
+    }
+
+    fn error(&self, message: impl Into<String>) -> ParseBibtexError {
+        ParseBibtexError { line: self.line(self.position), message: message.into() }
+    }
+
+    fn line(&self, position: usize) -> usize {
+        self.input[..position.min(self.input.len())].matches('\n').count() + 1
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.position..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.position += c.len_utf8();
+        Some(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), ParseBibtexError> {
+        self.skip_whitespace();
+        match self.bump() {
+            Some(c) if c == expected => Ok(()),
+            Some(c) => Err(self.error(format!("expected `{expected}`, found `{c}`"))),
+            None => Err(self.error(format!("expected `{expected}`, found end of input"))),
+        }
+    }
+
+    fn identifier(&mut self) -> String {
+        let start = self.position;
+        while self.peek().is_some_and(|c| !c.is_whitespace() && !"{}()\",=#%@".contains(c)) {
+            self.bump();
+        }
+
+        self.input[start..self.position].to_string()
+    }
+
+    /// Parses all entries, ignoring text outside of them as BibTeX does.
+    fn entries(mut self) -> Result<Vec<RawEntry>, ParseBibtexError> {
+        let mut entries = Vec::new();
+
+        while let Some(offset) = self.input[self.position..].find('@') {
+            self.position += offset + 1;
+            let line = self.line(self.position);
+            let kind = self.identifier().to_lowercase();
+
+            self.skip_whitespace();
+            let close = match self.bump() {
+                Some('{') => '}',
+                Some('(') => ')',
+                _ => return Err(self.error(format!("expected `{{` after `@{kind}`"))),
+            };
+
+            match kind.as_str() {
+                "comment" | "preamble" => {
+                    self.position -= 1;
+                    self.braced(close)?;
+                }
+                "string" => {
+                    self.skip_whitespace();
+                    let name = self.identifier().to_lowercase();
+                    self.expect('=')?;
+                    let value = self.value()?;
+                    self.strings.insert(name, value);
+                    self.expect(close)?;
+                }
+                _ => entries.push(self.entry(kind, line, close)?),
+            }
+        }
+
+        Ok(entries)
+    }
+
+    fn entry(&mut self, kind: String, line: usize, close: char) -> Result<RawEntry, ParseBibtexError> {
+        self.skip_whitespace();
+        let start = self.position;
+        while self.peek().is_some_and(|c| c != ',' && c != close) {
+            self.bump();
+        }
+        let key = self.input[start..self.position].trim().to_string();
+        let mut fields = Vec::new();
+
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => {
+                    self.bump();
+                }
+                Some(c) if c == close => {
+                    self.bump();
+                    break;
+                }
+                Some(_) => {
+                    let name = self.identifier().to_lowercase();
+                    if name.is_empty() {
+                        return Err(self.error(format!("expected a field name in entry `{key}`")));
+                    }
+                    self.expect('=')?;
+                    fields.push((name, self.value()?));
+                }
+                None => return Err(self.error(format!("unterminated entry `{key}`"))),
+            }
+        }
+
+        Ok(RawEntry { kind, key, line, fields })
+    }
+
+    /// Parses a value, which may be concatenated from several parts with `#`.
+    fn value(&mut self) -> Result<String, ParseBibtexError> {
+        let mut value = String::new();
+
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some('{') => {
+                    let text = self.braced('}')?;
+                    value.push_str(&text);
+                }
+                Some('"') => {
+                    self.bump();
+                    let start = self.position;
+                    let mut depth = 0;
+                    loop {
+                        match self.bump() {
+                            Some('{') => depth += 1,
+                            Some('}') => depth -= 1,
+                            Some('"') if depth == 0 => break,
+                            Some(_) => {}
+                            None => return Err(self.error("unterminated quoted value")),
+                        }
+                    }
+                    value.push_str(&self.input[start..self.position - 1]);
+                }
+                Some(c) if c.is_ascii_digit() => {
+                    let start = self.position;
+                    while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                        self.bump();
+                    }
+                    value.push_str(&self.input[start..self.position]);
+                }
+                Some(_) => {
+                    let name = self.identifier();
+                    if name.is_empty() {
+                        return Err(self.error("expected a value"));
+                    }
+                    // unknown macros are kept as they are
+                    let expanded = self.strings.get(&name.to_lowercase()).cloned().unwrap_or(name);
+                    value.push_str(&expanded);
+                }
+                None => return Err(self.error("expected a value, found end of input")),
+            }
+
+            self.skip_whitespace();
+            match self.peek() {
+                Some('#') => {
+                    self.bump();
+                }
+                _ => return Ok(value),
+            }
+        }
+    }
+
+    /// Parses a group starting at the current `{` (or `(`) and returns its content without the delimiters.
+    fn braced(&mut self, close: char) -> Result<String, ParseBibtexError> {
+        let open = self.bump();
+        let start = self.position;
+        let mut depth = 0;
+
+        loop {
+            match self.bump() {
+                Some('{') => depth += 1,
+                Some('}') if depth > 0 => depth -= 1,
+                Some(c) if c == close && depth == 0 => return Ok(self.input[start..self.position - 1].to_string()),
+                Some(_) => {}
+                None => return Err(self.error(format!("unbalanced `{}`", open.unwrap_or('{')))),
+            }
+        }
+    }
+}
+
+/// Splits a value at the given separator, ignoring separators inside braces.
+fn split_top_level(value: &str, separator: impl Fn(&str) -> Option<usize>) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    let mut index = 0;
+
+    while index < value.len() {
+        let rest = &value[index..];
+        let c = rest.chars().next().unwrap_or_default();
+        match c {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            _ if depth == 0 => {
+                if let Some(length) = separator(rest) {
+                    parts.push(&value[start..index]);
+                    index += length;
+                    start = index;
+                    continue;
+                }
+            }
+            _ => {}
+        }
+        index += c.len_utf8();
+    }
+    parts.push(&value[start..]);
+
+    parts
+}
+
+/// Parses a list of names separated by `and`, e.g. `Lewin, Luca and {The Rust Community}`.
+fn parse_names(value: &str) -> Vec<Author> {
+    let value = value.split_whitespace().collect::<Vec<_>>().join(" ");
+    let separator = |rest: &str| rest.get(..5).filter(|and| and.eq_ignore_ascii_case(" and ")).map(str::len);
+
+    split_top_level(&value, separator)
+        .into_iter()
+        .map(str::trim)
+        .filter(|name| !name.is_empty() && *name != "others")
+        .map(parse_name)
+        .collect()
+}
+
+/// Returns `true` if the whole text is a single group in braces, e.g. `{The Rust Community}`.
+fn is_braced(text: &str) -> bool {
+    let mut depth = 0;
+    for (index, c) in text.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            _ if index == 0 => return false,
+            _ => {}
+        }
+        if depth == 0 {
+            return index == text.len() - 1;
+        }
+    }
+
+    false
+}
+
+fn parse_name(name: &str) -> Author {
+    if is_braced(name) {
+        return Author::Entity(Entity { name: decode(name), ..Default::default() });
+    }
+
+    let parts: Vec<&str> = split_top_level(name, |rest| rest.starts_with(',').then_some(1)).into_iter().map(str::trim).collect();
+    let words = |part: &str| -> Vec<String> { split_top_level(part, |rest| rest.starts_with(' ').then_some(1)).into_iter().filter(|word| !word.is_empty()).map(String::from).collect() };
+    let is_lowercase = |word: &String| word.chars().next().is_some_and(char::is_lowercase);
+
+    let (first, von, last, suffix) = match parts.as_slice() {
+        [single] => {
+            let words = words(single);
+            let von_start = words.iter().take(words.len().saturating_sub(1)).position(is_lowercase);
+            match von_start {
+                Some(start) => {
+                    let end = words.iter().take(words.len() - 1).rposition(is_lowercase).unwrap_or(start) + 1;
+                    (words[..start].join(" "), words[start..end].join(" "), words[end..].join(" "), String::new())
+                }
+                None => {
+                    let split = words.len().saturating_sub(1);
+                    (words[..split].join(" "), String::new(), words[split..].join(" "), String::new())
+                }
+            }
+        }
+        [last, rest @ ..] => {
+            let words = words(last);
+            let von_end = words.iter().take(words.len().saturating_sub(1)).rposition(is_lowercase).map_or(0, |end| end + 1);
+            let (suffix, first) = match rest {
+                [suffix, first, ..] => (suffix.to_string(), first.to_string()),
+                [first] => (String::new(), first.to_string()),
+                [] => (String::new(), String::new()),
+            };
+            (first, words[..von_end].join(" "), words[von_end..].join(" "), suffix)
+        }
+        [] => Default::default(),
+    };
+
+    let part = |text: String| Some(decode(&text)).filter(|text| !text.is_empty());
+
+    Author::Person(Person {
+        given_names: part(first),
+        name_particle: part(von),
+        family_names: part(last),
+        name_suffix: part(suffix),
+        ..Default::default()
+    })
+}
+
+/// Decodes LaTeX text into plain text, resolving escapes and common accents and removing braces.
+fn decode(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' | '}' => {}
+            '~' => result.push(' '),
+            '\\' => {
+                let Some(&next) = chars.peek() else { break };
+                if !next.is_ascii_alphabetic() {
+                    chars.next();
+                    match next {
+                        '&' | '%' | '$' | '#' | '_' | '{' | '}' | '\\' | ' ' => result.push(next),
+                        accent => {
+                            let letter = argument(&mut chars);
+                            result.push_str(&accented(accent, &letter));
+                        }
+                    }
+                    continue;
+                }
+
+                let mut command = String::new();
+                while let Some(&c) = chars.peek().filter(|c| c.is_ascii_alphabetic()) {
+                    command.push(c);
+                    chars.next();
+                }
+                // spaces after a command name are not part of the text
+                while chars.peek() == Some(&' ') {
+                    chars.next();
+                }
+
+                match command.as_str() {
+                    "ss" => result.push('ß'),
+                    "o" => result.push('ø'),
+                    "O" => result.push('Ø'),
+                    "aa" => result.push('å'),
+                    "AA" => result.push('Å'),
+                    "ae" => result.push('æ'),
+                    "AE" => result.push('Æ'),
+                    "l" => result.push('ł'),
+                    "L" => result.push('Ł'),
+                    "i" => result.push('ı'),
+                    "textbackslash" => result.push('\\'),
+                    "textasciitilde" => result.push('~'),
+                    "textasciicircum" => result.push('^'),
+                    "c" | "v" | "u" | "H" | "k" | "r" => {
+                        let letter = argument(&mut chars);
+                        result.push_str(&accented(command.chars().next().unwrap_or_default(), &letter));
+                    }
+                    // other commands such as \emph or \textbf are dropped, keeping their argument
+                    _ => {}
+                }
+            }
+            '-' if chars.peek() == Some(&'-') => {
+                chars.next();
+                if chars.peek() == Some(&'-') {
+                    chars.next();
+                    result.push('—');
+                } else {
+                    result.push('–');
+                }
+            }
+            c if c.is_whitespace() => {
+                if !result.ends_with(' ') {
+                    result.push(' ');
+                }
+            }
+            c => result.push(c),
+        }
+    }
+
+    result.trim().to_string()
+}
+
+/// Reads the argument of an accent command, e.g. `o` from `{o}` or `o`.
+fn argument(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> String {
+    while chars.peek() == Some(&' ') {
+        chars.next();
+    }
+
+    match chars.next() {
+        Some('{') => {
+            let mut argument = String::new();
+            for c in chars.by_ref() {
+                if c == '}' {
+                    break;
+                }
+                argument.push(c);
+            }
+            argument.replace("\\i", "i")
+        }
+        Some(c) => c.to_string(),
+        None => String::new(),
+    }
+}
+
+/// Combines an accent command such as `"` and a letter into the precomposed character, e.g. `ö`.
+fn accented(accent: char, letter: &str) -> String {
+    const ACCENTS: &[(char, &str, &str)] = &[
+        ('"', "aeiouyAEIOU", "äëïöüÿÄËÏÖÜ"),
+        ('\'', "aeiouycnszAEIOUYCNSZ", "áéíóúýćńśźÁÉÍÓÚÝĆŃŚŹ"),
+        ('`', "aeiouAEIOU", "àèìòùÀÈÌÒÙ"),
+        ('^', "aeiouAEIOU", "âêîôûÂÊÎÔÛ"),
+        ('~', "anoANO", "ãñõÃÑÕ"),
+        ('c', "csCS", "çşÇŞ"),
+        ('v', "csznrzeCSZNRE", "čšžňřžěČŠŽŇŘĚ"),
+        ('u', "agAG", "ăğĂĞ"),
+        ('H', "ouOU", "őűŐŰ"),
+        ('k', "aeAE", "ąęĄĘ"),
+        ('r', "auAU", "åůÅŮ"),
+        ('=', "aeiouAEIOU", "āēīōūĀĒĪŌŪ"),
+        ('.', "zeZE", "żėŻĖ"),
+    ];
+
+    let mut chars = letter.chars();
+    let (Some(base), None) = (chars.next(), chars.next()) else { return letter.to_string() };
+
+    ACCENTS
+        .iter()
+        .find(|(command, _, _)| *command == accent)
+        .and_then(|(_, bases, accented)| bases.chars().position(|c| c == base).and_then(|index| accented.chars().nth(index)))
+        .map_or_else(|| letter.to_string(), String::from)
+}
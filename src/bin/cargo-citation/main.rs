@@ -0,0 +1,317 @@
+//! The `cargo citation` subcommand, built with the `cli` feature.
+//!
+//! Cargo runs `cargo-citation citation <args>` for `cargo citation <args>`, so the first argument is the name of
+//! the subcommand.
+
+use std::{
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use citation::{CargoWorkspace, Citation, DependencyOptions, Document, ParseOptions, Severity, Style};
+use clap::{Parser, Subcommand, ValueEnum};
+use serde_yaml::Value;
+
+/// The keys that are kept in sync with `Cargo.toml`. The title and the authors are only taken when the file is
+/// generated, as they are often more detailed in the citation (e.g. with ORCIDs).
+const SYNCED_KEYS: &[&str] = &["version", "abstract", "license", "repository-code", "url", "keywords"];
+
+#[derive(Parser)]
+#[command(name = "cargo", bin_name = "cargo")]
+enum Cargo {
+    Citation(Cli),
+}
+
+/// Generate, validate and sync the CITATION.cff files of a crate or workspace with Cargo.toml.
+///
+/// Every package has its own CITATION.cff next to its Cargo.toml.
+#[derive(clap::Args)]
+#[command(version)]
+struct Cli {
+    /// The manifest of the crate or workspace, by default the closest Cargo.toml.
+    #[arg(long, global = true)]
+    manifest_path: Option<PathBuf>,
+
+    /// Process all members of the workspace. This is the default for virtual manifests.
+    #[arg(long, global = true)]
+    workspace: bool,
+
+    /// Process only the given packages.
+    #[arg(long, short, global = true)]
+    package: Vec<String>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create the CITATION.cff files from Cargo.toml.
+    Generate {
+        /// Overwrite files that already exist.
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Validate the CITATION.cff files and check that they are in sync with Cargo.toml.
+    ///
+    /// Exits with 0 if all files are valid, 1 if there are warnings or differences and 2 if there are errors.
+    Validate,
+
+    /// Update the version, abstract, license, repository, URL and keywords of the CITATION.cff files from Cargo.toml.
+    ///
+    /// The files are edited in place, so comments and formatting are kept.
+    Sync {
+        /// Only report the differences, exiting with 1 if there are any.
+        #[arg(long)]
+        check: bool,
+    },
+
+    /// Print a bibliography of the dependencies, from their CITATION.cff files or else from their Cargo.toml.
+    ///
+    /// Exits with 1 if the citation of a dependency cannot be found, e.g. because its Cargo.toml has no authors.
+    Deps {
+        /// How to print the bibliography.
+        #[arg(long, value_enum, default_value_t = Format::Bibtex)]
+        format: Format,
+
+        /// Include the dev-dependencies of the workspace members.
+        #[arg(long)]
+        dev: bool,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// BibTeX entries keyed by the names of the packages.
+    Bibtex,
+    Apa,
+    Ieee,
+    Chicago,
+    Mla,
+    Harvard,
+    Vancouver,
+}
+
+/// A package of the workspace.
+struct Package {
+    name: String,
+    directory: PathBuf,
+}
+
+impl Package {
+    fn citation_path(&self) -> PathBuf {
+        self.directory.join("CITATION.cff")
+    }
+}
+
+fn main() -> ExitCode {
+    let Cargo::Citation(cli) = Cargo::parse();
+
+    if let Command::Deps { format, dev } = cli.command {
+        return deps(&cli, format, dev).unwrap_or_else(|error| {
+            eprintln!("error: {error}");
+            ExitCode::from(2)
+        });
+    }
+
+    let packages = match packages(&cli) {
+        Ok(packages) => packages,
+        Err(error) => {
+            eprintln!("error: {error}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let codes = packages.iter().map(|package| {
+        let path = package.citation_path();
+        let result = match cli.command {
+            Command::Generate { force } => generate(package, force),
+            Command::Validate => validate(package),
+            Command::Sync { check } => sync(package, check),
+            Command::Deps { .. } => unreachable!("the dependencies are listed for the whole workspace"),
+        };
+
+        result.unwrap_or_else(|error| {
+            eprintln!("{}: error: {error}", path.display());
+            2
+        })
+    });
+
+    ExitCode::from(codes.max().unwrap_or(0))
+}
+
+fn generate(package: &Package, force: bool) -> Result<u8, Box<dyn Error>> {
+    let path = package.citation_path();
+    if path.exists() && !force {
+        return Err("the file already exists, use `--force` to overwrite it".into());
+    }
+
+    Citation::from_cargo_manifest(package.directory.clone())?.write(path.clone())?;
+    println!("Created {}", path.display());
+
+    Ok(0)
+}
+
+fn validate(package: &Package) -> Result<u8, Box<dyn Error>> {
+    let path = package.citation_path();
+    let content = fs::read_to_string(&path)?;
+    let citation = Citation::parse_file(&path, &content, &ParseOptions::default()).map_err(citation::Error::from)?;
+    let report = citation.validate().with_source(path.display().to_string(), content);
+
+    for issue in &report {
+        let location = match issue.span {
+            Some(span) => format!("{}:{}:{}", path.display(), span.line, span.column),
+            None => path.display().to_string(),
+        };
+        println!("{location}: {}[{}] {}: {}", issue.severity, issue.code, issue.path, issue.message);
+    }
+
+    let differences = differences(&citation, &Citation::from_cargo_manifest(package.directory.clone())?)?;
+    for (key, value, manifest) in &differences {
+        println!("{}: warning[out-of-sync] {key}: {} in CITATION.cff, but {} in Cargo.toml", path.display(), display(value), display(manifest));
+    }
+
+    match report.iter().map(|issue| issue.severity).max() {
+        Some(Severity::Error) => Ok(2),
+        Some(Severity::Warning) => Ok(1),
+        _ => Ok(u8::from(!differences.is_empty())),
+    }
+}
+
+fn sync(package: &Package, check: bool) -> Result<u8, Box<dyn Error>> {
+    let path = package.citation_path();
+    let mut document = Document::read(path.clone())?;
+    let citation = document.citation()?;
+    let differences = differences(&citation, &Citation::from_cargo_manifest(package.directory.clone())?)?;
+
+    if differences.is_empty() {
+        println!("{}: in sync with Cargo.toml", path.display());
+        return Ok(0);
+    }
+    if check {
+        for (key, value, manifest) in &differences {
+            println!("{}: {key}: {} in CITATION.cff, but {} in Cargo.toml", path.display(), display(value), display(manifest));
+        }
+        return Ok(1);
+    }
+
+    let mut value = serde_yaml::to_value(&citation)?;
+    for (key, _, manifest) in &differences {
+        value[*key] = manifest.clone();
+    }
+    document.update(&serde_yaml::from_value(value)?)?;
+    document.write(path.clone())?;
+
+    for (key, value, manifest) in &differences {
+        println!("{}: updated {key} from {} to {}", path.display(), display(value), display(manifest));
+    }
+
+    Ok(0)
+}
+
+fn deps(cli: &Cli, format: Format, dev: bool) -> Result<ExitCode, Box<dyn Error>> {
+    let manifest = match &cli.manifest_path {
+        Some(path) => path.clone(),
+        None => closest_manifest()?,
+    };
+    let dependencies = Citation::from_cargo_dependencies(manifest, &DependencyOptions { dev_dependencies: dev, ..Default::default() })?;
+
+    let style = match format {
+        Format::Bibtex => None,
+        Format::Apa => Some(Style::Apa),
+        Format::Ieee => Some(Style::Ieee),
+        Format::Chicago => Some(Style::Chicago),
+        Format::Mla => Some(Style::Mla),
+        Format::Harvard => Some(Style::Harvard),
+        Format::Vancouver => Some(Style::Vancouver),
+    };
+    match style {
+        Some(style) => print!("{}", dependencies.format(style)),
+        None => print!("{}", dependencies.to_bibtex()),
+    }
+
+    for (name, error) in &dependencies.errors {
+        eprintln!("warning: {name}: {error}");
+    }
+
+    Ok(ExitCode::from(u8::from(!dependencies.errors.is_empty())))
+}
+
+/// Returns the synced keys whose values differ, with the value in the citation and in the manifest.
+/// Keys that are not set in the manifest are ignored.
+fn differences(citation: &Citation, manifest: &Citation) -> Result<Vec<(&'static str, Value, Value)>, serde_yaml::Error> {
+    let citation = serde_yaml::to_value(citation)?;
+    let manifest = serde_yaml::to_value(manifest)?;
+
+    Ok(SYNCED_KEYS
+        .iter()
+        .filter_map(|key| {
+            let expected = manifest.get(key)?;
+            let actual = citation.get(key).cloned().unwrap_or(Value::Null);
+            (actual != *expected).then(|| (*key, actual, expected.clone()))
+        })
+        .collect())
+}
+
+fn display(value: &Value) -> String {
+    match value {
+        Value::Null => "missing".to_string(),
+        Value::String(value) => format!("`{value}`"),
+        value => format!("`{}`", serde_json::to_string(value).unwrap_or_default()),
+    }
+}
+
+/// Finds the packages to process, in the order of the workspace members.
+fn packages(cli: &Cli) -> Result<Vec<Package>, Box<dyn Error>> {
+    let manifest = match &cli.manifest_path {
+        Some(path) => path.clone(),
+        None => closest_manifest()?,
+    };
+    let table = read_manifest(&manifest)?;
+    let directory = manifest.parent().filter(|parent| !parent.as_os_str().is_empty()).unwrap_or(Path::new(".")).to_path_buf();
+
+    let mut packages = Vec::new();
+    if cli.workspace || table.get("package").is_none() {
+        for member in CargoWorkspace::read(manifest)?.members {
+            let directory = member.manifest_path.parent().map(Path::to_path_buf).unwrap_or_default();
+            packages.push(Package { name: member.name, directory });
+        }
+    } else if let Some(name) = package_name(&manifest)? {
+        packages.push(Package { name, directory });
+    }
+
+    for name in &cli.package {
+        if !packages.iter().any(|package| package.name == *name) {
+            return Err(format!("package `{name}` not found").into());
+        }
+    }
+    packages.retain(|package| cli.package.is_empty() || cli.package.contains(&package.name));
+
+    Ok(packages)
+}
+
+/// Finds the `Cargo.toml` in the current directory or its closest ancestor, like Cargo does.
+fn closest_manifest() -> Result<PathBuf, Box<dyn Error>> {
+    let current = std::env::current_dir()?;
+
+    current
+        .ancestors()
+        .map(|directory| directory.join("Cargo.toml"))
+        .find(|path| path.is_file())
+        .ok_or_else(|| "could not find Cargo.toml in the current directory or any parent directory".into())
+}
+
+fn read_manifest(path: &Path) -> Result<toml::Table, Box<dyn Error>> {
+    let content = fs::read_to_string(path).map_err(|error| format!("{}: {error}", path.display()))?;
+
+    Ok(toml::from_str(&content)?)
+}
+
+fn package_name(manifest: &Path) -> Result<Option<String>, Box<dyn Error>> {
+    let table = read_manifest(manifest)?;
+
+    Ok(table.get("package").and_then(|package| package.get("name")).and_then(|name| name.as_str()).map(String::from))
+}
@@ -0,0 +1,78 @@
+use std::{error::Error, path::PathBuf, process::ExitCode};
+
+use citation::{Citation, Date, Document};
+
+/// Update `version`, `date-released` and `commit` for a new release.
+///
+/// The file is edited in place, so comments and formatting are kept.
+#[derive(clap::Args)]
+#[command(group = clap::ArgGroup::new("source").required(true).args(["version", "from_cargo", "from_git"]))]
+pub(crate) struct Args {
+    /// The new version.
+    version: Option<String>,
+
+    /// Read the new version from `Cargo.toml` next to the file.
+    #[arg(long)]
+    from_cargo: bool,
+
+    /// Read the new version from the latest git tag, and the release date from the tagged commit.
+    #[arg(long)]
+    from_git: bool,
+
+    /// The release date, today by default.
+    #[arg(long)]
+    date: Option<Date>,
+
+    /// Keep `commit` instead of setting it to the hash of `HEAD`.
+    #[arg(long)]
+    no_commit: bool,
+
+    /// The file to update.
+    #[arg(long, short, default_value = "CITATION.cff")]
+    file: PathBuf,
+}
+
+pub(crate) fn run(args: Args) -> ExitCode {
+    match bump(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{}: error: {error}", args.file.display());
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn bump(args: &Args) -> Result<(), Box<dyn Error>> {
+    let directory = args.file.parent().filter(|parent| !parent.as_os_str().is_empty()).unwrap_or(std::path::Path::new(".")).to_path_buf();
+
+    let mut document = Document::read(args.file.clone())?;
+    let mut citation = document.citation()?;
+    let previous = citation.version.clone();
+
+    // `fill_from_git` sets `commit` even if there is no tag, so the other values are only taken if it found one
+    let mut git = document.citation()?;
+    git.version = None;
+    let in_repository = git.fill_from_git(directory.clone()).is_ok();
+
+    let (version, date) = match (&args.version, args.from_cargo, args.from_git) {
+        (Some(version), _, _) => (version.clone(), None),
+        (None, true, _) => (Citation::from_cargo_manifest(directory)?.version.ok_or("the manifest has no version")?, None),
+        (None, _, _) => (git.version.clone().ok_or("no tag found in the git repository")?, git.date_released),
+    };
+
+    citation.version = Some(version.clone());
+    citation.date_released = Some(args.date.or(date).unwrap_or_else(Date::today));
+    if !args.no_commit && in_repository {
+        citation.commit = git.commit;
+    }
+
+    document.update(&citation)?;
+    document.write(args.file.clone())?;
+
+    match previous {
+        Some(previous) => println!("{}: bumped version {previous} to {version}", args.file.display()),
+        None => println!("{}: set version to {version}", args.file.display()),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,137 @@
+use std::{error::Error, fs, io::Read, path::PathBuf, process::ExitCode};
+
+use citation::Citation;
+use clap::ValueEnum;
+
+/// Convert a CITATION.cff file to other formats, or import a citation from them.
+#[derive(clap::Args)]
+pub(crate) struct Args {
+    /// The file to convert, or `-` to read from stdin.
+    #[arg(default_value = "CITATION.cff")]
+    input: PathBuf,
+
+    /// The format of the input, derived from the file extension if omitted.
+    #[arg(long, short)]
+    from: Option<Format>,
+
+    /// The format to convert to.
+    #[arg(long, short)]
+    to: Format,
+
+    /// The file to write to instead of stdout.
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    Cff,
+    Bibtex,
+    Biblatex,
+    Ris,
+    CslJson,
+    Codemeta,
+    Zenodo,
+    JsonLd,
+    DataciteXml,
+    DataciteJson,
+    DublinCore,
+    Endnote,
+    Toml,
+}
+
+impl Format {
+    /// Guesses the format of a file from its name, e.g. `references.bib` or `codemeta.json`.
+    fn detect(path: &std::path::Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?.to_lowercase();
+        match name.as_str() {
+            "codemeta.json" => return Some(Format::Codemeta),
+            ".zenodo.json" => return Some(Format::Zenodo),
+            _ => {}
+        }
+
+        match name.rsplit_once('.')?.1 {
+            "cff" | "yaml" | "yml" => Some(Format::Cff),
+            "bib" => Some(Format::Bibtex),
+            "ris" => Some(Format::Ris),
+            "json" => Some(Format::CslJson),
+            "toml" => Some(Format::Toml),
+            _ => None,
+        }
+    }
+
+    fn import(self, input: &str) -> Result<Citation, Box<dyn Error>> {
+        match self {
+            Format::Cff => Ok(input.parse()?),
+            Format::Bibtex | Format::Biblatex => Ok(Citation::from_bibtex(input)?),
+            Format::Ris => Ok(Citation::from_ris(input)?),
+            Format::CslJson => Ok(Citation::from_csl_json(input)?),
+            Format::Codemeta => Ok(Citation::from_codemeta(input)?),
+            Format::Zenodo => Ok(Citation::from_zenodo_json(input)?),
+            Format::Toml => Ok(Citation::from_toml_str(input)?),
+            _ => Err(format!("cannot import from {}", self.name()).into()),
+        }
+    }
+
+    fn export(self, citation: &Citation) -> Result<String, Box<dyn Error>> {
+        Ok(match self {
+            Format::Cff => citation.to_yaml_string()?,
+            Format::Bibtex => citation.to_bibtex(),
+            Format::Biblatex => citation.to_biblatex(),
+            Format::Ris => citation.to_ris(),
+            Format::CslJson => citation.to_csl_json(),
+            Format::Codemeta => citation.to_codemeta(),
+            Format::Zenodo => citation.to_zenodo_json(),
+            Format::JsonLd => citation.to_json_ld(),
+            Format::DataciteXml => citation.to_datacite_xml(),
+            Format::DataciteJson => citation.to_datacite_json(),
+            Format::DublinCore => citation.to_dublin_core(),
+            Format::Endnote => citation.to_endnote_xml(),
+            Format::Toml => return Err(format!("cannot export to {}", self.name()).into()),
+        })
+    }
+
+    fn name(self) -> String {
+        self.to_possible_value().map(|value| value.get_name().to_string()).unwrap_or_default()
+    }
+}
+
+pub(crate) fn run(args: Args) -> ExitCode {
+    match convert(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{}: error: {error}", args.input.display());
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn convert(args: &Args) -> Result<(), Box<dyn Error>> {
+    let stdin = args.input.as_os_str() == "-";
+    let from = match args.from.or_else(|| Format::detect(&args.input)) {
+        Some(format) => format,
+        None if stdin => Format::Cff,
+        None => return Err("cannot detect the format of the input, use `--from`".into()),
+    };
+
+    let input = match stdin {
+        true => {
+            let mut input = String::new();
+            std::io::stdin().read_to_string(&mut input)?;
+            input
+        }
+        false => fs::read_to_string(&args.input)?,
+    };
+
+    let mut output = args.to.export(&from.import(&input)?)?;
+    if !output.ends_with('\n') {
+        output.push('\n');
+    }
+
+    match &args.output {
+        Some(path) => fs::write(path, output)?,
+        None => print!("{output}"),
+    }
+
+    Ok(())
+}
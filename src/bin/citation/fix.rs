@@ -0,0 +1,87 @@
+use std::{error::Error, fs, path::PathBuf, process::ExitCode};
+
+use citation::{Document, Fix};
+
+/// Repair common problems in CITATION.cff files and print what changed.
+///
+/// Misspelled keys are renamed, bare ORCID iDs are converted to URLs, dates are converted to `YYYY-MM-DD`
+/// and strings that YAML would read as numbers or booleans are quoted.
+#[derive(clap::Args)]
+pub(crate) struct Args {
+    /// The files to fix.
+    #[arg(default_value = "CITATION.cff")]
+    files: Vec<PathBuf>,
+
+    /// Print the changes without writing them.
+    #[arg(long)]
+    dry_run: bool,
+}
+
+pub(crate) fn run(args: Args) -> ExitCode {
+    let mut code = ExitCode::SUCCESS;
+
+    for file in &args.files {
+        let result = fix(file, args.dry_run);
+
+        match result {
+            Ok((original, document, fixes)) => {
+                for fix in &fixes {
+                    println!("{}: {fix}", file.display());
+                }
+                if !fixes.is_empty() {
+                    print!("{}", diff(&original, document.as_str()));
+                }
+            }
+            Err(error) => {
+                eprintln!("{}: error: {error}", file.display());
+                code = ExitCode::FAILURE;
+            }
+        }
+    }
+
+    code
+}
+
+/// Fixes the file, returning its original content, the fixed document and the applied fixes.
+fn fix(file: &PathBuf, dry_run: bool) -> Result<(String, Document, Vec<Fix>), Box<dyn Error>> {
+    let original = fs::read_to_string(file)?;
+    let (document, fixes) = Document::fix(original.as_str())?;
+    if !dry_run && !fixes.is_empty() {
+        document.write(file.clone())?;
+    }
+
+    Ok((original, document, fixes))
+}
+
+/// Compares two texts line by line and returns the removed lines prefixed with `-` and the added lines prefixed with `+`.
+fn diff(old: &str, new: &str) -> String {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // lengths of the longest common subsequences of the remaining lines
+    let mut lengths = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i][j] = match old[i] == new[j] {
+                true => lengths[i + 1][j + 1] + 1,
+                false => lengths[i + 1][j].max(lengths[i][j + 1]),
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut output = String::new();
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            (i, j) = (i + 1, j + 1);
+        } else if i < old.len() && (j == new.len() || lengths[i + 1][j] >= lengths[i][j + 1]) {
+            output.push_str(&format!("-{}\n", old[i]));
+            i += 1;
+        } else {
+            output.push_str(&format!("+{}\n", new[j]));
+            j += 1;
+        }
+    }
+
+    output
+}
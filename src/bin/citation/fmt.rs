@@ -0,0 +1,51 @@
+use std::{path::PathBuf, process::ExitCode};
+
+use citation::Document;
+
+/// Rewrite CITATION.cff files in a canonical style.
+///
+/// Keys are sorted as in the schema guide, values are quoted consistently and ORCIDs are written as URLs.
+/// Comments are kept.
+#[derive(clap::Args)]
+pub(crate) struct Args {
+    /// The files to format.
+    #[arg(default_value = "CITATION.cff")]
+    files: Vec<PathBuf>,
+
+    /// Only check whether the files are formatted, and exit with 1 if they are not.
+    #[arg(long)]
+    check: bool,
+}
+
+pub(crate) fn run(args: Args) -> ExitCode {
+    let mut code = ExitCode::SUCCESS;
+
+    for file in &args.files {
+        let result = Document::read(file.clone()).and_then(|mut document| {
+            let original = document.clone();
+            document.format()?;
+            if document == original {
+                return Ok(false);
+            }
+            if !args.check {
+                document.write(file.clone())?;
+            }
+            Ok(true)
+        });
+
+        match result {
+            Ok(true) if args.check => {
+                println!("{}: not formatted", file.display());
+                code = ExitCode::FAILURE;
+            }
+            Ok(true) => println!("{}: formatted", file.display()),
+            Ok(false) => {}
+            Err(error) => {
+                eprintln!("{}: error: {error}", file.display());
+                code = ExitCode::from(2);
+            }
+        }
+    }
+
+    code
+}
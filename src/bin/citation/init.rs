@@ -0,0 +1,221 @@
+use std::{
+    error::Error,
+    io::{self, BufRead, Write},
+    path::{Path, PathBuf},
+    process::{Command, ExitCode},
+};
+
+use citation::{Author, Citation, License, Licenses, Orcid, Person, CFF_VERSION};
+
+/// Create a CITATION.cff file by answering a few questions.
+///
+/// The answers are pre-filled from `Cargo.toml` and the git configuration, if available.
+#[derive(clap::Args)]
+pub(crate) struct Args {
+    /// The file to create.
+    #[arg(default_value = "CITATION.cff")]
+    path: PathBuf,
+
+    /// Accept the pre-filled answers without asking.
+    #[arg(long, short)]
+    yes: bool,
+
+    /// Overwrite the file if it already exists.
+    #[arg(long)]
+    force: bool,
+}
+
+pub(crate) fn run(args: Args) -> ExitCode {
+    match init(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{}: error: {error}", args.path.display());
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn init(args: &Args) -> Result<(), Box<dyn Error>> {
+    if args.path.exists() && !args.force {
+        return Err("the file already exists, use `--force` to overwrite it".into());
+    }
+
+    let directory = args.path.parent().filter(|parent| !parent.as_os_str().is_empty()).unwrap_or(Path::new("."));
+    let defaults = defaults(directory);
+    let mut prompt = Prompt { input: io::stdin().lock(), yes: args.yes };
+
+    let title = prompt.required("Title", Some(&defaults.title))?;
+
+    let mut authors = Vec::new();
+    loop {
+        let default = defaults.authors.get(authors.len()).map(Author::display_name);
+        let question = format!("Author {} (empty to finish)", authors.len() + 1);
+        let name = match authors.is_empty() {
+            true => prompt.required(&question, default.as_deref())?,
+            false => match prompt.ask(&question, default.as_deref())? {
+                Some(name) => name,
+                None => break,
+            },
+        };
+
+        let email = defaults.authors.iter().find_map(|author| match author {
+            Author::Person(person) if person.display_name() == name => person.email.clone(),
+            _ => None,
+        });
+        let orcid = prompt.parse::<Orcid>(&format!("ORCID of {name}"))?;
+
+        authors.push(Author::Person(Person { email, orcid: orcid.map(|orcid| orcid.to_url()), ..Person::from_name(&name) }));
+        if prompt.yes && authors.len() >= defaults.authors.len() {
+            break;
+        }
+    }
+
+    let license = defaults.license.as_ref().map(|licenses| licenses.iter().map(License::id).collect::<Vec<_>>().join(" OR "));
+    let license = prompt.ask("License (SPDX identifiers separated by OR)", license.as_deref())?;
+    let repository = prompt.ask("Repository URL", defaults.repository.as_deref())?;
+
+    let mut builder = Citation::builder().cff_version(CFF_VERSION).title(title).authors(authors);
+    if let Some(license) = license {
+        let mut licenses: Vec<_> = license.split(" OR ").map(str::trim).filter(|id| !id.is_empty()).map(License::from_id).collect();
+        builder = builder.license(match licenses.len() {
+            1 => Licenses::Single(licenses.remove(0)),
+            _ => Licenses::Multiple(licenses),
+        });
+    }
+    if let Some(repository) = repository {
+        builder = builder.repository_code(repository);
+    }
+    if let Some(version) = defaults.version {
+        builder = builder.version(version);
+    }
+    if let Some(r#abstract) = defaults.r#abstract {
+        builder = builder.r#abstract(r#abstract);
+    }
+
+    let citation = builder.keywords(defaults.keywords).build()?;
+    citation.write(args.path.clone())?;
+
+    let report = citation.validate();
+    for issue in report.errors() {
+        eprintln!("{}: {}[{}] {}: {}", args.path.display(), issue.severity, issue.code, issue.path, issue.message);
+    }
+    println!("Created {}", args.path.display());
+
+    Ok(())
+}
+
+/// The pre-filled answers.
+#[derive(Default)]
+struct Defaults {
+    title: String,
+    authors: Vec<Author>,
+    license: Option<Licenses>,
+    repository: Option<String>,
+    version: Option<String>,
+    r#abstract: Option<String>,
+    keywords: Vec<String>,
+}
+
+/// Collects the pre-filled answers from the `Cargo.toml` manifest in the directory and the git configuration.
+///
+/// The title falls back to the name of the directory, and the author to `user.name` and `user.email` of git.
+fn defaults(directory: &Path) -> Defaults {
+    let mut defaults = match Citation::from_cargo_manifest(directory.to_path_buf()) {
+        Ok(citation) => Defaults {
+            title: citation.title,
+            authors: citation.authors,
+            license: citation.license,
+            repository: citation.repository_code,
+            version: citation.version,
+            r#abstract: citation.r#abstract,
+            keywords: citation.keywords,
+        },
+        Err(_) => Defaults::default(),
+    };
+
+    if defaults.title.is_empty() {
+        let directory = directory.canonicalize().unwrap_or_else(|_| directory.to_path_buf());
+        defaults.title = directory.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+    }
+    if defaults.authors.is_empty() {
+        if let Some(name) = git_config(directory, "user.name") {
+            defaults.authors.push(Author::Person(Person { email: git_config(directory, "user.email"), ..Person::from_name(&name) }));
+        }
+    }
+    if defaults.repository.is_none() {
+        defaults.repository = git_config(directory, "remote.origin.url").map(|url| repository_url(&url));
+    }
+
+    defaults
+}
+
+fn git_config(directory: &Path, key: &str) -> Option<String> {
+    let output = Command::new("git").arg("-C").arg(directory).args(["config", "--get", key]).output().ok()?;
+    let value = String::from_utf8(output.stdout).ok()?.trim().to_string();
+
+    (output.status.success() && !value.is_empty()).then_some(value)
+}
+
+/// Converts the URL of a git remote to the URL of the repository in the browser,
+/// e.g. `git@github.com:owner/repo.git` to `https://github.com/owner/repo`.
+fn repository_url(remote: &str) -> String {
+    let url = match remote.strip_prefix("git@").and_then(|rest| rest.split_once(':')) {
+        Some((host, path)) => format!("https://{host}/{path}"),
+        None => remote.to_string(),
+    };
+
+    url.trim_end_matches('/').trim_end_matches(".git").to_string()
+}
+
+/// Asks questions on stderr and reads the answers from stdin.
+struct Prompt<R> {
+    input: R,
+    yes: bool,
+}
+
+impl<R: BufRead> Prompt<R> {
+    /// Asks a question, returning the default if the answer is empty.
+    fn ask(&mut self, question: &str, default: Option<&str>) -> io::Result<Option<String>> {
+        if self.yes {
+            return Ok(default.map(String::from));
+        }
+
+        match default {
+            Some(default) => eprint!("{question} [{default}]: "),
+            None => eprint!("{question}: "),
+        }
+        io::stderr().flush()?;
+
+        let mut answer = String::new();
+        if self.input.read_line(&mut answer)? == 0 {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "no answer given"));
+        }
+
+        match answer.trim() {
+            "" => Ok(default.map(String::from)),
+            answer => Ok(Some(answer.to_string())),
+        }
+    }
+
+    /// Asks a question until it is answered.
+    fn required(&mut self, question: &str, default: Option<&str>) -> io::Result<String> {
+        loop {
+            match self.ask(question, default.filter(|default| !default.is_empty()))? {
+                Some(answer) => return Ok(answer),
+                None if self.yes => return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("no default for `{question}`"))),
+                None => eprintln!("an answer is required"),
+            }
+        }
+    }
+
+    /// Asks for an optional value until the answer is empty or valid.
+    fn parse<T: std::str::FromStr<Err: std::fmt::Display>>(&mut self, question: &str) -> io::Result<Option<T>> {
+        loop {
+            let Some(answer) = self.ask(question, None)? else { return Ok(None) };
+            match answer.parse() {
+                Ok(value) => return Ok(Some(value)),
+                Err(error) => eprintln!("{error}"),
+            }
+        }
+    }
+}
@@ -0,0 +1,44 @@
+//! The `citation` command line tool, built with the `cli` feature.
+
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+
+mod bump;
+mod convert;
+mod fix;
+mod fmt;
+mod init;
+mod readme;
+mod validate;
+
+/// Validate and convert CITATION.cff files.
+#[derive(Parser)]
+#[command(name = "citation", version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    Validate(validate::Args),
+    Convert(convert::Args),
+    Init(init::Args),
+    Fmt(fmt::Args),
+    Bump(bump::Args),
+    Fix(fix::Args),
+    Readme(readme::Args),
+}
+
+fn main() -> ExitCode {
+    match Cli::parse().command {
+        Command::Validate(args) => validate::run(args),
+        Command::Convert(args) => convert::run(args),
+        Command::Init(args) => init::run(args),
+        Command::Fmt(args) => fmt::run(args),
+        Command::Bump(args) => bump::run(args),
+        Command::Fix(args) => fix::run(args),
+        Command::Readme(args) => readme::run(args),
+    }
+}
@@ -0,0 +1,60 @@
+use std::{error::Error, fs, io, path::PathBuf, process::ExitCode};
+
+use citation::Citation;
+
+/// Add or update the "How to cite" section of a README, with the citation in APA style and as BibTeX.
+///
+/// The section is marked with `<!-- citation:start -->` and `<!-- citation:end -->` comments and appended to the
+/// README if it has none.
+#[derive(clap::Args)]
+pub(crate) struct Args {
+    /// The README to update.
+    #[arg(default_value = "README.md")]
+    readme: PathBuf,
+
+    /// The citation file to take the citation from.
+    #[arg(long, default_value = "CITATION.cff")]
+    citation: PathBuf,
+
+    /// Only check whether the section is up to date, and exit with 1 if it is not.
+    #[arg(long)]
+    check: bool,
+}
+
+pub(crate) fn run(args: Args) -> ExitCode {
+    match readme(&args) {
+        Ok(true) if args.check => {
+            println!("{}: the citation section is not up to date", args.readme.display());
+            ExitCode::FAILURE
+        }
+        Ok(true) => {
+            println!("{}: updated the citation section", args.readme.display());
+            ExitCode::SUCCESS
+        }
+        Ok(false) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Updates the section, returning whether it changed.
+fn readme(args: &Args) -> Result<bool, Box<dyn Error>> {
+    let citation = Citation::read(args.citation.clone())?;
+    let original = match fs::read_to_string(&args.readme) {
+        Ok(content) => content,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(error) => return Err(format!("{}: {error}", args.readme.display()).into()),
+    };
+
+    let updated = citation.update_markdown_section(&original);
+    if updated == original {
+        return Ok(false);
+    }
+    if !args.check {
+        fs::write(&args.readme, updated)?;
+    }
+
+    Ok(true)
+}
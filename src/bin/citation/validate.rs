@@ -0,0 +1,169 @@
+use std::{error::Error, fs, path::{Path, PathBuf}, process::ExitCode};
+
+use citation::{lint::{LintConfig, Linter}, Citation, ParseOptions, Severity, ValidationReport};
+use clap::ValueEnum;
+use serde_json::{json, Value};
+
+/// Validate CITATION.cff files.
+///
+/// Exits with 0 if all files are valid, 1 if there are warnings and 2 if there are errors.
+#[derive(clap::Args)]
+pub(crate) struct Args {
+    /// The files to validate.
+    #[arg(default_value = "CITATION.cff")]
+    files: Vec<PathBuf>,
+
+    /// Reject keys that are not defined by the schema.
+    #[arg(long)]
+    strict: bool,
+
+    /// A YAML file that disables rules or changes their severity, e.g. `rules: { CFF001: off }`.
+    #[arg(long)]
+    config: Option<PathBuf>,
+
+    /// How to print the results.
+    #[arg(long, value_enum, default_value_t = Format::Human)]
+    format: Format,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// A report for humans, with a suggestion for every issue.
+    Human,
+
+    /// A JSON array with a report per file.
+    Json,
+
+    /// A SARIF 2.1.0 log, e.g. for GitHub code scanning.
+    Sarif,
+
+    /// GitHub Actions workflow commands, which show the issues as annotations on pull requests.
+    Github,
+}
+
+/// The result of validating a file: a report, or the error that prevented parsing it.
+type Outcome = Result<ValidationReport, Box<dyn Error>>;
+
+pub(crate) fn run(args: Args) -> ExitCode {
+    let options = match args.strict {
+        true => ParseOptions::strict(),
+        false => ParseOptions::default(),
+    };
+
+    let linter = match args.config.map(LintConfig::read).transpose().and_then(|config| Linter::new(config.unwrap_or_default())) {
+        Ok(linter) => linter,
+        Err(error) => {
+            eprintln!("error: {error}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let outcomes: Vec<(&PathBuf, Outcome)> = args.files.iter().map(|file| (file, validate(file, &options, &linter))).collect();
+    match args.format {
+        Format::Human => outcomes.iter().for_each(|(file, outcome)| print(file, outcome)),
+        Format::Json => println!("{}", serde_json::to_string_pretty(&json(&outcomes)).unwrap_or_default()),
+        Format::Sarif => println!("{}", serde_json::to_string_pretty(&sarif(&outcomes)).unwrap_or_default()),
+        Format::Github => outcomes.iter().for_each(|(file, outcome)| github(file, outcome)),
+    }
+
+    let worst = outcomes
+        .iter()
+        .map(|(_, outcome)| match outcome {
+            Ok(report) => report.iter().map(|issue| issue.severity).max(),
+            Err(_) => Some(Severity::Error),
+        })
+        .max()
+        .flatten();
+
+    match worst {
+        Some(Severity::Error) => ExitCode::from(2),
+        Some(Severity::Warning) => ExitCode::from(1),
+        _ => ExitCode::SUCCESS,
+    }
+}
+
+fn validate(file: &Path, options: &ParseOptions, linter: &Linter) -> Outcome {
+    let content = fs::read_to_string(file)?;
+    let citation = Citation::parse_file(file, &content, options).map_err(citation::Error::from)?;
+
+    Ok(linter.apply(citation.validate()).with_source(file.display().to_string(), content))
+}
+
+/// Prints every issue with its location and a suggestion on how to fix it, followed by a summary.
+fn print(file: &Path, outcome: &Outcome) {
+    let report = match outcome {
+        Ok(report) => report,
+        Err(error) => return println!("{}: error: {error}", file.display()),
+    };
+
+    for issue in report {
+        let location = match issue.span {
+            Some(span) => format!("{}:{}:{}", file.display(), span.line, span.column),
+            None => file.display().to_string(),
+        };
+        println!("{location}: {}[{}] {}: {}", issue.severity, issue.code, issue.path, issue.message);
+        println!("  help: {}", issue.code.help());
+    }
+
+    println!("{}: {report}", file.display());
+}
+
+/// Returns the line and column of an error, if it is a parse error with a known location.
+fn error_location(error: &(dyn Error + 'static)) -> Option<(usize, usize)> {
+    error.downcast_ref::<citation::Error>()?.location()
+}
+
+fn json(outcomes: &[(&PathBuf, Outcome)]) -> Value {
+    let reports = outcomes.iter().map(|(file, outcome)| match outcome {
+        Ok(report) => serde_json::from_str(&report.to_json()).unwrap_or_default(),
+        Err(error) => {
+            let (line, column) = error_location(error.as_ref()).unzip();
+            json!({ "file": file.display().to_string(), "valid": false, "error": error.to_string(), "line": line, "column": column })
+        }
+    });
+
+    Value::Array(reports.collect())
+}
+
+/// Combines the reports into one SARIF log, reporting files that cannot be parsed with the rule `parse-error`.
+fn sarif(outcomes: &[(&PathBuf, Outcome)]) -> Value {
+    let reports = outcomes.iter().filter_map(|(_, outcome)| outcome.as_ref().ok());
+    let mut log: Value = serde_json::from_str(&ValidationReport::to_sarif_combined(reports)).unwrap_or_default();
+
+    let errors: Vec<Value> = outcomes
+        .iter()
+        .filter_map(|(file, outcome)| Some((file, outcome.as_ref().err()?)))
+        .map(|(file, error)| {
+            let mut location = json!({ "artifactLocation": { "uri": file.display().to_string() } });
+            if let Some((line, column)) = error_location(error.as_ref()) {
+                location["region"] = json!({ "startLine": line, "startColumn": column });
+            }
+            json!({ "ruleId": "parse-error", "level": "error", "message": { "text": error.to_string() }, "locations": [{ "physicalLocation": location }] })
+        })
+        .collect();
+
+    if !errors.is_empty() {
+        let run = &mut log["runs"][0];
+        if let Some(rules) = run["tool"]["driver"]["rules"].as_array_mut() {
+            rules.push(json!({ "id": "parse-error", "shortDescription": { "text": "fix the syntax of the file" }, "defaultConfiguration": { "level": "error" } }));
+        }
+        if let Some(results) = run["results"].as_array_mut() {
+            results.extend(errors);
+        }
+    }
+
+    log
+}
+
+/// Prints the issues as GitHub Actions workflow commands.
+fn github(file: &Path, outcome: &Outcome) {
+    match outcome {
+        Ok(report) => print!("{}", report.to_github_annotations()),
+        Err(error) => {
+            let location = error_location(error.as_ref()).map(|(line, column)| format!(",line={line},col={column}")).unwrap_or_default();
+            let escape = |value: String| value.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A");
+            let file = escape(file.display().to_string()).replace(':', "%3A").replace(',', "%2C");
+            println!("::error file={file}{location},title=parse-error::{}", escape(error.to_string()));
+        }
+    }
+}
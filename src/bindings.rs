@@ -0,0 +1,49 @@
+//! The names of styles and formats shared by the bindings for other languages, which take them as strings.
+
+use crate::{Citation, Error, OutputFormat, Style};
+
+/// Returns the style with the given name, e.g. `apa`.
+pub(crate) fn style(name: &str) -> Option<Style> {
+    match name {
+        "apa" => Some(Style::Apa),
+        "ieee" => Some(Style::Ieee),
+        "chicago" => Some(Style::Chicago),
+        "mla" => Some(Style::Mla),
+        "harvard" => Some(Style::Harvard),
+        "vancouver" => Some(Style::Vancouver),
+        _ => None,
+    }
+}
+
+/// Returns the output format with the given name, e.g. `html`.
+pub(crate) fn output(name: &str) -> Option<OutputFormat> {
+    match name {
+        "plain" => Some(OutputFormat::Plain),
+        "markdown" => Some(OutputFormat::Markdown),
+        "html" => Some(OutputFormat::Html),
+        "latex" => Some(OutputFormat::Latex),
+        _ => None,
+    }
+}
+
+/// Converts the citation to the format with the given name, named like the formats of `citation convert`, or
+/// returns `None` if there is no such format.
+pub(crate) fn convert(citation: &Citation, to: &str) -> Option<Result<String, Error>> {
+    let converted = match to {
+        "cff" => return Some(citation.to_yaml_string()),
+        "bibtex" => citation.to_bibtex(),
+        "biblatex" => citation.to_biblatex(),
+        "ris" => citation.to_ris(),
+        "csl-json" => citation.to_csl_json(),
+        "codemeta" => citation.to_codemeta(),
+        "zenodo" => citation.to_zenodo_json(),
+        "json-ld" => citation.to_json_ld(),
+        "datacite-xml" => citation.to_datacite_xml(),
+        "datacite-json" => citation.to_datacite_json(),
+        "dublin-core" => citation.to_dublin_core(),
+        "endnote" => citation.to_endnote_xml(),
+        _ => return None,
+    };
+
+    Some(Ok(converted))
+}
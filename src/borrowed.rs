@@ -0,0 +1,195 @@
+use std::borrow::Cow;
+
+use serde::{Deserialize, Deserializer};
+
+use crate::{parse::is_json, Date, ParseError};
+
+/// A borrowed view of the top-level keys of a `CITATION.cff` file, e.g. to index thousands of files.
+///
+/// Unlike [`Citation`](crate::Citation), strings are borrowed from the content instead of allocated, unless they
+/// contain escape sequences or span several lines. Values are not validated beyond their type, and
+/// `preferred-citation`, `references` and unknown keys are skipped without being read.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CitationRef<'a> {
+    #[serde(borrow)]
+    pub cff_version: Cow<'a, str>,
+
+    #[serde(borrow)]
+    pub title: Cow<'a, str>,
+
+    #[serde(borrow)]
+    pub message: Cow<'a, str>,
+
+    #[serde(borrow, default, deserialize_with = "option")]
+    pub r#type: Option<Cow<'a, str>>,
+
+    #[serde(borrow, default)]
+    pub authors: Vec<AuthorRef<'a>>,
+
+    #[serde(borrow, default)]
+    pub contact: Vec<AuthorRef<'a>>,
+
+    #[serde(borrow, default, deserialize_with = "option")]
+    pub r#abstract: Option<Cow<'a, str>>,
+
+    #[serde(borrow, default, deserialize_with = "option")]
+    pub version: Option<Cow<'a, str>>,
+
+    #[serde(borrow, default, deserialize_with = "option")]
+    pub commit: Option<Cow<'a, str>>,
+
+    #[serde(default)]
+    pub date_released: Option<Date>,
+
+    #[serde(borrow, default, deserialize_with = "option")]
+    pub doi: Option<Cow<'a, str>>,
+
+    #[serde(borrow, default)]
+    pub identifiers: Vec<IdentifierRef<'a>>,
+
+    #[serde(borrow, default, deserialize_with = "list")]
+    pub keywords: Vec<Cow<'a, str>>,
+
+    /// The license, or the licenses if there are several.
+    #[serde(borrow, default, deserialize_with = "one_or_many")]
+    pub license: Vec<Cow<'a, str>>,
+
+    #[serde(borrow, default, deserialize_with = "option")]
+    pub license_url: Option<Cow<'a, str>>,
+
+    #[serde(borrow, default, deserialize_with = "option")]
+    pub repository: Option<Cow<'a, str>>,
+
+    #[serde(borrow, default, deserialize_with = "option")]
+    pub repository_artifact: Option<Cow<'a, str>>,
+
+    #[serde(borrow, default, deserialize_with = "option")]
+    pub repository_code: Option<Cow<'a, str>>,
+
+    #[serde(borrow, default, deserialize_with = "option")]
+    pub url: Option<Cow<'a, str>>,
+}
+
+/// A borrowed view of a person or an entity, see [`CitationRef`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AuthorRef<'a> {
+    /// The name of an entity.
+    #[serde(borrow, default, deserialize_with = "option")]
+    pub name: Option<Cow<'a, str>>,
+
+    #[serde(borrow, default, deserialize_with = "option")]
+    pub given_names: Option<Cow<'a, str>>,
+
+    #[serde(borrow, default, deserialize_with = "option")]
+    pub name_particle: Option<Cow<'a, str>>,
+
+    #[serde(borrow, default, deserialize_with = "option")]
+    pub family_names: Option<Cow<'a, str>>,
+
+    #[serde(borrow, default, deserialize_with = "option")]
+    pub name_suffix: Option<Cow<'a, str>>,
+
+    #[serde(borrow, default, deserialize_with = "option")]
+    pub alias: Option<Cow<'a, str>>,
+
+    #[serde(borrow, default, deserialize_with = "option")]
+    pub affiliation: Option<Cow<'a, str>>,
+
+    #[serde(borrow, default, deserialize_with = "option")]
+    pub email: Option<Cow<'a, str>>,
+
+    #[serde(borrow, default, deserialize_with = "option")]
+    pub orcid: Option<Cow<'a, str>>,
+
+    #[serde(borrow, default, deserialize_with = "option")]
+    pub website: Option<Cow<'a, str>>,
+}
+
+impl AuthorRef<'_> {
+    /// Returns `true` if the author is an entity, i.e. has a `name`.
+    pub fn is_entity(&self) -> bool {
+        self.name.is_some()
+    }
+
+    /// Returns the name under which the author should be displayed, like [`Author::display_name`](crate::Author::display_name).
+    pub fn display_name(&self) -> String {
+        if let Some(name) = &self.name {
+            return name.to_string();
+        }
+
+        let parts = [&self.given_names, &self.name_particle, &self.family_names, &self.name_suffix];
+        let name = parts.into_iter().flatten().map(|part| part.trim()).filter(|part| !part.is_empty()).collect::<Vec<_>>().join(" ");
+
+        match (&self.alias, name.is_empty()) {
+            (Some(alias), true) => alias.to_string(),
+            _ => name,
+        }
+    }
+}
+
+/// A borrowed view of an identifier, see [`CitationRef`].
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct IdentifierRef<'a> {
+    /// The type of the identifier, e.g. `doi`.
+    #[serde(borrow)]
+    pub r#type: Cow<'a, str>,
+
+    #[serde(borrow)]
+    pub value: Cow<'a, str>,
+
+    #[serde(borrow, default, deserialize_with = "option")]
+    pub description: Option<Cow<'a, str>>,
+}
+
+/// A string borrowed from the content if possible. serde only borrows into a `Cow` that is the type of a field, not
+/// into one inside an `Option` or a `Vec`.
+#[derive(Deserialize)]
+#[serde(transparent)]
+struct Borrowed<'a>(#[serde(borrow)] Cow<'a, str>);
+
+/// Deserializes an optional string, borrowing it if possible.
+fn option<'de: 'a, 'a, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Cow<'a, str>>, D::Error> {
+    Ok(Option::<Borrowed>::deserialize(deserializer)?.map(|value| value.0))
+}
+
+/// Deserializes a list of strings, borrowing them if possible.
+fn list<'de: 'a, 'a, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Cow<'a, str>>, D::Error> {
+    Ok(Vec::<Borrowed>::deserialize(deserializer)?.into_iter().map(|value| value.0).collect())
+}
+
+/// Deserializes a single license or a list of licenses.
+fn one_or_many<'de: 'a, 'a, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Cow<'a, str>>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany<'a> {
+        One(#[serde(borrow)] Cow<'a, str>),
+        Many(#[serde(borrow)] Vec<Borrowed<'a>>),
+    }
+
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(license) => vec![license],
+        OneOrMany::Many(licenses) => licenses.into_iter().map(|license| license.0).collect(),
+    })
+}
+
+impl<'a> CitationRef<'a> {
+    /// Parses the content of a `CITATION.cff` file written in YAML or JSON, borrowing its strings.
+    pub fn parse(content: &'a str) -> Result<Self, ParseError> {
+        match is_json(content) {
+            true => {
+                let mut deserializer = serde_json::Deserializer::from_str(content);
+                let citation = serde_path_to_error::deserialize(&mut deserializer).map_err(|error| ParseError::from_json(error.inner(), Some(error.path().to_string()), content))?;
+                deserializer.end().map_err(|error| ParseError::from_json(&error, None, content))?;
+                Ok(citation)
+            }
+            false => serde_path_to_error::deserialize(serde_yaml::Deserializer::from_str(content)).map_err(|error| ParseError::from_yaml(error, content)),
+        }
+    }
+
+    /// Returns the DOI of the work, from `doi` or else the first identifier of type `doi`.
+    pub fn doi(&self) -> Option<&str> {
+        self.doi.as_deref().or_else(|| self.identifiers.iter().find(|identifier| identifier.r#type == "doi").map(|identifier| &*identifier.value))
+    }
+}
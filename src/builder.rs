@@ -0,0 +1,207 @@
+use std::{error::Error, fmt};
+
+use crate::{Author, Citation, Contact, Date, Doi, Identifier, Licenses, Reference, Type, DEFAULT_MESSAGE};
+
+/// Builder for [`Citation`], created with [`Citation::builder`].
+///
+/// The required keys of the schema (`authors`, `cff-version`, `title`) have to be
+/// set before [`CitationBuilder::build`] succeeds. `message` and `type` fall back
+/// to the defaults of the schema.
+#[derive(Debug, Clone, Default)]
+pub struct CitationBuilder {
+    r#abstract: Option<String>,
+    authors: Vec<Author>,
+    cff_version: Option<String>,
+    commit: Option<String>,
+    contact: Vec<Contact>,
+    date_released: Option<Date>,
+    doi: Option<Doi>,
+    identifiers: Vec<Identifier>,
+    keywords: Vec<String>,
+    license: Option<Licenses>,
+    license_url: Option<String>,
+    message: Option<String>,
+    preferred_citation: Option<Reference>,
+    references: Vec<Reference>,
+    repository: Option<String>,
+    repository_artifact: Option<String>,
+    repository_code: Option<String>,
+    title: Option<String>,
+    r#type: Option<Type>,
+    url: Option<String>,
+    version: Option<String>,
+}
+
+impl CitationBuilder {
+    pub fn r#abstract(mut self, r#abstract: impl Into<String>) -> Self {
+        self.r#abstract = Some(r#abstract.into());
+        self
+    }
+
+    /// Appends a single author.
+    pub fn author(mut self, author: Author) -> Self {
+        self.authors.push(author);
+        self
+    }
+
+    /// Appends all given authors.
+    pub fn authors(mut self, authors: impl IntoIterator<Item = Author>) -> Self {
+        self.authors.extend(authors);
+        self
+    }
+
+    pub fn cff_version(mut self, cff_version: impl Into<String>) -> Self {
+        self.cff_version = Some(cff_version.into());
+        self
+    }
+
+    pub fn commit(mut self, commit: impl Into<String>) -> Self {
+        self.commit = Some(commit.into());
+        self
+    }
+
+    /// Appends a single contact.
+    pub fn contact(mut self, contact: Contact) -> Self {
+        self.contact.push(contact);
+        self
+    }
+
+    pub fn date_released(mut self, date_released: Date) -> Self {
+        self.date_released = Some(date_released);
+        self
+    }
+
+    pub fn doi(mut self, doi: Doi) -> Self {
+        self.doi = Some(doi);
+        self
+    }
+
+    /// Appends a single identifier.
+    pub fn identifier(mut self, identifier: Identifier) -> Self {
+        self.identifiers.push(identifier);
+        self
+    }
+
+    /// Appends a single keyword.
+    pub fn keyword(mut self, keyword: impl Into<String>) -> Self {
+        self.keywords.push(keyword.into());
+        self
+    }
+
+    /// Appends all given keywords.
+    pub fn keywords<S: Into<String>>(mut self, keywords: impl IntoIterator<Item = S>) -> Self {
+        self.keywords.extend(keywords.into_iter().map(Into::into));
+        self
+    }
+
+    /// Sets a single license or a list of licenses.
+    pub fn license(mut self, license: impl Into<Licenses>) -> Self {
+        self.license = Some(license.into());
+        self
+    }
+
+    pub fn license_url(mut self, license_url: impl Into<String>) -> Self {
+        self.license_url = Some(license_url.into());
+        self
+    }
+
+    pub fn message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+
+    pub fn preferred_citation(mut self, preferred_citation: Reference) -> Self {
+        self.preferred_citation = Some(preferred_citation);
+        self
+    }
+
+    /// Appends a single reference.
+    pub fn reference(mut self, reference: Reference) -> Self {
+        self.references.push(reference);
+        self
+    }
+
+    pub fn repository(mut self, repository: impl Into<String>) -> Self {
+        self.repository = Some(repository.into());
+        self
+    }
+
+    pub fn repository_artifact(mut self, repository_artifact: impl Into<String>) -> Self {
+        self.repository_artifact = Some(repository_artifact.into());
+        self
+    }
+
+    pub fn repository_code(mut self, repository_code: impl Into<String>) -> Self {
+        self.repository_code = Some(repository_code.into());
+        self
+    }
+
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    pub fn r#type(mut self, r#type: Type) -> Self {
+        self.r#type = Some(r#type);
+        self
+    }
+
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    pub fn version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    /// Builds the [`Citation`], failing if a required key has not been set.
+    pub fn build(self) -> Result<Citation, BuildError> {
+        if self.authors.is_empty() {
+            return Err(BuildError::MissingField("authors"));
+        }
+
+        Ok(Citation {
+            r#abstract: self.r#abstract,
+            authors: self.authors,
+            cff_version: self.cff_version.ok_or(BuildError::MissingField("cff-version"))?,
+            commit: self.commit,
+            contact: self.contact,
+            date_released: self.date_released,
+            doi: self.doi,
+            identifiers: self.identifiers,
+            keywords: self.keywords,
+            license: self.license,
+            license_url: self.license_url,
+            message: self.message.unwrap_or_else(|| DEFAULT_MESSAGE.to_string()),
+            preferred_citation: self.preferred_citation,
+            references: self.references,
+            repository: self.repository,
+            repository_artifact: self.repository_artifact,
+            repository_code: self.repository_code,
+            title: self.title.ok_or(BuildError::MissingField("title"))?,
+            r#type: Some(self.r#type.unwrap_or_default()),
+            url: self.url,
+            version: self.version,
+            extra: Default::default(),
+        })
+    }
+}
+
+/// Error returned by [`CitationBuilder::build`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuildError {
+    /// A key required by the schema has not been set.
+    MissingField(&'static str),
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildError::MissingField(field) => write!(f, "missing required field `{field}`"),
+        }
+    }
+}
+
+impl Error for BuildError {}
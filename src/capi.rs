@@ -0,0 +1,186 @@
+//! A C API, built with the `capi` feature, e.g. for editors and CI tools written in C or C++.
+//!
+//! The declarations are in `include/citation.h`, which is generated by [cbindgen](https://github.com/mozilla/cbindgen)
+//! with `cbindgen --config cbindgen.toml --output include/citation.h`. The library is built with
+//! `cargo rustc --lib --release --features capi --crate-type cdylib` (or `staticlib`). Strings returned by the
+//! functions belong to the caller and are released with `citation_string_free`, citations with `citation_free`.
+//! Panics do not unwind into the caller: the functions return `NULL` instead.
+//!
+//! ```c
+//! char *error = NULL;
+//! CitationHandle *citation = citation_parse(content, length, &error);
+//! if (citation == NULL) {
+//!     fprintf(stderr, "%s\n", error);
+//!     citation_string_free(error);
+//!     return 1;
+//! }
+//!
+//! char *bibtex = citation_to_bibtex(citation);
+//! puts(bibtex);
+//! citation_string_free(bibtex);
+//! citation_free(citation);
+//! ```
+
+use std::{
+    any::Any,
+    ffi::{c_char, CStr, CString},
+    panic::{catch_unwind, AssertUnwindSafe},
+    ptr, slice,
+};
+
+use crate::{bindings, parse::parse, Citation, ParseOptions};
+
+/// A parsed `CITATION.cff` file, created by `citation_parse` and released by `citation_free`.
+pub struct CitationHandle {
+    citation: Citation,
+
+    /// The content the citation has been parsed from, which locates the issues found by `citation_validate`.
+    content: String,
+}
+
+/// Moves a string to the caller, dropping null bytes, which C strings cannot contain.
+fn string(value: impl Into<String>) -> *mut c_char {
+    let mut value = value.into();
+    value.retain(|c| c != '\0');
+
+    CString::new(value).map_or(ptr::null_mut(), CString::into_raw)
+}
+
+/// Describes the payload of a panic, which is usually its message.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    let message = payload.downcast_ref::<&str>().copied().or_else(|| payload.downcast_ref::<String>().map(String::as_str));
+
+    format!("internal error: {}", message.unwrap_or("unknown panic"))
+}
+
+/// Runs the body of an entry point, returning `NULL` instead of unwinding into the caller if it panics.
+fn guard<T>(body: impl FnOnce() -> *mut T) -> *mut T {
+    catch_unwind(AssertUnwindSafe(body)).unwrap_or(ptr::null_mut())
+}
+
+/// Parses the content of a `CITATION.cff` file, in YAML or JSON, from a buffer of `length` bytes of UTF-8, which
+/// does not need to end with a null byte.
+///
+/// Returns `NULL` if the content cannot be parsed, or if parsing fails unexpectedly, and stores a description of the
+/// problem in `*error` if `error` is not `NULL`.
+///
+/// # Safety
+///
+/// `buffer` must point to `length` readable bytes, and `error` must be `NULL` or point to a writable `char *`.
+#[no_mangle]
+pub unsafe extern "C" fn citation_parse(buffer: *const u8, length: usize, error: *mut *mut c_char) -> *mut CitationHandle {
+    let fail = |message: String| {
+        if !error.is_null() {
+            *error = string(message);
+        }
+        ptr::null_mut()
+    };
+
+    if buffer.is_null() {
+        return fail("the buffer is NULL".to_string());
+    }
+    let content = match std::str::from_utf8(slice::from_raw_parts(buffer, length)) {
+        Ok(content) => content,
+        Err(utf8) => return fail(format!("the content is not valid UTF-8: {utf8}")),
+    };
+
+    match catch_unwind(|| parse(content, &ParseOptions::default())) {
+        Ok(Ok(citation)) => Box::into_raw(Box::new(CitationHandle { citation, content: content.to_string() })),
+        Ok(Err(parse)) => fail(parse.to_string()),
+        Err(payload) => fail(panic_message(payload.as_ref())),
+    }
+}
+
+/// Validates the citation and returns the report as JSON, with whether the citation is valid, the number of issues
+/// per severity and every issue with its code, path, message, help and location. Returns `NULL` if `citation` is
+/// `NULL`.
+///
+/// # Safety
+///
+/// `citation` must be `NULL` or a citation returned by `citation_parse` that has not been released.
+#[no_mangle]
+pub unsafe extern "C" fn citation_validate(citation: *const CitationHandle) -> *mut c_char {
+    let Some(handle) = citation.as_ref() else { return ptr::null_mut() };
+
+    guard(|| string(handle.citation.validate().with_source("CITATION.cff", handle.content.as_str()).to_json()))
+}
+
+/// Converts the citation to a BibTeX entry, or returns `NULL` if `citation` is `NULL` or the conversion fails
+/// unexpectedly.
+///
+/// # Safety
+///
+/// `citation` must be `NULL` or a citation returned by `citation_parse` that has not been released.
+#[no_mangle]
+pub unsafe extern "C" fn citation_to_bibtex(citation: *const CitationHandle) -> *mut c_char {
+    let Some(handle) = citation.as_ref() else { return ptr::null_mut() };
+
+    guard(|| string(handle.citation.to_bibtex()))
+}
+
+/// Formats the citation in a style, one of `apa`, `ieee`, `chicago`, `mla`, `harvard` and `vancouver`, as `plain`
+/// text, `markdown`, `html` or `latex`. Returns `NULL` if there is no such style or output format.
+///
+/// # Safety
+///
+/// `citation` must be `NULL` or a citation returned by `citation_parse` that has not been released, and `style` and
+/// `output` must be `NULL` or null-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn citation_format(citation: *const CitationHandle, style: *const c_char, output: *const c_char) -> *mut c_char {
+    let (Some(handle), false, false) = (citation.as_ref(), style.is_null(), output.is_null()) else {
+        return ptr::null_mut();
+    };
+    let style = CStr::from_ptr(style).to_str().ok().and_then(bindings::style);
+    let output = CStr::from_ptr(output).to_str().ok().and_then(bindings::output);
+
+    match (style, output) {
+        (Some(style), Some(output)) => guard(|| string(handle.citation.format_as(style, output))),
+        _ => ptr::null_mut(),
+    }
+}
+
+/// Converts the citation to another format, named like the formats of `citation convert`: `cff`, `bibtex`,
+/// `biblatex`, `ris`, `csl-json`, `codemeta`, `zenodo`, `json-ld`, `datacite-xml`, `datacite-json`,
+/// `dublin-core` or `endnote`. Returns `NULL` if there is no such format or the conversion fails.
+///
+/// # Safety
+///
+/// `citation` must be `NULL` or a citation returned by `citation_parse` that has not been released, and `to` must
+/// be `NULL` or a null-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn citation_convert(citation: *const CitationHandle, to: *const c_char) -> *mut c_char {
+    let (Some(handle), false) = (citation.as_ref(), to.is_null()) else {
+        return ptr::null_mut();
+    };
+
+    let Ok(to) = CStr::from_ptr(to).to_str() else { return ptr::null_mut() };
+
+    guard(|| match bindings::convert(&handle.citation, to) {
+        Some(Ok(converted)) => string(converted),
+        _ => ptr::null_mut(),
+    })
+}
+
+/// Releases a citation returned by `citation_parse`. Does nothing if `citation` is `NULL`.
+///
+/// # Safety
+///
+/// `citation` must be `NULL` or a citation returned by `citation_parse` that has not been released.
+#[no_mangle]
+pub unsafe extern "C" fn citation_free(citation: *mut CitationHandle) {
+    if !citation.is_null() {
+        drop(Box::from_raw(citation));
+    }
+}
+
+/// Releases a string returned by one of the functions. Does nothing if `string` is `NULL`.
+///
+/// # Safety
+///
+/// `string` must be `NULL` or a string returned by one of the functions that has not been released.
+#[no_mangle]
+pub unsafe extern "C" fn citation_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
@@ -0,0 +1,512 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    io,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use serde::Deserialize;
+
+use crate::{bibtex::with_key, parse::toml_to_yaml, Author, Citation, Doi, Error, License, Licenses, ParseError, Person, Style, CFF_VERSION};
+
+#[derive(Deserialize)]
+struct Manifest {
+    package: Option<Package>,
+    workspace: Option<Workspace>,
+}
+
+#[derive(Deserialize)]
+struct Workspace {
+    package: Option<Package>,
+}
+
+/// The keys of `[package]` (or `[workspace.package]`) that are mapped to the citation.
+#[derive(Deserialize)]
+struct Package {
+    name: Option<String>,
+    version: Option<Inheritable<String>>,
+    description: Option<Inheritable<String>>,
+    authors: Option<Inheritable<Vec<String>>>,
+    license: Option<Inheritable<String>>,
+    repository: Option<Inheritable<String>>,
+    homepage: Option<Inheritable<String>>,
+    keywords: Option<Inheritable<Vec<String>>>,
+    metadata: Option<Metadata>,
+}
+
+#[derive(Deserialize)]
+struct Metadata {
+    citation: Option<toml::Table>,
+}
+
+/// A value that is either set directly or inherited from the workspace with `{ workspace = true }`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Inheritable<T> {
+    Value(T),
+    Workspace { workspace: bool },
+}
+
+impl<T: Clone> Inheritable<T> {
+    /// Resolves the value of a key of the package, given the value of the same key in the workspace.
+    fn resolve(value: Option<&Self>, workspace: Option<&Self>) -> Option<T> {
+        match (value?, workspace) {
+            (Inheritable::Value(value), _) => Some(value.clone()),
+            (Inheritable::Workspace { workspace: true }, Some(Inheritable::Value(value))) => Some(value.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl Citation {
+    /// Creates a citation from the `[package]` metadata of a `Cargo.toml` manifest.
+    ///
+    /// The path may point to the manifest or to the directory containing it. The keys are mapped as follows:
+    ///
+    /// | Cargo.toml    | CITATION.cff      |
+    /// |---------------|-------------------|
+    /// | `name`        | `title`           |
+    /// | `version`     | `version`         |
+    /// | `description` | `abstract`        |
+    /// | `authors`     | `authors`         |
+    /// | `license`     | `license`         |
+    /// | `repository`  | `repository-code` |
+    /// | `homepage`    | `url`             |
+    /// | `keywords`    | `keywords`        |
+    ///
+    /// Values inherited with `{ workspace = true }` are looked up in the workspace root.
+    /// Keys of a `[package.metadata.citation]` table take precedence over the mapped keys, so that values Cargo
+    /// cannot express (e.g. ORCIDs of the authors or a DOI) can be kept in the manifest:
+    ///
+    /// ```toml
+    /// [package.metadata.citation]
+    /// doi = "10.5281/zenodo.1234"
+    /// authors = [{ given-names = "Luca", family-names = "Lewin", orcid = "https://orcid.org/0009-0005-1296-096X" }]
+    /// ```
+    ///
+    /// Authors in the `Name <email>` format are split into given and family names.
+    /// License expressions are mapped if they only combine licenses with `OR` (or the legacy `/`),
+    /// as CFF cannot express `AND` or `WITH`.
+    ///
+    /// Fails if the manifest has no `[package]` table or no authors, as both `title` and `authors` are required.
+    pub fn from_cargo_manifest(path: PathBuf) -> Result<Self, Error> {
+        let path = match path.is_dir() {
+            true => path.join("Cargo.toml"),
+            false => path,
+        };
+
+        let manifest = read_manifest(&path)?;
+        let package = manifest.package.ok_or_else(|| Error::schema("package", "the manifest has no [package] table"))?;
+        let workspace = match &manifest.workspace {
+            Some(workspace) => workspace.package.as_ref(),
+            None => None,
+        };
+        let root = match workspace {
+            Some(_) => None,
+            None => workspace_package(&path)?,
+        };
+        let workspace = workspace.or(root.as_ref());
+
+        let inherit = |field: fn(&Package) -> Option<&Inheritable<String>>| {
+            Inheritable::resolve(field(&package), workspace.and_then(field))
+        };
+        let inherit_list = |field: fn(&Package) -> Option<&Inheritable<Vec<String>>>| {
+            Inheritable::resolve(field(&package), workspace.and_then(field)).unwrap_or_default()
+        };
+
+        // the metadata table may provide the authors if the package has none
+        let metadata = package.metadata.as_ref().and_then(|metadata| metadata.citation.clone()).map(|table| toml_to_yaml(toml::Value::Table(table)));
+        let authors: Vec<Author> = match metadata.as_ref().and_then(|metadata| metadata.get("authors")) {
+            Some(authors) => serde_yaml::from_value(authors.clone()).map_err(|error| Error::schema("package.metadata.citation.authors", error))?,
+            None => inherit_list(|package| package.authors.as_ref()).iter().map(|author| parse_author(author)).collect(),
+        };
+
+        let mut builder = Citation::builder()
+            .cff_version(CFF_VERSION)
+            .title(package.name.clone().ok_or_else(|| Error::schema("package.name", "the package has no name"))?)
+            .authors(authors)
+            .keywords(inherit_list(|package| package.keywords.as_ref()));
+
+        if let Some(version) = inherit(|package| package.version.as_ref()) {
+            builder = builder.version(version);
+        }
+        if let Some(description) = inherit(|package| package.description.as_ref()) {
+            builder = builder.r#abstract(description.trim());
+        }
+        if let Some(licenses) = inherit(|package| package.license.as_ref()).and_then(|license| parse_license(&license)) {
+            builder = builder.license(licenses);
+        }
+        if let Some(repository) = inherit(|package| package.repository.as_ref()) {
+            builder = builder.repository_code(repository);
+        }
+        if let Some(homepage) = inherit(|package| package.homepage.as_ref()) {
+            builder = builder.url(homepage);
+        }
+
+        let citation = builder.build()?;
+        let Some(serde_yaml::Value::Mapping(metadata)) = metadata else { return Ok(citation) };
+
+        let mut value = serde_yaml::to_value(&citation)?;
+        for (key, metadata) in metadata {
+            value[&key] = metadata;
+        }
+
+        serde_yaml::from_value(value).map_err(|error| Error::schema("package.metadata.citation", error))
+    }
+}
+
+/// Finds the `[workspace.package]` table of the workspace root above the manifest, if any.
+fn workspace_package(manifest: &Path) -> Result<Option<Package>, Error> {
+    let Some(directory) = manifest.parent() else { return Ok(None) };
+
+    for directory in directory.ancestors().skip(1) {
+        let path = directory.join("Cargo.toml");
+        if !path.is_file() {
+            continue;
+        }
+
+        let manifest = read_manifest(&path)?;
+        if let Some(workspace) = manifest.workspace {
+            return Ok(workspace.package);
+        }
+    }
+
+    Ok(None)
+}
+
+/// Reads and parses the manifest at the given path.
+fn read_manifest(path: &Path) -> Result<Manifest, Error> {
+    let content = std::fs::read_to_string(path)?;
+
+    toml::from_str(&content).map_err(|error| ParseError::from_toml(&error, &content).with_file_name(path.display().to_string()).into())
+}
+
+/// Parses an author in the `Name <email>` format used by Cargo.
+fn parse_author(author: &str) -> Author {
+    let (name, email) = match author.split_once('<') {
+        Some((name, rest)) => (name.trim(), rest.split_once('>').map(|(email, _)| email.trim().to_string())),
+        None => (author.trim(), None),
+    };
+
+    Author::Person(Person { email, ..Person::from_name(name) })
+}
+
+/// Maps an SPDX expression such as `MIT OR Apache-2.0` to licenses, if it only uses `OR`.
+fn parse_license(expression: &str) -> Option<Licenses> {
+    let expression = expression.replace(['(', ')'], " ");
+    let mut licenses = Vec::new();
+
+    for part in expression.split('/').flat_map(|part| part.split(" OR ")) {
+        let id = part.trim();
+        if id.is_empty() || id.contains(' ') {
+            return None;
+        }
+
+        licenses.push(License::from_id(id));
+    }
+
+    match licenses.len() {
+        0 => None,
+        1 => licenses.pop().map(Licenses::Single),
+        _ => Some(Licenses::Multiple(licenses)),
+    }
+}
+
+/// The output of `cargo metadata`, limited to the packages and the dependency graph.
+#[derive(Deserialize)]
+struct CargoMetadata {
+    packages: Vec<MetadataPackage>,
+    workspace_members: Vec<String>,
+    workspace_root: PathBuf,
+    resolve: Option<Resolve>,
+}
+
+#[derive(Deserialize)]
+struct MetadataPackage {
+    id: String,
+    name: String,
+    version: String,
+    manifest_path: PathBuf,
+    #[cfg(feature = "http")]
+    repository: Option<String>,
+    readme: Option<PathBuf>,
+}
+
+#[derive(Deserialize)]
+struct Resolve {
+    nodes: Vec<Node>,
+}
+
+#[derive(Deserialize)]
+struct Node {
+    id: String,
+    #[serde(default)]
+    deps: Vec<NodeDep>,
+}
+
+#[derive(Deserialize)]
+struct NodeDep {
+    pkg: String,
+    #[serde(default)]
+    dep_kinds: Vec<DepKind>,
+}
+
+#[derive(Deserialize)]
+struct DepKind {
+    kind: Option<String>,
+}
+
+/// Options for [`Citation::from_cargo_dependencies`].
+#[derive(Debug, Clone, Default)]
+pub struct DependencyOptions {
+    /// Also includes the dev-dependencies of the workspace members, which are only used by tests, examples and
+    /// benchmarks, and their dependencies.
+    pub dev_dependencies: bool,
+
+    /// Fetches the `CITATION.cff` file from the GitHub repository of a dependency if its package has none, see
+    /// [`Citation::fetch_from_github`], and otherwise the metadata registered for a DOI found in its README, see
+    /// [`Citation::from_doi`]. Requires the `http` feature, [`Citation::from_cargo_dependencies`] fails without it.
+    pub fetch: bool,
+}
+
+/// Where the citation of a [`Dependency`] has been found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DependencySource {
+    /// The `CITATION.cff` file in the source of the package, e.g. in the registry cache of Cargo.
+    File(PathBuf),
+
+    /// The `CITATION.cff` file of the GitHub repository of the package, e.g. `serde-rs/serde`.
+    Repository(String),
+
+    /// The metadata registered for a DOI found in the README of the package, e.g. in a Zenodo badge.
+    Doi(Doi),
+
+    /// The `[package]` metadata of the manifest of the package, see [`Citation::from_cargo_manifest`].
+    Manifest(PathBuf),
+}
+
+/// A dependency of a Cargo workspace with its citation, see [`Citation::from_cargo_dependencies`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dependency {
+    /// The name of the package.
+    pub name: String,
+
+    /// The version of the package.
+    pub version: String,
+
+    pub citation: Citation,
+
+    pub source: DependencySource,
+}
+
+/// The dependencies of a Cargo workspace with their citations, see [`Citation::from_cargo_dependencies`].
+#[derive(Debug, Default)]
+pub struct Dependencies {
+    /// The dependencies whose citation has been found, ordered by name and version.
+    pub dependencies: Vec<Dependency>,
+
+    /// The names of the dependencies whose citation could not be read or created, e.g. because their manifest has
+    /// no authors.
+    pub errors: Vec<(String, Error)>,
+}
+
+impl Dependencies {
+    /// Returns the key of the BibTeX entry of a dependency: its name, followed by its version if several versions
+    /// of the package are used.
+    fn key(&self, dependency: &Dependency) -> String {
+        match self.dependencies.iter().filter(|other| other.name == dependency.name).count() {
+            1 => dependency.name.clone(),
+            _ => format!("{}-{}", dependency.name, dependency.version),
+        }
+    }
+
+    /// Formats the citations as a BibTeX bibliography, see [`Citation::to_bibtex`]. The entries are keyed by the
+    /// names of the packages, e.g. `serde`, so that they can be cited in a document.
+    pub fn to_bibtex(&self) -> String {
+        let entries = self.dependencies.iter().map(|dependency| with_key(&dependency.citation.to_bibtex(), &self.key(dependency)));
+
+        entries.collect::<Vec<_>>().join("\n")
+    }
+
+    /// Formats the citations as a reference list in the given style, one citation per line.
+    pub fn format(&self, style: Style) -> String {
+        self.dependencies.iter().map(|dependency| format!("{}\n", dependency.citation.format(style))).collect()
+    }
+}
+
+/// Runs `cargo metadata` for the manifest with the given extra arguments, using the cargo that runs the build if any.
+fn cargo_metadata(manifest: &Path, args: &[&str]) -> Result<CargoMetadata, Error> {
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let output = Command::new(cargo).args(["metadata", "--format-version", "1"]).args(args).arg("--manifest-path").arg(manifest).output()?;
+    if !output.status.success() {
+        let message = String::from_utf8_lossy(&output.stderr);
+        return Err(io::Error::other(format!("cargo metadata failed: {}", message.trim())).into());
+    }
+
+    serde_json::from_slice(&output.stdout).map_err(|error| io::Error::other(format!("cannot read the output of cargo metadata: {error}")).into())
+}
+
+/// Returns the ids of the packages the workspace members depend on, directly or indirectly, in the order of their
+/// names and versions.
+fn dependency_ids(metadata: &CargoMetadata, options: &DependencyOptions) -> Vec<String> {
+    let Some(resolve) = &metadata.resolve else { return Vec::new() };
+    let nodes: HashMap<&str, &Node> = resolve.nodes.iter().map(|node| (node.id.as_str(), node)).collect();
+    let members: BTreeSet<&str> = metadata.workspace_members.iter().map(String::as_str).collect();
+
+    let mut found: BTreeSet<&str> = BTreeSet::new();
+    let mut queue: Vec<(&str, bool)> = members.iter().map(|member| (*member, true)).collect();
+    while let Some((id, member)) = queue.pop() {
+        let Some(node) = nodes.get(id) else { continue };
+        for dependency in &node.deps {
+            // dev-dependencies are only part of the build of the workspace members themselves
+            let used = dependency.dep_kinds.is_empty() || dependency.dep_kinds.iter().any(|kind| kind.kind.as_deref() != Some("dev") || (member && options.dev_dependencies));
+            if used && !members.contains(dependency.pkg.as_str()) && found.insert(&dependency.pkg) {
+                queue.push((&dependency.pkg, false));
+            }
+        }
+    }
+
+    let mut packages: Vec<&MetadataPackage> = metadata.packages.iter().filter(|package| found.contains(package.id.as_str())).collect();
+    packages.sort_by_cached_key(|package| (package.name.clone(), semver::Version::parse(&package.version).ok(), package.version.clone()));
+    packages.into_iter().map(|package| package.id.clone()).collect()
+}
+
+/// Finds the first DOI in the README of a package, e.g. in a link to `https://doi.org/` or a Zenodo badge such as
+/// `https://zenodo.org/badge/DOI/10.5281/zenodo.1003150.svg`.
+fn readme_doi(package: &MetadataPackage) -> Option<Doi> {
+    let directory = package.manifest_path.parent().unwrap_or(Path::new("."));
+    let readme = package.readme.clone().unwrap_or_else(|| PathBuf::from("README.md"));
+    let content = std::fs::read_to_string(directory.join(readme)).ok()?;
+
+    content.match_indices("10.").find_map(|(start, _)| {
+        if !content[..start].ends_with(['/', ':', ' ', '\n', '(', '<', '[']) {
+            return None;
+        }
+        let end = content[start..].find(|c: char| c.is_whitespace() || matches!(c, ')' | ']' | '>' | '"' | '\'' | '?' | '#')).map_or(content.len(), |end| start + end);
+        let doi = content[start..end].trim_end_matches(".svg").trim_end_matches(['.', ',', ';']);
+
+        Doi::new(doi).ok()
+    })
+}
+
+/// Finds the citation of a package, preferring its `CITATION.cff` file over the metadata of its manifest.
+#[cfg_attr(not(feature = "http"), allow(unused_variables))]
+fn dependency_citation(package: &MetadataPackage, options: &DependencyOptions) -> Result<(Citation, DependencySource), Error> {
+    let directory = package.manifest_path.parent().unwrap_or(Path::new("."));
+    for candidate in ["CITATION.cff", ".github/CITATION.cff"] {
+        let path = directory.join(candidate);
+        if path.is_file() {
+            return Ok((Citation::read(path.clone())?, DependencySource::File(path)));
+        }
+    }
+
+    #[cfg(feature = "http")]
+    if let Some(repository) = package.repository.as_deref().filter(|_| options.fetch).and_then(github_repository) {
+        if let Ok(citation) = Citation::fetch_from_github(&repository) {
+            return Ok((citation, DependencySource::Repository(repository)));
+        }
+    }
+
+    let doi = readme_doi(package);
+    #[cfg(feature = "http")]
+    if let Some(doi) = doi.as_ref().filter(|_| options.fetch) {
+        if let Ok(citation) = Citation::from_doi(doi.as_str()) {
+            return Ok((citation, DependencySource::Doi(doi.clone())));
+        }
+    }
+
+    let citation = Citation::from_cargo_manifest(package.manifest_path.clone())?;
+    Ok((Citation { doi: doi.or(citation.doi.clone()), ..citation }, DependencySource::Manifest(package.manifest_path.clone())))
+}
+
+/// Returns `owner/repo` for the URL of a GitHub repository, e.g. `https://github.com/serde-rs/serde.git`.
+#[cfg(feature = "http")]
+fn github_repository(url: &str) -> Option<String> {
+    let path = url.strip_prefix("https://github.com/").or_else(|| url.strip_prefix("http://github.com/"))?;
+    let mut parts = path.trim_end_matches('/').trim_end_matches(".git").split('/');
+
+    match (parts.next(), parts.next()) {
+        (Some(owner), Some(name)) if !owner.is_empty() && !name.is_empty() => Some(format!("{owner}/{name}")),
+        _ => None,
+    }
+}
+
+impl Citation {
+    /// Collects the citations of the dependencies of a Cargo package or workspace, e.g. to acknowledge the
+    /// software a project builds on. The path may point to the manifest or to the directory containing it.
+    ///
+    /// Runs `cargo metadata`, which downloads the sources of the dependencies if needed, and includes every
+    /// package the workspace members depend on directly or indirectly, except for dev-dependencies unless
+    /// [`DependencyOptions::dev_dependencies`] is set. The citation of a dependency is read from the
+    /// `CITATION.cff` file in its source or, failing that, created from its manifest, see
+    /// [`Citation::from_cargo_manifest`], with the DOI found in its README if any. With
+    /// [`DependencyOptions::fetch`], the citation is fetched from the GitHub repository or the DOI of the package
+    /// before falling back to the manifest.
+    ///
+    /// Dependencies whose citation cannot be read or created are reported in [`Dependencies::errors`] instead of
+    /// failing. Only fails if `cargo metadata` fails, e.g. because the manifest is invalid, or if
+    /// [`DependencyOptions::fetch`] is set without the `http` feature.
+    pub fn from_cargo_dependencies(path: PathBuf, options: &DependencyOptions) -> Result<Dependencies, Error> {
+        if cfg!(not(feature = "http")) && options.fetch {
+            return Err(io::Error::new(io::ErrorKind::Unsupported, "fetching the citations of dependencies requires the `http` feature").into());
+        }
+
+        let manifest = match path.is_dir() {
+            true => path.join("Cargo.toml"),
+            false => path,
+        };
+        let metadata = cargo_metadata(&manifest, &[])?;
+        let packages: HashMap<&str, &MetadataPackage> = metadata.packages.iter().map(|package| (package.id.as_str(), package)).collect();
+
+        let mut dependencies = Dependencies::default();
+        for id in dependency_ids(&metadata, options) {
+            let package = packages[id.as_str()];
+            match dependency_citation(package, options) {
+                Ok((citation, source)) => dependencies.dependencies.push(Dependency { name: package.name.clone(), version: package.version.clone(), citation, source }),
+                Err(error) => dependencies.errors.push((package.name.clone(), error)),
+            }
+        }
+
+        Ok(dependencies)
+    }
+}
+
+/// The members of a Cargo workspace, as listed by `cargo metadata`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CargoWorkspace {
+    /// The directory of the root manifest of the workspace.
+    pub root: PathBuf,
+
+    /// The packages of the workspace, in the order of `cargo metadata`, without the excluded ones.
+    pub members: Vec<WorkspaceMember>,
+}
+
+/// A package of a [`CargoWorkspace`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceMember {
+    /// The name of the package.
+    pub name: String,
+
+    /// The path of the `Cargo.toml` of the package.
+    pub manifest_path: PathBuf,
+}
+
+impl CargoWorkspace {
+    /// Lists the members of the workspace the manifest belongs to, which may be the root manifest or the manifest
+    /// of a member. The path may point to the manifest or to the directory containing it.
+    ///
+    /// Runs `cargo metadata` without resolving the dependencies, so that globs in `members` and `exclude` are
+    /// expanded exactly like Cargo does.
+    pub fn read(path: PathBuf) -> Result<Self, Error> {
+        let manifest = match path.is_dir() {
+            true => path.join("Cargo.toml"),
+            false => path,
+        };
+        let metadata = cargo_metadata(&manifest, &["--no-deps"])?;
+
+        let members = metadata.workspace_members.iter().filter_map(|id| metadata.packages.iter().find(|package| package.id == *id));
+        let members = members.map(|package| WorkspaceMember { name: package.name.clone(), manifest_path: package.manifest_path.clone() }).collect();
+
+        Ok(CargoWorkspace { root: metadata.workspace_root, members })
+    }
+}
@@ -0,0 +1,151 @@
+use std::{error::Error, fmt};
+#[cfg(feature = "fs")]
+use std::path::PathBuf;
+
+use hayagriva::{
+    archive::{self, ArchivedStyle},
+    citationberg::{json::Item, FontStyle, IndependentStyle, Locale, LocaleCode, Style},
+    BibliographyDriver, BibliographyRequest, CitationItem, CitationRequest, ElemChild, ElemChildren,
+};
+use serde_json::Value;
+
+use crate::{
+    csl,
+    style::{emphasis, link},
+    Citation, CitationFormatter, OutputFormat, Reference,
+};
+
+/// A citation style defined in a [CSL 1.0](https://citationstyles.org) style file, rendered with
+/// the citeproc implementation of [`hayagriva`].
+///
+/// Styles can be loaded from any `.csl` file, e.g. from the [Zotero style repository](https://www.zotero.org/styles),
+/// or from the styles bundled with hayagriva. Dependent styles are resolved through the bundled styles.
+///
+/// ```
+/// use citation::{Citation, CslStyle};
+///
+/// let citation: Citation = "cff-version: 1.2.0\nmessage: Cite me\ntitle: citation\nauthors:\n  - name: Lewin".parse().unwrap();
+/// let style = CslStyle::bundled("apa").unwrap();
+///
+/// assert_eq!(citation.format(&style), "Lewin. (n.d.). citation [Computer software].");
+/// ```
+#[derive(Debug, Clone)]
+pub struct CslStyle {
+    style: IndependentStyle,
+    locale: Option<LocaleCode>,
+    locales: Vec<Locale>,
+}
+
+impl CslStyle {
+    /// Parses the content of a `.csl` style file.
+    pub fn from_xml(xml: &str) -> Result<Self, ParseStyleError> {
+        let style = Style::from_xml(xml).map_err(|error| ParseStyleError { message: format!("{error}: {}", error.source) })?;
+
+        match style {
+            Style::Independent(style) => Ok(Self::new(style, None)),
+            Style::Dependent(dependent) => {
+                let parent = ArchivedStyle::by_id(&dependent.parent_link.href).map(ArchivedStyle::get);
+                match parent {
+                    Some(Style::Independent(style)) => Ok(Self::new(style, dependent.default_locale)),
+                    _ => Err(ParseStyleError { message: format!("unknown parent style `{}`", dependent.parent_link.href) }),
+                }
+            }
+        }
+    }
+
+    /// Reads the `.csl` style file at the given path.
+    #[cfg(feature = "fs")]
+    pub fn read(path: PathBuf) -> Result<Self, crate::Error> {
+        Ok(Self::from_xml(&std::fs::read_to_string(path)?)?)
+    }
+
+    /// Returns one of the styles bundled with hayagriva by its short name, e.g. `apa`, `ieee` or `nature`.
+    pub fn bundled(name: &str) -> Option<Self> {
+        match ArchivedStyle::by_name(name)?.get() {
+            Style::Independent(style) => Some(Self::new(style, None)),
+            Style::Dependent(_) => None,
+        }
+    }
+
+    /// Returns the title of the style, e.g. `American Psychological Association 7th edition`.
+    pub fn title(&self) -> &str {
+        &self.style.info.title.value
+    }
+
+    fn new(style: IndependentStyle, locale: Option<LocaleCode>) -> Self {
+        CslStyle { style, locale, locales: archive::locales() }
+    }
+
+    /// Renders a CSL-JSON item as a bibliography entry, or as a citation for styles without a bibliography.
+    fn render(&self, item: Value, output: OutputFormat) -> String {
+        let Ok(item) = serde_json::from_value::<Item>(item) else { return String::new() };
+
+        let mut driver = BibliographyDriver::new();
+        driver.citation(CitationRequest::new(
+            vec![CitationItem::with_entry(&item)],
+            &self.style,
+            self.locale.clone(),
+            &self.locales,
+            None,
+        ));
+        let rendered = driver.finish(BibliographyRequest::new(&self.style, self.locale.clone(), &self.locales));
+
+        let children = match rendered.bibliography.and_then(|bibliography| bibliography.items.into_iter().next()) {
+            Some(item) => item.content,
+            None => match rendered.citations.into_iter().next() {
+                Some(citation) => citation.citation,
+                None => return String::new(),
+            },
+        };
+
+        output.render(mark(&children).trim())
+    }
+
+    /// Renders the reference in this style with the given markup.
+    pub fn format_reference(&self, reference: &Reference, output: OutputFormat) -> String {
+        self.render(csl::reference_item(reference, "reference"), output)
+    }
+}
+
+impl CitationFormatter for CslStyle {
+    /// Renders the citation in this style.
+    ///
+    /// If the citation has a `preferred-citation`, that reference is formatted instead.
+    fn format(&self, citation: &Citation, output: OutputFormat) -> String {
+        match &citation.preferred_citation {
+            Some(reference) => self.format_reference(reference, output),
+            None => self.render(csl::citation_item(citation, "citation"), output),
+        }
+    }
+}
+
+/// Converts rendered elements to text with the markers of the built-in styles, keeping italics and links.
+fn mark(children: &ElemChildren) -> String {
+    children
+        .0
+        .iter()
+        .map(|child| match child {
+            ElemChild::Text(text) if text.formatting.font_style == FontStyle::Italic => emphasis(&text.text),
+            ElemChild::Text(text) => text.text.clone(),
+            ElemChild::Elem(elem) => mark(&elem.children),
+            ElemChild::Markup(markup) => markup.clone(),
+            ElemChild::Link { text, url } => link(url, &text.text),
+            ElemChild::Transparent { .. } => String::new(),
+        })
+        .collect()
+}
+
+/// Error returned when a `.csl` style file cannot be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseStyleError {
+    /// A description of the problem.
+    pub message: String,
+}
+
+impl fmt::Display for ParseStyleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for ParseStyleError {}
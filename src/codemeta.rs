@@ -0,0 +1,242 @@
+use serde::{de::Error as _, Deserialize, Deserializer};
+use serde_json::Value;
+
+use crate::{
+    jsonld::{insert, schema_org},
+    Author, Citation, Date, Doi, Entity, Identifier, License, Licenses, Orcid, Person, Type, CFF_VERSION,
+};
+
+/// The JSON-LD context of CodeMeta 3.0.
+const CONTEXT: &str = "https://w3id.org/codemeta/3.0";
+
+/// A `codemeta.json` file, limited to the properties that have a counterpart in the Citation File Format.
+///
+/// https://codemeta.github.io/terms/
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CodeMeta {
+    #[serde(default, rename = "@type")]
+    r#type: Option<String>,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default, deserialize_with = "list")]
+    author: Vec<Agent>,
+    #[serde(default, deserialize_with = "text")]
+    version: Option<String>,
+    #[serde(default, deserialize_with = "text")]
+    software_version: Option<String>,
+    #[serde(default)]
+    date_published: Option<String>,
+    #[serde(default)]
+    code_repository: Option<String>,
+    #[serde(default)]
+    download_url: Option<String>,
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default, deserialize_with = "list")]
+    license: Vec<Value>,
+    #[serde(default, deserialize_with = "list")]
+    identifier: Vec<Value>,
+    #[serde(default)]
+    keywords: Option<Value>,
+}
+
+/// A `Person` or `Organization`.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Agent {
+    #[serde(default, rename = "@type")]
+    r#type: Option<String>,
+    #[serde(default, rename = "@id")]
+    id: Option<String>,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default, deserialize_with = "names")]
+    given_name: Option<String>,
+    #[serde(default, deserialize_with = "names")]
+    family_name: Option<String>,
+    #[serde(default)]
+    email: Option<String>,
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default, deserialize_with = "list")]
+    affiliation: Vec<Value>,
+}
+
+/// Deserializes a property that may be written as a single value or as a list of values.
+fn list<'de, D: Deserializer<'de>, T: Deserialize<'de>>(deserializer: D) -> Result<Vec<T>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany<T> {
+        Many(Vec<T>),
+        One(T),
+    }
+
+    Ok(match Option::<OneOrMany<T>>::deserialize(deserializer)? {
+        Some(OneOrMany::One(value)) => vec![value],
+        Some(OneOrMany::Many(values)) => values,
+        None => Vec::new(),
+    })
+}
+
+/// Deserializes a value that may be written as a string or a number, e.g. `"version": 2`.
+fn text<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    Ok(match Option::<Value>::deserialize(deserializer)? {
+        Some(Value::String(value)) => Some(value),
+        Some(Value::Number(value)) => Some(value.to_string()),
+        _ => None,
+    })
+}
+
+/// Deserializes a name that may be written as a string or as a list of names, e.g. `"givenName": ["Arfon", "M."]`.
+fn names<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    let names: Vec<String> = list(deserializer)?;
+
+    Ok(Some(names.join(" ")).filter(|names| !names.is_empty()))
+}
+
+/// Returns the name of an organization, which may be written as a string or as an `Organization` object.
+fn organization(value: &Value) -> Option<String> {
+    match value {
+        Value::String(name) => Some(name.clone()),
+        Value::Object(object) => object.get("name").and_then(Value::as_str).map(String::from),
+        _ => None,
+    }
+}
+
+/// Returns the value of an identifier, which may be written as a string or as a `PropertyValue` object.
+fn identifier(value: &Value) -> Option<String> {
+    match value {
+        Value::String(value) => Some(value.clone()),
+        Value::Object(object) => object.get("value").or_else(|| object.get("@id")).and_then(Value::as_str).map(String::from),
+        _ => None,
+    }
+}
+
+impl Agent {
+    fn into_author(self) -> Author {
+        let orcid = self.id.filter(|id| id.contains("orcid.org/")).and_then(|id| id.parse::<Orcid>().ok()).map(|orcid| orcid.to_url());
+        let is_organization = self.r#type.as_deref() == Some("Organization");
+
+        match self.name {
+            Some(name) if is_organization || (self.given_name.is_none() && self.family_name.is_none()) => Author::Entity(Entity {
+                name,
+                email: self.email,
+                orcid,
+                website: self.url,
+                ..Default::default()
+            }),
+            _ => Author::Person(Person {
+                given_names: self.given_name,
+                family_names: self.family_name,
+                email: self.email,
+                orcid,
+                affiliation: Some(self.affiliation.iter().filter_map(organization).collect::<Vec<_>>().join("; ")).filter(|affiliation| !affiliation.is_empty()),
+                website: self.url,
+                ..Default::default()
+            }),
+        }
+    }
+}
+
+impl CodeMeta {
+    fn into_citation(self) -> Result<Citation, serde_json::Error> {
+        let mut builder = Citation::builder()
+            .cff_version(CFF_VERSION)
+            .authors(self.author.into_iter().map(Agent::into_author));
+
+        if let Some(name) = self.name {
+            builder = builder.title(name);
+        }
+        if self.r#type.as_deref() == Some("Dataset") {
+            builder = builder.r#type(Type::Dataset);
+        }
+        if let Some(description) = self.description {
+            builder = builder.r#abstract(description);
+        }
+        if let Some(version) = self.version.or(self.software_version) {
+            builder = builder.version(version);
+        }
+        if let Some(date) = self.date_published.and_then(|date| date.get(..10)?.parse::<Date>().ok()) {
+            builder = builder.date_released(date);
+        }
+        if let Some(url) = self.code_repository {
+            builder = builder.repository_code(url);
+        }
+        if let Some(url) = self.download_url {
+            builder = builder.repository_artifact(url);
+        }
+        if let Some(url) = self.url {
+            builder = builder.url(url);
+        }
+
+        let mut licenses = Vec::new();
+        for license in self.license.iter().filter_map(identifier) {
+            let id = license.trim_start_matches("https://spdx.org/licenses/").trim_start_matches("http://spdx.org/licenses/");
+            let id = id.trim_end_matches(".html").trim_end_matches(".json");
+            match License::from_id(id) {
+                License::Other(_) if license.contains("://") => builder = builder.license_url(license),
+                license => licenses.push(license),
+            }
+        }
+        match licenses.len() {
+            0 => {}
+            1 => builder = builder.license(Licenses::Single(licenses.remove(0))),
+            _ => builder = builder.license(Licenses::Multiple(licenses)),
+        }
+
+        let mut doi = None;
+        for value in self.identifier.iter().filter_map(identifier) {
+            let stripped = ["https://doi.org/", "http://doi.org/", "https://dx.doi.org/", "http://dx.doi.org/", "doi:"]
+                .iter()
+                .find_map(|prefix| value.strip_prefix(prefix));
+            let identifier = match stripped.and_then(|value| Doi::new(value).ok()) {
+                Some(value) if doi.is_none() => {
+                    doi = Some(value);
+                    continue;
+                }
+                Some(value) => Identifier::Doi { value: value.as_str().to_string(), description: None },
+                None if value.starts_with("swh:") => Identifier::Swh { value, description: None },
+                None if value.contains("://") => Identifier::Url { value, description: None },
+                None => Identifier::Other { value, description: None },
+            };
+            builder = builder.identifier(identifier);
+        }
+        if let Some(doi) = doi {
+            builder = builder.doi(doi);
+        }
+
+        let keywords = match self.keywords {
+            Some(Value::String(keywords)) => keywords.split(',').map(|keyword| keyword.trim().to_string()).filter(|keyword| !keyword.is_empty()).collect(),
+            Some(Value::Array(keywords)) => keywords.into_iter().filter_map(|keyword| keyword.as_str().map(String::from)).collect(),
+            _ => Vec::new(),
+        };
+
+        builder.keywords(keywords).build().map_err(serde_json::Error::custom)
+    }
+}
+
+impl Citation {
+    /// Converts the citation to a [CodeMeta](https://codemeta.github.io) `codemeta.json` file, following the
+    /// [crosswalk](https://codemeta.github.io/crosswalk/) between the Citation File Format and CodeMeta.
+    ///
+    /// The artifact repository is exported as `downloadUrl`. Contacts, references and the preferred citation have no
+    /// counterpart in CodeMeta and are left out.
+    pub fn to_codemeta(&self) -> String {
+        let mut object = schema_org(self);
+        object.insert("@context".into(), CONTEXT.into());
+        insert(&mut object, "downloadUrl", self.repository_artifact.as_deref());
+
+        serde_json::to_string_pretty(&object).unwrap_or_default()
+    }
+
+    /// Parses a `codemeta.json` file into a citation, the reverse of [`Citation::to_codemeta`].
+    ///
+    /// Licenses given as SPDX URLs become SPDX identifiers and the first DOI becomes the `doi` of the citation.
+    /// Fails if the file has no `name` or no authors.
+    pub fn from_codemeta(input: &str) -> Result<Citation, serde_json::Error> {
+        serde_json::from_str::<CodeMeta>(input)?.into_citation()
+    }
+}
@@ -0,0 +1,142 @@
+use serde_yaml::Value;
+
+use crate::{
+    parse::unknown_keys,
+    span::locate,
+    CffVersion, Citation, Code, Document, Issue,
+};
+
+/// How well the crate understands a document, see [`Document::compatibility`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Compatibility {
+    /// The value of `cff-version`.
+    pub declared: String,
+
+    /// The version of the schema the document is read as: the declared version if it is supported,
+    /// and the latest supported version otherwise.
+    pub version: CffVersion,
+
+    /// The keys and values that are not understood, as warnings located in the document.
+    ///
+    /// Unknown keys ([`Code::UnknownKey`]) are kept when the citation is written again, while values that
+    /// could not be read ([`Code::IgnoredValue`]) are missing from the parsed citation.
+    pub issues: Vec<Issue>,
+}
+
+impl Compatibility {
+    /// Returns `true` if the declared version is supported by this crate.
+    pub fn is_supported(&self) -> bool {
+        self.declared.parse::<CffVersion>().is_ok()
+    }
+
+    /// Returns `true` if every key and value of the document is understood.
+    pub fn is_complete(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Returns `true` if the parsed citation contains every value of the document, i.e. no value was ignored.
+    pub fn is_lossless(&self) -> bool {
+        !self.issues.iter().any(|issue| issue.code == Code::IgnoredValue)
+    }
+}
+
+impl Document {
+    /// Reports how well the document is understood, so that tools can decide how to proceed with files written
+    /// for a newer version of the schema than this crate supports.
+    ///
+    /// Such files are parsed as the latest supported version: keys that are not defined by it are kept as
+    /// they are, and values it cannot represent (e.g. a new value of `type`) are ignored instead of failing,
+    /// as long as they are not required. Both are reported as warnings.
+    pub fn compatibility(&self) -> Compatibility {
+        let value: Value = serde_yaml::from_str(&self.source).unwrap_or_default();
+        let declared = match value.get("cff-version") {
+            Some(Value::String(version)) => version.clone(),
+            Some(Value::Number(version)) => version.to_string(),
+            _ => String::new(),
+        };
+        let version = declared.parse().unwrap_or(CffVersion::LATEST);
+
+        let mut issues: Vec<Issue> = unknown_keys(&value)
+            .into_iter()
+            .map(|unknown| {
+                let message = unknown.message(&format!("`{}` is not defined by cff-version {version} and is kept as is", unknown.key));
+                Issue::new(Code::UnknownKey, unknown.path, message)
+            })
+            .collect();
+
+        if let Some((_, ignored)) = understood(value) {
+            let ignored = ignored.into_iter().map(|(path, message)| Issue::new(Code::IgnoredValue, path, format!("the value is not understood and is ignored: {message}")));
+            issues.extend(ignored);
+        }
+
+        for issue in &mut issues {
+            issue.span = locate(&self.source, &issue.path);
+        }
+
+        Compatibility { declared, version, issues }
+    }
+}
+
+/// Returns `true` if the value of `cff-version` is a version newer than the latest supported version.
+pub(crate) fn is_newer(value: &Value) -> bool {
+    let Some(version) = value.get("cff-version").and_then(Value::as_str) else { return false };
+    let parts: Option<Vec<u64>> = version.split('.').map(|part| part.parse().ok()).collect();
+
+    parts.is_some_and(|parts| parts.len() == 3 && parts > vec![1, 2, 0])
+}
+
+/// Parses the value as a citation, removing values that cannot be read one by one until it succeeds. Returns the
+/// citation and the paths of the removed values with the reason, or `None` if a required value cannot be read.
+pub(crate) fn understood(mut value: Value) -> Option<(Citation, Vec<(String, String)>)> {
+    let mut ignored = Vec::new();
+
+    loop {
+        match serde_path_to_error::deserialize::<_, Citation>(value.clone()) {
+            Ok(citation) => return Some((citation, ignored)),
+            Err(error) => {
+                let path = error.path().to_string();
+                if path == "." || !remove(&mut value, &path) {
+                    return None;
+                }
+                // removing a key may make its parent unreadable, e.g. an identifier without `type`
+                match ignored.last_mut() {
+                    Some((ignored, _)) if ignored.strip_prefix(path.as_str()).is_some_and(|rest| rest.starts_with(['.', '['])) => *ignored = path,
+                    _ => ignored.push((path, error.into_inner().to_string())),
+                }
+            }
+        }
+    }
+}
+
+/// Removes the entry at a path such as `references[0].type` from the value, returning `false` if there is none.
+pub(crate) fn remove(value: &mut Value, path: &str) -> bool {
+    let (parent, last) = match path.rfind(['.', '[']) {
+        Some(index) => (&path[..index], &path[index..]),
+        None => ("", path),
+    };
+
+    let mut current = value;
+    for part in parent.split('.').filter(|part| !part.is_empty()) {
+        let (key, indices) = part.split_once('[').map_or((part, ""), |(key, rest)| (key, rest));
+        if !key.is_empty() {
+            let Some(next) = current.get_mut(key) else { return false };
+            current = next;
+        }
+        for index in indices.split(['[', ']']).filter(|index| !index.is_empty()) {
+            let Some(next) = index.parse::<usize>().ok().and_then(|index| current.get_mut(index)) else { return false };
+            current = next;
+        }
+    }
+
+    match (last.strip_prefix('['), current) {
+        (Some(index), Value::Sequence(items)) => match index.trim_end_matches(']').parse::<usize>() {
+            Ok(index) if index < items.len() => {
+                items.remove(index);
+                true
+            }
+            _ => false,
+        },
+        (None, Value::Mapping(mapping)) => mapping.remove(last.trim_start_matches('.')).is_some(),
+        _ => false,
+    }
+}
@@ -0,0 +1,459 @@
+use serde::{de::Error as _, Deserialize, Deserializer};
+use serde_json::Value;
+
+use crate::{bibtex::key, Author, Citation, Date, Doi, Entity, IntOrString, Person, Reference, ReferenceType};
+
+/// An item of a CSL-JSON bibliography, as exported by Zotero, Pandoc and citeproc.
+///
+/// https://citeproc-js.readthedocs.io/en/latest/csl-json/markup.html
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct Item {
+    #[serde(default)]
+    id: Option<Value>,
+    r#type: String,
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    author: Vec<Name>,
+    #[serde(default)]
+    editor: Vec<Name>,
+    #[serde(default)]
+    issued: Option<CslDate>,
+    #[serde(default)]
+    accessed: Option<CslDate>,
+    #[serde(default)]
+    container_title: Option<String>,
+    #[serde(default)]
+    collection_title: Option<String>,
+    #[serde(default, deserialize_with = "text")]
+    volume: Option<String>,
+    #[serde(default, deserialize_with = "text")]
+    issue: Option<String>,
+    #[serde(default, deserialize_with = "text")]
+    number: Option<String>,
+    #[serde(default, deserialize_with = "text")]
+    page: Option<String>,
+    #[serde(default, deserialize_with = "text")]
+    edition: Option<String>,
+    #[serde(default, deserialize_with = "text")]
+    version: Option<String>,
+    #[serde(default)]
+    publisher: Option<String>,
+    #[serde(default)]
+    publisher_place: Option<String>,
+    #[serde(default)]
+    genre: Option<String>,
+    #[serde(default, rename = "DOI")]
+    doi: Option<String>,
+    #[serde(default, rename = "URL")]
+    url: Option<String>,
+    #[serde(default, rename = "ISBN")]
+    isbn: Option<String>,
+    #[serde(default, rename = "ISSN")]
+    issn: Option<String>,
+    #[serde(default)]
+    r#abstract: Option<String>,
+    #[serde(default)]
+    keyword: Option<String>,
+    #[serde(default)]
+    language: Option<String>,
+    #[serde(default)]
+    note: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct Name {
+    #[serde(default)]
+    family: Option<String>,
+    #[serde(default)]
+    given: Option<String>,
+    #[serde(default)]
+    non_dropping_particle: Option<String>,
+    #[serde(default)]
+    dropping_particle: Option<String>,
+    #[serde(default)]
+    suffix: Option<String>,
+    #[serde(default)]
+    literal: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct CslDate {
+    #[serde(default)]
+    date_parts: Vec<Vec<Value>>,
+    #[serde(default)]
+    raw: Option<String>,
+    #[serde(default)]
+    literal: Option<String>,
+}
+
+/// Deserializes a value that may be written as a string or a number, e.g. `"volume": 2`.
+fn text<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    Ok(match Option::<Value>::deserialize(deserializer)? {
+        Some(Value::String(value)) => Some(value),
+        Some(Value::Number(value)) => Some(value.to_string()),
+        _ => None,
+    })
+}
+
+/// Maps a CSL item type to the closest reference type.
+fn reference_type(r#type: &str) -> ReferenceType {
+    match r#type {
+        "article" | "article-journal" | "review" | "review-book" => ReferenceType::Article,
+        "article-magazine" => ReferenceType::MagazineArticle,
+        "article-newspaper" => ReferenceType::NewspaperArticle,
+        "bill" => ReferenceType::Bill,
+        "book" | "chapter" | "classic" => ReferenceType::Book,
+        "broadcast" | "motion_picture" => ReferenceType::FilmBroadcast,
+        "dataset" => ReferenceType::Data,
+        "entry-dictionary" => ReferenceType::Dictionary,
+        "entry-encyclopedia" => ReferenceType::Encyclopedia,
+        "graphic" => ReferenceType::Art,
+        "hearing" => ReferenceType::Hearing,
+        "interview" | "personal_communication" => ReferenceType::PersonalCommunication,
+        "legal_case" => ReferenceType::LegalCase,
+        "legislation" | "regulation" => ReferenceType::Statute,
+        "manuscript" => ReferenceType::Unpublished,
+        "map" => ReferenceType::Map,
+        "pamphlet" => ReferenceType::Pamphlet,
+        "paper-conference" => ReferenceType::ConferencePaper,
+        "patent" => ReferenceType::Patent,
+        "performance" | "speech" => ReferenceType::Audiovisual,
+        "periodical" => ReferenceType::Serial,
+        "post-weblog" => ReferenceType::Blog,
+        "post" | "webpage" => ReferenceType::Website,
+        "report" => ReferenceType::Report,
+        "software" => ReferenceType::Software,
+        "song" => ReferenceType::SoundRecording,
+        "standard" => ReferenceType::Standard,
+        "thesis" => ReferenceType::Thesis,
+        _ => ReferenceType::Generic,
+    }
+}
+
+impl Name {
+    fn into_author(self) -> Author {
+        if let Some(name) = self.literal.or_else(|| self.given.is_none().then_some(self.family.clone()).flatten()) {
+            return Author::Entity(Entity { name, ..Default::default() });
+        }
+
+        let particle = [self.dropping_particle, self.non_dropping_particle].into_iter().flatten().collect::<Vec<_>>().join(" ");
+
+        Author::Person(Person {
+            family_names: self.family,
+            given_names: self.given,
+            name_particle: Some(particle).filter(|particle| !particle.is_empty()),
+            name_suffix: self.suffix,
+            ..Default::default()
+        })
+    }
+}
+
+impl CslDate {
+    /// Returns the year, month and day of the date, as far as they are known.
+    fn parts(&self) -> (Option<String>, Option<u8>, Option<u8>) {
+        let number = |value: &Value| match value {
+            Value::Number(number) => number.as_u64(),
+            Value::String(text) => text.trim().parse().ok(),
+            _ => None,
+        };
+
+        if let Some(parts) = self.date_parts.first() {
+            let year = parts.first().and_then(number).map(|year| year.to_string());
+            let month = parts.get(1).and_then(number).and_then(|month| u8::try_from(month).ok());
+            let day = parts.get(2).and_then(number).and_then(|day| u8::try_from(day).ok());
+            return (year, month, day);
+        }
+
+        // raw dates are usually written as `YYYY-MM-DD` or a prefix of it
+        let raw = self.raw.as_deref().or(self.literal.as_deref()).unwrap_or_default();
+        let mut parts = raw.trim().splitn(3, ['-', '/']).map(str::trim);
+        let year = parts.next().filter(|year| year.len() == 4 && year.chars().all(|c| c.is_ascii_digit()));
+        let month = year.and(parts.next()).and_then(|month| month.parse().ok());
+        let day = month.and(parts.next()).and_then(|day| day.parse().ok());
+
+        (year.map(String::from), month, day)
+    }
+
+    fn date(&self) -> Option<Date> {
+        match self.parts() {
+            (Some(year), Some(month), Some(day)) => Date::new(year.parse().ok()?, month, day).ok(),
+            _ => None,
+        }
+    }
+}
+
+impl Item {
+    fn into_reference(self) -> Result<Reference, serde_json::Error> {
+        let id = match &self.id {
+            Some(Value::String(id)) => id.clone(),
+            Some(id) => id.to_string(),
+            None => self.title.clone().unwrap_or_default(),
+        };
+
+        let r#type = reference_type(&self.r#type);
+        let (journal, collection_title) = match r#type {
+            ReferenceType::Article | ReferenceType::MagazineArticle | ReferenceType::NewspaperArticle => {
+                (self.container_title, self.collection_title)
+            }
+            _ => (None, self.container_title.or(self.collection_title)),
+        };
+
+        let (start, end) = match self.page.as_deref().map(|page| page.split_once(['-', '–'])) {
+            Some(Some((start, end))) => (Some(start.trim().to_string()), Some(end.trim().to_string())),
+            Some(None) => (self.page.clone(), None),
+            None => (None, None),
+        };
+
+        let (year, month, _) = self.issued.as_ref().map(CslDate::parts).unwrap_or_default();
+
+        let doi = match self.doi {
+            Some(doi) => {
+                let value = doi.trim_start_matches("https://doi.org/").trim_start_matches("http://doi.org/");
+                Some(Doi::new(value).map_err(|_| serde_json::Error::custom(format!("invalid DOI `{doi}` in item `{id}`")))?)
+            }
+            None => None,
+        };
+
+        Ok(Reference {
+            title: self.title.ok_or_else(|| serde_json::Error::custom(format!("item `{id}` has no title")))?,
+            thesis_type: self.genre.filter(|_| r#type == ReferenceType::Thesis),
+            r#type,
+            authors: self.author.into_iter().map(Name::into_author).collect(),
+            editors: self.editor.into_iter().map(Name::into_author).collect(),
+            date_published: self.issued.as_ref().and_then(CslDate::date),
+            date_accessed: self.accessed.as_ref().and_then(CslDate::date),
+            year: year.map(IntOrString::from),
+            month: month.filter(|month| (1..=12).contains(month)).map(IntOrString::from),
+            journal,
+            collection_title,
+            volume: self.volume.map(IntOrString::from),
+            issue: self.issue.map(IntOrString::from),
+            number: self.number.map(IntOrString::from),
+            start: start.map(IntOrString::from),
+            end: end.map(IntOrString::from),
+            edition: self.edition,
+            version: self.version,
+            publisher: self.publisher.map(|name| Entity { name, city: self.publisher_place, ..Default::default() }),
+            doi,
+            url: self.url,
+            isbn: self.isbn,
+            issn: self.issn,
+            r#abstract: self.r#abstract,
+            keywords: self
+                .keyword
+                .iter()
+                .flat_map(|keywords| keywords.split([',', ';']))
+                .map(str::trim)
+                .filter(|keyword| !keyword.is_empty())
+                .map(String::from)
+                .collect(),
+            languages: self.language.into_iter().collect(),
+            notes: self.note,
+            ..Default::default()
+        })
+    }
+}
+
+/// Parses a CSL-JSON document, which is either an array of items or a single item.
+fn parse(input: &str) -> Result<Vec<Item>, serde_json::Error> {
+    match serde_json::from_str(input)? {
+        Value::Array(items) => items.into_iter().map(Item::deserialize).collect(),
+        item => Ok(vec![Item::deserialize(item)?]),
+    }
+}
+
+impl Citation {
+    /// Creates a citation from the first `software` or `dataset` item of a CSL-JSON bibliography,
+    /// e.g. an export from Zotero or Pandoc.
+    ///
+    /// Other items can be converted with [`Reference::from_csl_json`]. Fails if there is no such item
+    /// or if it has no authors or title.
+    pub fn from_csl_json(input: &str) -> Result<Citation, serde_json::Error> {
+        let item = parse(input)?
+            .into_iter()
+            .find(|item| matches!(item.r#type.as_str(), "software" | "dataset"))
+            .ok_or_else(|| serde_json::Error::custom("no software or dataset item found"))?;
+
+        item.into_reference()?
+            .into_citation()
+            .map_err(|_| serde_json::Error::custom("the item has no authors"))
+    }
+}
+
+impl Reference {
+    /// Parses all items of a CSL-JSON bibliography into references.
+    ///
+    /// The input may be an array of items or a single item. Item types are mapped to the closest
+    /// reference type, unknown types become `generic`.
+    pub fn from_csl_json(input: &str) -> Result<Vec<Reference>, serde_json::Error> {
+        parse(input)?.into_iter().map(Item::into_reference).collect()
+    }
+
+    /// Converts the reference to a CSL-JSON bibliography with a single item, whose id is a citation key like `lewin2024`.
+    pub fn to_csl_json(&self) -> String {
+        let year = self.year.as_ref().map(ToString::to_string).or_else(|| self.date_published.map(|date| date.year().to_string()));
+        let item = reference_item(self, &key(&self.authors, year.as_deref()));
+
+        serde_json::to_string_pretty(&[item]).unwrap_or_default()
+    }
+}
+
+impl Citation {
+    /// Converts the citation to a CSL-JSON bibliography with a single item, e.g. for Zotero or Pandoc.
+    ///
+    /// If the citation has a `preferred-citation`, that reference is converted instead, see [`Reference::to_csl_json`].
+    /// Otherwise a `software` item is created, or a `dataset` item for datasets.
+    pub fn to_csl_json(&self) -> String {
+        if let Some(reference) = &self.preferred_citation {
+            return reference.to_csl_json();
+        }
+
+        let year = self.date_released.map(|date| date.year().to_string());
+        let item = citation_item(self, &key(&self.authors, year.as_deref()));
+
+        serde_json::to_string_pretty(&[item]).unwrap_or_default()
+    }
+}
+
+/// Maps a reference type to the closest CSL item type.
+fn csl_type(r#type: &ReferenceType) -> &'static str {
+    match r#type {
+        ReferenceType::Art => "graphic",
+        ReferenceType::Article => "article-journal",
+        ReferenceType::Audiovisual | ReferenceType::Multimedia | ReferenceType::Slides => "speech",
+        ReferenceType::Bill => "bill",
+        ReferenceType::Blog => "post-weblog",
+        ReferenceType::Book | ReferenceType::EditedWork | ReferenceType::Manual => "book",
+        ReferenceType::ConferencePaper => "paper-conference",
+        ReferenceType::Data | ReferenceType::Database => "dataset",
+        ReferenceType::Dictionary => "entry-dictionary",
+        ReferenceType::Encyclopedia => "entry-encyclopedia",
+        ReferenceType::FilmBroadcast | ReferenceType::Video => "motion_picture",
+        ReferenceType::Hearing => "hearing",
+        ReferenceType::LegalCase => "legal_case",
+        ReferenceType::MagazineArticle => "article-magazine",
+        ReferenceType::Map => "map",
+        ReferenceType::Music | ReferenceType::SoundRecording => "song",
+        ReferenceType::NewspaperArticle => "article-newspaper",
+        ReferenceType::Pamphlet => "pamphlet",
+        ReferenceType::Patent => "patent",
+        ReferenceType::PersonalCommunication => "personal_communication",
+        ReferenceType::Report | ReferenceType::GovernmentDocument => "report",
+        ReferenceType::Serial => "periodical",
+        ReferenceType::Software
+        | ReferenceType::SoftwareCode
+        | ReferenceType::SoftwareContainer
+        | ReferenceType::SoftwareExecutable
+        | ReferenceType::SoftwareVirtualMachine => "software",
+        ReferenceType::Standard => "standard",
+        ReferenceType::Statute => "legislation",
+        ReferenceType::Thesis => "thesis",
+        ReferenceType::Unpublished => "manuscript",
+        ReferenceType::Website => "webpage",
+        _ => "document",
+    }
+}
+
+fn names(authors: &[Author]) -> Value {
+    authors
+        .iter()
+        .map(|author| match author {
+            Author::Entity(entity) => serde_json::json!({ "literal": entity.name }),
+            Author::Person(person) if person.family_names.is_none() => serde_json::json!({ "literal": author.display_name() }),
+            Author::Person(person) => {
+                let mut name = serde_json::Map::new();
+                let parts = [
+                    ("family", &person.family_names),
+                    ("given", &person.given_names),
+                    ("non-dropping-particle", &person.name_particle),
+                    ("suffix", &person.name_suffix),
+                ];
+                for (key, value) in parts {
+                    if let Some(value) = value {
+                        name.insert(key.to_string(), Value::String(value.clone()));
+                    }
+                }
+                Value::Object(name)
+            }
+        })
+        .collect()
+}
+
+/// Builds a CSL-JSON item from key-value pairs, leaving out missing values.
+fn object(fields: Vec<(&str, Option<Value>)>) -> Value {
+    let fields = fields.into_iter().filter_map(|(key, value)| Some((key.to_string(), value?)));
+    Value::Object(fields.filter(|(_, value)| !matches!(value, Value::Array(items) if items.is_empty())).collect())
+}
+
+fn string(value: Option<&str>) -> Option<Value> {
+    value.map(|value| Value::String(value.to_string()))
+}
+
+fn issued(date: Option<Date>, year: Option<&str>, month: Option<&str>) -> Option<Value> {
+    let parts: Vec<Value> = match date {
+        Some(date) => vec![date.year().into(), date.month().into(), date.day().into()],
+        None => {
+            let year: u16 = year?.trim().parse().ok()?;
+            [Some(year), month.and_then(|month| month.trim().parse().ok())].into_iter().flatten().map(Value::from).collect()
+        }
+    };
+
+    Some(serde_json::json!({ "date-parts": [parts] }))
+}
+
+/// Converts a citation into a CSL-JSON item with the given id, e.g. to render it with a CSL style.
+pub(crate) fn citation_item(citation: &Citation, id: &str) -> Value {
+    object(vec![
+        ("id", string(Some(id))),
+        ("type", string(Some(match citation.r#type {
+            Some(crate::Type::Dataset) => "dataset",
+            _ => "software",
+        }))),
+        ("title", string(Some(&citation.title))),
+        ("author", Some(names(&citation.authors))),
+        ("issued", issued(citation.date_released, None, None)),
+        ("version", string(citation.version.as_deref())),
+        ("DOI", string(citation.find_doi().as_deref())),
+        ("URL", string(citation.repository_code.as_deref().or(citation.url.as_deref()))),
+        ("abstract", string(citation.r#abstract.as_deref())),
+        ("keyword", string((!citation.keywords.is_empty()).then(|| citation.keywords.join(", ")).as_deref())),
+    ])
+}
+
+/// Converts a reference into a CSL-JSON item with the given id, e.g. to render it with a CSL style.
+pub(crate) fn reference_item(reference: &Reference, id: &str) -> Value {
+    let page = match (&reference.start, &reference.end) {
+        (Some(start), Some(end)) => Some(format!("{start}-{end}")),
+        (Some(start), None) => Some(start.to_string()),
+        _ => reference.pages.as_ref().map(ToString::to_string),
+    };
+
+    object(vec![
+        ("id", string(Some(id))),
+        ("type", string(Some(csl_type(&reference.r#type)))),
+        ("title", string(Some(&reference.title))),
+        ("author", Some(names(&reference.authors))),
+        ("editor", Some(names(&reference.editors))),
+        ("issued", issued(reference.date_published.or(reference.date_released), reference.year.as_deref(), reference.month.as_deref())),
+        ("container-title", string(reference.journal.as_deref().or(reference.collection_title.as_deref()))),
+        ("volume", string(reference.volume.as_deref())),
+        ("issue", string(reference.issue.as_deref())),
+        ("number", string(reference.number.as_deref())),
+        ("page", string(page.as_deref())),
+        ("edition", string(reference.edition.as_deref())),
+        ("version", string(reference.version.as_deref())),
+        ("publisher", string(reference.publisher.as_ref().or(reference.institution.as_ref()).map(|entity| entity.name.as_str()))),
+        ("publisher-place", string(reference.location.as_ref().map(|location| location.name.as_str()).or(reference.publisher.as_ref().and_then(|publisher| publisher.city.as_deref())))),
+        ("genre", string(reference.thesis_type.as_deref())),
+        ("DOI", string(reference.doi.as_ref().map(Doi::as_str))),
+        ("URL", string(reference.url.as_deref().or(reference.repository_code.as_deref()))),
+        ("ISBN", string(reference.isbn.as_deref())),
+        ("ISSN", string(reference.issn.as_deref())),
+        ("abstract", string(reference.r#abstract.as_deref())),
+        ("note", string(reference.notes.as_deref())),
+    ])
+}
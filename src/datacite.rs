@@ -0,0 +1,248 @@
+use serde_json::{json, Map, Value};
+
+use crate::{jsonld::insert, ris::name, style::host, xml::Element, Author, Citation, Identifier, License, Type};
+
+/// The code DataCite uses for required values that are not available.
+const UNAVAILABLE: &str = "(:unav)";
+
+/// A creator of the resource, as defined by the `creator` property.
+struct Creator<'a> {
+    name: String,
+    personal: bool,
+    given_name: Option<&'a str>,
+    family_name: Option<String>,
+    orcid: Option<&'a str>,
+    affiliation: Option<&'a str>,
+}
+
+impl<'a> Creator<'a> {
+    fn new(author: &'a Author) -> Self {
+        match author {
+            Author::Person(person) => Creator {
+                name: name(author),
+                personal: true,
+                given_name: person.given_names.as_deref(),
+                family_name: person.family_names.as_ref().map(|family| [person.name_particle.as_deref(), Some(family)].into_iter().flatten().collect::<Vec<_>>().join(" ")),
+                orcid: person.orcid.as_deref(),
+                affiliation: person.affiliation.as_deref(),
+            },
+            Author::Entity(entity) => Creator {
+                name: entity.name.clone(),
+                personal: false,
+                given_name: None,
+                family_name: None,
+                orcid: entity.orcid.as_deref(),
+                affiliation: None,
+            },
+        }
+    }
+
+    fn name_type(&self) -> &'static str {
+        match self.personal {
+            true => "Personal",
+            false => "Organizational",
+        }
+    }
+}
+
+/// The metadata of a citation in the terms of the DataCite Metadata Schema, shared by the XML and JSON serializations.
+struct Resource<'a> {
+    doi: Option<String>,
+    creators: Vec<Creator<'a>>,
+    publisher: String,
+    publication_year: Option<u16>,
+    resource_type: &'static str,
+    /// Identifiers of the resource itself, as `(identifier, type)`.
+    alternate_identifiers: Vec<(&'a str, &'static str)>,
+    /// Identifiers of related resources, as `(identifier, type, relation)`.
+    related_identifiers: Vec<(String, &'static str, &'static str)>,
+    licenses: &'a [License],
+}
+
+impl<'a> Resource<'a> {
+    fn new(citation: &'a Citation) -> Self {
+        let doi = citation.doi.as_ref().map(|doi| doi.to_string());
+        let publisher = [&citation.repository_code, &citation.url, &citation.repository_artifact, &citation.repository]
+            .into_iter()
+            .flatten()
+            .find_map(|url| host(url))
+            .unwrap_or_else(|| UNAVAILABLE.to_string());
+
+        let alternate_identifiers = citation
+            .identifiers
+            .iter()
+            .filter(|identifier| doi.as_deref() != Some(identifier.value()))
+            .map(|identifier| {
+                let r#type = match identifier {
+                    Identifier::Doi { .. } => "DOI",
+                    Identifier::Url { .. } => "URL",
+                    Identifier::Swh { .. } => "SWHID",
+                    Identifier::Other { .. } => "Other",
+                };
+                (identifier.value(), r#type)
+            })
+            .collect();
+
+        let mut related_identifiers = Vec::new();
+        if let Some(url) = &citation.repository_code {
+            related_identifiers.push((url.clone(), "URL", "IsSupplementTo"));
+        }
+        if let Some(url) = &citation.url {
+            related_identifiers.push((url.clone(), "URL", "IsDocumentedBy"));
+        }
+        if let Some(doi) = citation.preferred_citation.as_ref().and_then(|reference| reference.doi.as_ref()) {
+            related_identifiers.push((doi.to_string(), "DOI", "IsDescribedBy"));
+        }
+        for doi in citation.references.iter().filter_map(|reference| reference.doi.as_ref()) {
+            related_identifiers.push((doi.to_string(), "DOI", "References"));
+        }
+
+        Resource {
+            doi,
+            creators: citation.authors.iter().map(Creator::new).collect(),
+            publisher,
+            publication_year: citation.date_released.map(|date| date.year()),
+            resource_type: match citation.r#type {
+                Some(Type::Dataset) => "Dataset",
+                _ => "Software",
+            },
+            alternate_identifiers,
+            related_identifiers,
+            licenses: citation.licenses(),
+        }
+    }
+}
+
+fn spdx_url(license: &License) -> Option<String> {
+    license.is_known().then(|| format!("https://spdx.org/licenses/{license}"))
+}
+
+impl Citation {
+    /// Converts the citation to an XML document in the [DataCite Metadata Schema 4](https://schema.datacite.org),
+    /// as used to register DOIs with DataCite, e.g. through an institutional repository.
+    ///
+    /// Authors become creators, with their ORCID as name identifier. DataCite requires a publisher, which is derived
+    /// from the hosting platform of the repository (e.g. `GitHub`) or set to `(:unav)` if it is unknown.
+    pub fn to_datacite_xml(&self) -> String {
+        let resource = Resource::new(self);
+
+        let creators = resource.creators.iter().map(|creator| {
+            let mut children = vec![
+                Element::text("creatorName", Some(&creator.name)).attribute("nameType", creator.name_type()),
+                Element::text("givenName", creator.given_name),
+                Element::text("familyName", creator.family_name.as_deref()),
+            ];
+            if let Some(orcid) = creator.orcid {
+                children.push(Element::text("nameIdentifier", Some(orcid)).attribute("nameIdentifierScheme", "ORCID").attribute("schemeURI", "https://orcid.org"));
+            }
+            children.push(Element::text("affiliation", creator.affiliation));
+            Element::group("creator", children)
+        });
+
+        let rights = self.licenses().iter().map(|license| {
+            let mut rights = Element::text("rights", Some(license.id()));
+            if let Some(url) = spdx_url(license) {
+                rights = rights.attribute("rightsURI", url).attribute("rightsIdentifier", license.id()).attribute("rightsIdentifierScheme", "SPDX");
+            }
+            rights
+        });
+        let rights_url = self.license_url.iter().map(|url| Element::text("rights", Some(url)).attribute("rightsURI", url));
+
+        Element::group(
+            "resource",
+            vec![
+                Element::text("identifier", resource.doi.as_deref()).attribute("identifierType", "DOI"),
+                Element::group("creators", creators.collect()),
+                Element::group("titles", vec![Element::text("title", Some(&self.title))]),
+                Element::text("publisher", Some(&resource.publisher)),
+                Element::text("publicationYear", resource.publication_year.map(|year| year.to_string()).as_deref()),
+                Element::text("resourceType", Some(resource.resource_type)).attribute("resourceTypeGeneral", resource.resource_type),
+                Element::group("subjects", self.keywords.iter().map(|keyword| Element::text("subject", Some(keyword))).collect()),
+                Element::group("dates", vec![Element::text("date", self.date_released.map(|date| date.to_string()).as_deref()).attribute("dateType", "Issued")]),
+                Element::group(
+                    "alternateIdentifiers",
+                    resource.alternate_identifiers.iter().map(|(value, r#type)| Element::text("alternateIdentifier", Some(value)).attribute("alternateIdentifierType", *r#type)).collect(),
+                ),
+                Element::group(
+                    "relatedIdentifiers",
+                    resource
+                        .related_identifiers
+                        .iter()
+                        .map(|(value, r#type, relation)| Element::text("relatedIdentifier", Some(value)).attribute("relatedIdentifierType", *r#type).attribute("relationType", *relation))
+                        .collect(),
+                ),
+                Element::text("version", self.version.as_deref()),
+                Element::group("rightsList", rights.chain(rights_url).collect()),
+                Element::group("descriptions", vec![Element::text("description", self.r#abstract.as_deref()).attribute("descriptionType", "Abstract")]),
+            ],
+        )
+        .attribute("xmlns", "http://datacite.org/schema/kernel-4")
+        .attribute("xmlns:xsi", "http://www.w3.org/2001/XMLSchema-instance")
+        .attribute("xsi:schemaLocation", "http://datacite.org/schema/kernel-4 https://schema.datacite.org/meta/kernel-4/metadata.xsd")
+        .document()
+    }
+
+    /// Converts the citation to the JSON attributes of a DOI in the [DataCite REST API](https://support.datacite.org/docs/api),
+    /// with the same mapping as [`Citation::to_datacite_xml`].
+    pub fn to_datacite_json(&self) -> String {
+        let resource = Resource::new(self);
+        let mut object = Map::new();
+
+        insert(&mut object, "doi", resource.doi.as_deref());
+        let creators = resource.creators.iter().map(|creator| {
+            let mut object = Map::new();
+            object.insert("name".into(), creator.name.clone().into());
+            object.insert("nameType".into(), creator.name_type().into());
+            insert(&mut object, "givenName", creator.given_name);
+            insert(&mut object, "familyName", creator.family_name.as_deref());
+            if let Some(orcid) = creator.orcid {
+                object.insert("nameIdentifiers".into(), json!([{ "nameIdentifier": orcid, "nameIdentifierScheme": "ORCID", "schemeUri": "https://orcid.org" }]));
+            }
+            if let Some(affiliation) = creator.affiliation {
+                object.insert("affiliation".into(), json!([{ "name": affiliation }]));
+            }
+            Value::Object(object)
+        });
+        object.insert("creators".into(), creators.collect());
+        object.insert("titles".into(), json!([{ "title": self.title }]));
+        object.insert("publisher".into(), resource.publisher.into());
+        if let Some(year) = resource.publication_year {
+            object.insert("publicationYear".into(), year.into());
+        }
+        object.insert("types".into(), json!({ "resourceTypeGeneral": resource.resource_type, "resourceType": resource.resource_type }));
+        if !self.keywords.is_empty() {
+            object.insert("subjects".into(), self.keywords.iter().map(|keyword| json!({ "subject": keyword })).collect());
+        }
+        if let Some(date) = self.date_released {
+            object.insert("dates".into(), json!([{ "date": date.to_string(), "dateType": "Issued" }]));
+        }
+        if !resource.alternate_identifiers.is_empty() {
+            let identifiers = resource.alternate_identifiers.iter().map(|(value, r#type)| json!({ "alternateIdentifier": value, "alternateIdentifierType": r#type }));
+            object.insert("alternateIdentifiers".into(), identifiers.collect());
+        }
+        if !resource.related_identifiers.is_empty() {
+            let identifiers = resource.related_identifiers.iter().map(|(value, r#type, relation)| json!({ "relatedIdentifier": value, "relatedIdentifierType": r#type, "relationType": relation }));
+            object.insert("relatedIdentifiers".into(), identifiers.collect());
+        }
+        insert(&mut object, "version", self.version.as_deref());
+
+        let mut rights: Vec<Value> = resource
+            .licenses
+            .iter()
+            .map(|license| match spdx_url(license) {
+                Some(url) => json!({ "rights": license.id(), "rightsUri": url, "rightsIdentifier": license.id(), "rightsIdentifierScheme": "SPDX" }),
+                None => json!({ "rights": license.id() }),
+            })
+            .collect();
+        rights.extend(self.license_url.iter().map(|url| json!({ "rights": url, "rightsUri": url })));
+        if !rights.is_empty() {
+            object.insert("rightsList".into(), rights.into());
+        }
+        if let Some(r#abstract) = &self.r#abstract {
+            object.insert("descriptions".into(), json!([{ "description": r#abstract, "descriptionType": "Abstract" }]));
+        }
+        insert(&mut object, "url", self.url.as_deref().or(self.repository_code.as_deref()));
+
+        serde_json::to_string_pretty(&object).unwrap_or_default()
+    }
+}
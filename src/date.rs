@@ -0,0 +1,200 @@
+use std::{error::Error, fmt, str::FromStr};
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::de::FromStrVisitor;
+
+/// A calendar date in the `YYYY-MM-DD` format required by the schema, e.g. `2021-07-18`.
+///
+/// Dates are validated on construction, so impossible dates such as `2023-02-29` are rejected.
+/// Dates are ordered chronologically.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(into = "String")]
+pub struct Date {
+    year: u16,
+    month: u8,
+    day: u8,
+}
+
+impl Date {
+    /// Creates a date, failing if the day does not exist in the given month.
+    pub fn new(year: u16, month: u8, day: u8) -> Result<Self, ParseDateError> {
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return Err(ParseDateError::InvalidDate(format!("{year:04}-{month:02}-{day:02}")));
+        }
+
+        Ok(Date { year, month, day })
+    }
+
+    /// Returns the current date in UTC, e.g. for `date-released` when publishing a release.
+    pub fn today() -> Self {
+        #[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+        let seconds = (crate::wasm::now() / 1000.0) as u64;
+        #[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+        let seconds = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map_or(0, |duration| duration.as_secs());
+
+        Date::from_timestamp(seconds as i64).unwrap_or(Date { year: 1970, month: 1, day: 1 })
+    }
+
+    /// Returns the date of the given Unix timestamp, or `None` if the year is out of range.
+    pub(crate) fn from_timestamp(seconds: i64) -> Option<Self> {
+        // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+        let days = seconds.div_euclid(86_400) + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * month + 2) / 5 + 1;
+        let month = if month < 10 { month + 3 } else { month - 9 };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+        Date::new(u16::try_from(year).ok()?, month as u8, day as u8).ok()
+    }
+
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+}
+
+fn is_leap_year(year: u16) -> bool {
+    (year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400)
+}
+
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl FromStr for Date {
+    type Err = ParseDateError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid_format = || ParseDateError::InvalidFormat(value.to_string());
+
+        let bytes = value.as_bytes();
+        if bytes.len() != 10 || bytes[4] != b'-' || bytes[7] != b'-' {
+            return Err(invalid_format());
+        }
+
+        let number = |range: std::ops::Range<usize>| {
+            let digits = &value[range];
+            match digits.bytes().all(|b| b.is_ascii_digit()) {
+                true => digits.parse().map_err(|_| invalid_format()),
+                false => Err(invalid_format()),
+            }
+        };
+
+        let year = number(0..4)?;
+        let month = number(5..7)? as u8;
+        let day = number(8..10)? as u8;
+
+        Date::new(year, month, day).map_err(|_| ParseDateError::InvalidDate(value.to_string()))
+    }
+}
+
+impl TryFrom<String> for Date {
+    type Error = ParseDateError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl<'de> Deserialize<'de> for Date {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(FromStrVisitor::new("a date in the format YYYY-MM-DD"))
+    }
+}
+
+impl From<Date> for String {
+    fn from(date: Date) -> Self {
+        date.to_string()
+    }
+}
+
+/// Generates existing dates between the years 1 and 9999.
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Date {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let (year, month, day) = (u.int_in_range(1..=9999)?, u.int_in_range(1..=12)?, u.int_in_range(1..=31)?);
+
+        Ok(Date::new(year, month, day).or_else(|_| Date::new(year, month, 28)).expect("every month has 28 days"))
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for Date {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "Date".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "A date in the format YYYY-MM-DD.",
+            "type": "string",
+            "format": "date",
+            "pattern": "^[0-9]{4}-[0-9]{2}-[0-9]{2}$",
+        })
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<Date> for chrono::NaiveDate {
+    fn from(date: Date) -> Self {
+        chrono::NaiveDate::from_ymd_opt(date.year.into(), date.month.into(), date.day.into())
+            .expect("dates are validated on construction")
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<chrono::NaiveDate> for Date {
+    type Error = ParseDateError;
+
+    fn try_from(date: chrono::NaiveDate) -> Result<Self, Self::Error> {
+        use chrono::Datelike;
+
+        let year = u16::try_from(date.year()).map_err(|_| ParseDateError::InvalidDate(date.to_string()))?;
+
+        Date::new(year, date.month() as u8, date.day() as u8)
+    }
+}
+
+/// Error returned when a value is not a valid `YYYY-MM-DD` date.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseDateError {
+    /// The value is not in the `YYYY-MM-DD` format.
+    InvalidFormat(String),
+
+    /// The value is in the right format, but the date does not exist.
+    InvalidDate(String),
+}
+
+impl fmt::Display for ParseDateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseDateError::InvalidFormat(value) => write!(f, "`{value}` is not a date in the format YYYY-MM-DD"),
+            ParseDateError::InvalidDate(value) => write!(f, "`{value}` is not a valid date"),
+        }
+    }
+}
+
+impl Error for ParseDateError {}
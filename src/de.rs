@@ -0,0 +1,34 @@
+use std::{fmt, marker::PhantomData, str::FromStr};
+
+use serde::de::{Error, Visitor};
+
+/// Deserializes a value from a string through its [`FromStr`] implementation.
+///
+/// Failing inside the visitor (instead of converting after deserializing a `String`)
+/// lets the deserializer attach the location of the offending scalar to the error.
+pub(crate) struct FromStrVisitor<T> {
+    expecting: &'static str,
+    marker: PhantomData<T>,
+}
+
+impl<T> FromStrVisitor<T> {
+    pub(crate) fn new(expecting: &'static str) -> Self {
+        FromStrVisitor { expecting, marker: PhantomData }
+    }
+}
+
+impl<T> Visitor<'_> for FromStrVisitor<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.expecting)
+    }
+
+    fn visit_str<E: Error>(self, value: &str) -> Result<Self::Value, E> {
+        value.parse().map_err(E::custom)
+    }
+}
@@ -0,0 +1,144 @@
+//! [`miette::Diagnostic`] implementations, enabled with the `diagnostics` feature.
+
+use miette::{Diagnostic, LabeledSpan, MietteError, MietteSpanContents, SourceCode, SourceSpan, SpanContents};
+
+use crate::{
+    span::{locate, Source},
+    Error, Issue, ParseError, Severity, ValidationReport,
+};
+
+impl SourceCode for Source {
+    fn read_span<'a>(
+        &'a self,
+        span: &SourceSpan,
+        context_lines_before: usize,
+        context_lines_after: usize,
+    ) -> Result<Box<dyn SpanContents<'a> + 'a>, MietteError> {
+        let contents = self.content.read_span(span, context_lines_before, context_lines_after)?;
+
+        Ok(Box::new(MietteSpanContents::new_named(
+            self.name.clone(),
+            contents.data(),
+            *contents.span(),
+            contents.line(),
+            contents.column(),
+            contents.line_count(),
+        )))
+    }
+}
+
+impl From<Severity> for miette::Severity {
+    fn from(severity: Severity) -> Self {
+        match severity {
+            Severity::Hint => miette::Severity::Advice,
+            Severity::Warning => miette::Severity::Warning,
+            Severity::Error => miette::Severity::Error,
+        }
+    }
+}
+
+impl Diagnostic for ParseError {
+    fn code<'a>(&'a self) -> Option<Box<dyn std::fmt::Display + 'a>> {
+        Some(Box::new("citation::parse"))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn std::fmt::Display + 'a>> {
+        Some(Box::new(
+            "see https://github.com/citation-file-format/citation-file-format/blob/main/schema-guide.md for the valid keys and values",
+        ))
+    }
+
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        self.source.as_deref().map(|source| source as &dyn SourceCode)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        let offset = self.offset?;
+
+        // Prefer the full region of the offending value if it starts where the parser stopped.
+        let length = self
+            .source
+            .as_ref()
+            .zip(self.path.as_ref())
+            .and_then(|(source, path)| locate(&source.content, path))
+            .filter(|span| span.offset == offset)
+            .map_or(0, |span| span.length);
+
+        Some(Box::new(std::iter::once(LabeledSpan::new(Some(self.message.clone()), offset, length))))
+    }
+}
+
+impl Diagnostic for Issue {
+    fn code<'a>(&'a self) -> Option<Box<dyn std::fmt::Display + 'a>> {
+        Some(Box::new(self.code))
+    }
+
+    fn severity(&self) -> Option<miette::Severity> {
+        Some(self.severity.into())
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn std::fmt::Display + 'a>> {
+        Some(Box::new(self.code.help()))
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        let span = self.span?;
+
+        Some(Box::new(std::iter::once(LabeledSpan::new(Some(self.message.clone()), span.offset, span.length))))
+    }
+}
+
+impl Diagnostic for ValidationReport {
+    fn code<'a>(&'a self) -> Option<Box<dyn std::fmt::Display + 'a>> {
+        Some(Box::new("citation::validate"))
+    }
+
+    fn severity(&self) -> Option<miette::Severity> {
+        self.iter().map(|issue| issue.severity).max().map(Into::into)
+    }
+
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        self.source.as_deref().map(|source| source as &dyn SourceCode)
+    }
+
+    fn related<'a>(&'a self) -> Option<Box<dyn Iterator<Item = &'a dyn Diagnostic> + 'a>> {
+        Some(Box::new(self.iter().map(|issue| issue as &dyn Diagnostic)))
+    }
+}
+
+impl Error {
+    /// Returns the diagnostic of parse and validation errors, which have a location in the source.
+    fn diagnostic(&self) -> Option<&dyn Diagnostic> {
+        match self {
+            Error::Yaml(error) => Some(error),
+            Error::Validation(report) => Some(report),
+            _ => None,
+        }
+    }
+}
+
+impl Diagnostic for Error {
+    fn code<'a>(&'a self) -> Option<Box<dyn std::fmt::Display + 'a>> {
+        self.diagnostic()?.code()
+    }
+
+    fn severity(&self) -> Option<miette::Severity> {
+        self.diagnostic()?.severity()
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn std::fmt::Display + 'a>> {
+        self.diagnostic()?.help()
+    }
+
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        self.diagnostic()?.source_code()
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        self.diagnostic()?.labels()
+    }
+
+    fn related<'a>(&'a self) -> Option<Box<dyn Iterator<Item = &'a dyn Diagnostic> + 'a>> {
+        self.diagnostic()?.related()
+    }
+}
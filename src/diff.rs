@@ -0,0 +1,146 @@
+use std::fmt;
+
+use serde_yaml::Value;
+
+use crate::Citation;
+
+/// A difference between two citations, see [`Citation::diff`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    /// A key or item that only the new citation has.
+    Added { path: String, value: Value },
+
+    /// A key or item that only the old citation has.
+    Removed { path: String, value: Value },
+
+    /// A value that differs between the citations.
+    Modified { path: String, old: Value, new: Value },
+}
+
+impl Change {
+    /// Returns the path of the changed entry, e.g. `authors[1].orcid`. Added and modified entries have their path
+    /// in the new citation, removed entries their path in the old citation.
+    pub fn path(&self) -> &str {
+        match self {
+            Change::Added { path, .. } | Change::Removed { path, .. } | Change::Modified { path, .. } => path,
+        }
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::Added { path, value } => write!(f, "added {path}: {}", display(value)),
+            Change::Removed { path, value } => write!(f, "removed {path}: {}", display(value)),
+            Change::Modified { path, old, new } => write!(f, "changed {path} from {} to {}", display(old), display(new)),
+        }
+    }
+}
+
+/// Writes a value on a single line, e.g. `Lewin` or `{"family-names":"Lewin","given-names":"Luca"}`.
+pub(crate) fn display(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.clone(),
+        value => serde_json::to_string(value).unwrap_or_default(),
+    }
+}
+
+/// Returns what identifies an item of a sequence across versions of a citation, e.g. the ORCID of a person or the
+/// DOI of a reference, as well as their names and titles. Two items describe the same thing if they share any of
+/// these, so that adding an ORCID to an author is reported as a change of the author.
+pub(crate) fn identities(value: &Value) -> Vec<String> {
+    let get = |key: &str| value.get(key).map(display);
+
+    match value {
+        Value::Mapping(_) => [
+            get("orcid").map(|orcid| format!("orcid {orcid}")),
+            get("doi").map(|doi| format!("doi {doi}")),
+            get("name").map(|name| format!("name {name}")),
+            get("family-names").map(|family| format!("person {} {family}", get("given-names").unwrap_or_default())),
+            get("title").map(|title| format!("title {} {title}", get("type").unwrap_or_default())),
+            get("value").map(|value| format!("value {value}")),
+        ]
+        .into_iter()
+        .flatten()
+        .collect(),
+        value => vec![display(value)],
+    }
+}
+
+pub(crate) fn join(path: &str, key: &str) -> String {
+    match path {
+        "" => key.to_string(),
+        _ => format!("{path}.{key}"),
+    }
+}
+
+fn compare(path: &str, old: &Value, new: &Value, changes: &mut Vec<Change>) {
+    match (old, new) {
+        (Value::Mapping(old), Value::Mapping(new)) => {
+            for (key, value) in old {
+                if !new.contains_key(key) {
+                    changes.push(Change::Removed { path: join(path, &display(key)), value: value.clone() });
+                }
+            }
+            for (key, value) in new {
+                let key_path = join(path, &display(key));
+                match old.get(key) {
+                    Some(old) => compare(&key_path, old, value, changes),
+                    None => changes.push(Change::Added { path: key_path, value: value.clone() }),
+                }
+            }
+        }
+        (Value::Sequence(old), Value::Sequence(new)) => sequence(path, old, new, changes),
+        (old, new) if old != new => changes.push(Change::Modified { path: path.to_string(), old: old.clone(), new: new.clone() }),
+        _ => {}
+    }
+}
+
+/// Compares the items of two sequences, matching items that describe the same person, reference or value
+/// instead of comparing them by position, so that inserting an author does not change all the following ones.
+fn sequence(path: &str, old: &[Value], new: &[Value], changes: &mut Vec<Change>) {
+    let old_identities: Vec<Vec<String>> = old.iter().map(identities).collect();
+
+    let mut matched = vec![None; new.len()];
+    let mut used = vec![false; old.len()];
+    for (index, item) in new.iter().enumerate() {
+        let item = identities(item);
+        // items without identity are compared by position
+        let position = match item.is_empty() {
+            false => (0..old.len()).find(|&position| !used[position] && old_identities[position].iter().any(|identity| item.contains(identity))),
+            true => (index < old.len() && !used[index] && old_identities[index].is_empty()).then_some(index),
+        };
+        if let Some(position) = position {
+            used[position] = true;
+            matched[index] = Some(position);
+        }
+    }
+
+    for (position, value) in old.iter().enumerate().filter(|(position, _)| !used[*position]) {
+        changes.push(Change::Removed { path: format!("{path}[{position}]"), value: value.clone() });
+    }
+    for (index, value) in new.iter().enumerate() {
+        let item_path = format!("{path}[{index}]");
+        match matched[index] {
+            Some(position) => compare(&item_path, &old[position], value, changes),
+            None => changes.push(Change::Added { path: item_path, value: value.clone() }),
+        }
+    }
+}
+
+impl Citation {
+    /// Compares the citation with a newer version of it, e.g. to show what a pull request changes in the
+    /// metadata beyond a textual diff.
+    ///
+    /// Changes are reported per key. Authors, references and other lists are compared item by item, matching
+    /// items by their ORCID, DOI, name, title or value rather than by their position, so reordering items does
+    /// not report them as changed.
+    pub fn diff(&self, other: &Citation) -> Vec<Change> {
+        let old = serde_yaml::to_value(self).expect("citations can be serialized");
+        let new = serde_yaml::to_value(other).expect("citations can be serialized");
+
+        let mut changes = Vec::new();
+        compare("", &old, &new, &mut changes);
+        changes
+    }
+}
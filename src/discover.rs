@@ -0,0 +1,29 @@
+use std::path::PathBuf;
+
+use crate::{Citation, Error};
+
+/// The locations searched in every directory, relative to the directory.
+const CANDIDATES: &[&str] = &["CITATION.cff", ".github/CITATION.cff"];
+
+impl Citation {
+    /// Searches the given directory and its ancestors for a `CITATION.cff` file, like cargo does for
+    /// `Cargo.toml`, and parses the first one found.
+    ///
+    /// In every directory, `CITATION.cff` is preferred over `.github/CITATION.cff`.
+    /// Returns `None` if no file is found.
+    pub fn discover(start: PathBuf) -> Result<Option<(PathBuf, Self)>, Error> {
+        let start = start.canonicalize()?;
+
+        for directory in start.ancestors() {
+            for candidate in CANDIDATES {
+                let path = directory.join(candidate);
+                if path.is_file() {
+                    let citation = Citation::read(path.clone())?;
+                    return Ok(Some((path, citation)));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+}
@@ -0,0 +1,329 @@
+use std::fmt;
+#[cfg(feature = "fs")]
+use std::path::PathBuf;
+
+use serde_yaml::Value;
+
+use crate::{
+    parse::{parse_yaml, ParseOptions},
+    span::{entry, mapping_end},
+    Citation, ParseError,
+};
+
+/// A `CITATION.cff` file that is edited in place.
+///
+/// Unlike [`Citation::write`], which re-serializes the whole citation, a document only patches the
+/// values that changed. Key order, quoting style, comments and all untouched content are kept as they are.
+///
+/// Comments on the lines directly above an entry and at the end of its first line are attached to
+/// the entry: they are removed together with it and can be read and written with [`Document::comment`]
+/// and [`Document::set_comment`].
+///
+/// ```no_run
+/// use citation::Document;
+///
+/// let mut document = Document::read("CITATION.cff".into()).unwrap();
+/// let mut citation = document.citation().unwrap();
+/// citation.version = Some("1.1.0".into());
+///
+/// document.update(&citation).unwrap();
+/// document.write("CITATION.cff".into()).unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Document {
+    pub(crate) source: String,
+}
+
+impl Document {
+    /// Creates a document from the content of a `CITATION.cff` file, failing if it is not a valid citation.
+    pub fn parse(source: impl Into<String>) -> Result<Self, ParseError> {
+        let source = source.into();
+        parse_yaml(&source, &ParseOptions::default())?;
+
+        Ok(Document { source })
+    }
+
+    /// Reads the `CITATION.cff` file at the given path.
+    #[cfg(feature = "fs")]
+    pub fn read(path: PathBuf) -> Result<Self, crate::Error> {
+        let content = std::fs::read_to_string(&path)?;
+
+        Ok(Self::parse(content).map_err(|error| error.with_file_name(path.display().to_string()))?)
+    }
+
+    /// Parses the citation described by the document.
+    pub fn citation(&self) -> Result<Citation, ParseError> {
+        parse_yaml(&self.source, &ParseOptions::default())
+    }
+
+    /// Updates the document to describe the given citation, patching only the values that changed.
+    ///
+    /// If the changes cannot be applied in place, e.g. because the document uses flow-style
+    /// collections, the affected values (or, as a last resort, the whole document) are rewritten.
+    pub fn update(&mut self, citation: &Citation) -> Result<(), serde_yaml::Error> {
+        let target = serde_yaml::to_value(citation)?;
+        let current: Value = serde_yaml::from_str(&self.source)?;
+
+        let mut patched = self.source.clone();
+        let applied = patch(&mut patched, "", &current, &target).is_some();
+
+        self.source = match applied && serde_yaml::from_str::<Value>(&patched).is_ok_and(|value| value == target) {
+            true => patched,
+            false => serde_yaml::to_string(citation)?,
+        };
+
+        Ok(())
+    }
+
+    /// Returns the comment attached to the entry at the given path, e.g. `authors[0].orcid`,
+    /// without the `#` markers. Lines of the comment are separated by line breaks.
+    pub fn comment(&self, path: &str) -> Option<String> {
+        let entry = entry(&self.source, path)?;
+
+        let leading = self.source[entry.leading..entry.start].lines();
+        let trailing = entry.comment.map(|span| &self.source[span.offset..span.offset + span.length]);
+
+        let lines: Vec<&str> = leading
+            .chain(trailing)
+            .map(|line| line.trim_start().trim_start_matches('#'))
+            .map(|line| line.strip_prefix(' ').unwrap_or(line).trim_end())
+            .collect();
+
+        (!lines.is_empty()).then(|| lines.join("\n"))
+    }
+
+    /// Sets the comment on the lines directly above the entry at the given path, replacing the
+    /// existing ones. An empty comment removes them.
+    ///
+    /// Returns `false` if there is no entry at the given path.
+    pub fn set_comment(&mut self, path: &str, comment: &str) -> bool {
+        let Some(entry) = entry(&self.source, path) else { return false };
+
+        let comment: String = comment
+            .lines()
+            .map(|line| match line.is_empty() {
+                true => format!("{:indent$}#\n", "", indent = entry.indent),
+                false => format!("{:indent$}# {line}\n", "", indent = entry.indent),
+            })
+            .collect();
+
+        self.source.replace_range(entry.leading..entry.start, &comment);
+        true
+    }
+
+    /// Returns the content of the document.
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// Writes the document to the given path.
+    #[cfg(feature = "fs")]
+    pub fn write(&self, path: PathBuf) -> Result<(), crate::Error> {
+        std::fs::write(path, &self.source)?;
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Document {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+/// Patches the value at the given path from `current` to `target`, recursing into block collections.
+fn patch(source: &mut String, path: &str, current: &Value, target: &Value) -> Option<()> {
+    if current == target {
+        return Some(());
+    }
+
+    let is_block = path.is_empty() || entry(source, path).is_some_and(|entry| entry.inline.is_none());
+
+    let mut attempt = source.clone();
+    let patched = match (current, target) {
+        (Value::Mapping(current), Value::Mapping(target)) if is_block => {
+            patch_mapping(&mut attempt, path, current, target)
+        }
+        (Value::Sequence(current), Value::Sequence(target)) if is_block && !current.is_empty() => {
+            patch_sequence(&mut attempt, path, current, target)
+        }
+        _ => None,
+    };
+
+    match patched {
+        Some(()) => *source = attempt,
+        None => replace(source, path, target)?,
+    }
+
+    Some(())
+}
+
+fn patch_mapping(source: &mut String, path: &str, current: &serde_yaml::Mapping, target: &serde_yaml::Mapping) -> Option<()> {
+    for key in current.keys().filter(|key| !target.contains_key(*key)) {
+        remove(source, &join(path, key.as_str()?))?;
+    }
+
+    for (key, value) in target {
+        let name = key.as_str()?;
+
+        match current.get(key) {
+            Some(current) => patch(source, &join(path, name), current, value)?,
+            None => insert(source, path, name, value)?,
+        }
+    }
+
+    Some(())
+}
+
+fn patch_sequence(source: &mut String, path: &str, current: &[Value], target: &[Value]) -> Option<()> {
+    for index in (target.len()..current.len()).rev() {
+        remove(source, &format!("{path}[{index}]"))?;
+    }
+
+    for (index, (current, target)) in current.iter().zip(target).enumerate() {
+        patch(source, &format!("{path}[{index}]"), current, target)?;
+    }
+
+    for (index, value) in target.iter().enumerate().skip(current.len()) {
+        append_item(source, &format!("{path}[{}]", index - 1), value)?;
+    }
+
+    Some(())
+}
+
+fn join(path: &str, key: &str) -> String {
+    match path {
+        "" => key.to_string(),
+        _ => format!("{path}.{key}"),
+    }
+}
+
+/// Replaces the value of the entry at the given path.
+pub(crate) fn replace(source: &mut String, path: &str, target: &Value) -> Option<()> {
+    let entry = entry(source, path)?;
+
+    match entry.inline {
+        Some(span) if source[span.offset..].starts_with('[') && flow(target).is_some() => {
+            let text = flow(target)?;
+            source.replace_range(span.offset..span.offset + span.length, &text);
+        }
+        Some(span) if !render(target).contains('\n') => {
+            let text = requote(&source[span.offset..span.offset + span.length], target);
+            source.replace_range(span.offset..span.offset + span.length, &text);
+        }
+        _ => {
+            let item = source[entry.start + entry.indent..].starts_with('-');
+            let mut value = render_value(target, entry.indent + 2, item && target.is_mapping());
+            // keep the comment at the end of the line of the key
+            if let Some(span) = entry.comment {
+                let comment = &source[span.offset..span.offset + span.length];
+                let position = value.find('\n').unwrap_or(value.len());
+                value.insert_str(position, &format!(" {comment}"));
+            }
+
+            source.replace_range(entry.value..entry.end, &format!("{value}\n"));
+        }
+    }
+
+    Some(())
+}
+
+/// Removes the entry at the given path, including its nested block and attached comments.
+fn remove(source: &mut String, path: &str) -> Option<()> {
+    let entry = entry(source, path)?;
+    source.replace_range(entry.leading..entry.end, "");
+
+    Some(())
+}
+
+/// Appends a new entry to the mapping at the given path.
+fn insert(source: &mut String, path: &str, key: &str, value: &Value) -> Option<()> {
+    let (end, indent) = mapping_end(source, path)?;
+
+    let key = render(&Value::String(key.to_string()));
+    let value = render_value(value, indent + 2, false);
+    let separator = if end == source.len() && !source.is_empty() && !source.ends_with('\n') { "\n" } else { "" };
+
+    source.insert_str(end, &format!("{separator}{:indent$}{key}:{value}\n", ""));
+
+    Some(())
+}
+
+/// Appends a new item to a sequence after the item at the given path.
+fn append_item(source: &mut String, previous: &str, value: &Value) -> Option<()> {
+    let entry = entry(source, previous)?;
+
+    let value = render_value(value, entry.indent + 2, value.is_mapping());
+    let separator = if entry.end == source.len() && !source.ends_with('\n') { "\n" } else { "" };
+
+    source.insert_str(entry.end, &format!("{separator}{:indent$}-{value}\n", "", indent = entry.indent));
+
+    Some(())
+}
+
+/// Serializes a value to YAML without the trailing line break.
+fn render(value: &Value) -> String {
+    let rendered = serde_yaml::to_string(value).unwrap_or_default();
+
+    rendered.trim_end_matches('\n').to_string()
+}
+
+/// Renders a value as it follows a key or dash: inline after a space, or as a block on the
+/// following lines indented by `indent`.
+fn render_value(value: &Value, indent: usize, compact: bool) -> String {
+    let rendered = render(value);
+    let mut lines = rendered.lines();
+
+    if !rendered.contains('\n') {
+        return format!(" {rendered}");
+    }
+
+    let mut result = String::new();
+    let mut indent = indent;
+    // block scalars and mappings in sequence items start on the line of the key or dash
+    if compact || rendered.starts_with(['|', '>']) {
+        result.push(' ');
+        result.push_str(lines.next().unwrap_or_default());
+    }
+    // the content of block scalars is already indented by the serializer
+    if rendered.starts_with(['|', '>']) {
+        indent -= 2;
+    }
+
+    for line in lines {
+        result.push('\n');
+        if !line.is_empty() {
+            result.push_str(&format!("{:indent$}{line}", ""));
+        }
+    }
+
+    result
+}
+
+/// Renders a sequence of scalars in flow style, e.g. `[citation, cff]`.
+fn flow(value: &Value) -> Option<String> {
+    let items = value.as_sequence()?;
+    if items.iter().any(|item| item.is_sequence() || item.is_mapping()) {
+        return None;
+    }
+
+    let items: Vec<String> = items.iter().map(render).collect();
+    if items.iter().any(|item| item.contains(['\n', ',', '[', ']', '{', '}'])) {
+        return None;
+    }
+
+    Some(format!("[{}]", items.join(", ")))
+}
+
+/// Renders a scalar, keeping the quoting style of the value it replaces where possible.
+fn requote(original: &str, target: &Value) -> String {
+    let Value::String(value) = target else { return render(target) };
+
+    match original.chars().next() {
+        Some('\'') if !value.contains('\n') => format!("'{}'", value.replace('\'', "''")),
+        Some('"') if !value.chars().any(char::is_control) => {
+            format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+        }
+        _ => render(target),
+    }
+}
@@ -0,0 +1,136 @@
+use std::{error::Error, fmt, str::FromStr};
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::de::FromStrVisitor;
+
+/// A Digital Object Identifier, e.g. `10.5281/zenodo.1003150`.
+///
+/// The value is validated against the DOI pattern of the schema on construction.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+#[serde(into = "String")]
+pub struct Doi(String);
+
+impl Doi {
+    /// Validates and wraps the given DOI.
+    pub fn new(value: impl Into<String>) -> Result<Self, ParseDoiError> {
+        let value = value.into();
+
+        match is_doi(&value) {
+            true => Ok(Doi(value)),
+            false => Err(ParseDoiError(value)),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the prefix of the DOI, e.g. `10.5281`.
+    pub fn prefix(&self) -> &str {
+        self.0.split_once('/').map_or(&self.0, |(prefix, _)| prefix)
+    }
+
+    /// Returns the suffix of the DOI, e.g. `zenodo.1003150`.
+    pub fn suffix(&self) -> &str {
+        self.0.split_once('/').map_or("", |(_, suffix)| suffix)
+    }
+
+    /// Returns the link to the DOI resolver, e.g. `https://doi.org/10.5281/zenodo.1003150`.
+    pub fn to_url(&self) -> String {
+        format!("https://doi.org/{}", self.0)
+    }
+}
+
+impl fmt::Display for Doi {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for Doi {
+    type Err = ParseDoiError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Doi::new(value)
+    }
+}
+
+impl TryFrom<String> for Doi {
+    type Error = ParseDoiError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Doi::new(value)
+    }
+}
+
+impl<'de> Deserialize<'de> for Doi {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(FromStrVisitor::new("a DOI"))
+    }
+}
+
+impl From<Doi> for String {
+    fn from(doi: Doi) -> Self {
+        doi.0
+    }
+}
+
+/// Generates DOIs matching the pattern of the schema.
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Doi {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let registrant: u32 = u.int_in_range(1000..=999_999_999)?;
+        let suffix: String = String::arbitrary(u)?.chars().filter(|c| c.is_ascii_alphanumeric() || ":/_;-.()[]\\".contains(*c)).collect();
+        let suffix = if suffix.is_empty() { "0" } else { &suffix };
+
+        Ok(Doi::new(format!("10.{registrant}/{suffix}")).expect("the DOI matches the pattern"))
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for Doi {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "Doi".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "A DOI without the resolver, e.g. `10.5281/zenodo.1003150`.",
+            "type": "string",
+            "pattern": "^10\\.\\d{4,9}(\\.\\d+)?/.+$",
+        })
+    }
+}
+
+/// Error returned when a value does not match the DOI pattern.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDoiError(pub String);
+
+impl fmt::Display for ParseDoiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` is not a valid DOI", self.0)
+    }
+}
+
+impl Error for ParseDoiError {}
+
+/// Matches the DOI pattern of the schema: `^10\.\d{4,9}(\.\d+)?/[A-Za-z0-9:/_;\-\.\(\)\[\]\\]+$`
+pub(crate) fn is_doi(value: &str) -> bool {
+    let Some((prefix, suffix)) = value.strip_prefix("10.").and_then(|rest| rest.split_once('/')) else {
+        return false;
+    };
+
+    let (registrant, sub) = match prefix.split_once('.') {
+        Some((registrant, sub)) => (registrant, Some(sub)),
+        None => (prefix, None),
+    };
+
+    let is_digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+
+    (4..=9).contains(&registrant.len())
+        && is_digits(registrant)
+        && sub.is_none_or(is_digits)
+        && !suffix.is_empty()
+        && suffix.chars().all(|c| c.is_ascii_alphanumeric() || ":/_;-.()[]\\".contains(c))
+}
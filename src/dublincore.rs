@@ -0,0 +1,75 @@
+use crate::{ris::name, style::host, xml::Element, Citation, Identifier, Type};
+
+const DC: &str = "http://purl.org/dc/elements/1.1/";
+
+/// Converts the citation to the elements of the Dublin Core Metadata Element Set.
+fn elements(citation: &Citation) -> Vec<Element> {
+    let mut elements = vec![Element::text("dc:title", Some(&citation.title))];
+    elements.extend(citation.authors.iter().map(|author| Element::text("dc:creator", Some(&name(author)))));
+    elements.extend(citation.keywords.iter().map(|keyword| Element::text("dc:subject", Some(keyword))));
+    elements.push(Element::text("dc:description", citation.r#abstract.as_deref()));
+
+    let publisher = [&citation.repository_code, &citation.url, &citation.repository_artifact, &citation.repository].into_iter().flatten().find_map(|url| host(url));
+    elements.push(Element::text("dc:publisher", publisher.as_deref()));
+    elements.push(Element::text("dc:date", citation.date_released.map(|date| date.to_string()).as_deref()));
+    // The DCMI Type Vocabulary, https://www.dublincore.org/specifications/dublin-core/dcmi-type-vocabulary/
+    let r#type = match citation.r#type {
+        Some(Type::Dataset) => "Dataset",
+        _ => "Software",
+    };
+    elements.push(Element::text("dc:type", Some(r#type)));
+
+    let mut identifiers: Vec<String> = Vec::new();
+    let doi = citation.doi.as_ref().map(|doi| doi.to_url());
+    let urls = citation.url.iter().chain(&citation.repository_code).cloned();
+    let others = citation.identifiers.iter().map(|identifier| match identifier {
+        Identifier::Doi { value, .. } => format!("https://doi.org/{value}"),
+        _ => identifier.value().to_string(),
+    });
+    for identifier in doi.into_iter().chain(urls).chain(others) {
+        if !identifiers.contains(&identifier) {
+            identifiers.push(identifier);
+        }
+    }
+    elements.extend(identifiers.iter().map(|identifier| Element::text("dc:identifier", Some(identifier))));
+
+    let relations = citation.preferred_citation.iter().chain(&citation.references).filter_map(|reference| reference.doi.as_ref());
+    elements.extend(relations.map(|doi| Element::text("dc:relation", Some(&doi.to_url()))));
+    elements.extend(citation.licenses().iter().map(|license| Element::text("dc:rights", Some(license.id()))));
+    elements.push(Element::text("dc:rights", citation.license_url.as_deref()));
+
+    elements
+}
+
+impl Citation {
+    /// Converts the citation to an unqualified Dublin Core record in the `oai_dc` format, which every
+    /// [OAI-PMH](https://www.openarchives.org/pmh/) repository supports.
+    ///
+    /// Authors become creators written as `Family, Given`, keywords become subjects and the DOI, landing page and
+    /// repository become identifiers. References with a DOI are listed as relations.
+    pub fn to_dublin_core(&self) -> String {
+        Element::group("oai_dc:dc", elements(self))
+            .attribute("xmlns:oai_dc", "http://www.openarchives.org/OAI/2.0/oai_dc/")
+            .attribute("xmlns:dc", DC)
+            .attribute("xmlns:xsi", "http://www.w3.org/2001/XMLSchema-instance")
+            .attribute("xsi:schemaLocation", "http://www.openarchives.org/OAI/2.0/oai_dc/ http://www.openarchives.org/OAI/2.0/oai_dc.xsd")
+            .document()
+    }
+
+    /// Converts the citation to Dublin Core in RDF/XML, with the same elements as [`Citation::to_dublin_core`].
+    ///
+    /// The described resource is identified by its DOI, or by its landing page or repository if it has no DOI.
+    pub fn to_dublin_core_rdf(&self) -> String {
+        let about = self.doi.as_ref().map(|doi| doi.to_url()).or_else(|| self.url.clone()).or_else(|| self.repository_code.clone());
+
+        let mut description = Element::group("rdf:Description", elements(self));
+        if let Some(about) = about {
+            description = description.attribute("rdf:about", about);
+        }
+
+        Element::group("rdf:RDF", vec![description])
+            .attribute("xmlns:rdf", "http://www.w3.org/1999/02/22-rdf-syntax-ns#")
+            .attribute("xmlns:dc", DC)
+            .document()
+    }
+}
@@ -0,0 +1,178 @@
+use std::fmt::Write;
+
+use crate::{ris::name, xml::escape, Author, Citation, Date, Reference, ReferenceType, Type};
+
+/// The EndNote reference types as `(name, number)`, used in the `ref-type` element.
+fn ref_type(r#type: &ReferenceType) -> (&'static str, u8) {
+    match r#type {
+        ReferenceType::Art => ("Artwork", 2),
+        ReferenceType::Article => ("Journal Article", 17),
+        ReferenceType::Audiovisual | ReferenceType::Multimedia | ReferenceType::Slides => ("Audiovisual Material", 3),
+        ReferenceType::Bill => ("Bill", 4),
+        ReferenceType::Blog => ("Blog", 56),
+        ReferenceType::Book => ("Book", 6),
+        ReferenceType::Catalogue => ("Catalog", 8),
+        ReferenceType::Conference => ("Conference Proceedings", 10),
+        ReferenceType::ConferencePaper => ("Conference Paper", 47),
+        ReferenceType::Data => ("Dataset", 59),
+        ReferenceType::Database => ("Online Database", 45),
+        ReferenceType::Dictionary => ("Dictionary", 52),
+        ReferenceType::EditedWork => ("Edited Book", 28),
+        ReferenceType::Encyclopedia => ("Encyclopedia", 53),
+        ReferenceType::FilmBroadcast | ReferenceType::Video => ("Film or Broadcast", 21),
+        ReferenceType::GovernmentDocument => ("Government Document", 46),
+        ReferenceType::Grant => ("Grant", 54),
+        ReferenceType::Hearing => ("Hearing", 14),
+        ReferenceType::LegalCase => ("Case", 7),
+        ReferenceType::MagazineArticle => ("Magazine Article", 19),
+        ReferenceType::Map => ("Map", 20),
+        ReferenceType::Music | ReferenceType::SoundRecording => ("Music", 61),
+        ReferenceType::NewspaperArticle => ("Newspaper Article", 23),
+        ReferenceType::Pamphlet => ("Pamphlet", 24),
+        ReferenceType::Patent => ("Patent", 25),
+        ReferenceType::PersonalCommunication => ("Personal Communication", 26),
+        ReferenceType::Proceedings => ("Conference Proceedings", 10),
+        ReferenceType::Report => ("Report", 27),
+        ReferenceType::Serial => ("Serial", 57),
+        ReferenceType::Software
+        | ReferenceType::SoftwareCode
+        | ReferenceType::SoftwareContainer
+        | ReferenceType::SoftwareExecutable
+        | ReferenceType::SoftwareVirtualMachine => ("Computer Program", 9),
+        ReferenceType::Standard => ("Standard", 58),
+        ReferenceType::Statute => ("Statute", 31),
+        ReferenceType::Thesis => ("Thesis", 32),
+        ReferenceType::Unpublished => ("Unpublished Work", 34),
+        ReferenceType::Website => ("Web Page", 12),
+        _ => ("Generic", 13),
+    }
+}
+
+/// An element of a record. Elements without content are left out.
+enum Node {
+    Text(&'static str, Option<String>),
+    Group(&'static str, Vec<Node>),
+}
+
+impl Node {
+    fn is_empty(&self) -> bool {
+        match self {
+            Node::Text(_, value) => value.as_deref().is_none_or(|value| value.trim().is_empty()),
+            Node::Group(_, children) => children.iter().all(Node::is_empty),
+        }
+    }
+
+    fn render(&self, output: &mut String, depth: usize) {
+        if self.is_empty() {
+            return;
+        }
+
+        let indent = "  ".repeat(depth);
+        match self {
+            Node::Text(tag, value) => {
+                let value = escape(value.as_deref().unwrap_or_default().trim());
+                let _ = writeln!(output, "{indent}<{tag}>{value}</{tag}>");
+            }
+            Node::Group(tag, children) => {
+                let _ = writeln!(output, "{indent}<{tag}>");
+                for child in children {
+                    child.render(output, depth + 1);
+                }
+                let _ = writeln!(output, "{indent}</{tag}>");
+            }
+        }
+    }
+}
+
+fn text(tag: &'static str, value: Option<&str>) -> Node {
+    Node::Text(tag, value.map(String::from))
+}
+
+fn authors(tag: &'static str, authors: &[Author]) -> Node {
+    Node::Group(tag, authors.iter().map(|author| Node::Text("author", Some(name(author)))).collect())
+}
+
+fn dates(year: Option<String>, date: Option<Date>) -> Node {
+    let date = date.map(|date| date.to_string());
+
+    Node::Group("dates", vec![Node::Text("year", year), Node::Group("pub-dates", vec![Node::Text("date", date)])])
+}
+
+/// Renders a complete EndNote XML document containing a single record.
+fn document(r#type: (&'static str, u8), nodes: Vec<Node>) -> String {
+    let mut output = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<xml>\n  <records>\n    <record>\n");
+    let _ = writeln!(output, "      <ref-type name=\"{}\">{}</ref-type>", r#type.0, r#type.1);
+    for node in nodes {
+        node.render(&mut output, 3);
+    }
+    output.push_str("    </record>\n  </records>\n</xml>\n");
+
+    output
+}
+
+impl Citation {
+    /// Formats the citation as an EndNote XML document, as imported by EndNote and most reference managers.
+    ///
+    /// If the citation has a `preferred-citation`, that reference is formatted instead, see
+    /// [`Reference::to_endnote_xml`]. Otherwise a `Computer Program` record is created, or `Dataset` for datasets.
+    pub fn to_endnote_xml(&self) -> String {
+        if let Some(reference) = &self.preferred_citation {
+            return reference.to_endnote_xml();
+        }
+
+        let r#type = match self.r#type {
+            Some(Type::Dataset) => ("Dataset", 59),
+            _ => ("Computer Program", 9),
+        };
+
+        document(
+            r#type,
+            vec![
+                Node::Group("contributors", vec![authors("authors", &self.authors)]),
+                Node::Group("titles", vec![text("title", Some(&self.title))]),
+                dates(self.date_released.map(|date| date.year().to_string()), self.date_released),
+                text("edition", self.version.as_deref()),
+                Node::Group("keywords", self.keywords.iter().map(|keyword| text("keyword", Some(keyword))).collect()),
+                text("electronic-resource-num", self.find_doi().as_deref()),
+                text("abstract", self.r#abstract.as_deref()),
+                Node::Group("urls", vec![Node::Group("related-urls", [self.repository_code.as_deref(), self.url.as_deref()].into_iter().map(|url| text("url", url)).collect())]),
+            ],
+        )
+    }
+}
+
+impl Reference {
+    /// Formats the reference as an EndNote XML document, with the reference type derived from the type of the reference.
+    pub fn to_endnote_xml(&self) -> String {
+        let secondary = self.journal.as_deref().or(self.collection_title.as_deref());
+        let year = self.year.as_ref().map(ToString::to_string).or_else(|| self.date_published.map(|date| date.year().to_string()));
+        let pages = match (&self.start, &self.end) {
+            (Some(start), Some(end)) => Some(format!("{start}-{end}")),
+            (Some(start), None) => Some(start.to_string()),
+            _ => self.pages.as_ref().map(ToString::to_string),
+        };
+
+        document(
+            ref_type(&self.r#type),
+            vec![
+                Node::Group("contributors", vec![authors("authors", &self.authors), authors("secondary-authors", &self.editors)]),
+                Node::Group("titles", vec![text("title", Some(&self.title)), text("secondary-title", secondary)]),
+                Node::Group("periodical", vec![text("full-title", self.journal.as_deref())]),
+                text("pages", pages.as_deref()),
+                text("volume", self.volume.as_deref()),
+                text("number", self.issue.as_deref().or(self.number.as_deref())),
+                text("edition", self.edition.as_deref().or(self.version.as_deref())),
+                Node::Group("keywords", self.keywords.iter().map(|keyword| text("keyword", Some(keyword))).collect()),
+                dates(year, self.date_published),
+                text("pub-location", self.location.as_ref().map(|location| location.name.as_str()).or(self.publisher.as_ref().and_then(|publisher| publisher.city.as_deref()))),
+                text("publisher", self.publisher.as_ref().or(self.institution.as_ref()).map(|publisher| publisher.name.as_str())),
+                text("isbn", self.isbn.as_deref().or(self.issn.as_deref())),
+                text("work-type", self.thesis_type.as_deref()),
+                text("electronic-resource-num", self.doi.as_ref().map(|doi| doi.as_str())),
+                text("abstract", self.r#abstract.as_deref()),
+                text("notes", self.notes.as_deref()),
+                Node::Group("urls", vec![Node::Group("related-urls", [self.url.as_deref(), self.repository_code.as_deref()].into_iter().map(|url| text("url", url)).collect())]),
+            ],
+        )
+    }
+}
@@ -0,0 +1,224 @@
+use std::{fmt, io, sync::Arc};
+
+use crate::{span::{locate, Source}, BuildError, Span, ValidationReport};
+
+/// Error returned by the functions that read, write, convert or fetch citations.
+///
+/// Parse errors carry the location of the problem, see [`ParseError`].
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+    /// A file could not be read or written.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+
+    /// The content could not be parsed, e.g. because of a YAML syntax error or a value of the wrong type.
+    #[error(transparent)]
+    Yaml(#[from] ParseError),
+
+    /// The metadata does not describe a valid citation, e.g. a Cargo manifest without a package name.
+    #[error("{field}: {reason}")]
+    Schema {
+        /// The offending key, e.g. `package.name`.
+        field: String,
+
+        /// A description of the problem.
+        reason: String,
+    },
+
+    /// The citation has errors, see [`ValidationReport::into_result`].
+    #[error("{0}")]
+    Validation(ValidationReport),
+
+    /// A git operation failed.
+    #[cfg(feature = "git")]
+    #[error(transparent)]
+    Git(#[from] git2::Error),
+
+    /// A request to a web service failed.
+    #[cfg(feature = "http")]
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+
+    /// A `.csl` style file could not be parsed.
+    #[cfg(feature = "csl")]
+    #[error(transparent)]
+    Style(#[from] crate::ParseStyleError),
+}
+
+impl Error {
+    pub(crate) fn schema(field: impl Into<String>, reason: impl fmt::Display) -> Self {
+        Error::Schema { field: field.into(), reason: reason.to_string() }
+    }
+
+    /// Returns the line and column of the problem, if the error is a parse error with a known location.
+    pub fn location(&self) -> Option<(usize, usize)> {
+        match self {
+            Error::Yaml(error) => Some((error.line?, error.column?)),
+            _ => None,
+        }
+    }
+}
+
+impl From<BuildError> for Error {
+    fn from(error: BuildError) -> Self {
+        match error {
+            BuildError::MissingField(field) => Error::schema(field, "missing required field"),
+        }
+    }
+}
+
+impl From<serde_yaml::Error> for Error {
+    fn from(error: serde_yaml::Error) -> Self {
+        let location = error.location();
+
+        let mut message = error.to_string();
+        if let Some(location) = &location {
+            if let Some(stripped) = message.strip_suffix(&format!(" at line {} column {}", location.line(), location.column())) {
+                message.truncate(stripped.len());
+            }
+        }
+
+        Error::Yaml(ParseError {
+            path: None,
+            line: location.as_ref().map(|location| location.line()),
+            column: location.as_ref().map(|location| location.column()),
+            offset: location.as_ref().map(|location| location.index()),
+            message,
+            source: None,
+        })
+    }
+}
+
+/// Error returned when a `CITATION.cff` file cannot be parsed.
+///
+/// Besides the message, the error carries the location of the problem in the
+/// source and the path of the offending key, e.g. `authors[2].orcid`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The path of the key that could not be parsed, or `None` if the problem is at the top level.
+    pub path: Option<String>,
+
+    /// The line of the problem, starting at 1.
+    pub line: Option<usize>,
+
+    /// The column of the problem, starting at 1.
+    pub column: Option<usize>,
+
+    /// The byte offset of the problem.
+    pub offset: Option<usize>,
+
+    /// A description of the problem, without path and location.
+    pub message: String,
+
+    pub(crate) source: Option<Arc<Source>>,
+}
+
+impl ParseError {
+    pub(crate) fn from_yaml(error: serde_path_to_error::Error<serde_yaml::Error>, content: &str) -> Self {
+        let path = error.path().to_string();
+        let path = (path != ".").then_some(path);
+        let location = error.inner().location();
+
+        let mut message = error.inner().to_string();
+        if let Some(location) = &location {
+            let suffix = format!(" at line {} column {}", location.line(), location.column());
+            if let Some(stripped) = message.strip_suffix(&suffix) {
+                message.truncate(stripped.len());
+            }
+        }
+        // serde_yaml prefixes the message with the path it tracked itself, which stops early inside untagged enums.
+        if let Some((prefix, rest)) = message.split_once(": ") {
+            if path.as_ref().is_some_and(|path| path.starts_with(prefix)) {
+                message = rest.to_string();
+            }
+        }
+
+        // serde_yaml loses the location of values that are buffered, e.g. for internally tagged enums, and reports the
+        // start of the document instead, which cannot be the location of a nested value
+        let span = match location.filter(|location| location.index() > 0 || path.is_none()) {
+            Some(location) => Some(Span { offset: location.index(), length: 0, line: location.line(), column: location.column() }),
+            None => path.as_deref().and_then(|path| locate(content, path)),
+        };
+
+        ParseError {
+            path,
+            line: span.map(|span| span.line),
+            column: span.map(|span| span.column),
+            offset: span.map(|span| span.offset),
+            message,
+            source: Some(Arc::new(Source::new("CITATION.cff", content))),
+        }
+    }
+
+    pub(crate) fn from_json(error: &serde_json::Error, path: Option<String>, content: &str) -> Self {
+        let path = path.filter(|path| path != ".");
+        let (line, column) = (error.line(), error.column());
+
+        let mut message = error.to_string();
+        if let Some(stripped) = message.strip_suffix(&format!(" at line {line} column {column}")) {
+            message.truncate(stripped.len());
+        }
+
+        // serde_json only reports the line and column, and the line is 0 if the location is unknown
+        let offset = content.split_inclusive('\n').take(line.saturating_sub(1)).map(str::len).sum::<usize>() + column.saturating_sub(1);
+        let known = line > 0;
+
+        ParseError {
+            path,
+            line: known.then_some(line),
+            column: known.then_some(column),
+            offset: known.then_some(offset.min(content.len())),
+            message,
+            source: Some(Arc::new(Source::new("CITATION.cff", content))),
+        }
+    }
+
+    #[cfg(feature = "toml")]
+    pub(crate) fn from_toml(error: &toml::de::Error, content: &str) -> Self {
+        let offset = error.span().map(|span| span.start.min(content.len()));
+        let location = offset.map(|offset| {
+            let before = &content[..offset];
+            let line = before.matches('\n').count() + 1;
+            let column = before[before.rfind('\n').map_or(0, |index| index + 1)..].chars().count() + 1;
+            (line, column)
+        });
+
+        ParseError {
+            path: None,
+            line: location.map(|(line, _)| line),
+            column: location.map(|(_, column)| column),
+            offset,
+            message: error.message().to_string(),
+            source: Some(Arc::new(Source::new("CITATION.cff", content))),
+        }
+    }
+
+    /// Sets the name of the file the error occurred in, which is shown in diagnostics.
+    #[cfg_attr(not(feature = "fs"), allow(dead_code))]
+    pub(crate) fn with_file_name(mut self, name: impl Into<String>) -> Self {
+        if let Some(source) = &mut self.source {
+            Arc::make_mut(source).name = name.into();
+        }
+
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(path) = &self.path {
+            write!(f, "{path}: ")?;
+        }
+
+        write!(f, "{}", self.message)?;
+
+        if let (Some(line), Some(column)) = (self.line, self.column) {
+            write!(f, " at line {line} column {column}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseError {}
@@ -0,0 +1,178 @@
+use std::fmt;
+
+use serde_yaml::Value;
+
+use crate::{
+    document::replace,
+    parse::unknown_keys,
+    span::entry,
+    Date, Document, Orcid, ParseError,
+};
+
+/// The keys that hold dates.
+const DATE_KEYS: &[&str] = &["date-accessed", "date-downloaded", "date-end", "date-published", "date-released", "date-start"];
+
+/// The keys whose values are read as strings, so numbers would lose their formatting (e.g. `1.10` becomes `1.1`).
+const STRING_KEYS: &[&str] = &[
+    "abstract", "affiliation", "alias", "cff-version", "commit", "family-names", "fax", "given-names", "keywords",
+    "message", "name", "name-particle", "name-suffix", "post-code", "tel", "title", "value", "version",
+];
+
+/// Plain scalars that YAML 1.1 parsers, which are still common, read as booleans.
+const BOOLEANS: &[&str] = &["y", "Y", "yes", "Yes", "YES", "n", "N", "no", "No", "NO", "on", "On", "ON", "off", "Off", "OFF"];
+
+/// A change made to a citation, e.g. a repair applied by [`Document::fix`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fix {
+    /// The path of the changed entry, e.g. `authors[0].family-names`.
+    pub path: String,
+
+    /// A description of the repair.
+    pub message: String,
+}
+
+impl fmt::Display for Fix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+impl Document {
+    /// Repairs common problems in the content of a `CITATION.cff` file, which may not be a valid citation yet.
+    ///
+    /// - Misspelled keys are renamed, e.g. `familiy-names` to `family-names` or `author` to `authors`.
+    /// - Bare ORCID iDs are converted to URLs.
+    /// - Dates in other formats (e.g. `2021/07/18`, `18.07.2021` or `20210718`) are converted to `YYYY-MM-DD`.
+    /// - Strings that YAML reads as numbers or booleans, e.g. `version: 1.10` or `name: Yes`, are quoted.
+    ///
+    /// Like [`Document::update`], only the repaired values are patched. Fails if the content is not valid
+    /// YAML or still not a valid citation after the repairs.
+    pub fn fix(source: impl Into<String>) -> Result<(Document, Vec<Fix>), ParseError> {
+        let mut source = source.into();
+        let mut fixes = Vec::new();
+
+        // renaming a key may reveal misspelled keys nested in its value
+        loop {
+            let value = yaml(&source)?;
+            let renamed = unknown_keys(&value).into_iter().find_map(|unknown| {
+                let key = unknown.suggestion()?;
+                let renamed = rename(&source, &unknown.path, &unknown.key, key)?;
+                // the object may already have the key, which would make the document invalid
+                yaml(&renamed).ok()?;
+
+                let path = format!("{}{key}", &unknown.path[..unknown.path.len() - unknown.key.len()]);
+                Some((renamed, Fix { path, message: format!("renamed `{}` to `{key}`", unknown.key) }))
+            });
+
+            match renamed {
+                Some((renamed, fix)) => {
+                    source = renamed;
+                    fixes.push(fix);
+                }
+                None => break,
+            }
+        }
+
+        let mut entries = Vec::new();
+        scalars("", "", &yaml(&source)?, &mut entries);
+        for (path, key, value) in entries {
+            let text = match &value {
+                Value::String(text) => text.clone(),
+                Value::Number(number) => number.to_string(),
+                _ => continue,
+            };
+
+            let (target, message) = if key == "orcid" {
+                let Ok(orcid) = text.parse::<Orcid>() else { continue };
+                (Value::String(orcid.to_url()), format!("converted `{text}` to `{orcid}`"))
+            } else if DATE_KEYS.contains(&key.as_str()) {
+                if text.parse::<Date>().is_ok() {
+                    continue;
+                }
+                let Some(date) = repair_date(&text) else { continue };
+                (Value::String(date.to_string()), format!("changed `{text}` to `{date}`"))
+            } else {
+                let Some(entry) = entry(&source, &path) else { continue };
+                let Some(span) = entry.inline else { continue };
+                let written = source[span.offset..span.offset + span.length].to_string();
+
+                let ambiguous = match &value {
+                    Value::Number(_) | Value::Bool(_) => STRING_KEYS.contains(&key.as_str()),
+                    _ => BOOLEANS.contains(&written.as_str()),
+                };
+                if !ambiguous || written.starts_with(['\'', '"']) {
+                    continue;
+                }
+
+                source.replace_range(span.offset..span.offset + span.length, &format!("'{written}'"));
+                fixes.push(Fix { path, message: format!("quoted `{written}` so it is read as a string") });
+                continue;
+            };
+
+            if target != value && replace(&mut source, &path, &target).is_some() {
+                fixes.push(Fix { path, message });
+            }
+        }
+
+        Ok((Document::parse(source)?, fixes))
+    }
+}
+
+fn yaml(source: &str) -> Result<Value, ParseError> {
+    serde_path_to_error::deserialize(serde_yaml::Deserializer::from_str(source)).map_err(|error| ParseError::from_yaml(error, source))
+}
+
+/// Renames the key of the entry at the given path, returning the patched source.
+fn rename(source: &str, path: &str, key: &str, target: &str) -> Option<String> {
+    let entry = entry(source, path)?;
+    let line = &source[entry.start..entry.value];
+    let position = entry.start + line.rfind(key)?;
+
+    let mut renamed = source.to_string();
+    renamed.replace_range(position..position + key.len(), target);
+    Some(renamed)
+}
+
+/// Collects the path, key and value of all scalars of the value. Items of sequences inherit the key of the sequence.
+fn scalars(path: &str, key: &str, value: &Value, result: &mut Vec<(String, String, Value)>) {
+    match value {
+        Value::Mapping(mapping) => {
+            for (key, value) in mapping {
+                let Some(key) = key.as_str() else { continue };
+                let path = match path {
+                    "" => key.to_string(),
+                    _ => format!("{path}.{key}"),
+                };
+                scalars(&path, key, value, result);
+            }
+        }
+        Value::Sequence(items) => {
+            for (index, item) in items.iter().enumerate() {
+                scalars(&format!("{path}[{index}]"), key, item, result);
+            }
+        }
+        Value::Tagged(_) => {}
+        value => result.push((path.to_string(), key.to_string(), value.clone())),
+    }
+}
+
+/// Converts a date in a common format other than `YYYY-MM-DD` to a date, e.g. `2021/7/18`, `18.07.2021`,
+/// `20210718` or `2021-07-18T12:00:00Z`.
+fn repair_date(value: &str) -> Option<Date> {
+    let value = value.trim();
+    let value = value.split_once(['T', ' ']).map_or(value, |(date, _)| date);
+
+    if value.len() == 8 && value.bytes().all(|b| b.is_ascii_digit()) {
+        return Date::new(value[..4].parse().ok()?, value[4..6].parse().ok()?, value[6..].parse().ok()?).ok();
+    }
+
+    let parts: Vec<&str> = value.split(['-', '/', '.']).collect();
+    let [first, month, last] = parts[..] else { return None };
+    let (year, day) = match (first.len(), last.len()) {
+        (4, 1..=2) => (first, last),
+        (1..=2, 4) => (last, first),
+        _ => return None,
+    };
+
+    Date::new(year.parse().ok()?, month.parse().ok()?, day.parse().ok()?).ok()
+}
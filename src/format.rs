@@ -0,0 +1,160 @@
+use serde_yaml::{Mapping, Value};
+
+use crate::{Document, Orcid};
+
+/// The order of the top-level keys: the order of the example in the schema guide, followed by the remaining keys
+/// grouped by topic.
+const CITATION_KEYS: &[&str] = &[
+    "cff-version",
+    "message",
+    "title",
+    "type",
+    "abstract",
+    "authors",
+    "contact",
+    "version",
+    "commit",
+    "date-released",
+    "doi",
+    "identifiers",
+    "repository-code",
+    "repository",
+    "repository-artifact",
+    "url",
+    "license",
+    "license-url",
+    "keywords",
+    "preferred-citation",
+    "references",
+];
+
+/// The order of the keys of persons and entities, starting with their names.
+const AUTHOR_KEYS: &[&str] = &[
+    "name",
+    "family-names",
+    "given-names",
+    "name-particle",
+    "name-suffix",
+    "alias",
+    "affiliation",
+    "orcid",
+    "email",
+    "tel",
+    "fax",
+    "website",
+    "address",
+    "city",
+    "region",
+    "post-code",
+    "country",
+    "location",
+    "date-start",
+    "date-end",
+];
+
+/// The order of the first keys of references, the remaining keys keep their order.
+const REFERENCE_KEYS: &[&str] = &["type", "title", "authors", "editors", "year", "month", "date-published", "doi", "url"];
+
+const IDENTIFIER_KEYS: &[&str] = &["type", "value", "description"];
+
+/// Sorts the keys of the mapping by their position in `keys`. Keys that are not listed are moved to the end,
+/// keeping their relative order.
+fn sort(mapping: &mut Mapping, keys: &[&str]) {
+    let position = |key: &Value| key.as_str().and_then(|key| keys.iter().position(|k| *k == key)).unwrap_or(keys.len());
+
+    let mut entries: Vec<(Value, Value)> = std::mem::take(mapping).into_iter().collect();
+    entries.sort_by_key(|(key, _)| position(key));
+    mapping.extend(entries);
+}
+
+/// Brings the value at the given key into canonical form, recursing into nested mappings and sequences.
+fn canonicalize(key: &str, value: &mut Value) {
+    match value {
+        Value::Sequence(items) => items.iter_mut().for_each(|item| canonicalize(key, item)),
+        Value::Mapping(mapping) => {
+            let keys = match key {
+                "" => CITATION_KEYS,
+                "preferred-citation" | "references" => REFERENCE_KEYS,
+                "identifiers" => IDENTIFIER_KEYS,
+                _ => AUTHOR_KEYS,
+            };
+            sort(mapping, keys);
+
+            for (key, value) in mapping.iter_mut() {
+                canonicalize(key.as_str().unwrap_or_default(), value);
+            }
+        }
+        Value::String(orcid) if key == "orcid" => {
+            if let Ok(parsed) = orcid.parse::<Orcid>() {
+                *orcid = parsed.to_url();
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Collects the paths of all entries of the value, e.g. `authors[0].orcid`.
+fn paths(path: &str, value: &Value, result: &mut Vec<String>) {
+    match value {
+        Value::Mapping(mapping) => {
+            for (key, value) in mapping {
+                let Some(key) = key.as_str() else { continue };
+                let path = match path {
+                    "" => key.to_string(),
+                    _ => format!("{path}.{key}"),
+                };
+                result.push(path.clone());
+                paths(&path, value, result);
+            }
+        }
+        Value::Sequence(items) => {
+            for (index, item) in items.iter().enumerate() {
+                let path = format!("{path}[{index}]");
+                result.push(path.clone());
+                paths(&path, item, result);
+            }
+        }
+        _ => {}
+    }
+}
+
+impl Document {
+    /// Rewrites the document in a canonical style, like `rustfmt` does for Rust code.
+    ///
+    /// Keys are ordered as in the example of the schema guide (`cff-version`, `message`, `title`, ...), with
+    /// the names of persons and entities first. Keys that are not defined by the schema are moved to the end.
+    /// Values are quoted only where YAML requires it and ORCIDs are written as URLs.
+    ///
+    /// Comments are kept above the entries they are attached to, and comments at the top of the file that are
+    /// separated from the first key by a blank line stay there.
+    pub fn format(&mut self) -> Result<(), serde_yaml::Error> {
+        let original: Value = serde_yaml::from_str(&self.source)?;
+        let mut value = original.clone();
+        canonicalize("", &mut value);
+
+        let mut entries = Vec::new();
+        paths("", &original, &mut entries);
+        let comments: Vec<(String, String)> = entries.into_iter().filter_map(|path| self.comment(&path).map(|comment| (path, comment))).collect();
+
+        // comments at the top of the file that are separated from the first key by a blank line are its header
+        let lines: Vec<&str> = self.source.lines().take_while(|line| line.trim().is_empty() || line.trim_start().starts_with('#')).collect();
+        let header = match lines.iter().rposition(|line| line.trim().is_empty()) {
+            Some(blank) => lines[..=blank].iter().map(|line| format!("{line}\n")).collect(),
+            None => String::new(),
+        };
+
+        let mut formatted = Document { source: serde_yaml::to_string(&value)? };
+        for (path, comment) in &comments {
+            formatted.set_comment(path, comment);
+        }
+
+        self.source = header.trim_start().to_string() + &formatted.source;
+        Ok(())
+    }
+
+    /// Returns `true` if the document is already in the canonical style of [`Document::format`].
+    pub fn is_formatted(&self) -> bool {
+        let mut formatted = self.clone();
+        formatted.format().is_ok_and(|()| formatted == *self)
+    }
+}
@@ -0,0 +1,117 @@
+use std::{cmp::Reverse, path::PathBuf};
+
+use git2::{Commit, DescribeFormatOptions, DescribeOptions, ErrorClass, Repository};
+
+use crate::{Author, Citation, Code, Date, Error, Issue, Person, ValidationReport};
+
+/// Returns the date of the commit in the time zone of the committer.
+fn commit_date(commit: &Commit) -> Option<Date> {
+    let time = commit.time();
+    Date::from_timestamp(time.seconds() + i64::from(time.offset_minutes()) * 60)
+}
+
+impl Citation {
+    /// Fills `commit`, `version` and `date-released` from the git repository containing the given path.
+    ///
+    /// `commit` is set to the hash of `HEAD`, `version` to the nearest tag reachable from `HEAD`
+    /// (without a leading `v`, e.g. `v1.2.0` becomes `1.2.0`) and `date-released` to the date of
+    /// the commit the tag points to. If there is no tag, only `commit` is set.
+    pub fn fill_from_git(&mut self, path: PathBuf) -> Result<(), Error> {
+        let repository = Repository::discover(path)?;
+        let head = repository.head()?.peel_to_commit()?;
+        self.commit = Some(head.id().to_string());
+
+        let describe = match repository.describe(DescribeOptions::new().describe_tags()) {
+            Ok(describe) => describe,
+            Err(error) if error.class() == ErrorClass::Describe => return Ok(()),
+            Err(error) => return Err(error.into()),
+        };
+
+        let tag = describe.format(Some(DescribeFormatOptions::new().abbreviated_size(0)))?;
+        let commit = repository.revparse_single(&tag)?.peel_to_commit()?;
+
+        let version = tag.strip_prefix('v').filter(|version| version.starts_with(|c: char| c.is_ascii_digit()));
+        self.version = Some(version.unwrap_or(&tag).to_string());
+        self.date_released = commit_date(&commit);
+
+        Ok(())
+    }
+
+    /// Compares `date-released` with the history of the git repository containing the given path, and reports
+    /// a date older than the first commit, or a date that differs from the date of the commit the tag of `version`
+    /// (e.g. `v1.2.0` or `1.2.0`) points to, as [`Code::InconsistentDate`] warnings.
+    ///
+    /// Dates in the future are already reported by [`Citation::validate`].
+    pub fn check_git_history(&self, path: PathBuf) -> Result<ValidationReport, Error> {
+        let mut issues = Vec::new();
+        let Some(released) = self.date_released else {
+            return Ok(ValidationReport::new(issues));
+        };
+
+        let repository = Repository::discover(path)?;
+        let mut revwalk = repository.revwalk()?;
+        revwalk.push_head()?;
+
+        let mut first: Option<Date> = None;
+        for id in revwalk {
+            let commit = repository.find_commit(id?)?;
+            if commit.parent_count() == 0 {
+                first = first.into_iter().chain(commit_date(&commit)).min();
+            }
+        }
+        if let Some(first) = first.filter(|first| released < *first) {
+            issues.push(Issue::new(Code::InconsistentDate, "date-released", format!("{released} is older than the first commit on {first}")));
+        }
+
+        if let Some(version) = &self.version {
+            let tagged = |tag: &str| commit_date(&repository.revparse_single(&format!("refs/tags/{tag}")).ok()?.peel_to_commit().ok()?);
+            let tag = [format!("v{version}"), version.clone()].into_iter().find_map(|tag| Some((tagged(&tag)?, tag)));
+            if let Some((tagged, tag)) = tag.filter(|(tagged, _)| *tagged != released) {
+                issues.push(Issue::new(Code::InconsistentDate, "date-released", format!("{released} differs from the date of the tag `{tag}` of version {version}, {tagged}")));
+            }
+        }
+
+        Ok(ValidationReport::new(issues))
+    }
+}
+
+impl Author {
+    /// Derives authors from the commit history of the git repository containing the given path.
+    ///
+    /// Commit authors are identified by their email address after applying `.mailmap`, and only those
+    /// with at least `min_commits` commits are included. Bots, i.e. authors whose name ends in `[bot]`,
+    /// are skipped. The authors are ordered by their number of commits, most first.
+    pub fn from_git_history(path: PathBuf, min_commits: usize) -> Result<Vec<Author>, Error> {
+        let repository = Repository::discover(path)?;
+        let mailmap = repository.mailmap()?;
+
+        let mut revwalk = repository.revwalk()?;
+        revwalk.push_head()?;
+
+        // (name, email, commits), with the name from the most recent commit
+        let mut contributors: Vec<(String, String, usize)> = Vec::new();
+        for id in revwalk {
+            let commit = repository.find_commit(id?)?;
+            let signature = commit.author_with_mailmap(&mailmap)?;
+            let (Some(name), Some(email)) = (signature.name(), signature.email()) else { continue };
+
+            if name.ends_with("[bot]") {
+                continue;
+            }
+
+            let email = email.to_lowercase();
+            match contributors.iter_mut().find(|(_, known, _)| *known == email) {
+                Some((_, _, commits)) => *commits += 1,
+                None => contributors.push((name.to_string(), email, 1)),
+            }
+        }
+
+        contributors.retain(|(_, _, commits)| *commits >= min_commits);
+        contributors.sort_by_key(|(_, _, commits)| Reverse(*commits));
+
+        Ok(contributors
+            .into_iter()
+            .map(|(name, email, _)| Author::Person(Person { email: Some(email), ..Person::from_name(&name) }))
+            .collect())
+    }
+}
@@ -0,0 +1,83 @@
+use crate::{http, Citation, Error};
+
+/// The default URL of the GitHub REST API, which can be overridden with `GITHUB_API_URL`
+/// (e.g. for GitHub Enterprise Server, where GitHub Actions sets it automatically).
+const API_URL: &str = "https://api.github.com";
+
+/// A request for the `CITATION.cff` file of a repository through the contents API.
+struct Request {
+    url: String,
+    reference: Option<String>,
+    token: Option<String>,
+}
+
+impl Request {
+    /// Parses `owner/repo` or `owner/repo@ref`, where `ref` is a branch, tag or commit.
+    fn new(repository: &str) -> Result<Self, Error> {
+        let (repository, reference) = match repository.split_once('@') {
+            Some((repository, reference)) => (repository, Some(reference.to_string())),
+            None => (repository, None),
+        };
+
+        let valid = repository.split_once('/').is_some_and(|(owner, name)| !owner.is_empty() && !name.is_empty() && !name.contains('/'));
+        if !valid {
+            return Err(Error::schema("repository", format!("`{repository}` is not a repository in the format owner/repo")));
+        }
+
+        let api = std::env::var("GITHUB_API_URL").unwrap_or_else(|_| API_URL.to_string());
+
+        Ok(Request {
+            url: format!("{}/repos/{repository}/contents/CITATION.cff", api.trim_end_matches('/')),
+            reference,
+            token: std::env::var("GITHUB_TOKEN").ok().filter(|token| !token.is_empty()),
+        })
+    }
+
+    fn query(&self) -> Vec<(&str, &str)> {
+        self.reference.iter().map(|reference| ("ref", reference.as_str())).collect()
+    }
+}
+
+impl Citation {
+    /// Fetches and parses the `CITATION.cff` file of a GitHub repository through the contents API.
+    ///
+    /// The repository is given as `owner/repo`, optionally followed by a branch, tag or commit,
+    /// e.g. `owner/repo@v1.2.0`. Without one, the default branch is used.
+    ///
+    /// If the `GITHUB_TOKEN` environment variable is set, it is used to authenticate, which raises
+    /// the rate limit and gives access to private repositories.
+    pub fn fetch_from_github(repository: &str) -> Result<Self, Error> {
+        let request = Request::new(repository)?;
+
+        let mut builder = http::client()?
+            .get(&request.url)
+            .query(&request.query())
+            .header("Accept", "application/vnd.github.raw+json");
+        if let Some(token) = &request.token {
+            builder = builder.bearer_auth(token);
+        }
+
+        let content = builder.send()?.error_for_status()?.text()?;
+
+        Ok(content.parse::<Citation>().map_err(|error| error.with_file_name(format!("{repository}/CITATION.cff")))?)
+    }
+
+    /// Fetches and parses the `CITATION.cff` file of a GitHub repository without blocking the executor,
+    /// see [`Citation::fetch_from_github`].
+    #[cfg(feature = "async")]
+    pub async fn fetch_from_github_async(repository: &str) -> Result<Self, Error> {
+        let request = Request::new(repository)?;
+
+        let mut builder = http::async_client()?
+            .get(&request.url)
+            .query(&request.query())
+            .header("Accept", "application/vnd.github.raw+json");
+        if let Some(token) = &request.token {
+            builder = builder.bearer_auth(token);
+        }
+
+        let content = builder.send().await?.error_for_status()?.text().await?;
+
+        Ok(content.parse::<Citation>().map_err(|error| error.with_file_name(format!("{repository}/CITATION.cff")))?)
+    }
+}
@@ -0,0 +1,13 @@
+//! Shared HTTP clients for the network-backed parts of the crate.
+
+/// The user agent sent with every request, as some APIs (e.g. GitHub) reject requests without one.
+const USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
+pub(crate) fn client() -> reqwest::Result<reqwest::blocking::Client> {
+    reqwest::blocking::Client::builder().user_agent(USER_AGENT).build()
+}
+
+#[cfg(feature = "async")]
+pub(crate) fn async_client() -> reqwest::Result<reqwest::Client> {
+    reqwest::Client::builder().user_agent(USER_AGENT).build()
+}
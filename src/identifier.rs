@@ -0,0 +1,112 @@
+use std::{error::Error, fmt};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{doi::is_doi, Swhid};
+
+/// An identifier of a work, as defined by `definitions.identifier` in the schema.
+///
+/// https://github.com/citation-file-format/citation-file-format/blob/main/schema-guide.md#definitionsidentifier
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum Identifier {
+    /// A Digital Object Identifier, e.g. `10.5281/zenodo.1003150`.
+    Doi {
+        value: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+    },
+
+    /// A URL, e.g. `https://github.com/citation-file-format/citation-file-format`.
+    Url {
+        value: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+    },
+
+    /// A Software Heritage identifier, e.g. `swh:1:dir:bc286860f423ea7ced246ba7458eef4b4541cf2d`.
+    Swh {
+        value: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+    },
+
+    /// Any other kind of identifier, e.g. an arXiv ID.
+    Other {
+        value: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+    },
+}
+
+impl Identifier {
+    /// Returns the value of the identifier.
+    pub fn value(&self) -> &str {
+        match self {
+            Identifier::Doi { value, .. }
+            | Identifier::Url { value, .. }
+            | Identifier::Swh { value, .. }
+            | Identifier::Other { value, .. } => value,
+        }
+    }
+
+    /// Returns the description of the identifier, if any.
+    pub fn description(&self) -> Option<&str> {
+        match self {
+            Identifier::Doi { description, .. }
+            | Identifier::Url { description, .. }
+            | Identifier::Swh { description, .. }
+            | Identifier::Other { description, .. } => description.as_deref(),
+        }
+    }
+
+    /// Checks that the value matches the format required for the type of the identifier.
+    pub fn validate(&self) -> Result<(), IdentifierError> {
+        match self {
+            Identifier::Doi { value, .. } if !is_doi(value) => Err(IdentifierError::InvalidDoi(value.clone())),
+            Identifier::Url { value, .. } if !is_url(value) => Err(IdentifierError::InvalidUrl(value.clone())),
+            Identifier::Swh { value, .. } => match Swhid::new(value) {
+                Err(_) => Err(IdentifierError::InvalidSwhid(value.clone())),
+                Ok(swhid) if !swhid.is_core() => Err(IdentifierError::QualifiedSwhid(value.clone())),
+                Ok(_) => Ok(()),
+            },
+            Identifier::Other { value, .. } if value.trim().is_empty() => Err(IdentifierError::EmptyValue),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Error returned by [`Identifier::validate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IdentifierError {
+    InvalidDoi(String),
+    InvalidUrl(String),
+    InvalidSwhid(String),
+    /// A SWHID with qualifiers, which the schema does not allow in identifiers.
+    QualifiedSwhid(String),
+    EmptyValue,
+}
+
+impl fmt::Display for IdentifierError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IdentifierError::InvalidDoi(value) => write!(f, "`{value}` is not a valid DOI"),
+            IdentifierError::InvalidUrl(value) => write!(f, "`{value}` is not a valid URL"),
+            IdentifierError::InvalidSwhid(value) => write!(f, "`{value}` is not a valid Software Heritage identifier"),
+            IdentifierError::QualifiedSwhid(value) => write!(f, "`{value}` has qualifiers, only the core identifier is allowed"),
+            IdentifierError::EmptyValue => write!(f, "identifier value is empty"),
+        }
+    }
+}
+
+impl Error for IdentifierError {}
+
+/// Matches the URL pattern of the schema: `^(https|http|ftp|sftp)://.+`
+pub(crate) fn is_url(value: &str) -> bool {
+    ["https://", "http://", "ftp://", "sftp://"]
+        .iter()
+        .filter_map(|scheme| value.strip_prefix(scheme))
+        .any(|rest| !rest.is_empty() && !rest.contains(char::is_whitespace))
+}
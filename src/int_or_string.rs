@@ -0,0 +1,159 @@
+use std::{fmt, ops::Deref};
+
+use serde::{
+    de::{Error, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+/// A value that the schema allows to be an integer or a string, e.g. the `year`, `volume` or `start` of a reference.
+///
+/// The value is kept as text, but remembers whether it was written as an integer, so that `year: 2020` and
+/// `year: '2020'` are written back as they were read.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntOrString {
+    text: String,
+    integer: bool,
+}
+
+impl IntOrString {
+    /// Returns the value as text, e.g. `2020` or `xii-xv`.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the value if it has been written as an integer.
+    pub fn as_integer(&self) -> Option<i64> {
+        self.integer.then(|| self.text.parse().ok()).flatten()
+    }
+
+    /// Returns `true` if the value has been written as an integer.
+    pub fn is_integer(&self) -> bool {
+        self.integer
+    }
+}
+
+impl Deref for IntOrString {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.text
+    }
+}
+
+impl AsRef<str> for IntOrString {
+    fn as_ref(&self) -> &str {
+        &self.text
+    }
+}
+
+impl fmt::Display for IntOrString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+impl From<i64> for IntOrString {
+    fn from(value: i64) -> Self {
+        IntOrString { text: value.to_string(), integer: true }
+    }
+}
+
+impl From<u16> for IntOrString {
+    fn from(value: u16) -> Self {
+        i64::from(value).into()
+    }
+}
+
+impl From<u8> for IntOrString {
+    fn from(value: u8) -> Self {
+        i64::from(value).into()
+    }
+}
+
+impl From<String> for IntOrString {
+    fn from(text: String) -> Self {
+        IntOrString { text, integer: false }
+    }
+}
+
+impl From<&str> for IntOrString {
+    fn from(text: &str) -> Self {
+        text.to_string().into()
+    }
+}
+
+impl PartialEq<str> for IntOrString {
+    fn eq(&self, other: &str) -> bool {
+        self.text == other
+    }
+}
+
+impl PartialEq<&str> for IntOrString {
+    fn eq(&self, other: &&str) -> bool {
+        self.text == *other
+    }
+}
+
+impl Serialize for IntOrString {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.as_integer() {
+            Some(integer) => serializer.serialize_i64(integer),
+            None => serializer.serialize_str(&self.text),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for IntOrString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct IntOrStringVisitor;
+
+        impl Visitor<'_> for IntOrStringVisitor {
+            type Value = IntOrString;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("an integer or a string")
+            }
+
+            fn visit_i64<E: Error>(self, value: i64) -> Result<Self::Value, E> {
+                Ok(value.into())
+            }
+
+            fn visit_u64<E: Error>(self, value: u64) -> Result<Self::Value, E> {
+                i64::try_from(value).map(IntOrString::from).or_else(|_| Ok(value.to_string().into()))
+            }
+
+            // not allowed by the schema, but kept as text like other values that are not integers
+            fn visit_f64<E: Error>(self, value: f64) -> Result<Self::Value, E> {
+                Ok(value.to_string().into())
+            }
+
+            fn visit_str<E: Error>(self, value: &str) -> Result<Self::Value, E> {
+                Ok(value.into())
+            }
+        }
+
+        deserializer.deserialize_any(IntOrStringVisitor)
+    }
+}
+
+/// Generates integers and arbitrary strings alike.
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for IntOrString {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        match bool::arbitrary(u)? {
+            true => Ok(i64::arbitrary(u)?.into()),
+            false => Ok(String::arbitrary(u)?.into()),
+        }
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for IntOrString {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "IntOrString".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({ "anyOf": [{ "type": "integer" }, { "type": "string", "minLength": 1 }] })
+    }
+}
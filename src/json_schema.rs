@@ -0,0 +1,76 @@
+use std::sync::OnceLock;
+
+use jsonschema::Validator;
+
+use crate::{span::locate, CffVersion, Code, Document, Error, Issue, ValidationReport};
+
+/// The JSON Schema of `cff-version` 1.2.0, a transcription of
+/// https://citation-file-format.github.io/1.2.0/schema.json that can be replaced by a newer release.
+const SCHEMA_1_2: &str = include_str!("../schemas/1.2.0.json");
+
+impl CffVersion {
+    /// Returns the JSON Schema of the version, or `None` for versions before 1.2.0, which were only described by a
+    /// Kwalify schema.
+    pub fn json_schema(&self) -> Option<&'static str> {
+        match self {
+            CffVersion::V1_2 => Some(SCHEMA_1_2),
+            CffVersion::V1_0 | CffVersion::V1_1 => None,
+        }
+    }
+
+    /// Returns the compiled JSON Schema of the version, compiling it on first use.
+    fn validator(&self) -> Option<&'static Validator> {
+        static VALIDATOR_1_2: OnceLock<Validator> = OnceLock::new();
+
+        let schema = self.json_schema()?;
+        Some(VALIDATOR_1_2.get_or_init(|| {
+            let schema = serde_json::from_str(schema).expect("the embedded schema is valid JSON");
+            jsonschema::draft7::new(&schema).expect("the embedded schema is a valid JSON Schema")
+        }))
+    }
+}
+
+/// Turns a JSON pointer such as `/authors/0/orcid` into a key path such as `authors[0].orcid`.
+fn key_path(pointer: &str) -> String {
+    let mut path = String::new();
+
+    for segment in pointer.split('/').skip(1) {
+        let segment = segment.replace("~1", "/").replace("~0", "~");
+        match segment.parse::<usize>() {
+            Ok(index) => path.push_str(&format!("[{index}]")),
+            Err(_) if path.is_empty() => path.push_str(&segment),
+            Err(_) => path.push_str(&format!(".{segment}")),
+        }
+    }
+
+    path
+}
+
+impl Document {
+    /// Validates the document against the JSON Schema of its `cff-version`, which catches constraints that are not
+    /// enforced while parsing, e.g. the pattern of a DOI, the country codes or the minimum length of strings.
+    ///
+    /// Every violation is reported as [`Code::SchemaViolation`]. Fails if the `cff-version` has no JSON Schema,
+    /// see [`CffVersion::json_schema`].
+    pub fn validate_against_schema(&self) -> Result<ValidationReport, Error> {
+        let value: serde_json::Value = serde_yaml::from_str(&self.source)?;
+
+        let version = value["cff-version"].as_str().unwrap_or_default();
+        let version: CffVersion = version.parse().map_err(|error| Error::schema("cff-version", error))?;
+        let Some(validator) = version.validator() else {
+            return Err(Error::schema("cff-version", format!("there is no JSON Schema for cff-version {version}, update it to {}", CffVersion::LATEST)));
+        };
+
+        let issues = validator
+            .iter_errors(&value)
+            .map(|error| {
+                let path = key_path(error.instance_path.as_str());
+                let mut issue = Issue::new(Code::SchemaViolation, path, error.to_string());
+                issue.span = locate(&self.source, &issue.path);
+                issue
+            })
+            .collect();
+
+        Ok(ValidationReport::new(issues))
+    }
+}
@@ -0,0 +1,125 @@
+use serde_json::{json, Map, Value};
+
+use crate::{Author, Citation, Doi, Identifier, Type};
+
+/// Converts an author into a schema.org `Person` or `Organization`.
+pub(crate) fn agent(author: &Author) -> Value {
+    let mut object = Map::new();
+
+    match author {
+        Author::Person(person) => {
+            object.insert("@type".into(), "Person".into());
+            insert(&mut object, "@id", person.orcid.as_deref());
+            insert(&mut object, "givenName", person.given_names.as_deref());
+            let family = [person.name_particle.as_deref(), person.family_names.as_deref()].into_iter().flatten().collect::<Vec<_>>().join(" ");
+            insert(&mut object, "familyName", Some(family.as_str()).filter(|family| !family.is_empty()));
+            if person.given_names.is_none() && person.family_names.is_none() {
+                insert(&mut object, "name", Some(author.display_name().as_str()));
+            }
+            insert(&mut object, "honorificSuffix", person.name_suffix.as_deref());
+            insert(&mut object, "email", person.email.as_deref());
+            insert(&mut object, "url", person.website.as_deref());
+            if let Some(affiliation) = &person.affiliation {
+                object.insert("affiliation".into(), json!({ "@type": "Organization", "name": affiliation }));
+            }
+        }
+        Author::Entity(entity) => {
+            object.insert("@type".into(), "Organization".into());
+            insert(&mut object, "@id", entity.orcid.as_deref());
+            object.insert("name".into(), entity.name.clone().into());
+            insert(&mut object, "email", entity.email.as_deref());
+            insert(&mut object, "url", entity.website.as_deref());
+        }
+    }
+
+    Value::Object(object)
+}
+
+/// Inserts a value into a JSON object, unless it is missing.
+pub(crate) fn insert(object: &mut Map<String, Value>, key: &str, value: Option<&str>) {
+    if let Some(value) = value {
+        object.insert(key.to_string(), Value::String(value.to_string()));
+    }
+}
+
+/// Inserts a list of values into a JSON object, as a single value if there is only one and not at all if there are none.
+pub(crate) fn insert_list(object: &mut Map<String, Value>, key: &str, mut values: Vec<Value>) {
+    match values.len() {
+        0 => {}
+        1 => {
+            object.insert(key.to_string(), values.remove(0));
+        }
+        _ => {
+            object.insert(key.to_string(), Value::Array(values));
+        }
+    }
+}
+
+/// Returns the URLs of the SPDX licenses of a citation, or the license URL for non-standard licenses.
+pub(crate) fn licenses(citation: &Citation) -> Vec<Value> {
+    let licenses = citation.licenses().iter().map(|license| match license.is_known() {
+        true => Value::String(format!("https://spdx.org/licenses/{license}")),
+        false => Value::String(license.to_string()),
+    });
+
+    licenses.chain(citation.license_url.iter().map(|url| Value::String(url.clone()))).collect()
+}
+
+/// Returns the identifiers of a citation, with DOIs as `https://doi.org/` URLs.
+pub(crate) fn identifiers(citation: &Citation) -> Vec<Value> {
+    let doi = citation.doi.iter().map(Doi::to_url);
+    let identifiers = citation.identifiers.iter().map(|identifier| match identifier {
+        Identifier::Doi { value, .. } => format!("https://doi.org/{value}"),
+        _ => identifier.value().to_string(),
+    });
+
+    let mut values: Vec<String> = Vec::new();
+    for value in doi.chain(identifiers) {
+        if !values.contains(&value) {
+            values.push(value);
+        }
+    }
+
+    values.into_iter().map(Value::String).collect()
+}
+
+/// Converts the citation to a schema.org object without `@context`, shared by JSON-LD and CodeMeta.
+pub(crate) fn schema_org(citation: &Citation) -> Map<String, Value> {
+    let mut object = Map::new();
+    let r#type = match citation.r#type {
+        Some(Type::Dataset) => "Dataset",
+        _ => "SoftwareSourceCode",
+    };
+    object.insert("@type".into(), r#type.into());
+
+    object.insert("name".into(), citation.title.clone().into());
+    insert(&mut object, "description", citation.r#abstract.as_deref());
+    insert_list(&mut object, "author", citation.authors.iter().map(agent).collect());
+    insert(&mut object, "version", citation.version.as_deref());
+    insert(&mut object, "datePublished", citation.date_released.map(|date| date.to_string()).as_deref());
+    if !matches!(citation.r#type, Some(Type::Dataset)) {
+        insert(&mut object, "codeRepository", citation.repository_code.as_deref());
+    }
+    insert(&mut object, "url", citation.url.as_deref().or(citation.repository.as_deref()));
+    insert_list(&mut object, "license", licenses(citation));
+    insert_list(&mut object, "identifier", identifiers(citation));
+    if !citation.keywords.is_empty() {
+        object.insert("keywords".into(), citation.keywords.clone().into());
+    }
+
+    object
+}
+
+impl Citation {
+    /// Converts the citation to a [schema.org](https://schema.org) `SoftwareSourceCode` object, or
+    /// `Dataset` for datasets, in JSON-LD.
+    ///
+    /// The result can be embedded in a website in a `<script type="application/ld+json">` element,
+    /// so that search engines and reference managers pick up the citation metadata.
+    pub fn to_json_ld(&self) -> String {
+        let mut object = schema_org(self);
+        object.insert("@context".into(), "https://schema.org".into());
+
+        serde_json::to_string_pretty(&object).unwrap_or_default()
+    }
+}
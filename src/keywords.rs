@@ -0,0 +1,68 @@
+use crate::{Citation, Fix, Reference};
+
+/// Options for [`Citation::normalize_keywords`].
+#[derive(Debug, Clone, Default)]
+pub struct KeywordOptions {
+    /// Converts all keywords to lowercase, e.g. `Rust` to `rust`.
+    pub lowercase: bool,
+}
+
+/// Returns the keyword used to detect duplicates, which ignores case and surrounding whitespace.
+pub(crate) fn folded(keyword: &str) -> String {
+    keyword.trim().to_lowercase()
+}
+
+/// Trims the keywords and removes empty and duplicate ones, keeping the first of several that only differ in case.
+fn normalize(path: &str, keywords: &mut Vec<String>, options: &KeywordOptions) -> Vec<Fix> {
+    let mut fixes = Vec::new();
+    let mut normalized: Vec<String> = Vec::new();
+
+    for (index, keyword) in std::mem::take(keywords).into_iter().enumerate() {
+        let path = format!("{path}keywords[{index}]");
+        let mut value = keyword.trim().to_string();
+        if options.lowercase {
+            value = value.to_lowercase();
+        }
+
+        if value.is_empty() {
+            fixes.push(Fix { path, message: "removed empty keyword".to_string() });
+        } else if let Some(first) = normalized.iter().find(|first| folded(first) == folded(&value)) {
+            fixes.push(Fix { path, message: format!("removed `{keyword}`, a duplicate of `{first}`") });
+        } else {
+            if value != keyword {
+                fixes.push(Fix { path, message: format!("changed `{keyword}` to `{value}`") });
+            }
+            normalized.push(value);
+        }
+    }
+
+    *keywords = normalized;
+    fixes
+}
+
+impl Citation {
+    /// Cleans up the keywords of the citation, its preferred citation and its references: surrounding whitespace
+    /// is trimmed, and empty keywords and duplicates that only differ in case are removed. With
+    /// [`KeywordOptions::lowercase`], all keywords are converted to lowercase.
+    ///
+    /// Returns the changes, with the paths of the keywords before the changes.
+    pub fn normalize_keywords(&mut self, options: &KeywordOptions) -> Vec<Fix> {
+        let mut fixes = normalize("", &mut self.keywords, options);
+
+        if let Some(reference) = &mut self.preferred_citation {
+            fixes.extend(normalize("preferred-citation.", &mut reference.keywords, options));
+        }
+        for (index, reference) in self.references.iter_mut().enumerate() {
+            fixes.extend(normalize(&format!("references[{index}]."), &mut reference.keywords, options));
+        }
+
+        fixes
+    }
+}
+
+impl Reference {
+    /// Cleans up the keywords of the reference, see [`Citation::normalize_keywords`].
+    pub fn normalize_keywords(&mut self, options: &KeywordOptions) -> Vec<Fix> {
+        normalize("", &mut self.keywords, options)
+    }
+}
@@ -0,0 +1,159 @@
+use crate::{bibtex::with_key, Author, Citation};
+
+/// Words that are skipped when the first word of a title is taken for a citation key.
+const STOP_WORDS: &[&str] = &["a", "an", "the", "on", "of", "for", "in", "to", "and"];
+
+/// Options for [`Citation::to_latex_snippet`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LatexOptions {
+    /// The template of the citation key, with the placeholders `{author}` for the family name of the first author,
+    /// `{title}` for the first word of the title that is not an article or a preposition, `{year}` and `{version}`.
+    /// Defaults to `{author}_{title}_{year}`, e.g. `lewin_citation_2024`, see [`Citation::citation_key`].
+    pub key: String,
+
+    /// Writes a biblatex entry instead of a BibTeX entry, see [`Citation::to_biblatex`].
+    pub biblatex: bool,
+}
+
+impl Default for LatexOptions {
+    fn default() -> Self {
+        LatexOptions { key: "{author}_{title}_{year}".to_string(), biblatex: false }
+    }
+}
+
+/// A citation ready to be added to a LaTeX manuscript, see [`Citation::to_latex_snippet`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LatexSnippet {
+    /// The citation key, e.g. `lewin_citation_2024`.
+    pub key: String,
+
+    /// The entry for the `.bib` file, ending with a line break.
+    pub entry: String,
+
+    /// The command that cites the entry, e.g. `\cite{lewin_citation_2024}`.
+    pub cite: String,
+}
+
+/// Replaces letters with diacritics by their base letter, e.g. `ü` by `u`, and drops all other characters that are
+/// not ASCII letters or digits, so that keys only contain characters every BibTeX implementation accepts.
+fn ascii(text: &str) -> String {
+    let mut folded = String::with_capacity(text.len());
+    for c in text.to_lowercase().chars() {
+        let base = match c {
+            'a'..='z' | '0'..='9' => c,
+            'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => 'a',
+            'ç' | 'ć' | 'č' => 'c',
+            'ď' | 'đ' => 'd',
+            'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ė' | 'ę' | 'ě' => 'e',
+            'ğ' => 'g',
+            'ì' | 'í' | 'î' | 'ï' | 'ī' | 'į' | 'ı' => 'i',
+            'ł' | 'ľ' => 'l',
+            'ñ' | 'ń' | 'ň' => 'n',
+            'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ő' => 'o',
+            'ř' => 'r',
+            'ś' | 'š' | 'ş' => 's',
+            'ť' | 'ţ' => 't',
+            'ù' | 'ú' | 'û' | 'ü' | 'ū' | 'ů' | 'ű' => 'u',
+            'ý' | 'ÿ' => 'y',
+            'ź' | 'ż' | 'ž' => 'z',
+            'ß' => {
+                folded.push_str("ss");
+                continue;
+            }
+            'æ' => {
+                folded.push_str("ae");
+                continue;
+            }
+            _ => continue,
+        };
+        folded.push(base);
+    }
+
+    folded
+}
+
+/// Returns the name of the first author that is used in citation keys: the family name of a person, including a
+/// name particle, or the name of an entity.
+fn author(authors: &[Author]) -> String {
+    match authors.first() {
+        Some(Author::Person(person)) => {
+            let name = match (&person.name_particle, &person.family_names) {
+                (Some(particle), Some(family)) => Some(format!("{particle}{family}")),
+                (_, family) => family.clone(),
+            };
+            ascii(&name.or(person.given_names.clone()).or(person.alias.clone()).unwrap_or_default())
+        }
+        Some(Author::Entity(entity)) => ascii(&entity.name),
+        None => String::new(),
+    }
+}
+
+/// Returns the first word of the title that is not an article or a preposition, e.g. `citation` for
+/// `The citation library`.
+fn title(title: &str) -> String {
+    let mut words = title.split(|c: char| c.is_whitespace() || c == '-' || c == ':').map(ascii).filter(|word| !word.is_empty());
+    let first = words.clone().next().unwrap_or_default();
+
+    words.find(|word| !STOP_WORDS.contains(&word.as_str())).unwrap_or(first)
+}
+
+/// Fills in the placeholders of a key template, removing separators next to placeholders without a value.
+fn fill(template: &str, values: &[(&str, String)]) -> String {
+    let mut key = String::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        key.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('}') else {
+            key.push_str(&rest[start..]);
+            rest = "";
+            break;
+        };
+        let name = &rest[start + 1..start + end];
+        match values.iter().find(|(placeholder, _)| *placeholder == name).map(|(_, value)| value.as_str()).unwrap_or_default() {
+            "" => key.truncate(key.trim_end_matches(['_', '-', ':', '.']).len()),
+            value => key.push_str(value),
+        }
+        rest = &rest[start + end + 1..];
+    }
+    key.push_str(rest);
+
+    let key: String = key.chars().filter(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | ':' | '.')).collect();
+    key.trim_matches(['_', '-', ':', '.']).to_string()
+}
+
+impl Citation {
+    /// Creates a stable citation key from a template such as `{author}_{title}_{year}`, e.g.
+    /// `lewin_citation_2024`, see [`LatexOptions::key`]. The key describes the work that is cited, i.e. the
+    /// `preferred-citation` if there is one.
+    ///
+    /// Names and titles are lowercased and reduced to ASCII letters and digits, e.g. `Müller` becomes `muller`.
+    /// Placeholders without a value are left out together with the separator before them, e.g. `lewin_citation`
+    /// if the citation has no release date. Unknown placeholders are left out as well.
+    pub fn citation_key(&self, template: &str) -> String {
+        let (authors, work, year) = match &self.preferred_citation {
+            Some(reference) => (&reference.authors, &reference.title, reference.year.as_ref().map(ToString::to_string).or_else(|| reference.date_published.map(|date| date.year().to_string()))),
+            None => (&self.authors, &self.title, self.date_released.map(|date| date.year().to_string())),
+        };
+        let version = self.version.as_deref().map(|version| version.chars().filter(|c| c.is_ascii_alphanumeric() || *c == '.').collect()).unwrap_or_default();
+        let values = [("author", author(authors)), ("title", title(work)), ("year", ascii(&year.unwrap_or_default())), ("version", version)];
+
+        match fill(template, &values) {
+            key if key.is_empty() => "citation".to_string(),
+            key => key,
+        }
+    }
+
+    /// Creates the entry for a `.bib` file and the `\cite` command for it, so that the citation can be added to a
+    /// LaTeX manuscript. The entry is the one of [`Citation::to_bibtex`] (or [`Citation::to_biblatex`]) with the
+    /// key of [`Citation::citation_key`].
+    pub fn to_latex_snippet(&self, options: &LatexOptions) -> LatexSnippet {
+        let key = self.citation_key(&options.key);
+        let entry = match options.biblatex {
+            true => self.to_biblatex(),
+            false => self.to_bibtex(),
+        };
+
+        LatexSnippet { entry: with_key(&entry, &key), cite: format!("\\cite{{{key}}}"), key }
+    }
+}
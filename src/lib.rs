@@ -1,178 +1,453 @@
-use std::{path::PathBuf, error::Error};
+use std::{collections::BTreeMap, io, str::FromStr};
+#[cfg(feature = "fs")]
+use std::path::{Path, PathBuf};
 
 use serde::{Serialize, Deserialize};
 
+mod author;
+mod badge;
+#[cfg(feature = "rayon")]
+pub mod batch;
+#[cfg(any(feature = "wasm", feature = "python", feature = "capi"))]
+mod bindings;
+mod bibtex;
+mod borrowed;
+mod builder;
+#[cfg(feature = "capi")]
+pub mod capi;
+#[cfg(feature = "cargo")]
+mod cargo;
+#[cfg(feature = "csl")]
+mod citeproc;
+mod codemeta;
+mod compat;
+mod csl;
+mod datacite;
+#[cfg(feature = "diagnostics")]
+mod diagnostics;
+mod date;
+mod de;
+mod diff;
+#[cfg(feature = "fs")]
+mod discover;
+mod document;
+mod dublincore;
+mod doi;
+mod endnote;
+mod error;
+mod fix;
+mod format;
+#[cfg(feature = "git")]
+mod git;
+#[cfg(feature = "http")]
+mod github;
+#[cfg(feature = "http")]
+mod http;
+mod identifier;
+mod int_or_string;
+#[cfg(feature = "jsonschema")]
+mod json_schema;
+mod jsonld;
+mod keywords;
+mod latex;
+mod license;
+pub mod lint;
+mod lossy;
+mod markdown;
+mod merge;
+mod migrate;
+mod normalize;
+mod orcid;
+mod parse;
+#[cfg(feature = "python")]
+pub mod python;
+mod reference;
+mod report;
+#[cfg(feature = "http")]
+mod resolver;
+mod ris;
+mod schema;
+#[cfg(feature = "scan")]
+mod scan;
+#[cfg(feature = "semver")]
+mod semantic_version;
+mod span;
+mod style;
+mod swhid;
+#[cfg(feature = "proptest")]
+pub mod testing;
+#[cfg(feature = "url")]
+mod urls;
+mod validate;
+mod version;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+mod xml;
+mod zenodo;
+
+pub use author::{Author, Contact, Entity, Person};
+pub use badge::Badge;
+pub use bibtex::ParseBibtexError;
+pub use borrowed::{AuthorRef, CitationRef, IdentifierRef};
+pub use builder::{BuildError, CitationBuilder};
+#[cfg(feature = "cargo")]
+pub use cargo::{CargoWorkspace, Dependencies, Dependency, DependencyOptions, DependencySource, WorkspaceMember};
+pub use compat::Compatibility;
+#[cfg(feature = "csl")]
+pub use citeproc::{CslStyle, ParseStyleError};
+pub use date::{Date, ParseDateError};
+pub use diff::Change;
+pub use doi::{Doi, ParseDoiError};
+pub use document::Document;
+pub use error::{Error, ParseError};
+pub use fix::Fix;
+pub use identifier::{Identifier, IdentifierError};
+pub use keywords::KeywordOptions;
+pub use latex::{LatexOptions, LatexSnippet};
+pub use int_or_string::IntOrString;
+pub use license::{License, Licenses};
+pub use lossy::PartialCitation;
+pub use merge::{MergeConflict, MergeStrategy};
+pub use migrate::MigrationReport;
+pub use orcid::{Orcid, ParseOrcidError};
+#[cfg(feature = "http")]
+pub use orcid::OrcidRecord;
+pub use parse::ParseOptions;
+pub use reference::{Reference, ReferenceType, Status};
+pub use ris::ParseRisError;
+#[cfg(feature = "scan")]
+pub use scan::{Scan, ScanOptions};
+pub use span::Span;
+pub use style::{CitationFormatter, OutputFormat, Style};
+pub use swhid::{ObjectType, ParseSwhidError, Swhid};
+#[cfg(feature = "url")]
+pub use urls::{ParseUrlError, Urls};
+pub use validate::{Code, Issue, Severity, ValidationReport};
+pub use version::{CffVersion, ParseCffVersionError};
+#[cfg(feature = "http")]
+pub use zenodo::{Deposition, ZenodoClient};
+
+/// The version of the schema implemented by this crate, used for the `cff-version` key.
+pub const CFF_VERSION: &str = "1.2.0";
+
+/// The default value of the `message` key.
+pub const DEFAULT_MESSAGE: &str = "If you use this software, please cite it using the metadata from this file.";
+
 /// https://github.com/citation-file-format/citation-file-format/blob/main/schema-guide.md#valid-keys
-#[allow(unused)]
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[serde(rename_all = "kebab-case")]
 pub struct Citation {
     /// A description of the software or dataset.
     /// 
     /// required: false
-    r#abstract: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub r#abstract: Option<String>,
 
     /// The authors of a software or dataset
     /// 
     /// required: true (at least one item in the Vec)
-    authors: Vec<Author>,
+    pub authors: Vec<Author>,
 
     /// The Citation File Format schema version that the `CITATION.cff` file adheres to for providing the citation metadata.
     /// 
     /// required: true
-    #[serde(alias = "cff-version")]
-    cff_version: String,
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = version::arbitrary_cff_version))]
+    pub cff_version: String,
 
     /// The commit hash or revision number of the software version.
     /// 
     /// required: false
-    commit: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
 
+    /// The contact person, group, company, etc. for the software or dataset.
+    /// 
     /// required: false
-    contact: Option<Contact>, // FIXME
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub contact: Vec<Contact>,
 
     /// The date the software or data set has been released. Format is 4-digit year, 2-digit month, 2-digit day of month, separated by dashes.
     /// 
     /// required: false
-    #[serde(alias = "date-released")]
-    date_released: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_released: Option<Date>,
 
+    /// The DOI of the software or dataset.
+    /// 
     /// required: false
-    dio: Option<String>, // FIXME
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doi: Option<Doi>,
 
     /// The identifiers of the software or dataset.
     /// 
     /// required: false
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
-    identifiers: Vec<Identifier>,
+    pub identifiers: Vec<Identifier>,
 
     /// Keywords that describe the work.
     /// 
     /// required: false
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
-    keywords: Vec<String>,
+    pub keywords: Vec<String>,
 
     /// The [SPDX license identifier(s)](https://spdx.dev/ids/) for the license(s) under which the work is made available. When there are multiple licenses, it is assumed their relationship is OR, not AND.
     /// 
     /// required: false
-    license: Option<License>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license: Option<Licenses>,
 
     /// The URL of the license text under which the software or dataset is licensed (only for non-standard licenses not included in the SPDX License List).
     /// 
     /// required: false
-    #[serde(alias = "license-url")]
-    license_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license_url: Option<String>,
 
     /// A message to the human reader of the `CITATION.cff` file to let them know what to do with the citation metadata.
     /// 
     /// default: `If you use this software, please cite it using the metadata from this file.`  
     /// 
     /// required: true
-    message: String,
+    pub message: String,
 
     /// A reference to another work that should be cited instead of the software or dataset itself. Note that the principles of [software citation](https://doi.org/10.7717/peerj-cs.86) and [data citation](https://doi.org/10.25490/a97f-egyk) require that software should be cited on the same basis as any other research product such as a paper or a book. Adding a different preferred citation may result in a violation of the respective primary principle, "Importance", when others cite this work.
     /// 
     /// required: false
-    #[serde(alias = "preferred-citation")]
-    preferred_citation: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preferred_citation: Option<Reference>,
 
     /// Reference(s) to other creative works. Similar to a list of references in a paper, references of the software or dataset may include other software (dependencies), or other research products that the software or dataset builds on, but not work describing the software or dataset.
     /// 
     /// required: false
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
-    references: Vec<Reference>,
+    pub references: Vec<Reference>,
 
     /// The URL of the software or dataset in a repository/archive (when the repository is neither a source code repository nor a build artifact repository).
     /// 
     /// required: false
-    repository: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
 
     /// The URL of the work in a build artifact/binary repository (when the work is software).
     /// 
     /// required: false
-    #[serde(alias = "repository-artifact")]
-    repository_artifact: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository_artifact: Option<String>,
 
     /// The URL of the work in a source code repository.
     /// 
     /// required: false
-    #[serde(alias = "repository_code")]
-    repository_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository_code: Option<String>,
     
     /// The name of the software or dataset.
     /// 
     /// required: true
-    title: String,
+    pub title: String,
     
     /// The type of the work that is being described by this CITATION.cff file.
     /// 
     /// default: `Software`
     /// 
     /// required: false
-    #[serde(alias = "type")]
-    r#type: Option<Type>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<Type>,
     
     /// The URL of a landing page/website for the software or dataset.
     /// 
     /// required: false
-    url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
 
     /// The version of the software or dataset.
     /// 
     /// required: false
-    version: Option<String>
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+
+    /// Keys that are not defined by the schema, e.g. from newer schema versions or custom extensions.
+    /// They are kept so that they survive a round trip.
+    #[serde(flatten)]
+    #[cfg_attr(feature = "schemars", schemars(skip))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    pub extra: BTreeMap<String, serde_yaml::Value>,
 }
 
 impl Citation {
-    pub fn read(path: PathBuf) -> Result<Self, Box<dyn Error>> {
-        let content = std::fs::read_to_string(path)?;
+    /// Creates a [`CitationBuilder`] to construct a citation programmatically.
+    pub fn builder() -> CitationBuilder {
+        CitationBuilder::default()
+    }
 
-        Ok(serde_yaml::from_str(&content)?)
+    /// Reads and parses the `CITATION.cff` file at the given path.
+    #[cfg(feature = "fs")]
+    pub fn read(path: PathBuf) -> Result<Self, Error> {
+        Self::read_with(path, &ParseOptions::default())
     }
 
-    // pub fn validate(&self) -> Result<(), ()> {
-    //     todo!()
-    // }
-}
+    /// Reads and parses the `CITATION.cff` file at the given path, rejecting keys that are not defined by the schema.
+    #[cfg(feature = "fs")]
+    pub fn read_strict(path: PathBuf) -> Result<Self, Error> {
+        Self::read_with(path, &ParseOptions::strict())
+    }
 
-// #[derive(Debug, Serialize, Deserialize)]
-// pub enum Author {
-//     Person {
-//         given_names: String,
-//         family_names: String,
-//         email: Option<String>,
-//         dio: Option<String>
-//     },
-//     Entity { name: String }
-// }
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Author {
-    #[serde(alias = "given-names")]
-    given_names: String,
-    #[serde(alias = "family-names")]
-    family_names: String,
-    email: Option<String>,
-    orcid: Option<String>
-}
+    /// Reads and parses the `CITATION.cff` file at the given path with the given options.
+    ///
+    /// Files with the `.json` extension, or whose content is a JSON object, are parsed as JSON, see
+    /// [`Citation::from_json_str`]. With the `toml` feature, files with the `.toml` extension are parsed as TOML.
+    #[cfg(feature = "fs")]
+    pub fn read_with(path: PathBuf, options: &ParseOptions) -> Result<Self, Error> {
+        let content = std::fs::read_to_string(&path)?;
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Contact {}
+        Ok(Self::parse_file(&path, &content, options)?)
+    }
+
+    /// Parses the content of the file at the given path like [`Citation::read_with`], e.g. when the content is also
+    /// needed to locate the issues of the citation. Errors are reported with the name of the file.
+    #[cfg(feature = "fs")]
+    pub fn parse_file(path: &Path, content: &str, options: &ParseOptions) -> Result<Self, ParseError> {
+        parse::parse_file(path, content, options)
+    }
+
+    /// Reads and parses the `CITATION.cff` file at the given path without blocking the executor.
+    #[cfg(feature = "async")]
+    pub async fn read_async(path: PathBuf) -> Result<Self, Error> {
+        let content = tokio::fs::read_to_string(&path).await?;
+
+        Ok(parse::parse_file(&path, &content, &ParseOptions::default())?)
+    }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Identifier {}
+    /// Reads and parses a `CITATION.cff` file from the given reader, e.g. stdin or a network response.
+    pub fn from_reader<R: io::Read>(mut reader: R) -> Result<Self, Error> {
+        let mut content = String::new();
+        reader.read_to_string(&mut content)?;
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Reference {}
+        Ok(content.parse()?)
+    }
+
+    /// Parses the content of a `CITATION.cff` file like [`str::parse`], but reports every value that cannot be read
+    /// instead of stopping at the first, e.g. an invalid author, a malformed date and an unknown license at once.
+    /// With [`ParseOptions::strict`], every unknown key is reported as well.
+    ///
+    /// The errors are ordered as they are found, which is not necessarily their order in the file. A syntax error or a
+    /// missing required key ends the search, as nothing after it can be checked.
+    pub fn parse_all(content: &str, options: &ParseOptions) -> Result<Self, Vec<ParseError>> {
+        parse::parse_all(content, options)
+    }
+
+    /// Parses a citation written as JSON, e.g. a `CITATION.json` file or the output of a tool that converts
+    /// `CITATION.cff` to JSON. The keys and values are the same as in YAML.
+    pub fn from_json_str(content: &str) -> Result<Self, ParseError> {
+        parse::parse_json(content, &ParseOptions::default())
+    }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub enum License {}
+    /// Parses a citation written as TOML, e.g. a `Citation.toml` file. The keys are the same as in YAML,
+    /// and dates may be written as TOML dates.
+    ///
+    /// ```toml
+    /// cff-version = "1.2.0"
+    /// message = "If you use this software, please cite it using the metadata from this file."
+    /// title = "citation"
+    /// date-released = 2024-05-01
+    ///
+    /// [[authors]]
+    /// given-names = "Luca"
+    /// family-names = "Lewin"
+    /// ```
+    #[cfg(feature = "toml")]
+    pub fn from_toml_str(content: &str) -> Result<Self, ParseError> {
+        parse::parse_toml(content, &ParseOptions::default())
+    }
+
+    /// Returns the licenses of the work, regardless of whether `license` is a single identifier or a list.
+    pub fn licenses(&self) -> &[License] {
+        self.license.as_ref().map_or(&[], Licenses::as_slice)
+    }
+
+    /// Serializes the citation and writes it to the given path as a `CITATION.cff` file.
+    #[cfg(feature = "fs")]
+    pub fn write(&self, path: PathBuf) -> Result<(), Error> {
+        std::fs::write(path, self.to_yaml_string()?)?;
+
+        Ok(())
+    }
+
+    /// Serializes the citation and writes it to the given path without blocking the executor.
+    #[cfg(feature = "async")]
+    pub async fn write_async(&self, path: PathBuf) -> Result<(), Error> {
+        tokio::fs::write(path, self.to_yaml_string()?).await?;
+
+        Ok(())
+    }
+
+    /// Writes the citation to the given path, patching only the values that changed if the file already exists.
+    ///
+    /// Key order, quoting style and untouched content of the existing file are kept, see [`Document`].
+    #[cfg(feature = "fs")]
+    pub fn write_in_place(&self, path: PathBuf) -> Result<(), Error> {
+        if !path.exists() {
+            return self.write(path);
+        }
+
+        let mut document = Document::read(path.clone())?;
+        document.update(self)?;
+        document.write(path)
+    }
+
+    /// Serializes the citation to a YAML string using the keys defined by the schema.
+    pub fn to_yaml_string(&self) -> Result<String, Error> {
+        Ok(serde_yaml::to_string(self)?)
+    }
+}
+
+impl Default for Citation {
+    /// Returns a citation with the defaults of the schema: the latest supported `cff-version`, the default
+    /// `message` and the type `software`. The title and authors are empty, as they have no default.
+    fn default() -> Self {
+        Citation {
+            r#abstract: None,
+            authors: Vec::new(),
+            cff_version: CffVersion::LATEST.as_str().to_string(),
+            commit: None,
+            contact: Vec::new(),
+            date_released: None,
+            doi: None,
+            identifiers: Vec::new(),
+            keywords: Vec::new(),
+            license: None,
+            license_url: None,
+            message: DEFAULT_MESSAGE.to_string(),
+            preferred_citation: None,
+            references: Vec::new(),
+            repository: None,
+            repository_artifact: None,
+            repository_code: None,
+            title: String::new(),
+            r#type: Some(Type::Software),
+            url: None,
+            version: None,
+            extra: BTreeMap::new(),
+        }
+    }
+}
+
+impl FromStr for Citation {
+    type Err = ParseError;
+
+    /// Parses the content of a `CITATION.cff` file. Content that is a JSON object is parsed as JSON.
+    fn from_str(content: &str) -> Result<Self, Self::Err> {
+        parse::parse(content, &ParseOptions::default())
+    }
+}
 
-#[derive(Debug, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[serde(rename_all = "lowercase")]
 pub enum Type {
     #[default]
-    #[serde(alias = "software")]
     Software,
 
-    #[serde(alias = "dataset")]
     Dataset
 }
@@ -0,0 +1,223 @@
+use std::{convert::Infallible, fmt, str::FromStr};
+
+use serde::{
+    de::{self, value::SeqAccessDeserializer, SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::schema::closest_key;
+
+include!(concat!(env!("OUT_DIR"), "/licenses.rs"));
+
+impl License {
+    /// Looks up an SPDX identifier, falling back to [`License::Other`] for unknown identifiers.
+    ///
+    /// SPDX identifiers are matched case-insensitively, as required by the SPDX specification.
+    pub fn from_id(id: &str) -> Self {
+        License::from_known_id(id)
+            .or_else(|| License::ALL.iter().find(|license| license.id().eq_ignore_ascii_case(id)).cloned())
+            .unwrap_or_else(|| License::Other(id.to_string()))
+    }
+
+    /// Returns `true` if the identifier is part of the SPDX License List.
+    pub fn is_known(&self) -> bool {
+        !matches!(self, License::Other(_))
+    }
+
+    /// Returns what replaces a deprecated identifier, e.g. `GPL-3.0-only` and `GPL-3.0-or-later` for `GPL-3.0`.
+    ///
+    /// Identifiers that included an exception are replaced by a license expression, e.g.
+    /// `GPL-2.0-only WITH Classpath-exception-2.0`. Returns an empty slice for current identifiers.
+    pub fn replacements(&self) -> &'static [&'static str] {
+        match self.id() {
+            "AGPL-1.0" => &["AGPL-1.0-only", "AGPL-1.0-or-later"],
+            "AGPL-3.0" => &["AGPL-3.0-only", "AGPL-3.0-or-later"],
+            "BSD-2-Clause-FreeBSD" => &["BSD-2-Clause-Views"],
+            "BSD-2-Clause-NetBSD" => &["BSD-2-Clause"],
+            "bzip2-1.0.5" => &["bzip2-1.0.6"],
+            "eCos-2.0" => &["GPL-2.0-or-later WITH eCos-exception-2.0"],
+            "GFDL-1.1" => &["GFDL-1.1-only", "GFDL-1.1-or-later"],
+            "GFDL-1.2" => &["GFDL-1.2-only", "GFDL-1.2-or-later"],
+            "GFDL-1.3" => &["GFDL-1.3-only", "GFDL-1.3-or-later"],
+            "GPL-1.0" => &["GPL-1.0-only", "GPL-1.0-or-later"],
+            "GPL-1.0+" => &["GPL-1.0-or-later"],
+            "GPL-2.0" => &["GPL-2.0-only", "GPL-2.0-or-later"],
+            "GPL-2.0+" => &["GPL-2.0-or-later"],
+            "GPL-2.0-with-autoconf-exception" => &["GPL-2.0-only WITH Autoconf-exception-2.0"],
+            "GPL-2.0-with-bison-exception" => &["GPL-2.0-or-later WITH Bison-exception-2.2"],
+            "GPL-2.0-with-classpath-exception" => &["GPL-2.0-only WITH Classpath-exception-2.0"],
+            "GPL-2.0-with-font-exception" => &["GPL-2.0-only WITH Font-exception-2.0"],
+            "GPL-2.0-with-GCC-exception" => &["GPL-2.0-or-later WITH GCC-exception-2.0"],
+            "GPL-3.0" => &["GPL-3.0-only", "GPL-3.0-or-later"],
+            "GPL-3.0+" => &["GPL-3.0-or-later"],
+            "GPL-3.0-with-autoconf-exception" => &["GPL-3.0-or-later WITH Autoconf-exception-3.0"],
+            "GPL-3.0-with-GCC-exception" => &["GPL-3.0-or-later WITH GCC-exception-3.1"],
+            "LGPL-2.0" => &["LGPL-2.0-only", "LGPL-2.0-or-later"],
+            "LGPL-2.0+" => &["LGPL-2.0-or-later"],
+            "LGPL-2.1" => &["LGPL-2.1-only", "LGPL-2.1-or-later"],
+            "LGPL-2.1+" => &["LGPL-2.1-or-later"],
+            "LGPL-3.0" => &["LGPL-3.0-only", "LGPL-3.0-or-later"],
+            "LGPL-3.0+" => &["LGPL-3.0-or-later"],
+            "Nunit" => &["zlib-acknowledgement"],
+            "StandardML-NJ" => &["SMLNJ"],
+            "wxWindows" => &["GPL-2.0-or-later WITH WxWindows-exception-3.1"],
+            _ => &[],
+        }
+    }
+
+    /// Returns the current SPDX identifier most likely meant by an unknown identifier, e.g. `Apache-2.0` for
+    /// `Apache 2.0`.
+    pub fn suggestion(&self) -> Option<&'static str> {
+        let License::Other(id) = self else { return None };
+        let ids: Vec<&'static str> = License::ALL.iter().filter(|license| !license.is_deprecated()).map(|license| license.id()).collect();
+
+        closest_key(id, &ids)
+    }
+}
+
+impl From<&str> for License {
+    fn from(id: &str) -> Self {
+        License::from_id(id)
+    }
+}
+
+impl FromStr for License {
+    type Err = Infallible;
+
+    fn from_str(id: &str) -> Result<Self, Self::Err> {
+        Ok(License::from_id(id))
+    }
+}
+
+impl fmt::Display for License {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.id())
+    }
+}
+
+impl Serialize for License {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.id())
+    }
+}
+
+impl<'de> Deserialize<'de> for License {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let id = String::deserialize(deserializer)?;
+
+        Ok(License::from_id(&id))
+    }
+}
+
+/// Generates mostly identifiers of the SPDX License List, and sometimes an arbitrary [`License::Other`].
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for License {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        match u.ratio(1, 8)? {
+            true => Ok(License::from_id(<&str>::arbitrary(u)?)),
+            false => u.choose(License::ALL).cloned(),
+        }
+    }
+}
+
+/// Lists the identifiers of the SPDX License List, but accepts any string like [`License::Other`].
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for License {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "License".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        let ids: Vec<&str> = License::ALL.iter().map(|license| license.id()).collect();
+        schemars::json_schema!({
+            "description": "An SPDX license identifier.",
+            "anyOf": [{ "type": "string", "enum": ids }, { "type": "string" }],
+        })
+    }
+}
+
+/// The value of a `license` key, which is either a single SPDX identifier or a list of them.
+///
+/// When there are multiple licenses, their relationship is OR, not AND.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[serde(untagged)]
+pub enum Licenses {
+    Single(License),
+    Multiple(Vec<License>),
+}
+
+impl Licenses {
+    /// Returns the licenses as a slice, regardless of how they were written.
+    pub fn as_slice(&self) -> &[License] {
+        match self {
+            Licenses::Single(license) => std::slice::from_ref(license),
+            Licenses::Multiple(licenses) => licenses,
+        }
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, License> {
+        self.as_slice().iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.as_slice().is_empty()
+    }
+
+    /// Returns `true` if the given license is one of the licenses.
+    pub fn contains(&self, license: &License) -> bool {
+        self.as_slice().contains(license)
+    }
+}
+
+/// Tells a single license and a list apart by the kind of value instead of trying each variant on buffered content,
+/// so that an invalid item of a list is reported at its own path and location, e.g. `license[1]`.
+impl<'de> Deserialize<'de> for Licenses {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct LicensesVisitor;
+
+        impl<'de> Visitor<'de> for LicensesVisitor {
+            type Value = Licenses;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a license identifier or a list of license identifiers")
+            }
+
+            fn visit_str<E: de::Error>(self, id: &str) -> Result<Self::Value, E> {
+                Ok(Licenses::Single(License::from_id(id)))
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+                Vec::deserialize(SeqAccessDeserializer::new(seq)).map(Licenses::Multiple)
+            }
+        }
+
+        deserializer.deserialize_any(LicensesVisitor)
+    }
+}
+
+impl From<License> for Licenses {
+    fn from(license: License) -> Self {
+        Licenses::Single(license)
+    }
+}
+
+impl From<Vec<License>> for Licenses {
+    fn from(licenses: Vec<License>) -> Self {
+        Licenses::Multiple(licenses)
+    }
+}
+
+impl<'a> IntoIterator for &'a Licenses {
+    type Item = &'a License;
+    type IntoIter = std::slice::Iter<'a, License>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
@@ -0,0 +1,278 @@
+//! Configurable linting of citations.
+//!
+//! Every [`Code`] reported by [`Citation::validate`] is a lint rule with a stable id, e.g. `CFF001` for
+//! [`Code::MissingDoi`]. A [`LintConfig`] turns rules off or changes their severity, and a [`Linter`] applies it:
+//!
+//! ```yaml
+//! rules:
+//!   CFF001: off
+//!   future-date: error
+//! ```
+//!
+//! Custom rules implement [`LintRule`] and are registered with [`Linter::with_rules`].
+
+use std::{collections::BTreeMap, fmt, sync::Arc};
+#[cfg(feature = "fs")]
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+use crate::{Citation, Code, Error, Issue, Severity, ValidationReport};
+
+/// A lint rule, which reports the issues with one [`Code`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rule {
+    /// The stable id of the rule, e.g. `CFF001`.
+    pub id: &'static str,
+
+    /// The code of the issues reported by the rule.
+    pub code: Code,
+}
+
+impl Rule {
+    /// Returns the name of the rule, e.g. `missing-doi`.
+    pub fn name(&self) -> &'static str {
+        self.code.as_str()
+    }
+
+    /// Returns the severity of the issues reported by the rule, unless it is overridden.
+    pub fn default_severity(&self) -> Severity {
+        self.code.severity()
+    }
+}
+
+/// All built-in rules, ordered by id.
+pub const RULES: &[Rule] = &[
+    Rule { id: "CFF001", code: Code::MissingDoi },
+    Rule { id: "CFF002", code: Code::FutureDate },
+    Rule { id: "CFF003", code: Code::DeprecatedLicense },
+    Rule { id: "CFF004", code: Code::EmptyAuthors },
+    Rule { id: "CFF005", code: Code::EmptyTitle },
+    Rule { id: "CFF006", code: Code::InvalidCffVersion },
+    Rule { id: "CFF007", code: Code::InvalidOrcid },
+    Rule { id: "CFF008", code: Code::InvalidDate },
+    Rule { id: "CFF009", code: Code::InvalidUrl },
+    Rule { id: "CFF010", code: Code::InvalidIdentifier },
+    Rule { id: "CFF011", code: Code::EmptyMessage },
+    Rule { id: "CFF012", code: Code::DoiMismatch },
+    Rule { id: "CFF013", code: Code::OrcidMismatch },
+    Rule { id: "CFF014", code: Code::UnsupportedCffVersion },
+    Rule { id: "CFF015", code: Code::MissingKey },
+    Rule { id: "CFF016", code: Code::UnavailableKey },
+    Rule { id: "CFF017", code: Code::UnknownKey },
+    Rule { id: "CFF018", code: Code::IgnoredValue },
+    Rule { id: "CFF019", code: Code::EmptyKeyword },
+    Rule { id: "CFF020", code: Code::DuplicateKeyword },
+    Rule { id: "CFF021", code: Code::InvalidEmail },
+    Rule { id: "CFF022", code: Code::InconsistentDate },
+    Rule { id: "CFF023", code: Code::UnknownLicense },
+    Rule { id: "CFF024", code: Code::SchemaViolation },
+];
+
+/// Returns the built-in rule reporting the issues with the given code, or `None` for [`Code::Custom`].
+pub fn rule(code: Code) -> Option<&'static Rule> {
+    RULES.iter().find(|rule| rule.code == code)
+}
+
+/// Returns the built-in rule with the given id or name, e.g. `CFF001` or `missing-doi`.
+pub fn find(rule: &str) -> Option<&'static Rule> {
+    RULES.iter().find(|candidate| matches(rule, candidate.id, candidate.name()))
+}
+
+/// Returns `true` if a rule is referred to by its id, ignoring case, or by its name.
+fn matches(key: &str, id: &str, name: &str) -> bool {
+    key.eq_ignore_ascii_case(id) || key == name
+}
+
+/// A custom rule, e.g. a policy that every author has an ORCID, which is run by a [`Linter`] next to the built-in
+/// rules and configured the same way.
+pub trait LintRule: Send + Sync {
+    /// The stable id of the rule, e.g. `ACME001`, which must differ from the ids of all other rules.
+    fn id(&self) -> &'static str;
+
+    /// The name of the rule, e.g. `author-orcid`, which is the [`Code::Custom`] of its issues.
+    fn name(&self) -> &'static str;
+
+    /// Returns the severity of the issues reported by the rule, unless it is overridden.
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    /// Checks the citation, returning an issue for every problem found. The code of the issues is set to the name of
+    /// the rule and their severity to the configured one, so they can be created with any code, e.g.
+    /// `Issue::new(Code::Custom(self.name()), "authors[0].orcid", "the author has no ORCID")`.
+    fn check(&self, citation: &Citation) -> Vec<Issue>;
+}
+
+/// The level of a rule in a [`LintConfig`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    /// The rule is disabled.
+    Off,
+
+    /// The rule reports hints.
+    Hint,
+
+    /// The rule reports warnings.
+    Warning,
+
+    /// The rule reports errors.
+    Error,
+}
+
+impl Level {
+    /// Returns the severity of the issues reported at this level, or `None` if the rule is disabled.
+    pub fn severity(&self) -> Option<Severity> {
+        match self {
+            Level::Off => None,
+            Level::Hint => Some(Severity::Hint),
+            Level::Warning => Some(Severity::Warning),
+            Level::Error => Some(Severity::Error),
+        }
+    }
+}
+
+impl From<Severity> for Level {
+    fn from(severity: Severity) -> Self {
+        match severity {
+            Severity::Hint => Level::Hint,
+            Severity::Warning => Level::Warning,
+            Severity::Error => Level::Error,
+        }
+    }
+}
+
+/// Which rules a [`Linter`] reports, and with which severity.
+///
+/// Rules are referred to by id or name, e.g. `CFF001` or `missing-doi`. Rules that are not configured report issues
+/// with their default severity.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LintConfig {
+    #[serde(default)]
+    rules: BTreeMap<String, Level>,
+}
+
+impl LintConfig {
+    /// Creates a configuration with every rule at its default severity.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the level of a rule.
+    pub fn level(mut self, rule: impl Into<String>, level: Level) -> Self {
+        self.rules.insert(rule.into(), level);
+        self
+    }
+
+    /// Disables a rule.
+    pub fn disable(self, rule: impl Into<String>) -> Self {
+        self.level(rule, Level::Off)
+    }
+
+    /// Reports the issues of a rule with the given severity, which also enables a disabled rule.
+    pub fn severity(self, rule: impl Into<String>, severity: Severity) -> Self {
+        self.level(rule, severity.into())
+    }
+
+    /// Parses a configuration written in YAML or JSON, with a `rules` mapping from rule ids or names to levels.
+    ///
+    /// The rules are only checked by [`Linter::new`], as they may refer to custom rules.
+    pub fn from_yaml_str(content: &str) -> Result<Self, Error> {
+        Ok(serde_yaml::from_str(content)?)
+    }
+
+    /// Reads a configuration file, see [`LintConfig::from_yaml_str`].
+    #[cfg(feature = "fs")]
+    pub fn read(path: PathBuf) -> Result<Self, Error> {
+        let content = std::fs::read_to_string(&path)?;
+
+        Self::from_yaml_str(&content).map_err(|error| match error {
+            Error::Yaml(error) => Error::Yaml(error.with_file_name(path.display().to_string())),
+            error => error,
+        })
+    }
+
+    /// Fails if a configured rule is neither a built-in rule nor one of the given custom rules.
+    fn check(&self, custom: &[Arc<dyn LintRule>]) -> Result<(), Error> {
+        let known = |key: &str| find(key).is_some() || custom.iter().any(|rule| matches(key, rule.id(), rule.name()));
+        match self.rules.keys().find(|rule| !known(rule)) {
+            Some(rule) => Err(Error::schema(format!("rules.{rule}"), "unknown rule, expected an id such as `CFF001` or a name such as `missing-doi`")),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the configured level of the rule with the given id and name, if any.
+    fn get(&self, id: &str, name: &str) -> Option<Level> {
+        self.rules.iter().find(|(key, _)| matches(key, id, name)).map(|(_, level)| *level)
+    }
+}
+
+/// Checks citations with the built-in rules and any custom rules, as configured by a [`LintConfig`].
+#[derive(Clone, Default)]
+pub struct Linter {
+    config: LintConfig,
+    rules: Vec<Arc<dyn LintRule>>,
+}
+
+impl Linter {
+    /// Creates a linter with the given configuration, failing if it configures a rule that does not exist.
+    pub fn new(config: LintConfig) -> Result<Self, Error> {
+        Self::with_rules(config, Vec::new())
+    }
+
+    /// Creates a linter that also runs the given custom rules, failing if the configuration refers to a rule that
+    /// does not exist, or if a custom rule reuses the id or name of another rule.
+    pub fn with_rules(config: LintConfig, rules: Vec<Box<dyn LintRule>>) -> Result<Self, Error> {
+        let rules: Vec<Arc<dyn LintRule>> = rules.into_iter().map(Arc::from).collect();
+        for (index, rule) in rules.iter().enumerate() {
+            let taken = |id: &str, name: &str| matches(rule.id(), id, name) || matches(rule.name(), id, name);
+            if RULES.iter().any(|other| taken(other.id, other.name())) || rules[..index].iter().any(|other| taken(other.id(), other.name())) {
+                return Err(Error::schema(rule.id(), format!("the id or name `{}` of the rule is already used by another rule", rule.name())));
+            }
+        }
+        config.check(&rules)?;
+
+        Ok(Self { config, rules })
+    }
+
+    /// Validates the citation and runs the custom rules, reporting the issues of enabled rules with their configured
+    /// severity.
+    pub fn lint(&self, citation: &Citation) -> ValidationReport {
+        let mut report = citation.validate();
+        for rule in &self.rules {
+            report.issues.extend(rule.check(citation).into_iter().map(|issue| Issue { code: Code::Custom(rule.name()), severity: rule.default_severity(), ..issue }));
+        }
+
+        self.apply(report)
+    }
+
+    /// Applies the configuration to a report, e.g. one with a source from [`ValidationReport::with_source`]:
+    /// issues of disabled rules are removed and the severity of the others is overridden.
+    pub fn apply(&self, mut report: ValidationReport) -> ValidationReport {
+        report.issues.retain_mut(|issue| {
+            let level = match issue.code {
+                Code::Custom(name) => self.rules.iter().find(|rule| rule.name() == name).and_then(|rule| self.config.get(rule.id(), name)),
+                code => rule(code).and_then(|rule| self.config.get(rule.id, rule.name())),
+            };
+            match level.map(|level| level.severity()) {
+                Some(None) => false,
+                Some(Some(severity)) => {
+                    issue.severity = severity;
+                    true
+                }
+                None => true,
+            }
+        });
+
+        report
+    }
+}
+
+impl fmt::Debug for Linter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rules: Vec<&str> = self.rules.iter().map(|rule| rule.id()).collect();
+        f.debug_struct("Linter").field("config", &self.config).field("rules", &rules).finish()
+    }
+}
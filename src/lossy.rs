@@ -0,0 +1,105 @@
+#[cfg(feature = "fs")]
+use std::path::PathBuf;
+
+use serde_yaml::Value;
+
+use crate::{
+    compat::remove,
+    parse::parse_value,
+    span::locate,
+    Citation, Code, Issue, ParseError,
+};
+
+/// The keys the schema requires, which are filled with their default value if they are missing or cannot be read.
+const REQUIRED: &[&str] = &["authors", "cff-version", "message", "title"];
+
+/// A citation read with [`Citation::read_lossy`], which may lack values that could not be read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartialCitation {
+    /// The values that could be read. Required keys that are missing or could not be read have their default
+    /// value, see [`Citation::default`], e.g. an empty title.
+    pub citation: Citation,
+
+    /// The required keys that are missing or could not be read, e.g. `title`.
+    pub missing: Vec<String>,
+}
+
+impl PartialCitation {
+    /// Returns `true` if all required keys could be read.
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty()
+    }
+
+    /// Returns the citation if all required keys could be read.
+    pub fn complete(self) -> Option<Citation> {
+        self.is_complete().then_some(self.citation)
+    }
+}
+
+/// Reads as much of the content as possible, see [`Citation::parse_lossy`].
+fn lossy(content: &str) -> Result<(PartialCitation, Vec<Issue>), ParseError> {
+    let mut value = parse_value(content)?;
+    let defaults = serde_yaml::to_value(Citation::default()).expect("citations can be serialized");
+
+    let mut issues: Vec<Issue> = Vec::new();
+    let mut missing: Vec<String> = Vec::new();
+    let citation = loop {
+        if let Value::Mapping(mapping) = &mut value {
+            let absent: Vec<&str> = REQUIRED.iter().copied().filter(|key| !mapping.contains_key(*key)).collect();
+            for key in absent {
+                mapping.insert(Value::from(key), defaults[key].clone());
+                if !missing.iter().any(|missing| missing == key) {
+                    missing.push(key.to_string());
+                    issues.push(Issue::new(Code::MissingKey, key, format!("`{key}` is missing or cannot be read and is left at its default value")));
+                }
+            }
+        }
+
+        match serde_path_to_error::deserialize::<_, Citation>(value.clone()) {
+            Ok(citation) => break citation,
+            Err(error) => {
+                let path = error.path().to_string();
+                if path == "." || !remove(&mut value, &path) {
+                    issues.push(Issue::new(Code::IgnoredValue, "", format!("the citation cannot be read: {}", error.into_inner())));
+                    missing = REQUIRED.iter().map(|key| key.to_string()).collect();
+                    break Citation::default();
+                }
+                // removing a value may make its parent unreadable, e.g. an identifier without `type`
+                let message = format!("the value cannot be read and is skipped: {}", error.into_inner());
+                match issues.last_mut() {
+                    Some(last) if last.code == Code::IgnoredValue && last.path.strip_prefix(path.as_str()).is_some_and(|rest| rest.starts_with(['.', '['])) => last.path = path,
+                    _ => issues.push(Issue::new(Code::IgnoredValue, path, message)),
+                }
+            }
+        }
+    };
+
+    for issue in &mut issues {
+        issue.span = locate(content, &issue.path);
+    }
+
+    Ok((PartialCitation { citation, missing }, issues))
+}
+
+impl Citation {
+    /// Parses the content of a `CITATION.cff` file, skipping the parts that cannot be read instead of failing,
+    /// e.g. to index a large number of real-world files.
+    ///
+    /// Values that cannot be read are skipped and reported as [`Code::IgnoredValue`], e.g. a malformed date of a
+    /// reference, or a whole reference if it cannot be read without the value. Required keys that are missing or cannot be read are left at their default value
+    /// and reported as [`Code::MissingKey`], see [`PartialCitation::missing`].
+    ///
+    /// Only content that is not valid YAML or JSON fails, e.g. because of a syntax error.
+    pub fn parse_lossy(content: &str) -> Result<(PartialCitation, Vec<Issue>), ParseError> {
+        lossy(content)
+    }
+
+    /// Reads the `CITATION.cff` file at the given path, skipping the parts that cannot be read instead of failing,
+    /// see [`Citation::parse_lossy`].
+    #[cfg(feature = "fs")]
+    pub fn read_lossy(path: PathBuf) -> Result<(PartialCitation, Vec<Issue>), crate::Error> {
+        let content = std::fs::read_to_string(&path)?;
+
+        Ok(lossy(&content).map_err(|error| error.with_file_name(path.display().to_string()))?)
+    }
+}
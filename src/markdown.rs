@@ -0,0 +1,65 @@
+use crate::{Citation, OutputFormat, Style};
+
+/// The comments around the section, which mark it for [`Citation::update_markdown_section`].
+const START: &str = "<!-- citation:start -->";
+const END: &str = "<!-- citation:end -->";
+
+impl Citation {
+    /// Renders a "How to cite" section for a README, with the citation in APA style followed by a fenced BibTeX
+    /// entry, see [`Citation::to_bibtex`]:
+    ///
+    /// ````markdown
+    /// <!-- citation:start -->
+    /// ## How to cite
+    ///
+    /// If you use this software, please cite it as:
+    ///
+    /// Lewin, L. (2024). *citation* (Version 0.1.0) \[Computer software\]. GitHub. <https://github.com/lucalewin/citation>
+    ///
+    /// ```bibtex
+    /// @software{lewin2024,
+    ///   ...
+    /// }
+    /// ```
+    /// <!-- citation:end -->
+    /// ````
+    ///
+    /// The comments mark the section, so that it can be kept in sync with the citation by
+    /// [`Citation::update_markdown_section`].
+    pub fn to_markdown_section(&self) -> String {
+        let work = match &self.preferred_citation {
+            Some(_) => "this work",
+            None => "this software",
+        };
+
+        format!(
+            "{START}\n## How to cite\n\nIf you use {work}, please cite it as:\n\n{}\n\n```bibtex\n{}```\n{END}\n",
+            self.format_as(Style::Apa, OutputFormat::Markdown),
+            self.to_bibtex()
+        )
+    }
+
+    /// Replaces the section rendered by [`Citation::to_markdown_section`] in a Markdown document, e.g. the content
+    /// of a README, with the current one. If the document has no such section, it is appended at the end.
+    ///
+    /// The document is returned unchanged if the section is up to date, which allows checking that a README is in
+    /// sync with its `CITATION.cff` file.
+    pub fn update_markdown_section(&self, markdown: &str) -> String {
+        let section = self.to_markdown_section();
+
+        let range = markdown.find(START).and_then(|start| {
+            let end = start + markdown[start..].find(END)? + END.len();
+            let end = match markdown[end..].starts_with('\n') {
+                true => end + 1,
+                false => end,
+            };
+            Some(start..end)
+        });
+
+        match range {
+            Some(range) => format!("{}{section}{}", &markdown[..range.start], &markdown[range.end..]),
+            None if markdown.trim().is_empty() => section,
+            None => format!("{}\n\n{section}", markdown.trim_end()),
+        }
+    }
+}
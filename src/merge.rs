@@ -0,0 +1,100 @@
+use std::{error::Error, fmt};
+
+use serde_yaml::Value;
+
+use crate::{
+    diff::{display, identities, join},
+    Citation,
+};
+
+/// How [`Citation::merge`] resolves values that differ between the two citations.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MergeStrategy {
+    /// Keeps the value of the citation that is merged into, e.g. of a hand-maintained file.
+    #[default]
+    Ours,
+
+    /// Takes the value of the other citation, e.g. of one generated from `Cargo.toml`.
+    Theirs,
+
+    /// Fails at the first conflicting value.
+    Fail,
+}
+
+/// Error returned by [`Citation::merge`] with [`MergeStrategy::Fail`] when a value differs between the citations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeConflict {
+    /// The path of the conflicting value, e.g. `authors[0].email`.
+    pub path: String,
+
+    /// The value of the citation that is merged into, written on a single line.
+    pub ours: String,
+
+    /// The value of the other citation, written on a single line.
+    pub theirs: String,
+}
+
+impl fmt::Display for MergeConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "conflicting values for {}: {} and {}", self.path, self.ours, self.theirs)
+    }
+}
+
+impl Error for MergeConflict {}
+
+fn merge(path: &str, ours: &mut Value, theirs: Value, strategy: MergeStrategy) -> Result<(), MergeConflict> {
+    match (&mut *ours, theirs) {
+        (Value::Mapping(ours), Value::Mapping(theirs)) => {
+            for (key, value) in theirs {
+                match ours.get_mut(&key) {
+                    Some(ours) => merge(&join(path, &display(&key)), ours, value, strategy)?,
+                    None => {
+                        ours.insert(key, value);
+                    }
+                }
+            }
+        }
+        (Value::Sequence(ours), Value::Sequence(theirs)) => {
+            // items describing the same person, reference or value are merged, all others are appended
+            let mut used = vec![false; ours.len()];
+            for item in theirs {
+                let identities = identities(&item);
+                let position = (0..used.len()).find(|&position| !used[position] && identities.iter().any(|identity| self::identities(&ours[position]).contains(identity)));
+                match position {
+                    Some(position) => {
+                        used[position] = true;
+                        merge(&format!("{path}[{position}]"), &mut ours[position], item, strategy)?;
+                    }
+                    None => ours.push(item),
+                }
+            }
+        }
+        (value, theirs) if *value != theirs => match strategy {
+            MergeStrategy::Ours => {}
+            MergeStrategy::Theirs => *value = theirs,
+            MergeStrategy::Fail => return Err(MergeConflict { path: path.to_string(), ours: display(value), theirs: display(&theirs) }),
+        },
+        _ => {}
+    }
+
+    Ok(())
+}
+
+impl Citation {
+    /// Merges another citation into this one, e.g. a skeleton generated with [`Citation::from_cargo_manifest`]
+    /// into a hand-maintained `CITATION.cff`.
+    ///
+    /// Keys that only the other citation has are added. Authors, keywords, identifiers, references and other lists
+    /// are unioned, merging items that describe the same person, reference or value (see [`Citation::diff`]).
+    /// Values that differ are resolved by the given [`MergeStrategy`].
+    ///
+    /// With [`MergeStrategy::Fail`], the citation is left unchanged if there is a conflict.
+    pub fn merge(&mut self, other: Citation, strategy: MergeStrategy) -> Result<(), MergeConflict> {
+        let mut value = serde_yaml::to_value(&*self).expect("citations can be serialized");
+        let other = serde_yaml::to_value(other).expect("citations can be serialized");
+
+        merge("", &mut value, other, strategy)?;
+        *self = serde_yaml::from_value(value).expect("merging keeps the values valid");
+        Ok(())
+    }
+}
@@ -0,0 +1,88 @@
+use crate::{CffVersion, Citation, Code, Doi, Fix, Identifier, Issue, Licenses, Type};
+
+/// The result of [`Citation::migrate_to`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MigrationReport {
+    /// The changes made to the citation.
+    pub changes: Vec<Fix>,
+
+    /// The values that cannot be represented in the target version and have been removed, and the values
+    /// required by the target version that are missing, as [`Code::UnavailableKey`] and [`Code::MissingKey`] issues.
+    pub lost: Vec<Issue>,
+}
+
+impl MigrationReport {
+    /// Returns `true` if the migrated citation describes the work as completely as before.
+    pub fn is_lossless(&self) -> bool {
+        self.lost.is_empty()
+    }
+}
+
+impl Citation {
+    /// Migrates the citation to the given version of the schema and sets `cff-version` accordingly.
+    ///
+    /// Upgrading is lossless, as later versions only add keys and relax requirements. Downgrading to a version
+    /// before 1.2.0 is a best effort:
+    ///
+    /// - a DOI in `identifiers` is moved to `doi`, the other identifiers are removed,
+    /// - `preferred-citation` is moved to the front of `references`, where it is no longer marked as preferred,
+    /// - `type` is removed, which only loses information for datasets,
+    /// - only the first of several licenses is kept.
+    ///
+    /// Values that cannot be represented are reported in [`MigrationReport::lost`], as are `version` and
+    /// `date-released` if they are missing, since these versions require them.
+    pub fn migrate_to(&mut self, version: CffVersion) -> MigrationReport {
+        let mut report = MigrationReport::default();
+        let mut change = |path: &str, message: String| report.changes.push(Fix { path: path.to_string(), message });
+
+        if self.cff_version != version.as_str() {
+            change("cff-version", format!("changed `{}` to `{version}`", self.cff_version));
+            self.cff_version = version.as_str().to_string();
+        }
+
+        if version >= CffVersion::V1_2 {
+            return report;
+        }
+
+        let mut lost = Vec::new();
+        let mut unavailable = |path: String, message: String| lost.push(Issue::new(Code::UnavailableKey, path, message));
+
+        for (index, identifier) in std::mem::take(&mut self.identifiers).into_iter().enumerate() {
+            match identifier {
+                Identifier::Doi { value, .. } if self.doi.is_none() && Doi::new(value.as_str()).is_ok() => {
+                    change("doi", format!("moved `{value}` from `identifiers[{index}]`"));
+                    self.doi = Doi::new(value).ok();
+                }
+                identifier => unavailable(format!("identifiers[{index}]"), format!("removed `{}`, as identifiers are not available in {version}", identifier.value())),
+            }
+        }
+
+        if let Some(reference) = self.preferred_citation.take() {
+            change("references[0]", "moved `preferred-citation` to `references`".to_string());
+            unavailable("preferred-citation".to_string(), format!("`{}` is no longer marked as the preferred citation", reference.title));
+            self.references.insert(0, reference);
+        }
+
+        match self.r#type.take() {
+            Some(Type::Dataset) => unavailable("type".to_string(), format!("removed `dataset`, as the type of the work is not available in {version}")),
+            Some(Type::Software) => change("type", "removed `software`, which is implied".to_string()),
+            None => {}
+        }
+
+        if let Some(Licenses::Multiple(mut licenses)) = self.license.take() {
+            for (index, license) in licenses.iter().enumerate().skip(1) {
+                unavailable(format!("license[{index}]"), format!("removed `{}`, as a list of licenses is not available in {version}", license.id()));
+            }
+            self.license = (!licenses.is_empty()).then(|| Licenses::Single(licenses.remove(0)));
+        }
+
+        for (key, missing) in [("version", self.version.is_none()), ("date-released", self.date_released.is_none())] {
+            if missing {
+                lost.push(Issue::new(Code::MissingKey, key, format!("`{key}` is required by cff-version {version}")));
+            }
+        }
+
+        report.lost = lost;
+        report
+    }
+}
@@ -0,0 +1,58 @@
+use serde_yaml::Value;
+
+use crate::{keywords::folded, Citation, KeywordOptions, Orcid};
+
+/// Brings the value at the given key into its canonical form, recursing into mappings and sequences.
+fn normalize(key: &str, value: &mut Value) {
+    match value {
+        Value::Mapping(mapping) => {
+            let doi = mapping.get("type").and_then(Value::as_str) == Some("doi");
+
+            for (key, value) in mapping.iter_mut() {
+                match (key.as_str().unwrap_or_default(), &mut *value) {
+                    // the value of an identifier of type `doi`
+                    ("value", Value::String(value)) if doi => *value = value.trim().to_lowercase(),
+                    (key, value) => normalize(key, value),
+                }
+            }
+        }
+        Value::Sequence(items) => items.iter_mut().for_each(|item| normalize(key, item)),
+        Value::String(string) => {
+            *string = string.trim().to_string();
+
+            match key {
+                "orcid" => {
+                    if let Ok(orcid) = string.parse::<Orcid>() {
+                        *string = orcid.to_url();
+                    }
+                }
+                // DOIs are case-insensitive
+                "doi" => *string = string.to_lowercase(),
+                _ => {}
+            }
+        }
+        _ => {}
+    }
+}
+
+impl Citation {
+    /// Brings the citation into a canonical form, so that citations describing the same work compare equal and
+    /// serialize identically, e.g. before comparing or hashing them.
+    ///
+    /// - Surrounding whitespace is trimmed from all strings.
+    /// - ORCIDs are written as URLs and DOIs in lowercase.
+    /// - Keywords are deduplicated (see [`Citation::normalize_keywords`]) and sorted, ignoring case.
+    ///
+    /// Dates are always written zero-padded (`YYYY-MM-DD`), as they are parsed into [`Date`](crate::Date)s.
+    pub fn normalize(&mut self) {
+        let mut value = serde_yaml::to_value(&*self).expect("citations can be serialized");
+        normalize("", &mut value);
+        *self = serde_yaml::from_value(value).expect("normalizing keeps the values valid");
+
+        self.normalize_keywords(&KeywordOptions::default());
+        let references = self.preferred_citation.iter_mut().chain(&mut self.references);
+        for keywords in references.map(|reference| &mut reference.keywords).chain([&mut self.keywords]) {
+            keywords.sort_by_cached_key(|keyword| (folded(keyword), keyword.clone()));
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use std::{error::Error, fmt, str::FromStr};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::de::FromStrVisitor;
+#[cfg(feature = "http")]
+use crate::{http, validate::normalize, Author, Citation, Code, Issue, ParseError, ValidationReport};
+
+/// An [ORCID iD](https://orcid.org), e.g. `https://orcid.org/0000-0002-1825-0097`.
+///
+/// Both bare identifiers (`0000-0002-1825-0097`) and URLs are accepted, and the check digit is
+/// validated as specified by ISO 7064 MOD 11-2. The canonical URL form is used for display and serialization.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+#[serde(into = "String")]
+pub struct Orcid(String);
+
+impl Orcid {
+    /// Validates the given ORCID iD, which may be a bare identifier or a URL.
+    pub fn new(value: &str) -> Result<Self, ParseOrcidError> {
+        let id = ["https://orcid.org/", "http://orcid.org/", "orcid.org/"]
+            .iter()
+            .find_map(|prefix| value.trim().strip_prefix(prefix))
+            .unwrap_or(value.trim())
+            .to_ascii_uppercase();
+
+        let groups: Vec<_> = id.split('-').collect();
+        let digits: Vec<u8> = groups.concat().into_bytes();
+        let valid = groups.len() == 4
+            && groups.iter().all(|group| group.len() == 4)
+            && digits.iter().enumerate().all(|(index, b)| b.is_ascii_digit() || (index == 15 && *b == b'X'));
+        if !valid {
+            return Err(ParseOrcidError::InvalidFormat(value.to_string()));
+        }
+
+        if checksum(&digits[..15]) != digits[15] {
+            return Err(ParseOrcidError::InvalidChecksum(value.to_string()));
+        }
+
+        Ok(Orcid(id))
+    }
+
+    /// Returns the bare identifier, e.g. `0000-0002-1825-0097`.
+    pub fn id(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the canonical URL, e.g. `https://orcid.org/0000-0002-1825-0097`.
+    pub fn to_url(&self) -> String {
+        format!("https://orcid.org/{}", self.0)
+    }
+}
+
+/// Computes the ISO 7064 MOD 11-2 check digit of the first 15 digits of an ORCID iD.
+///
+/// https://support.orcid.org/hc/en-us/articles/360006897674-Structure-of-the-ORCID-Identifier
+pub(crate) fn checksum(digits: &[u8]) -> u8 {
+    let total = digits.iter().fold(0u32, |total, digit| (total + u32::from(digit - b'0')) * 2);
+
+    match (12 - total % 11) % 11 {
+        10 => b'X',
+        result => b'0' + result as u8,
+    }
+}
+
+impl fmt::Display for Orcid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "https://orcid.org/{}", self.0)
+    }
+}
+
+impl FromStr for Orcid {
+    type Err = ParseOrcidError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Orcid::new(value)
+    }
+}
+
+impl<'de> Deserialize<'de> for Orcid {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(FromStrVisitor::new("an ORCID iD"))
+    }
+}
+
+impl From<Orcid> for String {
+    fn from(orcid: Orcid) -> Self {
+        orcid.to_url()
+    }
+}
+
+/// Serializes the ORCID of an author in its canonical URL form, e.g. when a bare identifier has been parsed.
+///
+/// Invalid ORCIDs are serialized as they are, so that they are still reported by [`Citation::validate`](crate::Citation::validate).
+pub(crate) fn canonical<S: Serializer>(orcid: &Option<String>, serializer: S) -> Result<S::Ok, S::Error> {
+    match orcid.as_deref().map(Orcid::new) {
+        Some(Ok(orcid)) => serializer.serialize_str(&orcid.to_url()),
+        _ => orcid.serialize(serializer),
+    }
+}
+
+/// Error returned when a value is not a valid ORCID iD.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseOrcidError {
+    /// The value does not consist of four groups of four digits, optionally preceded by `https://orcid.org/`.
+    InvalidFormat(String),
+
+    /// The last character does not match the check digit computed from the other digits.
+    InvalidChecksum(String),
+}
+
+impl fmt::Display for ParseOrcidError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseOrcidError::InvalidFormat(value) => write!(f, "`{value}` is not a valid ORCID iD"),
+            ParseOrcidError::InvalidChecksum(value) => write!(f, "`{value}` has an invalid check digit"),
+        }
+    }
+}
+
+impl Error for ParseOrcidError {}
+
+/// The default URL of the ORCID public API, which can be overridden with `ORCID_API_URL`
+/// (e.g. for the sandbox at `https://pub.sandbox.orcid.org/v3.0`).
+#[cfg(feature = "http")]
+const API_URL: &str = "https://pub.orcid.org/v3.0";
+
+/// The public part of an ORCID record, as returned by [`Orcid::fetch`].
+///
+/// Values the owner of the record has not made public are `None`.
+#[cfg(feature = "http")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrcidRecord {
+    /// The given names.
+    pub given_names: Option<String>,
+
+    /// The family names.
+    pub family_names: Option<String>,
+
+    /// The organization of the current employment, or of the most recent one if there is no current one.
+    pub affiliation: Option<String>,
+}
+
+#[cfg(feature = "http")]
+impl Orcid {
+    /// Fetches the public part of the record of this ORCID iD from the [ORCID public API](https://info.orcid.org/documentation/features/public-api/).
+    pub fn fetch(&self) -> Result<OrcidRecord, crate::Error> {
+        let api = std::env::var("ORCID_API_URL").unwrap_or_else(|_| API_URL.to_string());
+        let content = http::client()?
+            .get(format!("{}/{}/record", api.trim_end_matches('/'), self.0))
+            .header("Accept", "application/json")
+            .send()?
+            .error_for_status()?
+            .text()?;
+
+        let record: serde_json::Value = serde_json::from_str(&content).map_err(|error| ParseError::from_json(&error, None, &content))?;
+        let value = |value: &serde_json::Value| value["value"].as_str().map(str::trim).filter(|value| !value.is_empty()).map(String::from);
+        let name = &record["person"]["name"];
+
+        let employments = record["activities-summary"]["employments"]["affiliation-group"].as_array().cloned().unwrap_or_default();
+        let employments: Vec<_> = employments.iter().filter_map(|group| group["summaries"].get(0)).map(|summary| &summary["employment-summary"]).collect();
+        let employment = employments.iter().find(|employment| employment["end-date"].is_null()).or(employments.first());
+
+        Ok(OrcidRecord {
+            given_names: value(&name["given-names"]),
+            family_names: value(&name["family-name"]),
+            affiliation: employment.and_then(|employment| employment["organization"]["name"].as_str()).map(String::from),
+        })
+    }
+}
+
+#[cfg(feature = "http")]
+impl Citation {
+    /// Fetches the ORCID records of the persons in `authors` and `contact`, fills in their missing given names,
+    /// family names and affiliation, and reports names that differ from the record as
+    /// [`Code::OrcidMismatch`](crate::Code::OrcidMismatch) warnings.
+    ///
+    /// Persons without an ORCID or with an invalid one are skipped. Names are compared ignoring case and punctuation,
+    /// and differing affiliations are not reported, as persons often have several.
+    pub fn enrich_from_orcid(&mut self) -> Result<ValidationReport, crate::Error> {
+        let mut issues = Vec::new();
+        let persons = self.authors.iter_mut().enumerate().map(|(index, author)| (format!("authors[{index}]"), author));
+        let persons = persons.chain(self.contact.iter_mut().enumerate().map(|(index, author)| (format!("contact[{index}]"), author)));
+
+        for (path, author) in persons {
+            let Author::Person(person) = author else { continue };
+            let Some(orcid) = person.orcid.as_deref().and_then(|orcid| orcid.parse::<Orcid>().ok()) else { continue };
+            let record = orcid.fetch()?;
+
+            for (key, local, registered) in [("given-names", &mut person.given_names, record.given_names), ("family-names", &mut person.family_names, record.family_names)] {
+                match (local.as_deref(), registered) {
+                    (None, registered) => *local = registered,
+                    (Some(name), Some(registered)) if normalize(name) != normalize(&registered) => {
+                        issues.push(Issue::new(Code::OrcidMismatch, format!("{path}.{key}"), format!("`{name}` differs from `{registered}` registered for {orcid}")));
+                    }
+                    _ => {}
+                }
+            }
+            if person.affiliation.is_none() {
+                person.affiliation = record.affiliation;
+            }
+        }
+
+        Ok(ValidationReport::new(issues))
+    }
+}
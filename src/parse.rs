@@ -0,0 +1,302 @@
+use std::sync::Arc;
+#[cfg(feature = "fs")]
+use std::path::Path;
+
+use serde_yaml::Value;
+
+use crate::{
+    compat::{is_newer, remove, understood},
+    schema::{closest_key, Child, Object},
+    span::{locate, Source},
+    CffVersion, Citation, ParseError,
+};
+
+/// Options controlling how `CITATION.cff` files are parsed.
+///
+/// By default parsing is lenient and keys that are not defined by the schema are kept in the `extra` map of the
+/// object they appear in, e.g. [`Citation::extra`], so that they are written back unchanged.
+#[derive(Debug, Clone, Default)]
+pub struct ParseOptions {
+    /// Rejects keys that are not defined by the schema, e.g. misspellings such as `author` instead of `authors`,
+    /// and keys that are not defined by the version declared in `cff-version`, e.g. `preferred-citation` in 1.1.0.
+    /// With the `url` feature, URLs that cannot be parsed or use a scheme not allowed by the schema are rejected too.
+    pub strict: bool,
+}
+
+impl ParseOptions {
+    /// Options for strict parsing, see [`ParseOptions::strict`].
+    pub fn strict() -> Self {
+        ParseOptions { strict: true }
+    }
+}
+
+/// A key that is not defined by the schema for the object it appears in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct UnknownKey {
+    /// The path of the key, e.g. `authors[0].familiy-names`.
+    pub(crate) path: String,
+    pub(crate) key: String,
+    pub(crate) object: Object,
+}
+
+impl UnknownKey {
+    /// Returns the key defined by the schema that is most likely meant, e.g. `family-names` for `familiy-names`.
+    pub(crate) fn suggestion(&self) -> Option<&'static str> {
+        closest_key(&self.key, self.object.keys())
+    }
+
+    /// Describes the key, suggesting the key that is most likely meant.
+    pub(crate) fn message(&self, description: &str) -> String {
+        match self.suggestion() {
+            Some(suggestion) => format!("{description}, did you mean `{suggestion}`?"),
+            None => description.to_string(),
+        }
+    }
+}
+
+/// Returns `true` if the content looks like JSON rather than YAML, i.e. it is a single object.
+pub(crate) fn is_json(content: &str) -> bool {
+    content.trim_start_matches('\u{feff}').trim_start().starts_with('{')
+}
+
+/// Parses the content as JSON if it is a JSON object, or as YAML otherwise.
+pub(crate) fn parse(content: &str, options: &ParseOptions) -> Result<Citation, ParseError> {
+    match is_json(content) {
+        true => parse_json(content, options),
+        false => parse_yaml(content, options),
+    }
+}
+
+/// Parses the content as a YAML or JSON value without interpreting it as a citation.
+pub(crate) fn parse_value(content: &str) -> Result<Value, ParseError> {
+    match is_json(content) {
+        true => serde_json::from_str(content).map_err(|error| ParseError::from_json(&error, None, content)),
+        false => serde_path_to_error::deserialize(serde_yaml::Deserializer::from_str(content)).map_err(|error| ParseError::from_yaml(error, content)),
+    }
+}
+
+/// Parses the content of the file at the given path, as JSON or TOML if the file has the `.json` or `.toml` extension.
+#[cfg(feature = "fs")]
+pub(crate) fn parse_file(path: &Path, content: &str, options: &ParseOptions) -> Result<Citation, ParseError> {
+    let extension = path.extension().map(|extension| extension.to_ascii_lowercase());
+    let citation = match extension.as_ref().and_then(|extension| extension.to_str()) {
+        Some("json") => parse_json(content, options),
+        #[cfg(feature = "toml")]
+        Some("toml") => parse_toml(content, options),
+        _ => parse(content, options),
+    };
+
+    citation.map_err(|error| error.with_file_name(path.display().to_string()))
+}
+
+pub(crate) fn parse_yaml(content: &str, options: &ParseOptions) -> Result<Citation, ParseError> {
+    let deserializer = serde_yaml::Deserializer::from_str(content);
+    let citation = match serde_path_to_error::deserialize(deserializer) {
+        Ok(citation) => citation,
+        Err(error) => forward(serde_yaml::from_str(content).ok(), options).ok_or_else(|| ParseError::from_yaml(error, content))?,
+    };
+
+    match options.strict {
+        true => {
+            // the typed parse tolerates some documents the untyped one rejects, e.g. a duplicated unknown key
+            let value = serde_path_to_error::deserialize(serde_yaml::Deserializer::from_str(content)).map_err(|error| ParseError::from_yaml(error, content))?;
+            check_strict(content, value, &citation).map(|()| citation)
+        }
+        false => Ok(citation),
+    }
+}
+
+/// Parses a citation written as JSON, which uses the same keys and values as YAML.
+pub(crate) fn parse_json(content: &str, options: &ParseOptions) -> Result<Citation, ParseError> {
+    let mut deserializer = serde_json::Deserializer::from_str(content);
+    let citation = match serde_path_to_error::deserialize(&mut deserializer) {
+        Ok(citation) => {
+            deserializer.end().map_err(|error| ParseError::from_json(&error, None, content))?;
+            citation
+        }
+        Err(error) => forward(serde_json::from_str(content).ok(), options)
+            .ok_or_else(|| ParseError::from_json(error.inner(), Some(error.path().to_string()), content))?,
+    };
+
+    match options.strict {
+        true => {
+            let value = serde_json::from_str(content).map_err(|error| ParseError::from_json(&error, None, content))?;
+            check_strict(content, value, &citation).map(|()| citation)
+        }
+        false => Ok(citation),
+    }
+}
+
+/// Parses a citation written as TOML. Dates may be written as TOML dates, e.g. `date-released = 2021-07-18`.
+#[cfg(feature = "toml")]
+pub(crate) fn parse_toml(content: &str, options: &ParseOptions) -> Result<Citation, ParseError> {
+    let table: toml::Table = toml::from_str(content).map_err(|error| ParseError::from_toml(&error, content))?;
+    let value = toml_to_yaml(toml::Value::Table(table));
+    let citation = match serde_path_to_error::deserialize(value.clone()) {
+        Ok(citation) => citation,
+        Err(error) => forward(Some(value.clone()), options).ok_or_else(|| ParseError::from_yaml(error, content))?,
+    };
+
+    match options.strict {
+        true => check_strict(content, value, &citation).map(|()| citation),
+        false => Ok(citation),
+    }
+}
+
+/// Converts a TOML value to the YAML data model, writing dates and times as strings.
+#[cfg(feature = "toml")]
+pub(crate) fn toml_to_yaml(value: toml::Value) -> Value {
+    match value {
+        toml::Value::String(value) => Value::String(value),
+        toml::Value::Integer(value) => Value::Number(value.into()),
+        toml::Value::Float(value) => Value::Number(value.into()),
+        toml::Value::Boolean(value) => Value::Bool(value),
+        toml::Value::Datetime(value) => Value::String(value.to_string()),
+        toml::Value::Array(values) => Value::Sequence(values.into_iter().map(toml_to_yaml).collect()),
+        toml::Value::Table(table) => Value::Mapping(table.into_iter().map(|(key, value)| (Value::String(key), toml_to_yaml(value))).collect()),
+    }
+}
+
+/// Parses a citation written for a newer version of the schema than supported, ignoring the values that cannot be
+/// read instead of failing, see [`Document::compatibility`](crate::Document::compatibility). Strict parsing fails instead.
+fn forward(value: Option<Value>, options: &ParseOptions) -> Option<Citation> {
+    let value = value.filter(|value| !options.strict && is_newer(value))?;
+
+    understood(value).map(|(citation, _)| citation)
+}
+
+/// Returns an error at the given path, located in the content.
+fn located(content: &str, path: String, message: String) -> ParseError {
+    let span = locate(content, &path);
+
+    ParseError {
+        path: (path != ".").then_some(path),
+        line: span.map(|span| span.line),
+        column: span.map(|span| span.column),
+        offset: span.map(|span| span.offset),
+        message,
+        source: Some(Arc::new(Source::new("CITATION.cff", content))),
+    }
+}
+
+/// Rejects the first key that is not defined by the schema and, with the `url` feature, the first malformed URL.
+fn check_strict(content: &str, value: Value, citation: &Citation) -> Result<(), ParseError> {
+    strict_errors(content, &value, citation).into_iter().next().map_or(Ok(()), Err)
+}
+
+/// Collects the keys that are not defined by the schema or the declared version and, with the `url` feature,
+/// the first malformed URL.
+#[cfg_attr(not(feature = "url"), allow(unused_variables))]
+fn strict_errors(content: &str, value: &Value, citation: &Citation) -> Vec<ParseError> {
+    let mut errors: Vec<ParseError> = unknown_keys(value)
+        .into_iter()
+        .map(|unknown| {
+            let message = unknown.message(&format!("unknown key `{}`", unknown.key));
+            located(content, unknown.path, message)
+        })
+        .collect();
+
+    // keys added in later versions of the schema are unknown to the declared version
+    let version = value.get("cff-version").and_then(Value::as_str).and_then(|version| version.parse::<CffVersion>().ok());
+    let keys = value.as_mapping().into_iter().flat_map(|mapping| mapping.keys()).filter_map(Value::as_str);
+    if let Some(version) = version {
+        let undefined = keys.filter(|key| !version.defines(key));
+        errors.extend(undefined.map(|key| located(content, key.to_string(), format!("key `{key}` is not defined by cff-version {version}"))));
+    }
+
+    #[cfg(feature = "url")]
+    if let Err(invalid) = citation.check_urls() {
+        errors.push(located(content, invalid.path, format!("`{}` is not a valid URL with the scheme http, https, ftp or sftp", invalid.value)));
+    }
+
+    errors
+}
+
+/// Parses the content like [`parse`], but reports every value that cannot be read instead of only the first.
+///
+/// Values that cannot be read are removed one by one until the rest can be read, so an invalid author does not
+/// hide a malformed date in a reference. Only a missing required key or a syntax error stops the search.
+pub(crate) fn parse_all(content: &str, options: &ParseOptions) -> Result<Citation, Vec<ParseError>> {
+    let error = match parse(content, options) {
+        Ok(citation) => return Ok(citation),
+        Err(error) => error,
+    };
+    let Ok(mut value) = parse_value(content) else { return Err(vec![error]) };
+
+    let mut errors: Vec<ParseError> = Vec::new();
+    let citation = loop {
+        match serde_path_to_error::deserialize::<_, Citation>(value.clone()) {
+            Ok(citation) => break Some(citation),
+            Err(error) => {
+                let path = error.path().to_string();
+                let removed = path != "." && remove(&mut value, &path);
+                // removing a value may make its parent unreadable, e.g. an identifier without `type`, which is
+                // already covered by the error about the value
+                let covered = errors.last().and_then(|last| last.path.as_deref()).is_some_and(|last| {
+                    last.strip_prefix(path.as_str()).is_some_and(|rest| rest.starts_with(['.', '[']))
+                });
+                if !covered {
+                    errors.push(located(content, path, error.into_inner().to_string()));
+                }
+                if !removed {
+                    break None;
+                }
+            }
+        }
+    };
+
+    if let (Some(citation), true) = (&citation, options.strict) {
+        errors.extend(strict_errors(content, &value, citation));
+    }
+
+    match errors.is_empty() {
+        true => Err(vec![error]),
+        false => Err(errors),
+    }
+}
+
+/// Collects all keys of the document that are not defined by the schema.
+pub(crate) fn unknown_keys(value: &Value) -> Vec<UnknownKey> {
+    let mut unknown = Vec::new();
+    walk(value, Object::Citation, "", &mut unknown);
+
+    unknown
+}
+
+fn walk(value: &Value, object: Object, path: &str, unknown: &mut Vec<UnknownKey>) {
+    let Value::Mapping(mapping) = value else { return };
+
+    for (key, value) in mapping {
+        let Some(key) = key.as_str() else { continue };
+        let path = match path {
+            "" => key.to_string(),
+            _ => format!("{path}.{key}"),
+        };
+
+        if !object.keys().contains(&key) {
+            unknown.push(UnknownKey { path, key: key.to_string(), object });
+            continue;
+        }
+
+        let Some(child) = object.child(key) else { continue };
+        match value {
+            Value::Sequence(items) => {
+                for (index, item) in items.iter().enumerate() {
+                    walk(item, child.object(item), &format!("{path}[{index}]"), unknown);
+                }
+            }
+            value => walk(value, child.object(value), &path, unknown),
+        }
+    }
+}
+
+impl Child {
+    /// Resolves the kind of the given object, deciding between persons and entities.
+    fn object(&self, value: &Value) -> Object {
+        match self {
+            Child::Object(object) => *object,
+            Child::Agent if value.get("name").is_some() => Object::Entity,
+            Child::Agent => Object::Person,
+        }
+    }
+}
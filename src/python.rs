@@ -0,0 +1,196 @@
+//! Bindings for Python, built with the `python` feature, e.g. with `maturin build --release` using the
+//! `pyproject.toml` at the root of the repository.
+//!
+//! ```python
+//! import citation
+//!
+//! cff = citation.Citation.read("CITATION.cff")
+//! for issue in cff.validate():
+//!     print(f"{issue.line}:{issue.column}: {issue.severity}[{issue.code}] {issue.message}")
+//! print(cff.format("apa"))
+//! print(cff.convert("bibtex"))
+//! ```
+
+#[cfg(feature = "fs")]
+use std::path::PathBuf;
+
+use pyo3::{create_exception, exceptions::{PyOSError, PyValueError}, prelude::*};
+
+use crate::{bindings, parse::parse, Citation, Error, Issue, ParseOptions};
+
+create_exception!(citation, ParseError, PyValueError, "Raised when a `CITATION.cff` file cannot be parsed.");
+
+/// Maps an error to a Python exception: `OSError` for files that cannot be read, `ParseError` for files that cannot
+/// be parsed and `ValueError` otherwise.
+fn exception(error: Error) -> PyErr {
+    match error {
+        Error::Io(error) => PyOSError::new_err(error.to_string()),
+        Error::Yaml(error) => ParseError::new_err(error.to_string()),
+        error => PyValueError::new_err(error.to_string()),
+    }
+}
+
+fn options(strict: bool) -> ParseOptions {
+    match strict {
+        true => ParseOptions::strict(),
+        false => ParseOptions::default(),
+    }
+}
+
+/// A parsed `CITATION.cff` file, exposed to Python as `citation.Citation`.
+#[pyclass(name = "Citation", module = "citation", frozen)]
+pub struct PyCitation {
+    citation: Citation,
+
+    /// The file name and content the citation has been parsed from, which locate the issues found by `validate`.
+    source: (String, String),
+}
+
+/// A problem found by `Citation.validate`, exposed to Python as `citation.Issue`.
+#[pyclass(name = "Issue", module = "citation", frozen, get_all)]
+#[derive(Debug)]
+pub struct PyIssue {
+    /// The code of the rule, e.g. `CFF001`.
+    pub code: String,
+
+    /// `error`, `warning` or `hint`.
+    pub severity: String,
+
+    /// The path of the offending key, e.g. `authors[2].orcid`.
+    pub path: String,
+
+    /// A human readable description of the problem.
+    pub message: String,
+
+    /// A suggestion on how to fix the problem.
+    pub help: String,
+
+    /// The line of the offending key, starting at 1, if it could be located.
+    pub line: Option<usize>,
+
+    /// The column of the offending key, starting at 1, if it could be located.
+    pub column: Option<usize>,
+}
+
+impl From<&Issue> for PyIssue {
+    fn from(issue: &Issue) -> Self {
+        PyIssue {
+            code: issue.code.as_str().to_string(),
+            severity: issue.severity.as_str().to_string(),
+            path: issue.path.clone(),
+            message: issue.message.clone(),
+            help: issue.code.help().to_string(),
+            line: issue.span.map(|span| span.line),
+            column: issue.span.map(|span| span.column),
+        }
+    }
+}
+
+#[pymethods]
+impl PyIssue {
+    fn __repr__(&self) -> String {
+        format!("Issue(code={:?}, severity={:?}, path={:?}, message={:?})", self.code, self.severity, self.path, self.message)
+    }
+}
+
+#[pymethods]
+impl PyCitation {
+    /// Parses the content of a `CITATION.cff` file, in YAML or JSON, rejecting keys that are not defined by the
+    /// schema if `strict` is set.
+    #[staticmethod]
+    #[pyo3(signature = (content, strict = false))]
+    pub fn parse(content: &str, strict: bool) -> PyResult<Self> {
+        let citation = parse(content, &options(strict)).map_err(|error| exception(error.into()))?;
+
+        Ok(PyCitation { citation, source: ("CITATION.cff".to_string(), content.to_string()) })
+    }
+
+    /// Reads and parses the `CITATION.cff` file at the given path, see `Citation.parse`.
+    #[cfg(feature = "fs")]
+    #[staticmethod]
+    #[pyo3(signature = (path, strict = false))]
+    pub fn read(path: PathBuf, strict: bool) -> PyResult<Self> {
+        let content = std::fs::read_to_string(&path).map_err(|error| exception(error.into()))?;
+        let citation = crate::parse::parse_file(&path, &content, &options(strict)).map_err(|error| exception(error.into()))?;
+
+        Ok(PyCitation { citation, source: (path.display().to_string(), content) })
+    }
+
+    /// Validates the citation against the constraints of the schema, returning every problem found.
+    pub fn validate(&self) -> Vec<PyIssue> {
+        let (name, content) = &self.source;
+        let report = self.citation.validate().with_source(name.as_str(), content.as_str());
+
+        report.iter().map(PyIssue::from).collect()
+    }
+
+    /// Formats the citation in a style, one of `apa`, `ieee`, `chicago`, `mla`, `harvard` and `vancouver`, as
+    /// `plain` text, `markdown`, `html` or `latex`.
+    #[pyo3(signature = (style = "apa", output = "plain"))]
+    pub fn format(&self, style: &str, output: &str) -> PyResult<String> {
+        let style = bindings::style(style).ok_or_else(|| PyValueError::new_err(format!("unknown style `{style}`")))?;
+        let output = bindings::output(output).ok_or_else(|| PyValueError::new_err(format!("unknown output format `{output}`")))?;
+
+        Ok(self.citation.format_as(style, output))
+    }
+
+    /// Converts the citation to another format, named like the formats of `citation convert`: `cff`, `bibtex`,
+    /// `biblatex`, `ris`, `csl-json`, `codemeta`, `zenodo`, `json-ld`, `datacite-xml`, `datacite-json`,
+    /// `dublin-core` or `endnote`.
+    pub fn convert(&self, to: &str) -> PyResult<String> {
+        match bindings::convert(&self.citation, to) {
+            Some(converted) => converted.map_err(exception),
+            None => Err(PyValueError::new_err(format!("cannot convert to {to}"))),
+        }
+    }
+
+    pub fn to_bibtex(&self) -> String {
+        self.citation.to_bibtex()
+    }
+
+    pub fn to_biblatex(&self) -> String {
+        self.citation.to_biblatex()
+    }
+
+    pub fn to_ris(&self) -> String {
+        self.citation.to_ris()
+    }
+
+    pub fn to_codemeta(&self) -> String {
+        self.citation.to_codemeta()
+    }
+
+    pub fn to_zenodo_json(&self) -> String {
+        self.citation.to_zenodo_json()
+    }
+
+    #[getter]
+    pub fn title(&self) -> &str {
+        &self.citation.title
+    }
+
+    #[getter]
+    pub fn version(&self) -> Option<&str> {
+        self.citation.version.as_deref()
+    }
+
+    /// The DOI of the work, from `doi` or from the first DOI in `identifiers`.
+    #[getter]
+    pub fn doi(&self) -> Option<String> {
+        self.citation.find_doi()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Citation(title={:?})", self.citation.title)
+    }
+}
+
+/// The `citation` Python module.
+#[pymodule]
+pub fn citation(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyCitation>()?;
+    module.add_class::<PyIssue>()?;
+    module.add("ParseError", module.py().get_type::<ParseError>())?;
+
+    Ok(())
+}
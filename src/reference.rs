@@ -0,0 +1,637 @@
+use std::{collections::BTreeMap, fmt};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Author, BuildError, Citation, Contact, Date, Doi, Entity, Identifier, IntOrString, License, Licenses, Type, CFF_VERSION};
+
+/// A reference to another work, as defined by `definitions.reference` in the schema.
+///
+/// https://github.com/citation-file-format/citation-file-format/blob/main/schema-guide.md#definitionsreference
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[serde(rename_all = "kebab-case")]
+pub struct Reference {
+    /// The abbreviation of a work.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub abbreviation: Option<String>,
+
+    /// The abstract of a work.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub r#abstract: Option<String>,
+
+    /// The author(s) of a work.
+    ///
+    /// required: true (at least one item in the Vec)
+    pub authors: Vec<Author>,
+
+    /// The DOI of a collection containing the work.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collection_doi: Option<Doi>,
+
+    /// The title of a collection or proceedings.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collection_title: Option<String>,
+
+    /// The type of a collection.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collection_type: Option<String>,
+
+    /// The commit hash or revision number of the work.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+
+    /// The conference where the work was presented.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conference: Option<Entity>,
+
+    /// The contact person, group, company, etc. for a work.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub contact: Vec<Contact>,
+
+    /// The copyright information pertaining to the work.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub copyright: Option<String>,
+
+    /// The data type of a data set.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data_type: Option<String>,
+
+    /// The name of the database where a work was accessed/is stored.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub database: Option<String>,
+
+    /// The provider of the database where a work was accessed/is stored.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub database_provider: Option<Entity>,
+
+    /// The date the work was accessed.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_accessed: Option<Date>,
+
+    /// The date the work has been downloaded.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_downloaded: Option<Date>,
+
+    /// The date the work has been published.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_published: Option<Date>,
+
+    /// The date the work has been released.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_released: Option<Date>,
+
+    /// The department where a work has been produced.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub department: Option<String>,
+
+    /// The DOI of the work.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doi: Option<Doi>,
+
+    /// The edition of the work.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub edition: Option<String>,
+
+    /// The editor(s) of a work.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub editors: Vec<Author>,
+
+    /// The editor(s) of a series in which a work has been published.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub editors_series: Vec<Author>,
+
+    /// The end page of the work.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end: Option<IntOrString>,
+
+    /// An entry in the collection that constitutes the work.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entry: Option<String>,
+
+    /// The name of the electronic file containing the work.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filename: Option<String>,
+
+    /// The format in which a work is represented.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+
+    /// The identifier(s) of the work.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub identifiers: Vec<Identifier>,
+
+    /// The institution where a work has been produced or published.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub institution: Option<Entity>,
+
+    /// The ISBN of the work.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub isbn: Option<String>,
+
+    /// The ISSN of the work.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issn: Option<String>,
+
+    /// The issue of a periodical in which a work appeared.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issue: Option<IntOrString>,
+
+    /// The publication date of the issue of a periodical in which a work appeared.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issue_date: Option<String>,
+
+    /// The name of the issue of a periodical in which the work appeared.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issue_title: Option<String>,
+
+    /// The name of the journal/magazine/newspaper/periodical where the work was published.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub journal: Option<String>,
+
+    /// Keywords pertaining to the work.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub keywords: Vec<String>,
+
+    /// The language identifier(s) of the work according to ISO 639 language strings.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub languages: Vec<String>,
+
+    /// The SPDX license identifier(s) for the license(s) under which the work is made available.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license: Option<Licenses>,
+
+    /// The URL of the license text under which the work is licensed (only for non-standard licenses not included in the SPDX License List).
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license_url: Option<String>,
+
+    /// The line of code in the file where the work ends.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc_end: Option<IntOrString>,
+
+    /// The line of code in the file where the work starts.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc_start: Option<IntOrString>,
+
+    /// The location of the work.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<Entity>,
+
+    /// The medium of the work.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub medium: Option<String>,
+
+    /// The month in which a work has been published.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub month: Option<IntOrString>,
+
+    /// The NIHMSID of a work.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nihmsid: Option<String>,
+
+    /// Notes pertaining to the work. Note that this key should contain notes that may be picked up by some downstream tooling (e.g., reference managers), but not others (e.g., a software index).
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+
+    /// The accession number for a work.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub number: Option<IntOrString>,
+
+    /// The number of volumes making up the collection in which the work has been published.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub number_volumes: Option<IntOrString>,
+
+    /// The number of pages of the work.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pages: Option<IntOrString>,
+
+    /// The states for which a patent is granted.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub patent_states: Vec<String>,
+
+    /// The PMCID of a work.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pmcid: Option<String>,
+
+    /// The publisher who has published the work.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub publisher: Option<Entity>,
+
+    /// The recipient(s) of a personal communication.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub recipients: Vec<Author>,
+
+    /// The URL of the work in a repository (when the repository is neither a source code repository nor a build artifact repository).
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+
+    /// The URL of the work in a build artifact/binary repository.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository_artifact: Option<String>,
+
+    /// The URL of the work in a source code repository.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository_code: Option<String>,
+
+    /// The scope of the reference, e.g., the section of the work it adheres to.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+
+    /// The section of a work that is referenced.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub section: Option<IntOrString>,
+
+    /// The sender(s) of a personal communication.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub senders: Vec<Author>,
+
+    /// The start page of the work.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start: Option<IntOrString>,
+
+    /// The publication status of the work.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<Status>,
+
+    /// The term being referenced if the work is a dictionary or encyclopedia.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub term: Option<String>,
+
+    /// The type of the thesis that is the work.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thesis_type: Option<String>,
+
+    /// The title of the work.
+    ///
+    /// required: true
+    pub title: String,
+
+    /// The translator(s) of a work.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub translators: Vec<Author>,
+
+    /// The type of the work.
+    ///
+    /// required: true
+    pub r#type: ReferenceType,
+
+    /// The URL of the work.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+
+    /// The version of the work.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+
+    /// The volume of the periodical in which a work appeared.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub volume: Option<IntOrString>,
+
+    /// The title of the volume in which the work appeared.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub volume_title: Option<String>,
+
+    /// The year in which a work has been published.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub year: Option<IntOrString>,
+
+    /// The year of the original publication.
+    ///
+    /// required: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub year_original: Option<IntOrString>,
+
+    /// Keys that are not defined by the schema.
+    #[serde(flatten)]
+    #[cfg_attr(feature = "schemars", schemars(skip))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    pub extra: BTreeMap<String, serde_yaml::Value>,
+}
+
+impl Reference {
+    /// Returns the licenses of the work, regardless of whether `license` is a single identifier or a list.
+    pub fn licenses(&self) -> &[License] {
+        self.license.as_ref().map_or(&[], Licenses::as_slice)
+    }
+
+    /// Converts a reference to a software (or dataset) into a citation of that work, used when importing
+    /// from other formats. URLs of code hosting platforms become `repository-code`.
+    pub(crate) fn into_citation(self) -> Result<Citation, BuildError> {
+        let url = self.url.or(self.repository_code);
+        let is_repository = url.as_deref().is_some_and(|url| {
+            ["github.com/", "gitlab.com/", "codeberg.org/", "bitbucket.org/"].iter().any(|host| url.contains(host))
+        });
+
+        let mut builder = Citation::builder()
+            .cff_version(CFF_VERSION)
+            .title(self.title)
+            .authors(self.authors)
+            .keywords(self.keywords);
+
+        if matches!(self.r#type, ReferenceType::Data | ReferenceType::Database) {
+            builder = builder.r#type(Type::Dataset);
+        }
+        if let Some(url) = url {
+            builder = match is_repository {
+                true => builder.repository_code(url),
+                false => builder.url(url),
+            };
+        }
+        if let Some(version) = self.version {
+            builder = builder.version(version);
+        }
+        if let Some(doi) = self.doi {
+            builder = builder.doi(doi);
+        }
+        if let Some(date) = self.date_released.or(self.date_published) {
+            builder = builder.date_released(date);
+        }
+        if let Some(r#abstract) = self.r#abstract {
+            builder = builder.r#abstract(r#abstract);
+        }
+        if let Some(license) = self.license {
+            builder = builder.license(license);
+        }
+
+        builder.build()
+    }
+}
+
+/// The publication status of a referenced work.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[serde(rename_all = "kebab-case")]
+pub enum Status {
+    Abstract,
+    AdvanceOnline,
+    InPreparation,
+    InPress,
+    Preprint,
+    Submitted,
+}
+
+macro_rules! reference_types {
+    ($($(#[$attribute:meta])* $variant:ident => $name:literal,)*) => {
+        /// The type of a referenced work.
+        ///
+        /// Values that are not known to this version of the crate are kept in
+        /// [`ReferenceType::Other`], so files written against newer schema versions still parse.
+        /// Defaults to [`ReferenceType::Generic`].
+        #[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+        pub enum ReferenceType {
+            $($(#[$attribute])* $variant,)*
+            Other(String),
+        }
+
+        impl ReferenceType {
+            /// Returns the value of the type as used in `CITATION.cff` files.
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(ReferenceType::$variant => $name,)*
+                    ReferenceType::Other(name) => name,
+                }
+            }
+        }
+
+        impl From<&str> for ReferenceType {
+            fn from(value: &str) -> Self {
+                match value {
+                    $($name => ReferenceType::$variant,)*
+                    other => ReferenceType::Other(other.to_string()),
+                }
+            }
+        }
+
+        /// Generates mostly known types, and sometimes an arbitrary [`ReferenceType::Other`].
+        #[cfg(feature = "arbitrary")]
+        impl<'a> arbitrary::Arbitrary<'a> for ReferenceType {
+            fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+                match u.ratio(1, 8)? {
+                    true => Ok(ReferenceType::from(<&str>::arbitrary(u)?)),
+                    false => Ok(ReferenceType::from(*u.choose(&[$($name),*])?)),
+                }
+            }
+        }
+
+        /// Lists the known types, but accepts any string like [`ReferenceType::Other`].
+        #[cfg(feature = "schemars")]
+        impl schemars::JsonSchema for ReferenceType {
+            fn schema_name() -> std::borrow::Cow<'static, str> {
+                "ReferenceType".into()
+            }
+
+            fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+                schemars::json_schema!({
+                    "description": "The type of a referenced work.",
+                    "anyOf": [{ "type": "string", "enum": [$($name),*] }, { "type": "string" }],
+                })
+            }
+        }
+    };
+}
+
+reference_types! {
+    Art => "art",
+    Article => "article",
+    Audiovisual => "audiovisual",
+    Bill => "bill",
+    Blog => "blog",
+    Book => "book",
+    Catalogue => "catalogue",
+    Conference => "conference",
+    ConferencePaper => "conference-paper",
+    Data => "data",
+    Database => "database",
+    Dictionary => "dictionary",
+    EditedWork => "edited-work",
+    Encyclopedia => "encyclopedia",
+    FilmBroadcast => "film-broadcast",
+    #[default]
+    Generic => "generic",
+    GovernmentDocument => "government-document",
+    Grant => "grant",
+    Hearing => "hearing",
+    HistoricalWork => "historical-work",
+    LegalCase => "legal-case",
+    LegalRule => "legal-rule",
+    MagazineArticle => "magazine-article",
+    Manual => "manual",
+    Map => "map",
+    Multimedia => "multimedia",
+    Music => "music",
+    NewspaperArticle => "newspaper-article",
+    Pamphlet => "pamphlet",
+    Patent => "patent",
+    PersonalCommunication => "personal-communication",
+    Proceedings => "proceedings",
+    Report => "report",
+    Serial => "serial",
+    Slides => "slides",
+    Software => "software",
+    SoftwareCode => "software-code",
+    SoftwareContainer => "software-container",
+    SoftwareExecutable => "software-executable",
+    SoftwareVirtualMachine => "software-virtual-machine",
+    SoundRecording => "sound-recording",
+    Standard => "standard",
+    Statute => "statute",
+    Thesis => "thesis",
+    Unpublished => "unpublished",
+    Video => "video",
+    Website => "website",
+}
+
+impl fmt::Display for ReferenceType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for ReferenceType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for ReferenceType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+
+        Ok(ReferenceType::from(value.as_str()))
+    }
+}
@@ -0,0 +1,153 @@
+use serde_json::{json, Map, Value};
+
+use crate::{Issue, Severity, ValidationReport};
+
+/// The name of the file a report refers to, if its source has been attached.
+fn file(report: &ValidationReport) -> Option<&str> {
+    report.source.as_ref().map(|source| source.name.as_str())
+}
+
+fn issue(issue: &Issue) -> Value {
+    let mut object = Map::new();
+    object.insert("code".into(), issue.code.as_str().into());
+    object.insert("severity".into(), issue.severity.as_str().into());
+    object.insert("path".into(), issue.path.clone().into());
+    object.insert("message".into(), issue.message.clone().into());
+    object.insert("help".into(), issue.code.help().into());
+    if let Some(span) = issue.span {
+        object.insert("line".into(), span.line.into());
+        object.insert("column".into(), span.column.into());
+    }
+
+    Value::Object(object)
+}
+
+/// Maps a severity to a SARIF result level.
+fn level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Hint => "note",
+    }
+}
+
+/// Escapes the message of a GitHub Actions workflow command.
+fn escape_data(value: &str) -> String {
+    value.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+/// Escapes a property of a GitHub Actions workflow command, e.g. the file name.
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+impl ValidationReport {
+    /// Serializes the report to JSON, with the file name (if the source has been attached), whether the citation is
+    /// valid, the number of issues per severity and every issue with its code, path, message, help and location.
+    pub fn to_json(&self) -> String {
+        let count = |severity| self.with_severity(severity).count();
+
+        let report = json!({
+            "file": file(self),
+            "valid": self.is_valid(),
+            "errors": count(Severity::Error),
+            "warnings": count(Severity::Warning),
+            "hints": count(Severity::Hint),
+            "issues": self.iter().map(issue).collect::<Vec<_>>(),
+        });
+
+        serde_json::to_string_pretty(&report).unwrap_or_default()
+    }
+
+    /// Serializes the report to a [SARIF 2.1.0](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html) log,
+    /// e.g. for GitHub code scanning, see [`ValidationReport::to_sarif_combined`].
+    pub fn to_sarif(&self) -> String {
+        ValidationReport::to_sarif_combined([self])
+    }
+
+    /// Serializes the reports of several files to a single SARIF log with one run.
+    ///
+    /// Issue codes become rule ids and hints become notes. Results are located by the file name given to
+    /// [`ValidationReport::with_source`] (`CITATION.cff` if there is none) and the line and column of the issue.
+    pub fn to_sarif_combined<'a>(reports: impl IntoIterator<Item = &'a ValidationReport>) -> String {
+        let mut codes = Vec::new();
+        let mut results = Vec::new();
+
+        for report in reports {
+            for issue in report {
+                if !codes.contains(&issue.code) {
+                    codes.push(issue.code);
+                }
+
+                let mut location = json!({ "artifactLocation": { "uri": file(report).unwrap_or("CITATION.cff") } });
+                if let Some(span) = issue.span {
+                    location["region"] = json!({ "startLine": span.line, "startColumn": span.column, "charLength": span.length });
+                }
+
+                results.push(json!({
+                    "ruleId": issue.code.as_str(),
+                    "ruleIndex": codes.iter().position(|code| *code == issue.code),
+                    "level": level(issue.severity),
+                    "message": { "text": format!("{}: {}", issue.path, issue.message) },
+                    "locations": [{ "physicalLocation": location }],
+                }));
+            }
+        }
+
+        let rules: Vec<Value> = codes
+            .iter()
+            .map(|code| {
+                json!({
+                    "id": code.as_str(),
+                    "shortDescription": { "text": code.help() },
+                    "defaultConfiguration": { "level": level(code.severity()) },
+                })
+            })
+            .collect();
+
+        let log = json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "citation",
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                        "rules": rules,
+                    }
+                },
+                "results": results,
+            }],
+        });
+
+        serde_json::to_string_pretty(&log).unwrap_or_default()
+    }
+
+    /// Formats the report as [GitHub Actions workflow commands](https://docs.github.com/en/actions/using-workflows/workflow-commands-for-github-actions),
+    /// one per line, e.g. `::error file=CITATION.cff,line=2,col=8,title=empty-title::title: title must not be empty`.
+    ///
+    /// When printed in a workflow, the issues appear as annotations on the lines of the file in pull requests.
+    /// Hints become notices. The file name is the one given to [`ValidationReport::with_source`], or
+    /// `CITATION.cff` if there is none.
+    pub fn to_github_annotations(&self) -> String {
+        let file = escape_property(file(self).unwrap_or("CITATION.cff"));
+
+        self.iter()
+            .map(|issue| {
+                let command = match issue.severity {
+                    Severity::Error => "error",
+                    Severity::Warning => "warning",
+                    Severity::Hint => "notice",
+                };
+                let location = match issue.span {
+                    Some(span) => format!(",line={},col={}", span.line, span.column),
+                    None => String::new(),
+                };
+                let message = escape_data(&format!("{}: {}", issue.path, issue.message));
+
+                format!("::{command} file={file}{location},title={}::{message}\n", issue.code)
+            })
+            .collect()
+    }
+}
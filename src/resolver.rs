@@ -0,0 +1,77 @@
+use serde::de::Error as _;
+
+use crate::{http, Citation, Doi, Error, ParseError, Reference, ValidationReport};
+
+/// The default URL of the DOI resolver, which can be overridden with `DOI_RESOLVER_URL`
+/// (e.g. to use a mirror or a local test server).
+const RESOLVER_URL: &str = "https://doi.org";
+
+/// The media type of CSL-JSON, which both Crossref and DataCite serve through DOI content negotiation.
+///
+/// https://citation.doi.org/docs.html
+const CSL_JSON: &str = "application/vnd.citationstyles.csl+json";
+
+/// Returns the URL to request the metadata of a DOI from. The DOI may also be given as a `https://doi.org/` URL.
+fn url(doi: &str) -> Result<String, Error> {
+    let doi = ["https://doi.org/", "http://doi.org/", "https://dx.doi.org/", "http://dx.doi.org/", "doi:"]
+        .iter()
+        .find_map(|prefix| doi.strip_prefix(prefix))
+        .unwrap_or(doi);
+    let doi = Doi::new(doi).map_err(|error| Error::schema("doi", error))?;
+    let resolver = std::env::var("DOI_RESOLVER_URL").unwrap_or_else(|_| RESOLVER_URL.to_string());
+
+    Ok(format!("{}/{doi}", resolver.trim_end_matches('/')))
+}
+
+/// Parses the CSL-JSON metadata of a DOI into a reference.
+fn reference(content: &str) -> Result<Reference, ParseError> {
+    let references = Reference::from_csl_json(content).map_err(|error| ParseError::from_json(&error, None, content))?;
+
+    references.into_iter().next().ok_or_else(|| ParseError::from_json(&serde_json::Error::custom("the DOI has no metadata"), None, content))
+}
+
+impl Reference {
+    /// Fetches the metadata registered for a DOI from Crossref or DataCite and converts it to a reference,
+    /// e.g. to add a paper to `references` or as `preferred-citation`.
+    ///
+    /// The metadata is requested as CSL-JSON through [DOI content negotiation](https://citation.doi.org),
+    /// so it works for DOIs of both registration agencies. The DOI may also be given as a `https://doi.org/` URL.
+    pub fn from_doi(doi: &str) -> Result<Self, Error> {
+        let content = http::client()?.get(url(doi)?).header("Accept", CSL_JSON).send()?.error_for_status()?.text()?;
+
+        Ok(reference(&content)?)
+    }
+
+    /// Fetches the metadata registered for a DOI without blocking the executor, see [`Reference::from_doi`].
+    #[cfg(feature = "async")]
+    pub async fn from_doi_async(doi: &str) -> Result<Self, Error> {
+        let content = http::async_client()?.get(url(doi)?).header("Accept", CSL_JSON).send().await?.error_for_status()?.text().await?;
+
+        Ok(reference(&content)?)
+    }
+}
+
+impl Citation {
+    /// Fetches the metadata registered for a DOI from Crossref or DataCite and converts it to a citation,
+    /// see [`Reference::from_doi`].
+    ///
+    /// Fails if the registered metadata has no authors.
+    pub fn from_doi(doi: &str) -> Result<Self, Error> {
+        Ok(Reference::from_doi(doi)?.into_citation()?)
+    }
+
+    /// Fetches the metadata registered for a DOI without blocking the executor, see [`Citation::from_doi`].
+    #[cfg(feature = "async")]
+    pub async fn from_doi_async(doi: &str) -> Result<Self, Error> {
+        Ok(Reference::from_doi_async(doi).await?.into_citation()?)
+    }
+
+    /// Fetches the metadata registered for the DOIs of the citation, its preferred citation and its references,
+    /// and reports differences in title, authors and year as [`Code::DoiMismatch`](crate::Code::DoiMismatch) warnings.
+    ///
+    /// This catches citations that were not updated after the registered metadata changed, e.g. when a paper was
+    /// published under a different title than its preprint. Titles and names are compared ignoring case and punctuation.
+    pub fn check_doi_metadata(&self) -> Result<ValidationReport, Error> {
+        self.validate_registered(|doi| Reference::from_doi(doi.as_str()))
+    }
+}
@@ -0,0 +1,395 @@
+use std::{error::Error, fmt};
+
+use crate::{bibtex::pages, Author, Citation, Date, Entity, IntOrString, Person, Reference, ReferenceType, Type};
+
+/// The RIS `TY` tags of the reference types, in both directions.
+///
+/// Types with several candidates map to the first one when exporting.
+const TYPES: &[(ReferenceType, &str)] = &[
+    (ReferenceType::Art, "ART"),
+    (ReferenceType::Article, "JOUR"),
+    (ReferenceType::Audiovisual, "ADVS"),
+    (ReferenceType::Bill, "BILL"),
+    (ReferenceType::Blog, "BLOG"),
+    (ReferenceType::Book, "BOOK"),
+    (ReferenceType::Catalogue, "CTLG"),
+    (ReferenceType::Conference, "CONF"),
+    (ReferenceType::ConferencePaper, "CPAPER"),
+    (ReferenceType::Data, "DATA"),
+    (ReferenceType::Database, "DBASE"),
+    (ReferenceType::Dictionary, "DICT"),
+    (ReferenceType::EditedWork, "EDBOOK"),
+    (ReferenceType::Encyclopedia, "ENCYC"),
+    (ReferenceType::FilmBroadcast, "VIDEO"),
+    (ReferenceType::Generic, "GEN"),
+    (ReferenceType::GovernmentDocument, "GOVDOC"),
+    (ReferenceType::Grant, "GRANT"),
+    (ReferenceType::Hearing, "HEAR"),
+    (ReferenceType::LegalCase, "CASE"),
+    (ReferenceType::MagazineArticle, "MGZN"),
+    (ReferenceType::Map, "MAP"),
+    (ReferenceType::Multimedia, "MULTI"),
+    (ReferenceType::Music, "MUSIC"),
+    (ReferenceType::NewspaperArticle, "NEWS"),
+    (ReferenceType::Pamphlet, "PAMP"),
+    (ReferenceType::Patent, "PAT"),
+    (ReferenceType::PersonalCommunication, "PCOMM"),
+    (ReferenceType::Proceedings, "CONF"),
+    (ReferenceType::Report, "RPRT"),
+    (ReferenceType::Serial, "SER"),
+    (ReferenceType::Slides, "SLIDE"),
+    (ReferenceType::Software, "COMP"),
+    (ReferenceType::SoftwareCode, "COMP"),
+    (ReferenceType::SoftwareContainer, "COMP"),
+    (ReferenceType::SoftwareExecutable, "COMP"),
+    (ReferenceType::SoftwareVirtualMachine, "COMP"),
+    (ReferenceType::SoundRecording, "SOUND"),
+    (ReferenceType::Standard, "STAND"),
+    (ReferenceType::Statute, "STAT"),
+    (ReferenceType::Thesis, "THES"),
+    (ReferenceType::Unpublished, "UNPB"),
+    (ReferenceType::Video, "VIDEO"),
+    (ReferenceType::Website, "WEB"),
+    // tags that are only read
+    (ReferenceType::Article, "EJOUR"),
+    (ReferenceType::Article, "JFULL"),
+    (ReferenceType::Article, "INPR"),
+    (ReferenceType::Book, "CHAP"),
+    (ReferenceType::Book, "EBOOK"),
+    (ReferenceType::Book, "ECHAP"),
+    (ReferenceType::Website, "ELEC"),
+];
+
+fn ris_type(r#type: &ReferenceType) -> &'static str {
+    TYPES.iter().find(|(known, _)| known == r#type).map_or("GEN", |(_, tag)| tag)
+}
+
+/// A RIS record, written as `TAG  - value` lines.
+struct Record {
+    lines: Vec<(&'static str, String)>,
+}
+
+impl Record {
+    fn new(r#type: &'static str) -> Self {
+        Record { lines: vec![("TY", r#type.to_string())] }
+    }
+
+    fn field(&mut self, tag: &'static str, value: Option<&str>) {
+        if let Some(value) = value.map(str::trim).filter(|value| !value.is_empty()) {
+            // RIS values cannot span several lines
+            self.lines.push((tag, value.split_whitespace().collect::<Vec<_>>().join(" ")));
+        }
+    }
+
+    fn authors(&mut self, tag: &'static str, authors: &[Author]) {
+        for author in authors {
+            self.field(tag, Some(&name(author)));
+        }
+    }
+
+    fn date(&mut self, date: Option<Date>) {
+        if let Some(date) = date {
+            self.field("DA", Some(&format!("{:04}/{:02}/{:02}", date.year(), date.month(), date.day())));
+        }
+    }
+}
+
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (tag, value) in &self.lines {
+            writeln!(f, "{tag}  - {value}")?;
+        }
+        writeln!(f, "ER  - ")
+    }
+}
+
+/// Formats a name as `Last, First` or `Last, First, Suffix`, as expected in `AU` lines.
+pub(crate) fn name(author: &Author) -> String {
+    match author {
+        Author::Entity(entity) => entity.name.clone(),
+        Author::Person(person) => {
+            let last = [person.name_particle.as_deref(), person.family_names.as_deref()].into_iter().flatten().collect::<Vec<_>>().join(" ");
+            let parts: Vec<&str> = [Some(last.as_str()), person.given_names.as_deref(), person.name_suffix.as_deref()]
+                .into_iter()
+                .flatten()
+                .filter(|part| !part.is_empty())
+                .collect();
+
+            match parts.is_empty() {
+                true => author.display_name(),
+                false => parts.join(", "),
+            }
+        }
+    }
+}
+
+impl Citation {
+    /// Formats the citation as a RIS record.
+    ///
+    /// If the citation has a `preferred-citation`, that reference is formatted instead, see
+    /// [`Reference::to_ris`]. Otherwise a `COMP` (computer program) record is created, or `DATA` for datasets.
+    pub fn to_ris(&self) -> String {
+        if let Some(reference) = &self.preferred_citation {
+            return reference.to_ris();
+        }
+
+        let mut record = Record::new(match self.r#type {
+            Some(Type::Dataset) => "DATA",
+            _ => "COMP",
+        });
+        record.authors("AU", &self.authors);
+        record.field("TI", Some(&self.title));
+        record.field("PY", self.date_released.map(|date| date.year().to_string()).as_deref());
+        record.date(self.date_released);
+        record.field("ET", self.version.as_deref());
+        record.field("DO", self.find_doi().as_deref());
+        record.field("UR", self.repository_code.as_deref().or(self.url.as_deref()));
+        record.field("AB", self.r#abstract.as_deref());
+        for keyword in &self.keywords {
+            record.field("KW", Some(keyword));
+        }
+
+        record.to_string()
+    }
+
+    /// Creates a citation from the first `COMP` (computer program) or `DATA` record of a RIS file.
+    ///
+    /// Other records can be converted with [`Reference::from_ris`]. Fails if there is no such record
+    /// or if it has no authors or title.
+    pub fn from_ris(input: &str) -> Result<Citation, ParseRisError> {
+        let records = parse(input)?;
+        let record = records
+            .iter()
+            .find(|record| matches!(record.tag("TY"), Some("COMP" | "DATA")))
+            .ok_or_else(|| ParseRisError { line: 1, message: "no COMP or DATA record found".to_string() })?;
+
+        record
+            .to_reference()?
+            .into_citation()
+            .map_err(|_| ParseRisError { line: record.line, message: "the record has no authors".to_string() })
+    }
+}
+
+impl Reference {
+    /// Formats the reference as a RIS record, with the `TY` tag derived from the type of the reference.
+    pub fn to_ris(&self) -> String {
+        let mut record = Record::new(ris_type(&self.r#type));
+        let year = self.year.as_ref().map(ToString::to_string).or_else(|| self.date_published.map(|date| date.year().to_string()));
+
+        record.authors("AU", &self.authors);
+        record.authors("A2", &self.editors);
+        record.field("TI", Some(&self.title));
+        record.field("T2", self.journal.as_deref().or(self.collection_title.as_deref()));
+        record.field("PY", year.as_deref());
+        record.date(self.date_published);
+        record.field("VL", self.volume.as_deref());
+        record.field("IS", self.issue.as_deref().or(self.number.as_deref()));
+        match (&self.start, &self.end) {
+            (Some(_), _) => {
+                record.field("SP", self.start.as_deref());
+                record.field("EP", self.end.as_deref());
+            }
+            _ => record.field("SP", pages(self).as_deref()),
+        }
+        record.field("ET", self.edition.as_deref().or(self.version.as_deref()));
+        record.field("PB", self.publisher.as_ref().map(|publisher| publisher.name.as_str()));
+        record.field("CY", self.location.as_ref().map(|location| location.name.as_str()).or(self.publisher.as_ref().and_then(|publisher| publisher.city.as_deref())));
+        record.field("SN", self.isbn.as_deref().or(self.issn.as_deref()));
+        record.field("M3", self.thesis_type.as_deref());
+        record.field("DO", self.doi.as_ref().map(|doi| doi.as_str()));
+        record.field("UR", self.url.as_deref().or(self.repository_code.as_deref()));
+        record.field("AB", self.r#abstract.as_deref());
+        for keyword in &self.keywords {
+            record.field("KW", Some(keyword));
+        }
+        record.field("N1", self.notes.as_deref());
+
+        record.to_string()
+    }
+
+    /// Parses all records of a RIS file into references.
+    ///
+    /// `TY` tags are mapped to the closest reference type, unknown tags become `generic`.
+    /// Names in `AU` lines are expected in the `Last, First` format.
+    pub fn from_ris(input: &str) -> Result<Vec<Reference>, ParseRisError> {
+        parse(input)?.iter().map(RawRecord::to_reference).collect()
+    }
+}
+
+/// Error returned when RIS input cannot be parsed or converted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseRisError {
+    /// The line of the problem, starting at 1.
+    pub line: usize,
+
+    /// A description of the problem.
+    pub message: String,
+}
+
+impl fmt::Display for ParseRisError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at line {}", self.message, self.line)
+    }
+}
+
+impl Error for ParseRisError {}
+
+/// A record as written in the input.
+struct RawRecord {
+    line: usize,
+    fields: Vec<(String, String)>,
+}
+
+impl RawRecord {
+    fn tag(&self, tag: &str) -> Option<&str> {
+        self.fields.iter().find(|(name, _)| name == tag).map(|(_, value)| value.as_str())
+    }
+
+    fn tags<'a>(&'a self, tags: &'a [&str]) -> impl Iterator<Item = &'a str> {
+        self.fields.iter().filter(|(name, _)| tags.contains(&name.as_str())).map(|(_, value)| value.as_str())
+    }
+
+    fn first(&self, tags: &[&str]) -> Option<String> {
+        self.tags(tags).next().map(String::from)
+    }
+
+    fn to_reference(&self) -> Result<Reference, ParseRisError> {
+        let error = |message: String| ParseRisError { line: self.line, message };
+
+        let tag = self.tag("TY").unwrap_or("GEN");
+        let r#type = TYPES.iter().find(|(_, known)| *known == tag).map_or(ReferenceType::Generic, |(r#type, _)| r#type.clone());
+        let secondary = self.first(&["T2", "JO", "JF", "JA", "BT", "T3"]);
+        let (journal, collection_title) = match r#type {
+            ReferenceType::Article | ReferenceType::MagazineArticle | ReferenceType::NewspaperArticle => (secondary, None),
+            _ => (None, secondary),
+        };
+
+        let mut reference = Reference {
+            r#type,
+            title: self.first(&["TI", "T1", "CT"]).ok_or_else(|| error("the record has no title".to_string()))?,
+            authors: self.tags(&["AU", "A1"]).map(parse_name).collect(),
+            editors: self.tags(&["A2", "ED"]).map(parse_name).collect(),
+            journal,
+            collection_title,
+            volume: self.first(&["VL"]).map(IntOrString::from),
+            issue: self.first(&["IS"]).map(IntOrString::from),
+            start: self.first(&["SP"]).map(IntOrString::from),
+            end: self.first(&["EP"]).map(IntOrString::from),
+            publisher: self.first(&["PB"]).map(|name| Entity { name, city: self.first(&["CY"]), ..Default::default() }),
+            url: self.first(&["UR", "L2"]),
+            r#abstract: self.first(&["AB", "N2"]),
+            keywords: self.tags(&["KW"]).map(String::from).collect(),
+            notes: self.first(&["N1"]),
+            thesis_type: self.first(&["M3"]).filter(|_| tag == "THES"),
+            ..Default::default()
+        };
+
+        match tag {
+            "COMP" => reference.version = self.first(&["ET", "VO"]),
+            _ => reference.edition = self.first(&["ET"]),
+        }
+
+        if let Some(number) = self.first(&["SN"]) {
+            let digits = number.chars().filter(|c| c.is_ascii_alphanumeric()).count();
+            match digits {
+                8 => reference.issn = Some(number),
+                _ => reference.isbn = Some(number),
+            }
+        }
+
+        // PY and Y1 are written as `YYYY` or `YYYY/MM/DD/other`, DA as `YYYY/MM/DD`
+        let dates: Vec<&str> = self.tags(&["DA", "PY", "Y1"]).collect();
+        for date in &dates {
+            let mut parts = date.split('/').map(str::trim);
+            let (year, month, day) = (parts.next(), parts.next(), parts.next());
+
+            let numbers = (year.and_then(|year| year.parse().ok()), month.and_then(|month| month.parse().ok()), day.and_then(|day| day.parse().ok()));
+            if let (Some(year), Some(month), Some(day)) = numbers {
+                reference.date_published = reference.date_published.or(Date::new(year, month, day).ok());
+            }
+            if reference.year.is_none() {
+                reference.year = year.filter(|year| !year.is_empty()).map(IntOrString::from);
+            }
+            if let Some(month) = numbers.1.filter(|month: &u8| (1..=12).contains(month)) {
+                reference.month.get_or_insert(month.into());
+            }
+        }
+
+        if let Some(doi) = self.first(&["DO"]) {
+            let value = ["https://doi.org/", "http://doi.org/", "https://dx.doi.org/", "doi:"]
+                .iter()
+                .find_map(|prefix| doi.strip_prefix(prefix))
+                .unwrap_or(&doi);
+            reference.doi = Some(value.parse().map_err(|_| error(format!("invalid DOI `{doi}`")))?);
+        }
+
+        Ok(reference)
+    }
+}
+
+/// Parses a name in the `Last, First` or `Last, First, Suffix` format, e.g. `van Beethoven, Ludwig`.
+///
+/// Names without a comma are taken as the name of an entity.
+fn parse_name(name: &str) -> Author {
+    let mut parts = name.split(',').map(str::trim);
+    let last = parts.next().unwrap_or_default();
+    let (Some(first), suffix) = (parts.next(), parts.next()) else {
+        return Author::Entity(Entity { name: name.trim().to_string(), ..Default::default() });
+    };
+
+    // leading lowercase words of the last name are a name particle, e.g. `van`
+    let words: Vec<&str> = last.split_whitespace().collect();
+    let particle = words.iter().take(words.len().saturating_sub(1)).take_while(|word| word.starts_with(char::is_lowercase)).count();
+    let part = |text: String| Some(text).filter(|text| !text.is_empty());
+
+    Author::Person(Person {
+        given_names: part(first.to_string()),
+        name_particle: part(words[..particle].join(" ")),
+        family_names: part(words[particle..].join(" ")),
+        name_suffix: suffix.map(String::from).and_then(part),
+        ..Default::default()
+    })
+}
+
+/// Splits the input into records, each starting with `TY` and ending with `ER`.
+fn parse(input: &str) -> Result<Vec<RawRecord>, ParseRisError> {
+    let mut records = Vec::new();
+    let mut current: Option<RawRecord> = None;
+
+    for (index, line) in input.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim_start_matches('\u{feff}').trim_end();
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let tagged = line
+            .get(..2)
+            .filter(|tag| tag.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit()))
+            .and_then(|tag| line[2..].trim_start().strip_prefix('-').map(|value| (tag, value.trim())));
+
+        match (tagged, &mut current) {
+            (Some(("TY", value)), None) => {
+                current = Some(RawRecord { line: line_number, fields: vec![("TY".to_string(), value.to_string())] });
+            }
+            (Some(("TY", _)), Some(_)) => {
+                return Err(ParseRisError { line: line_number, message: "the previous record is not terminated with ER".to_string() });
+            }
+            (Some(("ER", _)), Some(_)) => records.extend(current.take()),
+            (Some((tag, value)), Some(record)) => record.fields.push((tag.to_string(), value.to_string())),
+            // lines without a tag continue the previous value
+            (None, Some(record)) => {
+                if let Some((_, value)) = record.fields.last_mut() {
+                    value.push(' ');
+                    value.push_str(line.trim());
+                }
+            }
+            (_, None) => return Err(ParseRisError { line: line_number, message: "expected a record starting with TY".to_string() }),
+        }
+    }
+
+    match current {
+        Some(record) => Err(ParseRisError { line: record.line, message: "the record is not terminated with ER".to_string() }),
+        None => Ok(records),
+    }
+}
@@ -0,0 +1,117 @@
+use std::{io, path::{Path, PathBuf}};
+
+use ignore::WalkBuilder;
+
+use crate::{parse::parse_file, Citation, Error, ParseError, ParseOptions};
+
+/// Options for [`Citation::scan`].
+#[derive(Debug, Clone, Default)]
+pub struct ScanOptions {
+    /// Also reads `.zenodo.json` files, see [`Citation::from_zenodo_json`].
+    pub zenodo: bool,
+
+    /// Also reads `codemeta.json` files, see [`Citation::from_codemeta`].
+    pub codemeta: bool,
+
+    /// How `CITATION.cff` files are parsed.
+    pub parse: ParseOptions,
+}
+
+/// The result of [`Citation::scan`].
+#[derive(Debug, Default)]
+pub struct Scan {
+    /// The citations that have been read, with the path of their file.
+    pub citations: Vec<(PathBuf, Citation)>,
+
+    /// The files that could not be read or parsed, and the directories that could not be listed.
+    pub errors: Vec<(PathBuf, Error)>,
+}
+
+/// The kinds of files a scan reads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Cff,
+    Zenodo,
+    CodeMeta,
+}
+
+impl Kind {
+    fn of(name: &str, options: &ScanOptions) -> Option<Self> {
+        match name {
+            "CITATION.cff" => Some(Kind::Cff),
+            ".zenodo.json" if options.zenodo => Some(Kind::Zenodo),
+            "codemeta.json" if options.codemeta => Some(Kind::CodeMeta),
+            _ => None,
+        }
+    }
+}
+
+/// Reads and parses a file found by a scan.
+fn read(path: &Path, kind: Kind, options: &ScanOptions) -> Result<Citation, Error> {
+    let content = std::fs::read_to_string(path)?;
+    let converted = match kind {
+        Kind::Cff => return Ok(parse_file(path, &content, &options.parse)?),
+        Kind::Zenodo => Citation::from_zenodo_json(&content),
+        Kind::CodeMeta => Citation::from_codemeta(&content),
+    };
+
+    Ok(converted.map_err(|error| ParseError::from_json(&error, None, &content).with_file_name(path.display().to_string()))?)
+}
+
+/// Turns an error of the walker into an I/O error, with the path it occurred at if known.
+fn walk_error(error: ignore::Error, root: &Path) -> (PathBuf, Error) {
+    let path = match &error {
+        ignore::Error::WithPath { path, .. } => path.clone(),
+        ignore::Error::WithDepth { err, .. } | ignore::Error::WithLineNumber { err, .. } => match err.as_ref() {
+            ignore::Error::WithPath { path, .. } => path.clone(),
+            _ => root.to_path_buf(),
+        },
+        _ => root.to_path_buf(),
+    };
+    let error = match error.into_io_error() {
+        Some(error) => error,
+        None => io::Error::other("the directory cannot be listed"),
+    };
+
+    (path, Error::Io(error))
+}
+
+impl Citation {
+    /// Finds and reads every `CITATION.cff` file under the given directory, and `.zenodo.json` and `codemeta.json`
+    /// files if enabled in the options, e.g. to collect the citations of all packages in a monorepo.
+    ///
+    /// Files and directories excluded by `.gitignore`, `.ignore` and `.git/info/exclude` files are skipped, as is
+    /// the `.git` directory itself. Files are visited in order of their path, and files that cannot be read or
+    /// parsed are reported in [`Scan::errors`] instead of ending the scan.
+    pub fn scan(root: PathBuf, options: &ScanOptions) -> Scan {
+        let walker = WalkBuilder::new(&root)
+            .hidden(false)
+            .require_git(false)
+            .filter_entry(|entry| entry.file_name() != ".git")
+            .sort_by_file_name(|a, b| a.cmp(b))
+            .build();
+
+        let mut scan = Scan::default();
+        for entry in walker {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(error) => {
+                    scan.errors.push(walk_error(error, &root));
+                    continue;
+                }
+            };
+            let kind = match entry.file_name().to_str().and_then(|name| Kind::of(name, options)) {
+                Some(kind) if entry.file_type().is_some_and(|file_type| file_type.is_file()) => kind,
+                _ => continue,
+            };
+
+            let path = entry.into_path();
+            match read(&path, kind, options) {
+                Ok(citation) => scan.citations.push((path, citation)),
+                Err(error) => scan.errors.push((path, error)),
+            }
+        }
+
+        scan
+    }
+}
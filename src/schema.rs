@@ -0,0 +1,115 @@
+//! The keys defined by the schema for each kind of object.
+
+/// The keys of the top-level object.
+pub(crate) const CITATION_KEYS: &[&str] = &[
+    "abstract", "authors", "cff-version", "commit", "contact", "date-released", "doi",
+    "identifiers", "keywords", "license", "license-url", "message", "preferred-citation",
+    "references", "repository", "repository-artifact", "repository-code", "title", "type", "url",
+    "version",
+];
+
+/// The keys of `definitions.person`.
+pub(crate) const PERSON_KEYS: &[&str] = &[
+    "address", "affiliation", "alias", "city", "country", "email", "family-names", "fax",
+    "given-names", "name-particle", "name-suffix", "orcid", "post-code", "region", "tel", "website",
+];
+
+/// The keys of `definitions.entity`.
+pub(crate) const ENTITY_KEYS: &[&str] = &[
+    "address", "alias", "city", "country", "date-end", "date-start", "email", "fax", "location",
+    "name", "orcid", "post-code", "region", "tel", "website",
+];
+
+/// The keys of `definitions.identifier`.
+pub(crate) const IDENTIFIER_KEYS: &[&str] = &["description", "type", "value"];
+
+/// The keys of `definitions.reference`.
+pub(crate) const REFERENCE_KEYS: &[&str] = &[
+    "abbreviation", "abstract", "authors", "collection-doi", "collection-title", "collection-type",
+    "commit", "conference", "contact", "copyright", "data-type", "database", "database-provider",
+    "date-accessed", "date-downloaded", "date-published", "date-released", "department", "doi",
+    "edition", "editors", "editors-series", "end", "entry", "filename", "format", "identifiers",
+    "institution", "isbn", "issn", "issue", "issue-date", "issue-title", "journal", "keywords",
+    "languages", "license", "license-url", "loc-end", "loc-start", "location", "medium", "month",
+    "nihmsid", "notes", "number", "number-volumes", "pages", "patent-states", "pmcid", "publisher",
+    "recipients", "repository", "repository-artifact", "repository-code", "scope", "section",
+    "senders", "start", "status", "term", "thesis-type", "title", "translators", "type", "url",
+    "version", "volume", "volume-title", "year", "year-original",
+];
+
+/// The kind of object a mapping in a `CITATION.cff` file describes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Object {
+    Citation,
+    Person,
+    Entity,
+    Identifier,
+    Reference,
+}
+
+impl Object {
+    pub(crate) fn keys(&self) -> &'static [&'static str] {
+        match self {
+            Object::Citation => CITATION_KEYS,
+            Object::Person => PERSON_KEYS,
+            Object::Entity => ENTITY_KEYS,
+            Object::Identifier => IDENTIFIER_KEYS,
+            Object::Reference => REFERENCE_KEYS,
+        }
+    }
+
+    /// Returns the kind of the objects stored under the given key of this object, if the
+    /// key holds objects (or lists of objects) at all.
+    pub(crate) fn child(&self, key: &str) -> Option<Child> {
+        match (self, key) {
+            (Object::Citation | Object::Reference, "authors" | "contact") => Some(Child::Agent),
+            (Object::Citation | Object::Reference, "identifiers") => Some(Child::Object(Object::Identifier)),
+            (Object::Citation, "preferred-citation" | "references") => Some(Child::Object(Object::Reference)),
+            (Object::Reference, "editors" | "editors-series" | "recipients" | "senders" | "translators") => Some(Child::Agent),
+            (Object::Reference, "conference" | "database-provider" | "institution" | "location" | "publisher") => {
+                Some(Child::Object(Object::Entity))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The kind of the objects stored under a key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Child {
+    Object(Object),
+
+    /// A person or an entity, depending on whether the mapping has a `name` key.
+    Agent,
+}
+
+/// Returns the key that is most likely meant by a misspelled key, e.g. `family-names` for `familiy-names`.
+///
+/// Keys are compared by their edit distance, allowing up to two edits for longer keys.
+pub(crate) fn closest_key(key: &str, keys: &[&'static str]) -> Option<&'static str> {
+    let limit = if key.len() > 6 { 2 } else { 1 };
+
+    keys.iter()
+        .map(|candidate| (distance(key, candidate), *candidate))
+        .filter(|(distance, _)| *distance <= limit)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Computes the Levenshtein distance between two keys, ignoring case.
+fn distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.to_lowercase().chars().collect();
+    let b: Vec<char> = b.to_lowercase().chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
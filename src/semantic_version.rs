@@ -0,0 +1,35 @@
+use std::cmp::Ordering;
+
+use semver::Version;
+
+use crate::Citation;
+
+/// Parses a version as a semantic version, allowing a leading `v` as used by many git tags, e.g. `v1.2.0`.
+fn parse(version: &str) -> Option<Version> {
+    let version = version.trim();
+
+    Version::parse(version.strip_prefix(['v', 'V']).unwrap_or(version)).ok()
+}
+
+impl Citation {
+    /// Parses `version` as a [semantic version](https://semver.org), e.g. `1.2.0` or `v2.0.0-beta.1`.
+    ///
+    /// Returns `None` if there is no version or it is not a semantic version, e.g. `2024.05` or `1.0`.
+    pub fn semver(&self) -> Option<Version> {
+        parse(self.version.as_deref()?)
+    }
+
+    /// Compares the version of the citation with the given semantic version, returning `None` if the
+    /// version of the citation is not a semantic version.
+    pub fn compare_version(&self, version: &Version) -> Option<Ordering> {
+        Some(self.semver()?.cmp(version))
+    }
+
+    /// Returns `true` if the version of the citation is older than the version of the other citation, e.g. of one
+    /// created with [`Citation::from_cargo_manifest`] to check whether `CITATION.cff` is behind `Cargo.toml`.
+    ///
+    /// Returns `None` if either version is missing or not a semantic version.
+    pub fn is_behind(&self, other: &Citation) -> Option<bool> {
+        Some(self.compare_version(&other.semver()?)? == Ordering::Less)
+    }
+}
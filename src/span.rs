@@ -0,0 +1,373 @@
+/// A region in the source of a `CITATION.cff` file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Span {
+    /// The byte offset of the region.
+    pub offset: usize,
+
+    /// The length of the region in bytes.
+    pub length: usize,
+
+    /// The line of the region, starting at 1.
+    pub line: usize,
+
+    /// The column of the region, starting at 1.
+    pub column: usize,
+}
+
+enum Segment<'a> {
+    Key(&'a str),
+    Index(usize),
+}
+
+/// Splits a key path such as `references[0].authors[1].orcid` into its segments.
+fn segments(path: &str) -> Vec<Segment<'_>> {
+    let mut segments = Vec::new();
+
+    for part in path.split('.').filter(|part| !part.is_empty()) {
+        let (key, mut indices) = part.split_once('[').map_or((part, ""), |(key, rest)| (key, rest));
+        if !key.is_empty() {
+            segments.push(Segment::Key(key));
+        }
+
+        while let Some((index, rest)) = indices.split_once(']') {
+            if let Ok(index) = index.parse() {
+                segments.push(Segment::Index(index));
+            }
+            indices = rest.strip_prefix('[').unwrap_or(rest);
+        }
+    }
+
+    segments
+}
+
+struct Line<'a> {
+    offset: usize,
+    text: &'a str,
+}
+
+impl Line<'_> {
+    fn indent(&self) -> usize {
+        self.text.len() - self.text.trim_start_matches(' ').len()
+    }
+
+    /// Returns `true` for lines without content, i.e. empty lines, comments and document markers.
+    fn is_blank(&self) -> bool {
+        self.is_empty() || self.is_comment()
+    }
+
+    fn is_comment(&self) -> bool {
+        self.text.trim_start().starts_with('#')
+    }
+
+    /// Returns `true` for empty lines and document markers.
+    fn is_empty(&self) -> bool {
+        let trimmed = self.text.trim();
+        trimmed.is_empty() || trimmed == "---"
+    }
+}
+
+/// The start of a block node, i.e. a mapping or a sequence.
+#[derive(Clone, Copy)]
+struct Node {
+    line: usize,
+    column: usize,
+}
+
+/// Locates the value of the key at the given path in block-style YAML.
+///
+/// If the full path cannot be resolved (e.g. because the key is missing or a
+/// flow-style collection is used), the region of the deepest resolvable key is returned.
+pub(crate) fn locate(source: &str, path: &str) -> Option<Span> {
+    let lines = lines(source);
+    let locator = Locator { lines: &lines };
+    let mut node = locator.root();
+    let mut found = None;
+
+    for segment in segments(path) {
+        let Some(current) = node else { break };
+
+        match locator.find(current, &segment) {
+            Some(entry) => {
+                found = Some(entry.span);
+                node = entry.node;
+            }
+            None => break,
+        }
+    }
+
+    found
+}
+
+/// The location of a mapping entry or sequence item, including its nested block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Entry {
+    /// The byte offset of the first line of the entry.
+    pub(crate) start: usize,
+
+    /// The byte offset of the first comment line directly above the entry, or `start` if there is none.
+    pub(crate) leading: usize,
+
+    /// The span of the comment at the end of the first line of the entry, including the `#`.
+    pub(crate) comment: Option<Span>,
+
+    /// The byte offset right after the colon of the key or the dash of the item.
+    pub(crate) value: usize,
+
+    /// The byte offset right after the last line of the entry, including its line break.
+    pub(crate) end: usize,
+
+    /// The column of the key or dash, starting at 0.
+    pub(crate) indent: usize,
+
+    /// The span of the value if it is written on the same line and the entry spans a single line.
+    pub(crate) inline: Option<Span>,
+}
+
+/// Resolves the entry at the given path in block-style YAML.
+///
+/// Unlike [`locate`], this only succeeds if the full path can be resolved.
+pub(crate) fn entry(source: &str, path: &str) -> Option<Entry> {
+    let lines = lines(source);
+    let locator = Locator { lines: &lines };
+    let mut node = locator.root();
+    let mut found = None;
+
+    for segment in segments(path) {
+        let found = found.insert(locator.find(node?, &segment)?);
+        node = found.node;
+    }
+
+    let found = found?;
+    let line = &lines[found.line];
+    let last = locator.last_line(&found);
+    let end = lines.get(last + 1).map_or(source.len(), |line| line.offset);
+
+    let first = (0..found.line)
+        .rev()
+        .take_while(|&index| lines[index].is_comment() && lines[index].indent() == found.column)
+        .last()
+        .unwrap_or(found.line);
+
+    let comment = line.text[found.value_column..].find(" #").map(|position| {
+        let column = found.value_column + position + 1;
+        locator.span(found.line, column, line.text.len() - column)
+    });
+
+    Some(Entry {
+        start: line.offset,
+        leading: lines[first].offset,
+        comment,
+        value: line.offset + found.value_column,
+        end,
+        indent: found.column,
+        inline: found.value.filter(|_| last == found.line),
+    })
+}
+
+/// Returns the byte offset at which a new entry can be appended to the mapping at the
+/// given path, together with the indentation of its entries.
+///
+/// Returns `None` if the path does not resolve to a non-empty block mapping.
+pub(crate) fn mapping_end(source: &str, path: &str) -> Option<(usize, usize)> {
+    let lines = lines(source);
+    let locator = Locator { lines: &lines };
+    let mut node = locator.root();
+
+    for segment in segments(path) {
+        node = locator.find(node?, &segment)?.node;
+    }
+
+    let node = node?;
+    let text = lines[node.line].text.get(node.column..)?;
+    if text.starts_with('-') || split_key(text).is_none() {
+        return None;
+    }
+
+    // comments indented like the entries of the mapping are kept with them
+    let last = (node.line + 1..lines.len())
+        .filter(|&index| !lines[index].is_empty())
+        .take_while(|&index| lines[index].indent() >= node.column)
+        .last()
+        .unwrap_or(node.line);
+
+    let end = lines.get(last + 1).map_or(source.len(), |line| line.offset);
+    Some((end, node.column))
+}
+
+fn lines(source: &str) -> Vec<Line<'_>> {
+    let mut offset = 0;
+
+    source
+        .split_inclusive('\n')
+        .map(|text| {
+            let line = Line { offset, text: text.trim_end_matches(['\n', '\r']) };
+            offset += text.len();
+            line
+        })
+        .collect()
+}
+
+/// A resolved mapping entry or sequence item.
+struct Found {
+    line: usize,
+    column: usize,
+    value_column: usize,
+
+    /// The span of the inline value, or `None` for a nested block value.
+    value: Option<Span>,
+
+    /// The span of the inline value, or of the key (or dash) for a nested block value.
+    span: Span,
+
+    /// The start of the nested node, if any.
+    node: Option<Node>,
+}
+
+struct Locator<'a> {
+    lines: &'a [Line<'a>],
+}
+
+impl Locator<'_> {
+    fn root(&self) -> Option<Node> {
+        let first = self.lines.iter().position(|line| !line.is_blank())?;
+
+        Some(Node { line: first, column: self.lines[first].indent() })
+    }
+
+    fn find(&self, node: Node, segment: &Segment) -> Option<Found> {
+        match segment {
+            Segment::Key(key) => self.key(node, key),
+            Segment::Index(index) => self.item(node, *index),
+        }
+    }
+
+    /// Returns the last line of the entry, i.e. the last line of its value or of a comment nested in it.
+    fn last_line(&self, found: &Found) -> usize {
+        let item = self.lines[found.line].text[found.column..].starts_with('-');
+
+        (found.line + 1..self.lines.len())
+            .filter(|&index| !self.lines[index].is_empty())
+            .take_while(|&index| {
+                let line = &self.lines[index];
+                let indent = line.indent();
+                // block sequences may be written at the same indentation as their key
+                indent > found.column
+                    || (!item && found.value.is_none() && indent == found.column && line.text[indent..].starts_with('-'))
+            })
+            .last()
+            .unwrap_or(found.line)
+    }
+
+    fn span(&self, line: usize, column: usize, length: usize) -> Span {
+        Span { offset: self.lines[line].offset + column, length, line: line + 1, column: column + 1 }
+    }
+
+    /// Returns the lines that may contain entries of the node, i.e. all lines up to the
+    /// first line that is indented less than the node.
+    fn entries(&self, node: Node) -> impl Iterator<Item = usize> + '_ {
+        std::iter::once(node.line).chain(
+            (node.line + 1..self.lines.len())
+                .filter(|&index| !self.lines[index].is_blank())
+                .take_while(move |&index| self.lines[index].indent() >= node.column)
+                .filter(move |&index| self.lines[index].indent() == node.column),
+        )
+    }
+
+    /// Returns the span of an inline value, or the start of a nested block value.
+    fn value(&self, line: usize, column: usize, parent: usize) -> (Option<Span>, Option<Node>) {
+        let text = &self.lines[line].text[column..];
+        let value = text.split(" #").next().unwrap_or_default().trim_end();
+        let leading = value.len() - value.trim_start().len();
+
+        if !value.trim_start().is_empty() {
+            let span = self.span(line, column + leading, value.len() - leading);
+            return (Some(span), Some(Node { line, column: column + leading }));
+        }
+
+        let next = (line + 1..self.lines.len()).find(|&index| !self.lines[index].is_blank());
+        let node = next
+            .filter(|&index| {
+                let indent = self.lines[index].indent();
+                indent > parent || (indent == parent && self.lines[index].text[indent..].starts_with('-'))
+            })
+            .map(|index| Node { line: index, column: self.lines[index].indent() });
+
+        (None, node)
+    }
+
+    fn key(&self, node: Node, key: &str) -> Option<Found> {
+        for index in self.entries(node) {
+            let text = self.lines[index].text.get(node.column..)?;
+            if text.starts_with('-') {
+                continue;
+            }
+
+            let Some((name, rest)) = split_key(text) else { continue };
+            if name != key {
+                continue;
+            }
+
+            let key_span = self.span(index, node.column, text.len() - text.trim_start_matches(|c| c != ':').len());
+            let value_column = node.column + text.len() - rest.len();
+            let (value, next) = self.value(index, value_column, node.column);
+
+            return Some(Found {
+                line: index,
+                column: node.column,
+                value_column,
+                value,
+                span: value.unwrap_or(key_span),
+                node: next,
+            });
+        }
+
+        None
+    }
+
+    fn item(&self, node: Node, position: usize) -> Option<Found> {
+        let index = self
+            .entries(node)
+            .filter(|&index| self.lines[index].text.get(node.column..).is_some_and(|text| text == "-" || text.starts_with("- ")))
+            .nth(position)?;
+
+        let (value, next) = self.value(index, node.column + 1, node.column);
+
+        Some(Found {
+            line: index,
+            column: node.column,
+            value_column: node.column + 1,
+            value,
+            span: value.unwrap_or_else(|| self.span(index, node.column, 1)),
+            node: next,
+        })
+    }
+}
+
+/// Splits a mapping entry into its (unquoted) key and the remainder after the colon.
+fn split_key(text: &str) -> Option<(&str, &str)> {
+    for quote in ['\'', '"'] {
+        if let Some(quoted) = text.strip_prefix(quote) {
+            let (key, rest) = quoted.split_once(quote)?;
+            return Some((key, rest.trim_start().strip_prefix(':')?));
+        }
+    }
+
+    let position = text.match_indices(':').map(|(position, _)| position).find(|&position| {
+        text[position + 1..].is_empty() || text[position + 1..].starts_with(' ')
+    })?;
+
+    Some((text[..position].trim_end(), &text[position + 1..]))
+}
+
+/// The name and content of a parsed file, kept to render diagnostics.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Source {
+    pub(crate) name: String,
+    pub(crate) content: String,
+}
+
+impl Source {
+    pub(crate) fn new(name: impl Into<String>, content: impl Into<String>) -> Self {
+        Source { name: name.into(), content: content.into() }
+    }
+}
@@ -0,0 +1,882 @@
+use std::fmt;
+
+use crate::{Author, Citation, Date, Person, Reference, ReferenceType, Type};
+
+/// A citation style for [`Citation::format`] and [`Reference::format`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Style {
+    /// APA, 7th edition, following its guidance for software and datasets.
+    Apa,
+
+    /// IEEE, with initials before family names, quoted titles and `[Online]. Available:` links.
+    /// The number of the entry in the reference list is not included.
+    Ieee,
+
+    /// Chicago Manual of Style, 17th edition, author-date system.
+    Chicago,
+
+    /// MLA, 9th edition.
+    Mla,
+
+    /// Harvard, following the Cite Them Right conventions.
+    Harvard,
+
+    /// Vancouver, as used in medicine and the life sciences.
+    Vancouver,
+}
+
+/// The markup of a rendered citation.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OutputFormat {
+    /// Plain text without any markup.
+    #[default]
+    Plain,
+
+    /// Markdown, with titles in italics and links as `<url>` or `[text](url)`.
+    Markdown,
+
+    /// HTML, with titles in `<cite>` elements and links as `<a>` elements.
+    Html,
+
+    /// LaTeX, with titles in `\emph` and links in `\url` or `\href`.
+    Latex,
+}
+
+impl OutputFormat {
+    /// Converts text with the markers of [`title`], [`emphasis`] and [`link`] to this format,
+    /// escaping all other text.
+    pub(crate) fn render(self, marked: &str) -> String {
+        let mut output = String::new();
+        let mut chars = marked.chars();
+
+        while let Some(c) = chars.next() {
+            match c {
+                TITLE_START => output.push_str(self.pick("*", "<cite>", "\\emph{")),
+                TITLE_END => output.push_str(self.pick("*", "</cite>", "}")),
+                EMPHASIS_START => output.push_str(self.pick("*", "<i>", "\\emph{")),
+                EMPHASIS_END => output.push_str(self.pick("*", "</i>", "}")),
+                LINK_START => {
+                    let target: String = chars.by_ref().take_while(|&c| c != LINK_TEXT).collect();
+                    let text: String = chars.by_ref().take_while(|&c| c != LINK_END).collect();
+                    let escaped = self.escape(&text);
+
+                    output.push_str(&match self {
+                        OutputFormat::Plain => text,
+                        OutputFormat::Markdown if text == target => format!("<{target}>"),
+                        OutputFormat::Markdown => format!("[{escaped}]({target})"),
+                        OutputFormat::Html => format!("<a href=\"{}\">{escaped}</a>", self.escape(&target)),
+                        OutputFormat::Latex if text == target => format!("\\url{{{}}}", latex_target(&target)),
+                        OutputFormat::Latex => format!("\\href{{{}}}{{{escaped}}}", latex_target(&target)),
+                    });
+                }
+                c => output.push_str(&self.escape(c.encode_utf8(&mut [0; 4]))),
+            }
+        }
+
+        output
+    }
+
+    /// Returns the markup for Markdown, HTML or LaTeX, and nothing for plain text.
+    fn pick(self, markdown: &'static str, html: &'static str, latex: &'static str) -> &'static str {
+        match self {
+            OutputFormat::Plain => "",
+            OutputFormat::Markdown => markdown,
+            OutputFormat::Html => html,
+            OutputFormat::Latex => latex,
+        }
+    }
+
+    /// Escapes the characters with a special meaning in this format.
+    fn escape(self, text: &str) -> String {
+        let mut escaped = String::with_capacity(text.len());
+        for c in text.chars() {
+            match (self, c) {
+                (OutputFormat::Markdown, '*' | '_' | '`' | '\\' | '[' | ']' | '<') => {
+                    escaped.push('\\');
+                    escaped.push(c);
+                }
+                (OutputFormat::Html, '&') => escaped.push_str("&amp;"),
+                (OutputFormat::Html, '<') => escaped.push_str("&lt;"),
+                (OutputFormat::Html, '>') => escaped.push_str("&gt;"),
+                (OutputFormat::Html, '"') => escaped.push_str("&quot;"),
+                (OutputFormat::Latex, '&' | '%' | '$' | '#' | '_' | '{' | '}') => {
+                    escaped.push('\\');
+                    escaped.push(c);
+                }
+                (OutputFormat::Latex, '\\') => escaped.push_str("\\textbackslash{}"),
+                (OutputFormat::Latex, '~') => escaped.push_str("\\textasciitilde{}"),
+                (OutputFormat::Latex, '^') => escaped.push_str("\\textasciicircum{}"),
+                _ => escaped.push(c),
+            }
+        }
+
+        escaped
+    }
+}
+
+/// Escapes a link target for `\href` and `\url`, which accept `%` and `#` escaped like in text. Braces and
+/// backslashes cannot be escaped in a URL, so they are percent-encoded.
+fn latex_target(target: &str) -> String {
+    let mut escaped = String::with_capacity(target.len());
+    for c in target.chars() {
+        match c {
+            '%' | '#' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '{' => escaped.push_str("%7B"),
+            '}' => escaped.push_str("%7D"),
+            '\\' => escaped.push_str("%5C"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+// Styles mark up their output with these characters from the private use area, which are
+// replaced by `OutputFormat::render` once the citation is complete.
+const TITLE_START: char = '\u{e000}';
+const TITLE_END: char = '\u{e001}';
+const EMPHASIS_START: char = '\u{e002}';
+const EMPHASIS_END: char = '\u{e003}';
+const LINK_START: char = '\u{e004}';
+const LINK_TEXT: char = '\u{e005}';
+const LINK_END: char = '\u{e006}';
+const MARKERS: [char; 7] = [TITLE_START, TITLE_END, EMPHASIS_START, EMPHASIS_END, LINK_START, LINK_TEXT, LINK_END];
+
+/// Marks the title of a work, which is set in italics.
+pub(crate) fn title(text: &str) -> String {
+    format!("{TITLE_START}{text}{TITLE_END}")
+}
+
+/// Marks text that is set in italics, but is not a title.
+pub(crate) fn emphasis(text: &str) -> String {
+    format!("{EMPHASIS_START}{text}{EMPHASIS_END}")
+}
+
+/// Marks a link to the given target.
+pub(crate) fn link(target: &str, text: &str) -> String {
+    format!("{LINK_START}{target}{LINK_TEXT}{text}{LINK_END}")
+}
+
+/// Renders citations as text, e.g. in a citation style.
+///
+/// The built-in styles are implemented by [`Style`]. Other crates can implement this trait to
+/// provide additional styles, which are used with [`Citation::format`] just like the built-in ones.
+///
+/// ```
+/// use citation::{Citation, CitationFormatter, OutputFormat};
+///
+/// struct Short;
+///
+/// impl CitationFormatter for Short {
+///     fn format(&self, citation: &Citation, output: OutputFormat) -> String {
+///         match output {
+///             OutputFormat::Html => format!("<cite>{}</cite>", citation.title),
+///             _ => citation.title.clone(),
+///         }
+///     }
+/// }
+///
+/// let citation: Citation = "cff-version: 1.2.0\nmessage: Cite me\ntitle: citation\nauthors:\n  - name: Lewin".parse().unwrap();
+/// assert_eq!(citation.format(Short), "citation");
+/// assert_eq!(citation.format_as(Short, OutputFormat::Html), "<cite>citation</cite>");
+/// ```
+pub trait CitationFormatter {
+    /// Renders the given citation with the given markup.
+    fn format(&self, citation: &Citation, output: OutputFormat) -> String;
+}
+
+impl<F: CitationFormatter + ?Sized> CitationFormatter for &F {
+    fn format(&self, citation: &Citation, output: OutputFormat) -> String {
+        (**self).format(citation, output)
+    }
+}
+
+impl<F: CitationFormatter + ?Sized> CitationFormatter for Box<F> {
+    fn format(&self, citation: &Citation, output: OutputFormat) -> String {
+        (**self).format(citation, output)
+    }
+}
+
+impl CitationFormatter for Style {
+    /// Renders the citation in this style.
+    ///
+    /// If the citation has a `preferred-citation`, that reference is formatted instead, see [`Reference::format`].
+    fn format(&self, citation: &Citation, output: OutputFormat) -> String {
+        match &citation.preferred_citation {
+            Some(reference) => reference.format_as(*self, output),
+            None => output.render(&Work::from_citation(citation).format(*self)),
+        }
+    }
+}
+
+impl Citation {
+    /// Renders the citation as plain text with the given formatter, e.g. one of the built-in [`Style`]s,
+    /// ready to be pasted into a document.
+    pub fn format(&self, formatter: impl CitationFormatter) -> String {
+        formatter.format(self, OutputFormat::Plain)
+    }
+
+    /// Renders the citation with the given formatter and markup, e.g. as HTML for a website.
+    pub fn format_as(&self, formatter: impl CitationFormatter, output: OutputFormat) -> String {
+        formatter.format(self, output)
+    }
+}
+
+impl fmt::Display for Citation {
+    /// Writes the citation of the work itself as plain text in APA style on a single line, e.g.
+    /// `Lewin, L. (2024). citation (Version 0.1.0) [Computer software]. GitHub. https://github.com/lucalewin/citation`.
+    ///
+    /// Unlike [`Citation::format`], the `preferred-citation` is not used, so the output describes the software or
+    /// dataset, e.g. for `--version` output and logs.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&OutputFormat::Plain.render(&Work::from_citation(self).format(Style::Apa)))
+    }
+}
+
+impl Reference {
+    /// Renders the reference as plain text in the given style, ready to be pasted into a document.
+    pub fn format(&self, style: Style) -> String {
+        self.format_as(style, OutputFormat::Plain)
+    }
+
+    /// Renders the reference in the given style and markup, e.g. as HTML for a website.
+    pub fn format_as(&self, style: Style, output: OutputFormat) -> String {
+        output.render(&Work::from_reference(self).format(style))
+    }
+}
+
+/// The kind of a work, as far as it matters for the layout of a citation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Software,
+    Dataset,
+    Article,
+    Chapter,
+    Book,
+    Thesis,
+    Report,
+    Website,
+    Other,
+}
+
+/// The parts of a citation or reference used by the styles.
+struct Work<'a> {
+    kind: Kind,
+    authors: &'a [Author],
+    title: &'a str,
+    year: Option<String>,
+    version: Option<&'a str>,
+    edition: Option<&'a str>,
+    container: Option<&'a str>,
+    volume: Option<&'a str>,
+    issue: Option<&'a str>,
+    pages: Option<String>,
+    publisher: Option<String>,
+    thesis_type: Option<&'a str>,
+    doi: Option<String>,
+    url: Option<&'a str>,
+}
+
+impl<'a> Work<'a> {
+    fn from_citation(citation: &'a Citation) -> Self {
+        let url = citation.repository_code.as_deref().or(citation.url.as_deref()).or(citation.repository.as_deref());
+
+        Work {
+            kind: match citation.r#type {
+                Some(Type::Dataset) => Kind::Dataset,
+                _ => Kind::Software,
+            },
+            authors: &citation.authors,
+            title: &citation.title,
+            year: citation.date_released.map(|date| date.year().to_string()),
+            version: citation.version.as_deref(),
+            edition: None,
+            container: None,
+            volume: None,
+            issue: None,
+            pages: None,
+            publisher: url.and_then(host),
+            thesis_type: None,
+            doi: citation.find_doi(),
+            url,
+        }
+    }
+
+    fn from_reference(reference: &'a Reference) -> Self {
+        let url = reference.url.as_deref().or(reference.repository_code.as_deref()).or(reference.repository.as_deref());
+        let kind = match reference.r#type {
+            ReferenceType::Software
+            | ReferenceType::SoftwareCode
+            | ReferenceType::SoftwareContainer
+            | ReferenceType::SoftwareExecutable
+            | ReferenceType::SoftwareVirtualMachine => Kind::Software,
+            ReferenceType::Data | ReferenceType::Database => Kind::Dataset,
+            ReferenceType::Article | ReferenceType::MagazineArticle | ReferenceType::NewspaperArticle => Kind::Article,
+            ReferenceType::ConferencePaper => Kind::Chapter,
+            ReferenceType::Book | ReferenceType::EditedWork | ReferenceType::Manual if reference.collection_title.is_some() => Kind::Chapter,
+            ReferenceType::Book | ReferenceType::EditedWork | ReferenceType::Manual => Kind::Book,
+            ReferenceType::Thesis => Kind::Thesis,
+            ReferenceType::Report | ReferenceType::Standard | ReferenceType::GovernmentDocument => Kind::Report,
+            ReferenceType::Website | ReferenceType::Blog => Kind::Website,
+            _ => Kind::Other,
+        };
+        let publisher = match kind {
+            Kind::Thesis => reference.institution.as_ref().or(reference.publisher.as_ref()).map(|entity| entity.name.clone()),
+            _ => reference.publisher.as_ref().or(reference.institution.as_ref()).map(|entity| entity.name.clone()),
+        };
+
+        Work {
+            kind,
+            authors: &reference.authors,
+            title: &reference.title,
+            year: reference.year.as_ref().map(ToString::to_string).or_else(|| reference.date_published.or(reference.date_released).map(|date: Date| date.year().to_string())),
+            version: reference.version.as_deref(),
+            edition: reference.edition.as_deref(),
+            container: reference.journal.as_deref().or(reference.collection_title.as_deref()).or(reference.conference.as_ref().map(|conference| conference.name.as_str())),
+            volume: reference.volume.as_deref(),
+            issue: reference.issue.as_deref().or(reference.number.as_deref()),
+            pages: match (&reference.start, &reference.end) {
+                (Some(start), Some(end)) => Some(format!("{start}–{end}")),
+                (Some(start), None) => Some(start.to_string()),
+                _ => reference.pages.as_ref().map(ToString::to_string),
+            },
+            publisher: publisher.or_else(|| matches!(kind, Kind::Software | Kind::Dataset).then(|| url.and_then(host)).flatten()),
+            thesis_type: reference.thesis_type.as_deref(),
+            doi: reference.doi.as_ref().map(|doi| doi.to_string()),
+            url,
+        }
+    }
+
+    fn format(&self, style: Style) -> String {
+        match style {
+            Style::Apa => apa(self),
+            Style::Ieee => ieee(self),
+            Style::Chicago => chicago(self),
+            Style::Mla => mla(self),
+            Style::Harvard => harvard(self),
+            Style::Vancouver => vancouver(self),
+        }
+    }
+
+    /// Returns the DOI as a URL, or the URL of the work.
+    fn link(&self) -> Option<String> {
+        match &self.doi {
+            Some(doi) => Some(self.doi_link(&format!("https://doi.org/{doi}"))),
+            None => self.url_link(),
+        }
+    }
+
+    /// Returns a link to the DOI of the work with the given text.
+    fn doi_link(&self, text: &str) -> String {
+        link(&format!("https://doi.org/{}", self.doi.as_deref().unwrap_or_default()), text)
+    }
+
+    /// Returns a link to the URL of the work, showing the URL itself.
+    fn url_link(&self) -> Option<String> {
+        self.url.map(|url| link(url, url))
+    }
+}
+
+/// Returns the name of well-known hosting platforms, which APA and DataCite list as the publisher of software.
+pub(crate) fn host(url: &str) -> Option<String> {
+    let hosts = [
+        ("github.com", "GitHub"),
+        ("gitlab.com", "GitLab"),
+        ("codeberg.org", "Codeberg"),
+        ("bitbucket.org", "Bitbucket"),
+        ("zenodo.org", "Zenodo"),
+        ("figshare.com", "figshare"),
+        ("crates.io", "crates.io"),
+        ("pypi.org", "PyPI"),
+        ("cran.r-project.org", "CRAN"),
+    ];
+
+    let domain = url.split("://").nth(1).unwrap_or(url).split('/').next()?;
+    let domain = domain.strip_prefix("www.").unwrap_or(domain);
+    hosts.iter().find(|(host, _)| domain == *host).map(|(_, name)| name.to_string())
+}
+
+/// Returns the initials of given names, e.g. `A. M.` for `Arfon Michael` and `J.-P.` for `Jean-Paul`.
+fn initials(given_names: &str) -> String {
+    given_names
+        .split_whitespace()
+        .map(|name| {
+            name.split('-')
+                .filter_map(|part| part.chars().next())
+                .map(|initial| format!("{initial}."))
+                .collect::<Vec<_>>()
+                .join("-")
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Returns the family name of a person including the name particle, e.g. `van Beethoven`.
+fn family_name(person: &Person) -> Option<String> {
+    let parts: Vec<&str> = [person.name_particle.as_deref(), person.family_names.as_deref()]
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .collect();
+
+    (!parts.is_empty()).then(|| parts.join(" "))
+}
+
+/// Formats an author as `Family, G. G.`, followed by the name suffix if there is one.
+fn inverted_initials(author: &Author) -> String {
+    let Author::Person(person) = author else { return author.display_name() };
+    let Some(family) = family_name(person) else { return author.display_name() };
+
+    let given = person.given_names.as_deref().map(initials).filter(|initials| !initials.is_empty());
+    [Some(family), given, person.name_suffix.clone()].into_iter().flatten().collect::<Vec<_>>().join(", ")
+}
+
+/// Formats an author as `Family, Given`, followed by the name suffix if there is one.
+fn inverted(author: &Author) -> String {
+    let Author::Person(person) = author else { return author.display_name() };
+    let Some(family) = family_name(person) else { return author.display_name() };
+
+    [Some(family), person.given_names.clone(), person.name_suffix.clone()].into_iter().flatten().collect::<Vec<_>>().join(", ")
+}
+
+/// Formats an author as `G. G. Family`, followed by the name suffix if there is one.
+fn initials_first(author: &Author) -> String {
+    let Author::Person(person) = author else { return author.display_name() };
+    let Some(family) = family_name(person) else { return author.display_name() };
+
+    let given = person.given_names.as_deref().map(initials).filter(|initials| !initials.is_empty());
+    let name = [given, Some(family)].into_iter().flatten().collect::<Vec<_>>().join(" ");
+    match &person.name_suffix {
+        Some(suffix) => format!("{name}, {suffix}"),
+        None => name,
+    }
+}
+
+/// Joins names as `A, B, and C`, or `A and B` for two names.
+fn and_list(names: &[String]) -> Option<String> {
+    match names {
+        [] => None,
+        [name] => Some(name.clone()),
+        [first, second] => Some(format!("{first} and {second}")),
+        [first @ .., last] => Some(format!("{}, and {last}", first.join(", "))),
+    }
+}
+
+/// Prefixes pages with `p.` or `pp.` for page ranges.
+fn page_range(pages: &str) -> String {
+    match pages.contains(['–', '-', ',']) {
+        true => format!("pp. {pages}"),
+        false => format!("p. {pages}"),
+    }
+}
+
+/// Appends a period unless the text already ends with a punctuation mark.
+fn sentence(text: &str) -> String {
+    match text.trim_end_matches(MARKERS).ends_with(['.', '?', '!']) {
+        true => text.to_string(),
+        false => format!("{text}."),
+    }
+}
+
+/// Renders a citation in APA style, e.g.
+/// `Lewin, L. (2024). citation (Version 0.1.0) [Computer software]. GitHub. https://github.com/lucalewin/citation`
+///
+/// Up to 20 authors are listed, longer lists are shortened to the first 19 and the last one.
+fn apa(work: &Work) -> String {
+    let mut parts = Vec::new();
+
+    let names: Vec<String> = work.authors.iter().map(inverted_initials).collect();
+    let authors = match names.as_slice() {
+        [] => None,
+        [name] => Some(name.clone()),
+        [first @ .., last] if names.len() <= 20 => Some(format!("{}, & {last}", first.join(", "))),
+        [first @ .., last] => Some(format!("{}, . . . {last}", first[..19].join(", "))),
+    };
+    if let Some(authors) = authors {
+        parts.push(sentence(&authors));
+    }
+    parts.push(format!("({}).", work.year.as_deref().unwrap_or("n.d.")));
+
+    match work.kind {
+        Kind::Article => {
+            parts.push(sentence(work.title));
+
+            let mut source = work.container.map(title).unwrap_or_default();
+            if let Some(volume) = work.volume {
+                source.push_str(&format!(", {}", emphasis(volume)));
+            }
+            if let Some(issue) = work.issue {
+                match work.volume {
+                    Some(_) => source.push_str(&format!("({issue})")),
+                    None => source.push_str(&format!(", ({issue})")),
+                }
+            }
+            if let Some(pages) = &work.pages {
+                source.push_str(&format!(", {pages}"));
+            }
+            if !source.is_empty() {
+                parts.push(sentence(source.trim_start_matches(", ")));
+            }
+        }
+        Kind::Chapter => {
+            parts.push(sentence(work.title));
+            if let Some(container) = work.container {
+                let pages = work.pages.as_ref().map(|pages| format!(" (pp. {pages})")).unwrap_or_default();
+                parts.push(sentence(&format!("In {}{pages}", title(container))));
+            }
+            parts.extend(work.publisher.as_deref().map(sentence));
+        }
+        _ => {
+            let mut heading = title(work.title);
+            let details: Vec<String> = [
+                work.edition.map(|edition| format!("{edition} ed.")),
+                work.version.map(|version| format!("Version {version}")),
+            ]
+            .into_iter()
+            .flatten()
+            .collect();
+            if !details.is_empty() {
+                heading.push_str(&format!(" ({})", details.join(", ")));
+            }
+
+            let description = match work.kind {
+                Kind::Software => Some("Computer software".to_string()),
+                Kind::Dataset => Some("Data set".to_string()),
+                Kind::Thesis => Some(match (work.thesis_type, &work.publisher) {
+                    (Some(kind), Some(institution)) => format!("{kind}, {institution}"),
+                    (Some(kind), None) => kind.to_string(),
+                    (None, Some(institution)) => format!("Thesis, {institution}"),
+                    (None, None) => "Thesis".to_string(),
+                }),
+                _ => None,
+            };
+            if let Some(description) = description {
+                heading.push_str(&format!(" [{description}]"));
+            }
+            parts.push(sentence(&heading));
+
+            // the publisher is omitted if it is the author, and for theses it is part of the description
+            let authored = work.authors.len() == 1 && work.publisher.as_deref() == Some(work.authors[0].display_name().as_str());
+            if work.kind != Kind::Thesis && !authored {
+                parts.extend(work.publisher.as_deref().map(sentence));
+            }
+        }
+    }
+
+    parts.extend(work.link());
+    parts.join(" ")
+}
+
+/// Renders a citation in IEEE style, e.g.
+/// `L. Lewin, “citation,” Version 0.1.0, Computer software, GitHub, 2024. [Online]. Available: https://github.com/lucalewin/citation`
+///
+/// More than six authors are shortened to the first one followed by `et al.`
+fn ieee(work: &Work) -> String {
+    let mut names: Vec<String> = work.authors.iter().map(initials_first).collect();
+    if names.len() > 6 {
+        names = vec![format!("{} et al.", names[0])];
+    }
+
+    let mut rest = Vec::new();
+    match work.kind {
+        Kind::Article => {
+            rest.extend(work.container.map(title));
+            rest.extend(work.volume.map(|volume| format!("vol. {volume}")));
+            rest.extend(work.issue.map(|issue| format!("no. {issue}")));
+            rest.extend(work.pages.as_deref().map(page_range));
+        }
+        Kind::Chapter => {
+            rest.extend(work.container.map(|container| format!("in {}", title(container))));
+            rest.extend(work.publisher.clone());
+            rest.extend(work.pages.as_deref().map(page_range));
+        }
+        Kind::Thesis => {
+            rest.push(work.thesis_type.unwrap_or("Thesis").to_string());
+            rest.extend(work.publisher.clone());
+        }
+        Kind::Software | Kind::Dataset => {
+            rest.extend(work.version.map(|version| format!("Version {version}")));
+            rest.push(match work.kind {
+                Kind::Software => "Computer software".to_string(),
+                _ => "Data set".to_string(),
+            });
+            rest.extend(work.publisher.clone());
+        }
+        _ => {
+            rest.extend(work.edition.map(|edition| format!("{edition} ed.")));
+            rest.extend(work.version.map(|version| format!("Version {version}")));
+            rest.extend(work.publisher.clone());
+        }
+    }
+    rest.extend(work.year.clone());
+    rest.extend(work.doi.as_ref().map(|doi| format!("doi: {}", work.doi_link(doi))));
+
+    let head = and_list(&names).map(|names| format!("{names}, ")).unwrap_or_default();
+    let text = match (work.kind, rest.is_empty()) {
+        // books and reports are set in italics, all other titles are quoted with the comma inside the quotes
+        (Kind::Book | Kind::Report, _) => sentence(&format!("{head}{}", [title(work.title)].into_iter().chain(rest).collect::<Vec<_>>().join(", "))),
+        (_, true) => format!("{head}\u{201c}{}\u{201d}", sentence(work.title)),
+        (_, false) => format!("{head}\u{201c}{},\u{201d} {}", work.title, sentence(&rest.join(", "))),
+    };
+
+    match (&work.doi, work.url_link()) {
+        (None, Some(url)) => format!("{text} [Online]. Available: {url}"),
+        _ => text,
+    }
+}
+
+/// Formats authors as `Family, Given, Given Family, and Given Family`, with only the first name inverted.
+fn inverted_first(authors: &[Author]) -> Vec<String> {
+    authors
+        .iter()
+        .enumerate()
+        .map(|(index, author)| match index {
+            0 => inverted(author),
+            _ => author.display_name(),
+        })
+        .collect()
+}
+
+/// Renders a citation in the author-date system of the Chicago Manual of Style, e.g.
+/// `Lewin, Luca. 2024. citation. Version 0.1.0. GitHub. https://github.com/lucalewin/citation.`
+///
+/// More than ten authors are shortened to the first seven followed by `et al.`
+fn chicago(work: &Work) -> String {
+    let mut names = inverted_first(work.authors);
+    let authors = match names.len() {
+        // the first name is inverted, so two names are separated by a comma as well
+        2 => Some(format!("{}, and {}", names[0], names[1])),
+        11.. => {
+            names.truncate(7);
+            Some(format!("{}, et al.", names.join(", ")))
+        }
+        _ => and_list(&names),
+    };
+
+    let mut parts: Vec<String> = authors.as_deref().map(sentence).into_iter().collect();
+    parts.push(sentence(work.year.as_deref().unwrap_or("n.d")));
+
+    match work.kind {
+        Kind::Article => {
+            parts.push(format!("\u{201c}{}\u{201d}", sentence(work.title)));
+
+            let mut source = work.container.map(title).unwrap_or_default();
+            source.extend(work.volume.map(|volume| format!(" {volume}")));
+            source.extend(work.issue.map(|issue| format!(" ({issue})")));
+            source.extend(work.pages.as_ref().map(|pages| format!(": {pages}")));
+            if !source.trim().is_empty() {
+                parts.push(sentence(source.trim()));
+            }
+        }
+        Kind::Chapter => {
+            parts.push(format!("\u{201c}{}\u{201d}", sentence(work.title)));
+            if let Some(container) = work.container {
+                let pages = work.pages.as_ref().map(|pages| format!(", {pages}")).unwrap_or_default();
+                parts.push(sentence(&format!("In {}{pages}", title(container))));
+            }
+            parts.extend(work.publisher.as_deref().map(sentence));
+        }
+        Kind::Thesis => {
+            parts.push(format!("\u{201c}{}\u{201d}", sentence(work.title)));
+            let description = [work.thesis_type.or(Some("Thesis")), work.publisher.as_deref()].into_iter().flatten().collect::<Vec<_>>();
+            parts.push(sentence(&description.join(", ")));
+        }
+        _ => {
+            parts.push(sentence(&title(work.title)));
+            parts.extend(work.edition.map(|edition| format!("{edition} ed.")));
+            parts.extend(work.version.map(|version| sentence(&format!("Version {version}"))));
+            if work.kind == Kind::Dataset {
+                parts.push("Dataset.".to_string());
+            }
+            parts.extend(work.publisher.as_deref().map(sentence));
+        }
+    }
+
+    parts.extend(work.link().map(|link| sentence(&link)));
+    parts.join(" ")
+}
+
+/// Renders a citation in MLA style, e.g.
+/// `Lewin, Luca. citation. Version 0.1.0, GitHub, 2024, github.com/lucalewin/citation.`
+///
+/// Three or more authors are shortened to the first one followed by `et al.`
+fn mla(work: &Work) -> String {
+    let names = inverted_first(work.authors);
+    let authors = match names.as_slice() {
+        [] => None,
+        [name] => Some(name.clone()),
+        [first, second] => Some(format!("{first}, and {second}")),
+        [first, ..] => Some(format!("{first}, et al.")),
+    };
+
+    let mut parts: Vec<String> = authors.as_deref().map(sentence).into_iter().collect();
+    let mut container = Vec::new();
+
+    match work.kind {
+        Kind::Article | Kind::Chapter => {
+            parts.push(format!("\u{201c}{}\u{201d}", sentence(work.title)));
+            container.extend(work.container.map(title));
+            container.extend(work.volume.map(|volume| format!("vol. {volume}")));
+            container.extend(work.issue.map(|issue| format!("no. {issue}")));
+            if work.kind == Kind::Chapter {
+                container.extend(work.publisher.clone());
+            }
+            container.extend(work.year.clone());
+            container.extend(work.pages.as_deref().map(page_range));
+        }
+        Kind::Thesis => {
+            parts.push(sentence(&title(work.title)));
+            parts.extend(work.year.as_deref().map(sentence));
+            let description = [work.publisher.as_deref(), work.thesis_type.or(Some("Thesis"))].into_iter().flatten().collect::<Vec<_>>();
+            container.push(description.join(", "));
+        }
+        _ => {
+            parts.push(sentence(&title(work.title)));
+            container.extend(work.edition.map(|edition| format!("{edition} ed.")));
+            container.extend(work.version.map(|version| format!("Version {version}")));
+            container.extend(work.publisher.clone());
+            container.extend(work.year.clone());
+        }
+    }
+
+    // DOIs are written with a `doi:` prefix, URLs without their scheme
+    container.extend(match (&work.doi, work.url) {
+        (Some(doi), _) => Some(work.doi_link(&format!("doi:{doi}"))),
+        (None, Some(url)) => Some(link(url, url.split_once("://").map_or(url, |(_, rest)| rest))),
+        (None, None) => None,
+    });
+    if !container.is_empty() {
+        parts.push(sentence(&container.join(", ")));
+    }
+
+    parts.join(" ")
+}
+
+/// Renders a citation in Harvard style, e.g.
+/// `Lewin, L. (2024) citation (Version 0.1.0) [Computer program]. GitHub. Available at: https://github.com/lucalewin/citation`
+///
+/// More than three authors are shortened to the first one followed by `et al.`
+fn harvard(work: &Work) -> String {
+    // initials are written without spaces, e.g. `Smith, A.M.`
+    let names: Vec<String> = work.authors.iter().map(|author| inverted_initials(author).replace(". ", ".")).collect();
+    let authors = match names.as_slice() {
+        [] => None,
+        [name] => Some(name.clone()),
+        [first, second] => Some(format!("{first} and {second}")),
+        [first, second, third] => Some(format!("{first}, {second} and {third}")),
+        [first, ..] => Some(format!("{first} et al.")),
+    };
+
+    let mut parts: Vec<String> = authors.into_iter().collect();
+    parts.push(format!("({})", work.year.as_deref().unwrap_or("no date")));
+
+    match work.kind {
+        Kind::Article => {
+            let mut source = format!("\u{2018}{}\u{2019}", work.title);
+            source.extend(work.container.map(|container| format!(", {}", title(container))));
+            source.extend(work.volume.map(|volume| format!(", {volume}")));
+            source.extend(work.issue.map(|issue| format!("({issue})")));
+            source.extend(work.pages.as_deref().map(|pages| format!(", {}", page_range(pages))));
+            parts.push(sentence(&source));
+        }
+        Kind::Chapter => {
+            parts.push(format!("\u{2018}{}\u{2019},", work.title));
+            parts.extend(work.container.map(|container| sentence(&format!("in {}", title(container)))));
+            parts.extend(work.publisher.as_deref().map(sentence));
+            parts.extend(work.pages.as_deref().map(|pages| sentence(&page_range(pages))));
+        }
+        Kind::Thesis => {
+            parts.push(sentence(&title(work.title)));
+            parts.push(sentence(work.thesis_type.unwrap_or("Thesis")));
+            parts.extend(work.publisher.as_deref().map(sentence));
+        }
+        _ => {
+            let mut heading = title(work.title);
+            heading.extend(work.edition.map(|edition| format!(". {edition} edn")));
+            heading.extend(work.version.map(|version| format!(" (Version {version})")));
+            heading.push_str(match work.kind {
+                Kind::Software => " [Computer program]",
+                Kind::Dataset => " [Dataset]",
+                _ => "",
+            });
+            parts.push(sentence(&heading));
+            parts.extend(work.publisher.as_deref().map(sentence));
+        }
+    }
+
+    parts.extend(work.link().map(|link| format!("Available at: {link}")));
+    parts.join(" ")
+}
+
+/// Renders a citation in Vancouver style, e.g.
+/// `Lewin L. citation [Computer software]. Version 0.1.0. GitHub; 2024. Available from: https://github.com/lucalewin/citation`
+///
+/// More than six authors are shortened to the first six followed by `et al.`
+fn vancouver(work: &Work) -> String {
+    // names are written as `Family GG`, without periods or commas
+    let mut names: Vec<String> = work
+        .authors
+        .iter()
+        .map(|author| {
+            let Author::Person(person) = author else { return author.display_name() };
+            let Some(family) = family_name(person) else { return author.display_name() };
+
+            let initials = person.given_names.as_deref().map(|given| initials(given).replace(['.', ' '], ""));
+            [Some(family), initials, person.name_suffix.clone()].into_iter().flatten().collect::<Vec<_>>().join(" ")
+        })
+        .collect();
+    if names.len() > 6 {
+        names.truncate(6);
+        names.push("et al".to_string());
+    }
+
+    let mut parts: Vec<String> = (!names.is_empty()).then(|| sentence(&names.join(", "))).into_iter().collect();
+    let year = work.year.as_deref().unwrap_or("[date unknown]");
+
+    match work.kind {
+        Kind::Article => {
+            parts.push(sentence(work.title));
+            parts.extend(work.container.map(sentence));
+
+            let mut source = year.to_string();
+            source.extend(work.volume.map(|volume| format!(";{volume}")));
+            source.extend(work.issue.map(|issue| format!("({issue})")));
+            source.extend(work.pages.as_ref().map(|pages| format!(":{pages}")));
+            parts.push(sentence(&source));
+        }
+        Kind::Chapter => {
+            parts.push(sentence(work.title));
+            parts.extend(work.container.map(|container| sentence(&format!("In: {container}"))));
+            parts.push(sentence(&[work.publisher.as_deref(), Some(year)].into_iter().flatten().collect::<Vec<_>>().join("; ")));
+            parts.extend(work.pages.as_ref().map(|pages| sentence(&format!("p. {pages}"))));
+        }
+        _ => {
+            let description = match work.kind {
+                Kind::Software => Some("Computer software"),
+                Kind::Dataset => Some("Dataset"),
+                Kind::Thesis => Some(work.thesis_type.unwrap_or("dissertation")),
+                Kind::Website => Some("Internet"),
+                _ => None,
+            };
+            parts.push(sentence(&match description {
+                Some(description) => format!("{} [{description}]", work.title),
+                None => work.title.to_string(),
+            }));
+            parts.extend(work.edition.map(|edition| format!("{edition} ed.")));
+            parts.extend(work.version.map(|version| sentence(&format!("Version {version}"))));
+            parts.push(sentence(&[work.publisher.as_deref(), Some(year)].into_iter().flatten().collect::<Vec<_>>().join("; ")));
+        }
+    }
+
+    match (&work.doi, work.url_link()) {
+        (Some(doi), _) => parts.push(work.doi_link(&format!("doi:{doi}"))),
+        (None, Some(url)) => parts.push(format!("Available from: {url}")),
+        (None, None) => {}
+    }
+
+    parts.join(" ")
+}
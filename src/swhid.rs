@@ -0,0 +1,222 @@
+use std::{error::Error, fmt, str::FromStr};
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::de::FromStrVisitor;
+
+/// A [Software Heritage identifier](https://docs.softwareheritage.org/devel/swh-model/persistent-identifiers.html),
+/// e.g. `swh:1:dir:d198bc9d7a6bcf6db04f476d29314f157507d505;origin=https://github.com/python/cpython`.
+///
+/// A SWHID consists of a core identifier (scheme version, object type and hash), optionally followed by qualifiers.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+#[serde(into = "String")]
+pub struct Swhid {
+    object_type: ObjectType,
+    hash: String,
+    qualifiers: Vec<(String, String)>,
+}
+
+/// The type of the object a [`Swhid`] points to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ObjectType {
+    /// `snp`, a snapshot of all branches of a repository.
+    Snapshot,
+
+    /// `rel`, a release.
+    Release,
+
+    /// `rev`, a revision, i.e. a commit.
+    Revision,
+
+    /// `dir`, a directory.
+    Directory,
+
+    /// `cnt`, the content of a file.
+    Content,
+}
+
+impl ObjectType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ObjectType::Snapshot => "snp",
+            ObjectType::Release => "rel",
+            ObjectType::Revision => "rev",
+            ObjectType::Directory => "dir",
+            ObjectType::Content => "cnt",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "snp" => Some(ObjectType::Snapshot),
+            "rel" => Some(ObjectType::Release),
+            "rev" => Some(ObjectType::Revision),
+            "dir" => Some(ObjectType::Directory),
+            "cnt" => Some(ObjectType::Content),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ObjectType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Swhid {
+    /// Parses and validates a SWHID, including its qualifiers.
+    pub fn new(value: &str) -> Result<Self, ParseSwhidError> {
+        let error = |message: &str| ParseSwhidError { value: value.to_string(), message: message.to_string() };
+
+        let mut qualifiers = value.split(';');
+        let core: Vec<_> = qualifiers.next().unwrap_or_default().split(':').collect();
+        let [scheme, version, object_type, hash] = core[..] else {
+            return Err(error("expected `swh:1:<type>:<hash>`"));
+        };
+
+        if scheme != "swh" {
+            return Err(error("the scheme must be `swh`"));
+        }
+        if version != "1" {
+            return Err(error("the scheme version must be `1`"));
+        }
+        let object_type = ObjectType::parse(object_type).ok_or_else(|| error("the object type must be one of `snp`, `rel`, `rev`, `dir` and `cnt`"))?;
+        if hash.len() != 40 || !hash.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(error("the hash must consist of 40 hexadecimal digits"));
+        }
+
+        let mut swhid = Swhid { object_type, hash: hash.to_ascii_lowercase(), qualifiers: Vec::new() };
+        for qualifier in qualifiers {
+            let (key, value) = qualifier.split_once('=').ok_or_else(|| error(&format!("the qualifier `{qualifier}` has no value")))?;
+            let valid = match key {
+                "origin" | "path" => !value.is_empty(),
+                "visit" => Swhid::new(value).is_ok_and(|visit| visit.is_core() && visit.object_type == ObjectType::Snapshot),
+                "anchor" => Swhid::new(value).is_ok_and(|anchor| anchor.is_core() && anchor.object_type != ObjectType::Content),
+                "lines" | "bytes" => {
+                    let is_number = |value: &str| !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit());
+                    match value.split_once('-') {
+                        Some((start, end)) => is_number(start) && is_number(end),
+                        None => is_number(value),
+                    }
+                }
+                _ => return Err(error(&format!("unknown qualifier `{key}`"))),
+            };
+            if !valid {
+                return Err(error(&format!("`{value}` is not a valid value for the qualifier `{key}`")));
+            }
+            swhid.qualifiers.push((key.to_string(), value.to_string()));
+        }
+
+        Ok(swhid)
+    }
+
+    pub fn object_type(&self) -> ObjectType {
+        self.object_type
+    }
+
+    /// Returns the hash of the object in lowercase hexadecimal digits.
+    pub fn hash(&self) -> &str {
+        &self.hash
+    }
+
+    /// Returns the qualifiers as `(key, value)` in the order they were given.
+    pub fn qualifiers(&self) -> &[(String, String)] {
+        &self.qualifiers
+    }
+
+    /// Returns the value of the given qualifier, e.g. `origin`.
+    pub fn qualifier(&self, key: &str) -> Option<&str> {
+        self.qualifiers.iter().find(|(name, _)| name == key).map(|(_, value)| value.as_str())
+    }
+
+    /// Returns `true` if the SWHID has no qualifiers, as required for identifiers in a `CITATION.cff` file.
+    pub fn is_core(&self) -> bool {
+        self.qualifiers.is_empty()
+    }
+
+    /// Returns the core identifier without qualifiers.
+    pub fn core(&self) -> Swhid {
+        Swhid { object_type: self.object_type, hash: self.hash.clone(), qualifiers: Vec::new() }
+    }
+
+    /// Returns the link to the object in the Software Heritage archive.
+    pub fn to_url(&self) -> String {
+        format!("https://archive.softwareheritage.org/{self}")
+    }
+}
+
+impl fmt::Display for Swhid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "swh:1:{}:{}", self.object_type, self.hash)?;
+        for (key, value) in &self.qualifiers {
+            write!(f, ";{key}={value}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Swhid {
+    type Err = ParseSwhidError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Swhid::new(value)
+    }
+}
+
+impl<'de> Deserialize<'de> for Swhid {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(FromStrVisitor::new("a Software Heritage identifier"))
+    }
+}
+
+impl From<Swhid> for String {
+    fn from(swhid: Swhid) -> Self {
+        swhid.to_string()
+    }
+}
+
+/// Error returned when a value is not a valid SWHID.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseSwhidError {
+    /// The invalid value.
+    pub value: String,
+
+    /// A description of the problem.
+    pub message: String,
+}
+
+impl fmt::Display for ParseSwhidError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` is not a valid Software Heritage identifier: {}", self.value, self.message)
+    }
+}
+
+impl Error for ParseSwhidError {}
+
+/// The default URL of the Software Heritage API, which can be overridden with `SWH_API_URL`.
+#[cfg(feature = "http")]
+const API_URL: &str = "https://archive.softwareheritage.org/api/1";
+
+#[cfg(feature = "http")]
+impl Swhid {
+    /// Checks through the [Software Heritage API](https://archive.softwareheritage.org/api/) whether the object
+    /// is actually archived. Qualifiers are ignored.
+    ///
+    /// If the `SWH_TOKEN` environment variable is set, it is used to authenticate, which raises the rate limit.
+    pub fn is_archived(&self) -> Result<bool, crate::Error> {
+        let api = std::env::var("SWH_API_URL").unwrap_or_else(|_| API_URL.to_string());
+
+        let mut builder = crate::http::client()?.get(format!("{}/resolve/{}/", api.trim_end_matches('/'), self.core()));
+        if let Ok(token) = std::env::var("SWH_TOKEN") {
+            builder = builder.bearer_auth(token);
+        }
+
+        let response = builder.send()?;
+        match response.status() {
+            reqwest::StatusCode::NOT_FOUND => Ok(false),
+            _ => response.error_for_status().map(|_| true).map_err(Into::into),
+        }
+    }
+}
@@ -0,0 +1,204 @@
+//! [proptest](https://docs.rs/proptest) strategies for testing code that reads, writes or validates citations.
+//!
+//! [`citation`] generates citations that [`Citation::validate`] reports no errors or warnings for, and
+//! [`near_valid_citation`] generates citations with exactly one problem, e.g. to check that a validator or an
+//! editor reports it. Strings are drawn from [`text`], which includes edge cases such as YAML keywords, quotes,
+//! line breaks and non-ASCII names.
+//!
+//! ```no_run
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     #[test]
+//!     fn round_trip(citation in citation::testing::citation()) {
+//!         let yaml = citation.to_yaml_string().unwrap();
+//!         prop_assert_eq!(yaml.parse::<citation::Citation>().unwrap(), citation);
+//!     }
+//! }
+//! ```
+
+use proptest::{collection::vec, option, prelude::*, sample::select};
+
+use crate::{orcid::checksum, Author, Citation, Code, Date, Doi, Entity, IntOrString, License, Licenses, Person, Reference, ReferenceType, DEFAULT_MESSAGE};
+
+/// Strings that are easy to get wrong when reading or writing YAML.
+const EDGE_CASES: &[&str] = &[
+    "yes", "no", "null", "~", "true", "1.0", "0x1F", "1e3", "2024-01-01", "- item", "key: value", "#hashtag",
+    "'quoted'", "\"double quoted\"", "back\\slash", "  padded  ", "line\nbreak", "tab\tseparated", "{braces}", "[brackets]",
+    "&anchor", "*alias", "!tag", "%directive", "@at", "`backtick`", "Łukasz Wróblewski", "Zoë Müller-Lüdenscheidt",
+    "José Martínez", "山田太郎", "Иван Петров", "محمد", "🦀 crab",
+];
+
+/// Generates non-empty strings, mostly plain words and sometimes one of many edge cases, e.g. `yes`, `key: value`,
+/// a line break or a non-ASCII name.
+pub fn text() -> impl Strategy<Value = String> {
+    prop_oneof![
+        3 => "[A-Za-z][A-Za-z0-9]{0,11}( [A-Za-z0-9]{1,12}){0,3}",
+        1 => select(EDGE_CASES).prop_map(str::to_string),
+    ]
+}
+
+/// Generates dates between 1970 and 2020, which lie neither in the future nor before the first software release.
+pub fn date() -> impl Strategy<Value = Date> {
+    (1970u16..=2020, 1u8..=12, 1u8..=28).prop_map(|(year, month, day)| Date::new(year, month, day).expect("every month has 28 days"))
+}
+
+/// Generates ORCID URLs with a valid check digit, e.g. `https://orcid.org/0000-0002-1825-0097`.
+pub fn orcid() -> impl Strategy<Value = String> {
+    vec(b'0'..=b'9', 15).prop_map(|mut digits| {
+        digits.push(checksum(&digits));
+        let groups: Vec<&str> = digits.chunks(4).map(|group| std::str::from_utf8(group).expect("digits are ASCII")).collect();
+        format!("https://orcid.org/{}", groups.join("-"))
+    })
+}
+
+/// Generates email addresses such as `jane.doe@example.org`.
+pub fn email() -> impl Strategy<Value = String> {
+    "[a-z][a-z0-9]{0,9}(\\.[a-z0-9]{1,10})?@[a-z][a-z0-9-]{0,9}[a-z0-9]\\.(org|com|dev|ac\\.uk)"
+}
+
+/// Generates `https` URLs such as `https://example.org/jane/project`.
+pub fn url() -> impl Strategy<Value = String> {
+    "https://[a-z]{1,10}\\.(org|com|dev)(/[a-z0-9_-]{1,10}){0,3}"
+}
+
+/// Generates DOIs such as `10.5281/zenodo.1003150`.
+pub fn doi() -> impl Strategy<Value = Doi> {
+    "10\\.[0-9]{4,9}/[a-z0-9]{1,10}(\\.[0-9]{1,8})?".prop_map(|doi| Doi::new(doi).expect("the DOI matches the pattern"))
+}
+
+/// Generates current identifiers of the SPDX License List.
+pub fn license() -> impl Strategy<Value = License> {
+    select(License::ALL.iter().filter(|license| !license.is_deprecated()).cloned().collect::<Vec<_>>())
+}
+
+/// Generates a person with family names and optionally given names, an ORCID, an email address and an affiliation.
+pub fn person() -> impl Strategy<Value = Person> {
+    (option::of(text()), text(), option::of(orcid()), option::of(email()), option::of(text())).prop_map(|(given_names, family_names, orcid, email, affiliation)| Person {
+        given_names,
+        family_names: Some(family_names),
+        orcid,
+        email,
+        affiliation,
+        ..Person::default()
+    })
+}
+
+/// Generates an entity with a name and optionally an email address and a website.
+pub fn entity() -> impl Strategy<Value = Entity> {
+    (text(), option::of(email()), option::of(url())).prop_map(|(name, email, website)| Entity { name, email, website, ..Entity::default() })
+}
+
+/// Generates mostly persons and sometimes entities.
+pub fn author() -> impl Strategy<Value = Author> {
+    prop_oneof![
+        3 => person().prop_map(Author::Person),
+        1 => entity().prop_map(Author::Entity),
+    ]
+}
+
+/// Generates keywords that are unique ignoring case and surrounding whitespace.
+pub fn keywords() -> impl Strategy<Value = Vec<String>> {
+    vec(text(), 0..5).prop_map(|mut keywords| {
+        let mut seen = Vec::new();
+        keywords.retain(|keyword| {
+            let folded = keyword.trim().to_lowercase();
+            !folded.is_empty() && !seen.contains(&folded) && {
+                seen.push(folded);
+                true
+            }
+        });
+        keywords
+    })
+}
+
+/// Generates a reference to an article, a book, a report, a thesis or software, with a title, authors and
+/// optionally a year, a month, a DOI and a journal.
+pub fn reference() -> impl Strategy<Value = Reference> {
+    let r#type = select(&["article", "book", "conference-paper", "report", "software", "thesis"][..]).prop_map(ReferenceType::from);
+    let details = (option::of(1900u16..=2020), option::of(1u8..=12), option::of(doi()), option::of(text()));
+
+    (r#type, text(), vec(author(), 1..4), details).prop_map(|(r#type, title, authors, (year, month, doi, journal))| Reference {
+        r#type,
+        title,
+        authors,
+        year: year.map(IntOrString::from),
+        month: month.map(IntOrString::from),
+        doi,
+        journal,
+        ..Reference::default()
+    })
+}
+
+/// Generates citations of cff-version 1.2.0 without errors or warnings, with one to four authors, a title and
+/// optional values such as a DOI, a license, keywords and references.
+pub fn citation() -> impl Strategy<Value = Citation> {
+    let required = (text(), vec(author(), 1..5), prop_oneof![Just(DEFAULT_MESSAGE.to_string()), text()]);
+    let release = (option::of(text()), option::of("[0-9]{1,2}\\.[0-9]{1,2}\\.[0-9]{1,2}"), option::of(date()), option::of(doi()));
+    let details = (option::of(prop_oneof![license().prop_map(Licenses::Single), vec(license(), 1..3).prop_map(Licenses::Multiple)]), keywords(), option::of(url()), vec(reference(), 0..3));
+
+    (required, release, details).prop_map(|((title, authors, message), (r#abstract, version, date_released, doi), (license, keywords, repository_code, references))| Citation {
+        title,
+        authors,
+        message,
+        r#abstract,
+        version,
+        date_released,
+        doi,
+        license,
+        keywords,
+        repository_code,
+        references,
+        ..Citation::default()
+    })
+}
+
+/// Generates citations with exactly one problem, together with the code [`Citation::validate`] reports it with,
+/// e.g. an empty title, an ORCID with a wrong check digit or a duplicate keyword.
+pub fn near_valid_citation() -> impl Strategy<Value = (Citation, Code)> {
+    let problems = select(&[
+        Code::EmptyTitle,
+        Code::EmptyAuthors,
+        Code::EmptyMessage,
+        Code::InvalidCffVersion,
+        Code::InvalidOrcid,
+        Code::InvalidEmail,
+        Code::InvalidUrl,
+        Code::InvalidDate,
+        Code::DeprecatedLicense,
+        Code::UnknownLicense,
+        Code::EmptyKeyword,
+        Code::DuplicateKeyword,
+        Code::FutureDate,
+    ][..]);
+
+    (citation(), problems, person()).prop_map(|(mut citation, code, mut person)| {
+        match code {
+            Code::EmptyTitle => citation.title = " ".to_string(),
+            Code::EmptyAuthors => citation.authors.clear(),
+            Code::EmptyMessage => citation.message = String::new(),
+            Code::InvalidCffVersion => citation.cff_version = "latest".to_string(),
+            Code::InvalidOrcid => {
+                person.orcid = Some("https://orcid.org/0000-0002-1825-0098".to_string());
+                citation.authors[0] = Author::Person(person);
+            }
+            Code::InvalidEmail => {
+                person.email = Some("jane.doe.example.org".to_string());
+                citation.authors[0] = Author::Person(person);
+            }
+            Code::InvalidUrl => citation.url = Some("www.example.org".to_string()),
+            Code::InvalidDate => {
+                let reference = Reference { title: "Software citation principles".to_string(), authors: vec![Author::Person(person)], month: Some(13u8.into()), ..Reference::default() };
+                citation.references.push(reference);
+            }
+            Code::DeprecatedLicense => citation.license = Some(Licenses::Single(License::from_id("GPL-3.0"))),
+            Code::UnknownLicense => citation.license = Some(Licenses::Single(License::from_id("Apache 2.0"))),
+            Code::EmptyKeyword => citation.keywords.push(String::new()),
+            Code::DuplicateKeyword => citation.keywords = vec!["citation".to_string(), " Citation".to_string()],
+            Code::FutureDate => citation.date_released = Some(Date::new(9999, 12, 31).expect("the date exists")),
+            _ => unreachable!("only the selected problems are injected"),
+        }
+
+        (citation, code)
+    })
+}
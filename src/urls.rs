@@ -0,0 +1,93 @@
+use std::{error::Error, fmt};
+
+use url::Url;
+
+use crate::{Citation, Reference};
+
+/// The schemes allowed for URLs by the schema.
+const SCHEMES: &[&str] = &["http", "https", "ftp", "sftp"];
+
+/// The URLs of a citation or reference, parsed with the [`url`] crate, see [`Citation::urls`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Urls {
+    pub license_url: Option<Url>,
+    pub repository: Option<Url>,
+    pub repository_artifact: Option<Url>,
+    pub repository_code: Option<Url>,
+    pub url: Option<Url>,
+}
+
+/// Error returned when a value is not a URL with one of the schemes allowed by the schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseUrlError {
+    /// The path of the offending key, e.g. `references[0].url`.
+    pub path: String,
+
+    /// The offending value.
+    pub value: String,
+}
+
+impl fmt::Display for ParseUrlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: `{}` is not a valid URL with the scheme http, https, ftp or sftp", self.path, self.value)
+    }
+}
+
+impl Error for ParseUrlError {}
+
+impl Urls {
+    /// Parses the values of the keys `license-url`, `repository`, `repository-artifact`, `repository-code` and
+    /// `url`, whose paths start with the given prefix.
+    fn parse(prefix: &str, values: [&Option<String>; 5]) -> Result<Self, ParseUrlError> {
+        let [license_url, repository, repository_artifact, repository_code, url] = values;
+        let parse = |key: &str, value: &Option<String>| match value {
+            Some(value) => parse(value).map(Some).ok_or_else(|| ParseUrlError { path: format!("{prefix}{key}"), value: value.clone() }),
+            None => Ok(None),
+        };
+
+        Ok(Urls {
+            license_url: parse("license-url", license_url)?,
+            repository: parse("repository", repository)?,
+            repository_artifact: parse("repository-artifact", repository_artifact)?,
+            repository_code: parse("repository-code", repository_code)?,
+            url: parse("url", url)?,
+        })
+    }
+}
+
+/// Parses a URL, returning `None` if it is malformed or uses a scheme other than those allowed by the schema.
+fn parse(value: &str) -> Option<Url> {
+    Url::parse(value).ok().filter(|url| SCHEMES.contains(&url.scheme()) && url.has_host())
+}
+
+impl Citation {
+    /// Parses the URLs of the citation (`license-url`, `repository`, `repository-artifact`, `repository-code` and
+    /// `url`), failing at the first value that is not a URL with a scheme allowed by the schema.
+    pub fn urls(&self) -> Result<Urls, ParseUrlError> {
+        Urls::parse("", [&self.license_url, &self.repository, &self.repository_artifact, &self.repository_code, &self.url])
+    }
+
+    /// Checks the URLs of the citation, its preferred citation and its references, see [`Citation::urls`].
+    pub(crate) fn check_urls(&self) -> Result<(), ParseUrlError> {
+        self.urls()?;
+
+        let references = self.preferred_citation.iter().map(|reference| ("preferred-citation.".to_string(), reference));
+        let references = references.chain(self.references.iter().enumerate().map(|(index, reference)| (format!("references[{index}]."), reference)));
+        for (prefix, reference) in references {
+            reference.urls_at(&prefix)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Reference {
+    /// Parses the URLs of the reference, see [`Citation::urls`].
+    pub fn urls(&self) -> Result<Urls, ParseUrlError> {
+        self.urls_at("")
+    }
+
+    fn urls_at(&self, prefix: &str) -> Result<Urls, ParseUrlError> {
+        Urls::parse(prefix, [&self.license_url, &self.repository, &self.repository_artifact, &self.repository_code, &self.url])
+    }
+}
@@ -0,0 +1,719 @@
+use std::{error::Error, fmt, sync::Arc};
+
+use crate::{
+    identifier::is_url,
+    keywords::folded,
+    span::{locate, Source},
+    Span, Author, CffVersion, Citation, Date, Entity, Identifier, Licenses, Orcid, Person, Reference,
+};
+
+/// The result of [`Citation::validate`], listing every problem found in the citation.
+#[derive(Debug, Clone, Default)]
+pub struct ValidationReport {
+    pub(crate) issues: Vec<Issue>,
+    pub(crate) source: Option<Arc<Source>>,
+}
+
+impl ValidationReport {
+    pub(crate) fn new(issues: Vec<Issue>) -> Self {
+        ValidationReport { issues, source: None }
+    }
+
+    /// Attaches the source the citation has been parsed from, which resolves the
+    /// [`Issue::span`] of every issue and is shown in diagnostics.
+    pub fn with_source(mut self, name: impl Into<String>, content: impl Into<String>) -> Self {
+        let source = Source::new(name, content);
+
+        for issue in &mut self.issues {
+            issue.span = locate(&source.content, &issue.path);
+        }
+
+        self.source = Some(Arc::new(source));
+        self
+    }
+
+    /// Returns `true` if no errors have been found. Warnings and hints do not make a citation invalid.
+    pub fn is_valid(&self) -> bool {
+        !self.has_errors()
+    }
+
+    /// Returns the report if no errors have been found, or [`Error::Validation`](crate::Error::Validation) otherwise,
+    /// so that invalid citations can be rejected with `?`.
+    pub fn into_result(self) -> Result<Self, crate::Error> {
+        match self.has_errors() {
+            true => Err(crate::Error::Validation(self)),
+            false => Ok(self),
+        }
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.issues.iter().any(|issue| issue.severity == Severity::Error)
+    }
+
+    pub fn has_warnings(&self) -> bool {
+        self.issues.iter().any(|issue| issue.severity == Severity::Warning)
+    }
+
+    /// Returns the issues with exactly the given severity.
+    pub fn with_severity(&self, severity: Severity) -> impl Iterator<Item = &Issue> {
+        self.issues.iter().filter(move |issue| issue.severity == severity)
+    }
+
+    /// Returns the issues with the given severity or a more severe one.
+    pub fn at_least(&self, severity: Severity) -> impl Iterator<Item = &Issue> {
+        self.issues.iter().filter(move |issue| issue.severity >= severity)
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &Issue> {
+        self.with_severity(Severity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &Issue> {
+        self.with_severity(Severity::Warning)
+    }
+
+    pub fn hints(&self) -> impl Iterator<Item = &Issue> {
+        self.with_severity(Severity::Hint)
+    }
+
+    pub fn issues(&self) -> &[Issue] {
+        &self.issues
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Issue> {
+        self.issues.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.issues.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+impl<'a> IntoIterator for &'a ValidationReport {
+    type Item = &'a Issue;
+    type IntoIter = std::slice::Iter<'a, Issue>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let count = |severity| self.with_severity(severity).count();
+        let plural = |count: usize| if count == 1 { "" } else { "s" };
+
+        let (errors, warnings, hints) = (count(Severity::Error), count(Severity::Warning), count(Severity::Hint));
+        write!(f, "{errors} error{}, {warnings} warning{}, {hints} hint{}", plural(errors), plural(warnings), plural(hints))
+    }
+}
+
+impl Error for ValidationReport {}
+
+/// A single problem found during validation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Issue {
+    /// The kind of problem.
+    pub code: Code,
+
+    /// How severe the problem is.
+    pub severity: Severity,
+
+    /// The path of the offending key, e.g. `authors[2].orcid`.
+    pub path: String,
+
+    /// A human readable description of the problem.
+    pub message: String,
+
+    /// The region of the offending key in the source, if the source has been attached
+    /// with [`ValidationReport::with_source`] and the key could be located.
+    pub span: Option<Span>,
+}
+
+impl Issue {
+    /// Creates an issue with the default severity of the code, e.g. in a custom [`LintRule`](crate::lint::LintRule).
+    pub fn new(code: Code, path: impl Into<String>, message: impl Into<String>) -> Self {
+        Issue { code, severity: code.severity(), path: path.into(), message: message.into(), span: None }
+    }
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}[{}] {}: {}", self.severity, self.code, self.path, self.message)
+    }
+}
+
+impl Error for Issue {}
+
+/// How severe a validation problem is.
+///
+/// Severities are ordered from [`Severity::Hint`] to [`Severity::Error`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// A suggestion to improve the citation.
+    Hint,
+
+    /// A problem that does not violate the schema, but likely is a mistake.
+    Warning,
+
+    /// A violation of the schema.
+    Error,
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Hint => "hint",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The kind of a validation problem.
+///
+/// The string representation returned by [`Code::as_str`] is stable and can be used to
+/// match on specific problems, e.g. in CI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Code {
+    /// `authors` (of the citation or of a reference) is empty.
+    EmptyAuthors,
+
+    /// `title` (of the citation or of a reference) is empty.
+    EmptyTitle,
+
+    /// `cff-version` is not a version in the format `MAJOR.MINOR.PATCH`.
+    InvalidCffVersion,
+
+    /// An `orcid` is not an ORCID URL, e.g. `https://orcid.org/0000-0002-1825-0097`, or has an invalid check digit.
+    InvalidOrcid,
+
+    /// A `month` or `year` of a reference is not a valid value.
+    InvalidDate,
+
+    /// A URL does not use one of the schemes allowed by the schema (`http`, `https`, `ftp`, `sftp`).
+    InvalidUrl,
+
+    /// An identifier does not match the format required for its type.
+    InvalidIdentifier,
+
+    /// `message` is empty.
+    EmptyMessage,
+
+    /// A `license` uses an identifier deprecated by the SPDX License List.
+    DeprecatedLicense,
+
+    /// The citation has neither a `doi` nor an identifier of type `doi`.
+    MissingDoi,
+
+    /// The title, authors or year differ from the metadata registered for the DOI.
+    DoiMismatch,
+
+    /// The name of a person differs from the name registered for their ORCID iD.
+    OrcidMismatch,
+
+    /// `cff-version` is a valid version, but not one of the versions supported by this crate.
+    UnsupportedCffVersion,
+
+    /// A key required by the schema is missing, e.g. `title` (see [`Citation::read_lossy`]), or a key required by the
+    /// version of the schema, e.g. `date-released` before 1.2.0.
+    MissingKey,
+
+    /// A key or value is not available in the version of the schema, e.g. `preferred-citation` before 1.2.0.
+    UnavailableKey,
+
+    /// A key is not defined by the schema, see [`Document::compatibility`](crate::Document::compatibility).
+    UnknownKey,
+
+    /// A value cannot be read and is ignored, see [`Document::compatibility`](crate::Document::compatibility) and
+    /// [`Citation::read_lossy`].
+    IgnoredValue,
+
+    /// A keyword is empty.
+    EmptyKeyword,
+
+    /// A keyword appears more than once, ignoring case and surrounding whitespace.
+    DuplicateKeyword,
+
+    /// A `date-released` or `date-published` lies in the future.
+    FutureDate,
+
+    /// An `email` is not a valid email address.
+    InvalidEmail,
+
+    /// `date-released` is older than the first commit, or differs from the date of the tag of `version`.
+    InconsistentDate,
+
+    /// A license is not an identifier from the SPDX License List.
+    UnknownLicense,
+
+    /// A value violates the JSON Schema of the `cff-version`, see `Document::validate_against_schema`.
+    SchemaViolation,
+
+    /// A problem reported by a custom [`LintRule`](crate::lint::LintRule), with the name of the rule.
+    Custom(&'static str),
+}
+
+impl Code {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Code::EmptyAuthors => "empty-authors",
+            Code::EmptyTitle => "empty-title",
+            Code::InvalidCffVersion => "invalid-cff-version",
+            Code::InvalidOrcid => "invalid-orcid",
+            Code::InvalidDate => "invalid-date",
+            Code::InvalidUrl => "invalid-url",
+            Code::InvalidIdentifier => "invalid-identifier",
+            Code::EmptyMessage => "empty-message",
+            Code::DeprecatedLicense => "deprecated-license",
+            Code::MissingDoi => "missing-doi",
+            Code::DoiMismatch => "doi-mismatch",
+            Code::OrcidMismatch => "orcid-mismatch",
+            Code::UnsupportedCffVersion => "unsupported-cff-version",
+            Code::MissingKey => "missing-key",
+            Code::UnavailableKey => "unavailable-key",
+            Code::UnknownKey => "unknown-key",
+            Code::IgnoredValue => "ignored-value",
+            Code::EmptyKeyword => "empty-keyword",
+            Code::DuplicateKeyword => "duplicate-keyword",
+            Code::FutureDate => "future-date",
+            Code::InvalidEmail => "invalid-email",
+            Code::InconsistentDate => "inconsistent-date",
+            Code::UnknownLicense => "unknown-license",
+            Code::SchemaViolation => "schema-violation",
+            Code::Custom(name) => name,
+        }
+    }
+
+    /// Returns a suggestion on how to fix problems of this kind.
+    pub fn help(&self) -> &'static str {
+        match self {
+            Code::EmptyAuthors => "add at least one person (`given-names`, `family-names`) or entity (`name`) to `authors`",
+            Code::EmptyTitle => "set `title` to the name of the work",
+            Code::InvalidCffVersion => "use the version of the schema the file adheres to, e.g. `1.2.0`",
+            Code::InvalidOrcid => "use the full ORCID URL, e.g. `https://orcid.org/0000-0002-1825-0097`",
+            Code::InvalidDate => "use a month between 1 and 12 and a numeric year",
+            Code::InvalidUrl => "use a URL starting with `https://`, `http://`, `ftp://` or `sftp://`",
+            Code::InvalidIdentifier => "make sure the value matches the `type` of the identifier",
+            Code::EmptyMessage => "tell readers how to cite the work, e.g. `If you use this software, please cite it using the metadata from this file.`",
+            Code::DeprecatedLicense => "use the current SPDX identifier, e.g. `GPL-3.0-only` or `GPL-3.0-or-later` instead of `GPL-3.0`",
+            Code::MissingDoi => "archive the work (e.g. on Zenodo) and add the DOI with the `doi` key",
+            Code::DoiMismatch => "update the metadata to match the metadata registered for the DOI, or correct the DOI",
+            Code::OrcidMismatch => "use the name from the ORCID record, or correct the ORCID iD",
+            Code::UnsupportedCffVersion => "use one of the supported versions of the schema, preferably `1.2.0`",
+            Code::MissingKey => "add the key; keys only required by older versions of the schema can also be dropped by updating `cff-version` to `1.2.0`",
+            Code::UnavailableKey => "update `cff-version` to `1.2.0`, or remove the key",
+            Code::UnknownKey => "check the spelling of the key, or update the crate if it was added in a newer version of the schema",
+            Code::IgnoredValue => "use a value defined by the schema, or update the crate if it was added in a newer version of the schema",
+            Code::EmptyKeyword => "remove the empty keyword",
+            Code::DuplicateKeyword => "remove the duplicate, e.g. with `Citation::normalize_keywords`",
+            Code::FutureDate => "use the date the work was actually released or published",
+            Code::InvalidEmail => "use an address of the form `name@example.org`, without a display name or `mailto:`",
+            Code::InconsistentDate => "use the date of the release, e.g. with `Citation::fill_from_git`",
+            Code::UnknownLicense => "use an identifier from https://spdx.org/licenses/, or link other licenses with `license-url`",
+            Code::SchemaViolation => "change the value to match the schema, see https://github.com/citation-file-format/citation-file-format/blob/main/schema-guide.md",
+            Code::Custom(_) => "see the documentation of the rule",
+        }
+    }
+
+    /// Returns the severity with which problems of this kind are reported.
+    pub fn severity(&self) -> Severity {
+        match self {
+            Code::EmptyMessage | Code::DeprecatedLicense | Code::DoiMismatch | Code::OrcidMismatch | Code::UnsupportedCffVersion => Severity::Warning,
+            Code::UnknownKey | Code::IgnoredValue | Code::EmptyKeyword | Code::DuplicateKeyword | Code::FutureDate | Code::Custom(_) => Severity::Warning,
+            Code::InconsistentDate => Severity::Warning,
+            Code::MissingDoi => Severity::Hint,
+            _ => Severity::Error,
+        }
+    }
+}
+
+impl fmt::Display for Code {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Citation {
+    /// Validates the citation against the constraints of the schema that are not
+    /// already enforced while parsing, collecting all problems instead of stopping at the first.
+    pub fn validate(&self) -> ValidationReport {
+        let mut validator = Validator::default();
+        validator.citation(self);
+
+        ValidationReport::new(validator.issues)
+    }
+
+    /// Compares the title, authors and year of the citation, its preferred citation and its references with the
+    /// metadata registered for their DOIs, which is looked up with the given function.
+    #[cfg(feature = "http")]
+    pub(crate) fn validate_registered<E>(&self, mut lookup: impl FnMut(&crate::Doi) -> Result<Reference, E>) -> Result<ValidationReport, E> {
+        let mut validator = Validator::default();
+
+        if let Some(doi) = &self.doi {
+            let year = self.date_released.map(|date| ("date-released", date.year().to_string()));
+            validator.registered("", &self.title, &self.authors, year, &lookup(doi)?);
+        }
+
+        let references = self.preferred_citation.iter().map(|reference| ("preferred-citation".to_string(), reference));
+        let references = references.chain(self.references.iter().enumerate().map(|(index, reference)| (format!("references[{index}]"), reference)));
+        for (path, reference) in references {
+            if let Some(doi) = &reference.doi {
+                let year = match (&reference.year, reference.date_published) {
+                    (Some(year), _) => Some(("year", year.to_string())),
+                    (None, Some(date)) => Some(("date-published", date.year().to_string())),
+                    (None, None) => None,
+                };
+                validator.registered(&format!("{path}."), &reference.title, &reference.authors, year, &lookup(doi)?);
+            }
+        }
+
+        Ok(ValidationReport::new(validator.issues))
+    }
+}
+
+#[derive(Default)]
+struct Validator {
+    issues: Vec<Issue>,
+}
+
+impl Validator {
+    fn issue(&mut self, code: Code, path: impl Into<String>, message: impl Into<String>) {
+        self.issues.push(Issue::new(code, path, message));
+    }
+
+    fn date(&mut self, path: &str, date: Option<Date>) {
+        if let Some(date) = date.filter(|date| *date > Date::today()) {
+            self.issue(Code::FutureDate, path, format!("{date} lies in the future"));
+        }
+    }
+
+    fn citation(&mut self, citation: &Citation) {
+        match citation.schema_version() {
+            Some(version) => self.schema_version(citation, version),
+            None if is_version(&citation.cff_version) => {
+                let message = format!("`{}` is not a supported version, the file is read as {}", citation.cff_version, CffVersion::LATEST);
+                self.issue(Code::UnsupportedCffVersion, "cff-version", message);
+            }
+            None => self.issue(Code::InvalidCffVersion, "cff-version", format!("`{}` is not a valid version", citation.cff_version)),
+        }
+
+        if citation.title.trim().is_empty() {
+            self.issue(Code::EmptyTitle, "title", "title must not be empty");
+        }
+
+        if citation.authors.is_empty() {
+            self.issue(Code::EmptyAuthors, "authors", "at least one author is required");
+        }
+
+        if citation.message.trim().is_empty() {
+            self.issue(Code::EmptyMessage, "message", "message should tell readers how to cite the work");
+        }
+
+        if citation.doi.is_none() && !citation.identifiers.iter().any(|identifier| matches!(identifier, Identifier::Doi { .. })) {
+            self.issue(Code::MissingDoi, "doi", "consider adding a DOI, so the work can be cited persistently");
+        }
+
+        self.licenses("license", &citation.license);
+        self.keywords("", &citation.keywords);
+        self.date("date-released", citation.date_released);
+
+        self.authors("authors", &citation.authors);
+        self.authors("contact", &citation.contact);
+
+        for (index, identifier) in citation.identifiers.iter().enumerate() {
+            if let Err(error) = identifier.validate() {
+                self.issue(Code::InvalidIdentifier, format!("identifiers[{index}].value"), error.to_string());
+            }
+        }
+
+        self.url("license-url", &citation.license_url);
+        self.url("repository", &citation.repository);
+        self.url("repository-artifact", &citation.repository_artifact);
+        self.url("repository-code", &citation.repository_code);
+        self.url("url", &citation.url);
+
+        if let Some(reference) = &citation.preferred_citation {
+            self.reference("preferred-citation", reference);
+        }
+
+        for (index, reference) in citation.references.iter().enumerate() {
+            self.reference(&format!("references[{index}]"), reference);
+        }
+    }
+
+    /// Checks the differences of older versions of the schema: `version` and `date-released` are required,
+    /// and keys added later are not available.
+    fn schema_version(&mut self, citation: &Citation, version: CffVersion) {
+        if version.requires_release() {
+            if citation.version.is_none() {
+                self.issue(Code::MissingKey, "version", format!("`version` is required by cff-version {version}"));
+            }
+            if citation.date_released.is_none() {
+                self.issue(Code::MissingKey, "date-released", format!("`date-released` is required by cff-version {version}"));
+            }
+        }
+
+        let keys = [
+            ("type", citation.r#type.is_some()),
+            ("identifiers", !citation.identifiers.is_empty()),
+            ("preferred-citation", citation.preferred_citation.is_some()),
+        ];
+        for (key, _) in keys.into_iter().filter(|(key, present)| *present && !version.defines(key)) {
+            self.issue(Code::UnavailableKey, key, format!("`{key}` is not available in cff-version {version}"));
+        }
+
+        if version < CffVersion::V1_2 && matches!(citation.license, Some(Licenses::Multiple(_))) {
+            self.issue(Code::UnavailableKey, "license", format!("a list of licenses is not available in cff-version {version}"));
+        }
+    }
+
+    fn reference(&mut self, path: &str, reference: &Reference) {
+        if reference.title.trim().is_empty() {
+            self.issue(Code::EmptyTitle, format!("{path}.title"), "title must not be empty");
+        }
+
+        if reference.authors.is_empty() {
+            self.issue(Code::EmptyAuthors, format!("{path}.authors"), "at least one author is required");
+        }
+
+        self.authors(&format!("{path}.authors"), &reference.authors);
+        self.authors(&format!("{path}.contact"), &reference.contact);
+        self.authors(&format!("{path}.editors"), &reference.editors);
+        self.authors(&format!("{path}.editors-series"), &reference.editors_series);
+        self.authors(&format!("{path}.recipients"), &reference.recipients);
+        self.authors(&format!("{path}.senders"), &reference.senders);
+        self.authors(&format!("{path}.translators"), &reference.translators);
+
+        let entities = [
+            ("conference", &reference.conference),
+            ("database-provider", &reference.database_provider),
+            ("institution", &reference.institution),
+            ("location", &reference.location),
+            ("publisher", &reference.publisher),
+        ];
+        for (key, entity) in entities {
+            if let Some(entity) = entity {
+                self.entity(&format!("{path}.{key}"), entity);
+            }
+        }
+
+        self.licenses(&format!("{path}.license"), &reference.license);
+        self.keywords(&format!("{path}."), &reference.keywords);
+        self.date(&format!("{path}.date-released"), reference.date_released);
+        self.date(&format!("{path}.date-published"), reference.date_published);
+
+        for (index, identifier) in reference.identifiers.iter().enumerate() {
+            if let Err(error) = identifier.validate() {
+                self.issue(Code::InvalidIdentifier, format!("{path}.identifiers[{index}].value"), error.to_string());
+            }
+        }
+
+        if let Some(month) = &reference.month {
+            if !month.parse::<u8>().is_ok_and(|month| (1..=12).contains(&month)) {
+                self.issue(Code::InvalidDate, format!("{path}.month"), format!("`{month}` is not a month between 1 and 12"));
+            }
+        }
+
+        for (key, year) in [("year", &reference.year), ("year-original", &reference.year_original)] {
+            if let Some(year) = year {
+                if year.parse::<i32>().is_err() {
+                    self.issue(Code::InvalidDate, format!("{path}.{key}"), format!("`{year}` is not a valid year"));
+                }
+            }
+        }
+
+        self.url(&format!("{path}.license-url"), &reference.license_url);
+        self.url(&format!("{path}.repository"), &reference.repository);
+        self.url(&format!("{path}.repository-artifact"), &reference.repository_artifact);
+        self.url(&format!("{path}.repository-code"), &reference.repository_code);
+        self.url(&format!("{path}.url"), &reference.url);
+    }
+
+    fn authors(&mut self, path: &str, authors: &[Author]) {
+        for (index, author) in authors.iter().enumerate() {
+            match author {
+                Author::Entity(entity) => self.entity(&format!("{path}[{index}]"), entity),
+                Author::Person(person) => self.person(&format!("{path}[{index}]"), person),
+            }
+        }
+    }
+
+    fn person(&mut self, path: &str, person: &Person) {
+        self.orcid(&format!("{path}.orcid"), &person.orcid);
+        self.url(&format!("{path}.website"), &person.website);
+        self.email(&format!("{path}.email"), &person.email);
+    }
+
+    fn entity(&mut self, path: &str, entity: &Entity) {
+        self.orcid(&format!("{path}.orcid"), &entity.orcid);
+        self.url(&format!("{path}.website"), &entity.website);
+        self.email(&format!("{path}.email"), &entity.email);
+    }
+
+    fn email(&mut self, path: &str, email: &Option<String>) {
+        let Some(email) = email.as_deref() else { return };
+
+        if let Some(reason) = email_error(email) {
+            self.issue(Code::InvalidEmail, path, format!("`{email}` is not an email address: {reason}"));
+        }
+    }
+
+    fn licenses(&mut self, path: &str, licenses: &Option<Licenses>) {
+        let Some(licenses) = licenses else { return };
+
+        for (index, license) in licenses.iter().enumerate() {
+            let path = match licenses {
+                Licenses::Single(_) => path.to_string(),
+                Licenses::Multiple(_) => format!("{path}[{index}]"),
+            };
+            if license.is_deprecated() {
+                let replacements: Vec<String> = license.replacements().iter().map(|replacement| format!("`{replacement}`")).collect();
+                let message = match replacements.is_empty() {
+                    true => format!("`{license}` is a deprecated SPDX license identifier"),
+                    false => format!("`{license}` is a deprecated SPDX license identifier, use {} instead", replacements.join(" or ")),
+                };
+                self.issue(Code::DeprecatedLicense, path, message);
+            } else if !license.is_known() {
+                let message = match license.suggestion() {
+                    Some(suggestion) => format!("`{license}` is not an SPDX license identifier, did you mean `{suggestion}`?"),
+                    None => format!("`{license}` is not an SPDX license identifier"),
+                };
+                self.issue(Code::UnknownLicense, path, message);
+            }
+        }
+    }
+
+    fn orcid(&mut self, path: &str, orcid: &Option<String>) {
+        let Some(orcid) = orcid.as_deref() else { return };
+
+        if !is_orcid(orcid) {
+            self.issue(Code::InvalidOrcid, path, format!("`{orcid}` is not an ORCID URL"));
+        } else if let Err(error) = Orcid::new(orcid) {
+            self.issue(Code::InvalidOrcid, path, error.to_string());
+        }
+    }
+
+    /// Reports the differences between the title, authors and year (as `(key, year)`) of a work and the registered metadata.
+    #[cfg(feature = "http")]
+    fn registered(&mut self, prefix: &str, title: &str, authors: &[Author], year: Option<(&str, String)>, registered: &Reference) {
+        if normalize(title) != normalize(&registered.title) {
+            self.issue(Code::DoiMismatch, format!("{prefix}title"), format!("`{title}` differs from the registered title `{}`", registered.title));
+        }
+
+        let names = |authors: &[Author]| authors.iter().map(|author| normalize(&family_name(author))).collect::<Vec<_>>();
+        let (local, remote) = (names(authors), names(&registered.authors));
+        let missing: Vec<_> = registered.authors.iter().zip(&remote).filter(|(_, name)| !local.contains(name)).map(|(author, _)| family_name(author)).collect();
+        let unknown: Vec<_> = authors.iter().zip(&local).filter(|(_, name)| !remote.contains(name)).map(|(author, _)| family_name(author)).collect();
+        if !missing.is_empty() || !unknown.is_empty() {
+            let list = |names: Vec<String>| names.iter().map(|name| format!("`{name}`")).collect::<Vec<_>>().join(", ");
+            let message = match (missing.is_empty(), unknown.is_empty()) {
+                (false, true) => format!("the registered authors {} are missing", list(missing)),
+                (true, false) => format!("the authors {} are not registered", list(unknown)),
+                _ => format!("the registered authors {} are missing and the authors {} are not registered", list(missing), list(unknown)),
+            };
+            self.issue(Code::DoiMismatch, format!("{prefix}authors"), message);
+        }
+
+        let registered_year = registered.year.as_ref().map(ToString::to_string).or_else(|| registered.date_published.map(|date| date.year().to_string()));
+        if let (Some((key, year)), Some(registered_year)) = (year, registered_year) {
+            if year != registered_year {
+                self.issue(Code::DoiMismatch, format!("{prefix}{key}"), format!("`{year}` differs from the registered year `{registered_year}`"));
+            }
+        }
+    }
+
+    fn keywords(&mut self, prefix: &str, keywords: &[String]) {
+        for (index, keyword) in keywords.iter().enumerate() {
+            let path = format!("{prefix}keywords[{index}]");
+            if keyword.trim().is_empty() {
+                self.issue(Code::EmptyKeyword, path, "keyword must not be empty");
+            } else if let Some(first) = keywords[..index].iter().find(|first| folded(first) == folded(keyword)) {
+                self.issue(Code::DuplicateKeyword, path, format!("`{keyword}` is a duplicate of `{first}`"));
+            }
+        }
+    }
+
+    fn url(&mut self, path: &str, url: &Option<String>) {
+        if let Some(url) = url.as_deref().filter(|url| !is_url(url)) {
+            self.issue(Code::InvalidUrl, path, format!("`{url}` is not a valid URL"));
+        }
+    }
+}
+
+/// Returns the family names of a person, or the name of an entity.
+#[cfg(feature = "http")]
+fn family_name(author: &Author) -> String {
+    match author {
+        Author::Person(Person { family_names: Some(family_names), .. }) => family_names.clone(),
+        _ => author.display_name(),
+    }
+}
+
+/// Normalizes a title or name for comparison, ignoring case, punctuation, whitespace and markup like `<i>`.
+#[cfg(feature = "http")]
+pub(crate) fn normalize(value: &str) -> String {
+    let mut normalized = String::new();
+    let mut in_tag = false;
+    for c in value.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag && c.is_alphanumeric() => normalized.extend(c.to_lowercase()),
+            _ => {}
+        }
+    }
+
+    normalized
+}
+
+/// Matches `MAJOR.MINOR.PATCH`, e.g. `1.2.0`.
+fn is_version(value: &str) -> bool {
+    let parts: Vec<_> = value.split('.').collect();
+
+    parts.len() == 3 && parts.iter().all(|part| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// Returns why the value is not an email address, accepting the common subset of RFC 5322: a dot-atom local part
+/// and a domain name with at least two labels, e.g. `contact@lucalewin.dev`.
+fn email_error(value: &str) -> Option<&'static str> {
+    let Some((local, domain)) = value.rsplit_once('@') else {
+        return Some("it has no `@`");
+    };
+
+    let atext = |c: char| c.is_ascii_alphanumeric() || "!#$%&'*+-/=?^_`{|}~".contains(c);
+    if local.is_empty() || local.len() > 64 {
+        Some("the part before the `@` must have between 1 and 64 characters")
+    } else if local.split('.').any(|atom| atom.is_empty() || !atom.chars().all(atext)) {
+        Some("the part before the `@` contains a character that is not allowed or a misplaced `.`")
+    } else if domain.len() > 253 || !domain.contains('.') {
+        Some("the domain must be a name like `example.org`")
+    } else if domain.split('.').any(|label| label.is_empty() || label.len() > 63 || label.starts_with('-') || label.ends_with('-') || !label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')) {
+        Some("the domain contains a character that is not allowed or a misplaced `.` or `-`")
+    } else {
+        None
+    }
+}
+
+/// Matches the ORCID pattern of the schema: `https://orcid\.org/[0-9]{4}-[0-9]{4}-[0-9]{4}-[0-9]{3}[0-9X]{1}`
+fn is_orcid(value: &str) -> bool {
+    let Some(id) = value.strip_prefix("https://orcid.org/") else {
+        return false;
+    };
+
+    let groups: Vec<_> = id.split('-').collect();
+
+    groups.len() == 4
+        && groups.iter().all(|group| group.len() == 4)
+        && groups.iter().flat_map(|group| group.bytes()).enumerate().all(|(index, b)| b.is_ascii_digit() || (index == 15 && b == b'X'))
+}
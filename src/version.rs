@@ -0,0 +1,93 @@
+use std::{error::Error, fmt, str::FromStr};
+
+use crate::Citation;
+
+/// The top-level keys added in 1.2.0.
+const ADDED_IN_1_2: &[&str] = &["identifiers", "preferred-citation", "type"];
+
+/// A version of the Citation File Format schema.
+///
+/// Patch releases of the schema only fix the documentation, so they are represented by their minor version,
+/// e.g. `1.0.1`, `1.0.2` and `1.0.3` are all [`CffVersion::V1_0`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CffVersion {
+    /// `1.0.x`, the first release of the schema.
+    V1_0,
+
+    /// `1.1.0`.
+    V1_1,
+
+    /// `1.2.0`, which added `type`, `identifiers` and `preferred-citation`, allows a list of licenses and no
+    /// longer requires `version` and `date-released`.
+    V1_2,
+}
+
+impl CffVersion {
+    /// The latest version of the schema, which is implemented by this crate.
+    pub const LATEST: CffVersion = CffVersion::V1_2;
+
+    /// Returns the latest release of the version, e.g. `1.0.3` for [`CffVersion::V1_0`].
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CffVersion::V1_0 => "1.0.3",
+            CffVersion::V1_1 => "1.1.0",
+            CffVersion::V1_2 => "1.2.0",
+        }
+    }
+
+    /// Returns `false` if the top-level key was only added in a later version. Keys that are not defined by any
+    /// version are left to [`ParseOptions::strict`](crate::ParseOptions::strict).
+    pub(crate) fn defines(&self, key: &str) -> bool {
+        *self >= CffVersion::V1_2 || !ADDED_IN_1_2.contains(&key)
+    }
+
+    /// Returns `true` if the version requires `version` and `date-released`, as versions before 1.2.0 do.
+    pub(crate) fn requires_release(&self) -> bool {
+        *self < CffVersion::V1_2
+    }
+}
+
+/// Generates a supported `cff-version`, so that arbitrary citations are parsed with the schema they were generated for.
+#[cfg(feature = "arbitrary")]
+pub(crate) fn arbitrary_cff_version(u: &mut arbitrary::Unstructured) -> arbitrary::Result<String> {
+    Ok(u.choose(&[CffVersion::V1_0, CffVersion::V1_1, CffVersion::V1_2])?.as_str().to_string())
+}
+
+impl fmt::Display for CffVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for CffVersion {
+    type Err = ParseCffVersionError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim() {
+            "1.0.1" | "1.0.2" | "1.0.3" => Ok(CffVersion::V1_0),
+            "1.1.0" => Ok(CffVersion::V1_1),
+            "1.2.0" => Ok(CffVersion::V1_2),
+            _ => Err(ParseCffVersionError(value.to_string())),
+        }
+    }
+}
+
+/// Error returned when a value is not a version of the schema supported by this crate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseCffVersionError(pub String);
+
+impl fmt::Display for ParseCffVersionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` is not a supported version of the schema (1.0.x, 1.1.0 or 1.2.0)", self.0)
+    }
+}
+
+impl Error for ParseCffVersionError {}
+
+impl Citation {
+    /// Returns the version of the schema the citation adheres to according to `cff-version`, or `None` if the
+    /// version is not supported by this crate.
+    pub fn schema_version(&self) -> Option<CffVersion> {
+        self.cff_version.parse().ok()
+    }
+}
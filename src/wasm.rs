@@ -0,0 +1,82 @@
+//! Bindings for JavaScript, built with the `wasm` feature, e.g. to validate `CITATION.cff` files in the browser.
+//!
+//! Build the module as a `cdylib` without the default features, which leaves out everything that reads or writes
+//! files, and generate the JavaScript glue with `wasm-bindgen`:
+//!
+//! ```sh
+//! cargo rustc --lib --release --target wasm32-unknown-unknown --no-default-features --features wasm --crate-type cdylib
+//! wasm-bindgen --target web --out-dir pkg target/wasm32-unknown-unknown/release/citation.wasm
+//! ```
+//!
+//! The functions take the content of a `CITATION.cff` file, in YAML or JSON.
+//!
+//! ```js
+//! import init, { validate, format } from "citation";
+//!
+//! await init();
+//! const report = JSON.parse(validate(content, false));
+//! const apa = format(content, "apa", "html");
+//! ```
+
+use serde_json::json;
+use wasm_bindgen::prelude::*;
+
+use crate::{bindings, parse::parse, Citation, ParseOptions};
+
+#[wasm_bindgen]
+extern "C" {
+    /// Returns the current time in milliseconds since the Unix epoch, as `SystemTime::now` panics in the browser.
+    #[wasm_bindgen(js_namespace = Date, js_name = now)]
+    pub(crate) fn now() -> f64;
+}
+
+/// Parses the content of a `CITATION.cff` file, with the error as a JavaScript `Error`.
+fn citation(content: &str) -> Result<Citation, JsError> {
+    Ok(parse(content, &ParseOptions::default())?)
+}
+
+/// Parses and validates the content of a `CITATION.cff` file, rejecting keys that are not defined by the schema if
+/// `strict` is set, and returns the report as JSON, see [`ValidationReport::to_json`](crate::ValidationReport::to_json).
+///
+/// A file that cannot be parsed is reported as `{ "valid": false, "error": "...", "line": 3, "column": 5 }`, like
+/// `citation validate --format json` does.
+#[wasm_bindgen]
+pub fn validate(content: &str, strict: bool) -> String {
+    let options = match strict {
+        true => ParseOptions::strict(),
+        false => ParseOptions::default(),
+    };
+
+    match parse(content, &options) {
+        Ok(citation) => citation.validate().with_source("CITATION.cff", content).to_json(),
+        Err(error) => {
+            let report = json!({ "file": "CITATION.cff", "valid": false, "error": error.to_string(), "line": error.line, "column": error.column });
+            serde_json::to_string_pretty(&report).unwrap_or_default()
+        }
+    }
+}
+
+/// Formats the citation in a style, one of `apa`, `ieee`, `chicago`, `mla`, `harvard` and `vancouver`, as `plain`
+/// text, `markdown`, `html` or `latex`, see [`Citation::format_as`].
+#[wasm_bindgen]
+pub fn format(content: &str, style: &str, output: &str) -> Result<String, JsError> {
+    let Some(style) = bindings::style(style) else {
+        return Err(JsError::new(&format!("unknown style `{style}`")));
+    };
+    let Some(output) = bindings::output(output) else {
+        return Err(JsError::new(&format!("unknown output format `{output}`")));
+    };
+
+    Ok(citation(content)?.format_as(style, output))
+}
+
+/// Converts the citation to another format, named like the formats of `citation convert`: `cff`, `bibtex`,
+/// `biblatex`, `ris`, `csl-json`, `codemeta`, `zenodo`, `json-ld`, `datacite-xml`, `datacite-json`, `dublin-core` or
+/// `endnote`.
+#[wasm_bindgen]
+pub fn convert(content: &str, to: &str) -> Result<String, JsError> {
+    match bindings::convert(&citation(content)?, to) {
+        Some(converted) => Ok(converted?),
+        None => Err(JsError::new(&format!("cannot convert to {to}"))),
+    }
+}
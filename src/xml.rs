@@ -0,0 +1,74 @@
+//! A minimal XML writer for the XML-based export formats.
+
+use std::fmt::Write;
+
+/// An XML element. Elements without text or non-empty children are left out when rendered, even if they have attributes.
+pub(crate) struct Element {
+    name: &'static str,
+    attributes: Vec<(&'static str, String)>,
+    text: Option<String>,
+    children: Vec<Element>,
+}
+
+impl Element {
+    /// Creates an element containing the given text, or an empty element if there is none.
+    pub(crate) fn text(name: &'static str, text: Option<&str>) -> Self {
+        let text = text.map(str::trim).filter(|text| !text.is_empty()).map(String::from);
+
+        Element { name, attributes: Vec::new(), text, children: Vec::new() }
+    }
+
+    /// Creates an element containing the given children.
+    pub(crate) fn group(name: &'static str, children: Vec<Element>) -> Self {
+        Element { name, attributes: Vec::new(), text: None, children }
+    }
+
+    /// Adds an attribute to the element.
+    pub(crate) fn attribute(mut self, name: &'static str, value: impl Into<String>) -> Self {
+        self.attributes.push((name, value.into()));
+        self
+    }
+
+    fn is_empty(&self) -> bool {
+        self.text.is_none() && self.children.iter().all(Element::is_empty)
+    }
+
+    /// Renders the element indented by two spaces per level, followed by a line break.
+    pub(crate) fn render(&self, output: &mut String, depth: usize) {
+        if self.is_empty() {
+            return;
+        }
+
+        let indent = "  ".repeat(depth);
+        let _ = write!(output, "{indent}<{}", self.name);
+        for (name, value) in &self.attributes {
+            let _ = write!(output, " {name}=\"{}\"", escape(value));
+        }
+
+        match &self.text {
+            Some(text) => {
+                let _ = writeln!(output, ">{}</{}>", escape(text), self.name);
+            }
+            None => {
+                output.push_str(">\n");
+                for child in &self.children {
+                    child.render(output, depth + 1);
+                }
+                let _ = writeln!(output, "{indent}</{}>", self.name);
+            }
+        }
+    }
+
+    /// Renders the element as a complete XML document.
+    pub(crate) fn document(&self) -> String {
+        let mut output = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        self.render(&mut output, 0);
+
+        output
+    }
+}
+
+/// Escapes the characters with a special meaning in XML.
+pub(crate) fn escape(value: &str) -> String {
+    value.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;").replace('\'', "&apos;")
+}
@@ -0,0 +1,285 @@
+use serde::{de::Error as _, Deserialize};
+use serde_json::{json, Map, Value};
+
+use crate::{
+    jsonld::insert,
+    ris::name,
+    Author, Citation, Date, Doi, Entity, Identifier, License, Licenses, Orcid, Person, Type, CFF_VERSION,
+};
+
+/// Converts an author into a Zenodo creator, with the name written as `Family, Given`.
+fn creator(author: &Author) -> Value {
+    let mut object = Map::new();
+    object.insert("name".into(), name(author).into());
+
+    if let Author::Person(person) = author {
+        insert(&mut object, "affiliation", person.affiliation.as_deref());
+        insert(&mut object, "orcid", person.orcid.as_deref().and_then(|orcid| orcid.parse::<Orcid>().ok()).as_ref().map(Orcid::id));
+    }
+
+    Value::Object(object)
+}
+
+fn related(identifier: &str, relation: &str, scheme: &str) -> Value {
+    json!({ "identifier": identifier, "relation": relation, "scheme": scheme })
+}
+
+/// Converts the citation to the metadata of a Zenodo deposition, as used by `.zenodo.json` and the deposition API.
+pub(crate) fn metadata(citation: &Citation) -> Map<String, Value> {
+    let mut object = Map::new();
+    object.insert("title".into(), citation.title.clone().into());
+    let upload_type = match citation.r#type {
+        Some(Type::Dataset) => "dataset",
+        _ => "software",
+    };
+    object.insert("upload_type".into(), upload_type.into());
+    object.insert("creators".into(), citation.authors.iter().map(creator).collect());
+    // Zenodo requires a description, so the title is used if there is no abstract
+    object.insert("description".into(), citation.r#abstract.as_deref().unwrap_or(&citation.title).into());
+    insert(&mut object, "version", citation.version.as_deref());
+    insert(&mut object, "publication_date", citation.date_released.map(|date| date.to_string()).as_deref());
+    object.insert("access_right".into(), "open".into());
+    // Zenodo accepts a single license per deposition
+    insert(&mut object, "license", citation.licenses().first().map(|license| license.to_string()).as_deref());
+    if !citation.keywords.is_empty() {
+        object.insert("keywords".into(), citation.keywords.clone().into());
+    }
+
+    let mut related_identifiers = Vec::new();
+    if let Some(url) = &citation.repository_code {
+        related_identifiers.push(related(url, "isSupplementTo", "url"));
+    }
+    if let Some(url) = &citation.url {
+        related_identifiers.push(related(url, "isDocumentedBy", "url"));
+    }
+    for identifier in &citation.identifiers {
+        match identifier {
+            Identifier::Doi { value, .. } => related_identifiers.push(related(value, "isIdenticalTo", "doi")),
+            Identifier::Url { value, .. } => related_identifiers.push(related(value, "isIdenticalTo", "url")),
+            Identifier::Swh { value, .. } => related_identifiers.push(related(value, "isIdenticalTo", "swh")),
+            Identifier::Other { .. } => {}
+        }
+    }
+    if let Some(doi) = citation.preferred_citation.as_ref().and_then(|reference| reference.doi.as_ref()) {
+        related_identifiers.push(related(doi.as_str(), "isSupplementTo", "doi"));
+    }
+    for doi in citation.references.iter().filter_map(|reference| reference.doi.as_ref()) {
+        related_identifiers.push(related(doi.as_str(), "references", "doi"));
+    }
+    if !related_identifiers.is_empty() {
+        object.insert("related_identifiers".into(), related_identifiers.into());
+    }
+
+    object
+}
+
+/// A `.zenodo.json` file, limited to the metadata that has a counterpart in the Citation File Format.
+///
+/// https://developers.zenodo.org/#representation
+#[derive(Deserialize)]
+struct ZenodoJson {
+    title: Option<String>,
+    upload_type: Option<String>,
+    #[serde(default)]
+    creators: Vec<Creator>,
+    description: Option<String>,
+    version: Option<String>,
+    publication_date: Option<String>,
+    license: Option<Value>,
+    doi: Option<String>,
+    #[serde(default)]
+    keywords: Vec<String>,
+    #[serde(default)]
+    related_identifiers: Vec<RelatedIdentifier>,
+}
+
+#[derive(Deserialize)]
+struct Creator {
+    name: String,
+    affiliation: Option<String>,
+    orcid: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RelatedIdentifier {
+    identifier: String,
+    relation: String,
+    scheme: Option<String>,
+}
+
+impl Creator {
+    /// Converts a creator into an author, the reverse of [`creator`]: names written as `Family, Given` are persons and
+    /// all other names are entities.
+    fn into_author(self) -> Author {
+        let orcid = self.orcid.and_then(|orcid| orcid.parse::<Orcid>().ok()).map(|orcid| orcid.to_url());
+
+        match self.name.split_once(',') {
+            Some((family, given)) => Author::Person(Person {
+                given_names: Some(given.trim().to_string()).filter(|given| !given.is_empty()),
+                family_names: Some(family.trim().to_string()),
+                affiliation: self.affiliation,
+                orcid,
+                ..Default::default()
+            }),
+            None => Author::Entity(Entity { name: self.name, orcid, ..Default::default() }),
+        }
+    }
+}
+
+impl ZenodoJson {
+    fn into_citation(self) -> Result<Citation, serde_json::Error> {
+        let mut builder = Citation::builder()
+            .cff_version(CFF_VERSION)
+            .authors(self.creators.into_iter().map(Creator::into_author))
+            .keywords(self.keywords);
+
+        if let Some(title) = &self.title {
+            builder = builder.title(title);
+        }
+        if self.upload_type.as_deref() == Some("dataset") {
+            builder = builder.r#type(Type::Dataset);
+        }
+        // the description is the title if the citation had no abstract, see `metadata`
+        if let Some(description) = self.description.filter(|description| Some(description) != self.title.as_ref()) {
+            builder = builder.r#abstract(description);
+        }
+        if let Some(version) = self.version {
+            builder = builder.version(version);
+        }
+        if let Some(date) = self.publication_date.and_then(|date| date.parse::<Date>().ok()) {
+            builder = builder.date_released(date);
+        }
+        // the license is either an id or an object with an id, depending on the version of the API
+        let license = match &self.license {
+            Some(Value::String(id)) => Some(id.as_str()),
+            Some(Value::Object(object)) => object.get("id").and_then(Value::as_str),
+            _ => None,
+        };
+        if let Some(id) = license {
+            builder = builder.license(Licenses::Single(License::from_id(id)));
+        }
+        if let Some(doi) = self.doi.and_then(|doi| Doi::new(doi).ok()) {
+            builder = builder.doi(doi);
+        }
+
+        for related in self.related_identifiers {
+            builder = match (related.relation.as_str(), related.scheme.as_deref()) {
+                ("isSupplementTo", Some("url")) => builder.repository_code(related.identifier),
+                ("isDocumentedBy", Some("url")) => builder.url(related.identifier),
+                ("isIdenticalTo", Some("doi")) => builder.identifier(Identifier::Doi { value: related.identifier, description: None }),
+                ("isIdenticalTo", Some("url")) => builder.identifier(Identifier::Url { value: related.identifier, description: None }),
+                ("isIdenticalTo", Some("swh")) => builder.identifier(Identifier::Swh { value: related.identifier, description: None }),
+                _ => builder,
+            };
+        }
+
+        builder.build().map_err(serde_json::Error::custom)
+    }
+}
+
+impl Citation {
+    /// Parses a `.zenodo.json` file into a citation, the reverse of [`Citation::to_zenodo_json`].
+    ///
+    /// The source code repository, the landing page and identical identifiers are read from the related
+    /// identifiers, while related works such as references are left out. Creators named `Family, Given` become
+    /// persons and all others entities. Fails if the file has no title or no creators.
+    pub fn from_zenodo_json(input: &str) -> Result<Citation, serde_json::Error> {
+        serde_json::from_str::<ZenodoJson>(input)?.into_citation()
+    }
+
+    /// Converts the citation to a `.zenodo.json` file, which Zenodo reads when archiving a GitHub release.
+    ///
+    /// The source code repository, landing page, identifiers, preferred citation and references are exported as
+    /// related identifiers. Only the first license is exported, as Zenodo accepts a single license per deposition.
+    pub fn to_zenodo_json(&self) -> String {
+        serde_json::to_string_pretty(&metadata(self)).unwrap_or_default()
+    }
+}
+
+/// The URL of the Zenodo REST API.
+#[cfg(feature = "http")]
+const API_URL: &str = "https://zenodo.org/api";
+
+/// The URL of the REST API of the [Zenodo sandbox](https://sandbox.zenodo.org), for testing.
+#[cfg(feature = "http")]
+const SANDBOX_API_URL: &str = "https://sandbox.zenodo.org/api";
+
+/// A client for the Zenodo deposition API, which archives software releases and mints DOIs for them.
+///
+/// ```no_run
+/// use citation::{Citation, ZenodoClient};
+///
+/// let citation = Citation::read("CITATION.cff".into()).unwrap();
+/// let client = ZenodoClient::new(std::env::var("ZENODO_TOKEN").unwrap());
+///
+/// let deposition = client.create_deposition(&citation).unwrap();
+/// println!("reserved {}", deposition.doi);
+/// ```
+#[cfg(feature = "http")]
+#[derive(Debug, Clone)]
+pub struct ZenodoClient {
+    url: String,
+    token: String,
+}
+
+/// A Zenodo deposition, as returned by [`ZenodoClient`].
+#[cfg(feature = "http")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deposition {
+    /// The ID of the deposition, used to update it.
+    pub id: u64,
+
+    /// The DOI reserved for the deposition, which becomes active when it is published.
+    pub doi: crate::Doi,
+}
+
+#[cfg(feature = "http")]
+impl ZenodoClient {
+    /// Creates a client for [zenodo.org](https://zenodo.org) with a personal access token with the `deposit:write` scope.
+    pub fn new(token: impl Into<String>) -> Self {
+        ZenodoClient { url: API_URL.to_string(), token: token.into() }
+    }
+
+    /// Creates a client for the [Zenodo sandbox](https://sandbox.zenodo.org), which needs a separate account and token.
+    pub fn sandbox(token: impl Into<String>) -> Self {
+        ZenodoClient { url: SANDBOX_API_URL.to_string(), token: token.into() }
+    }
+
+    /// Uses the API at the given URL instead, e.g. of a self-hosted InvenioRDM instance.
+    pub fn with_api_url(mut self, url: impl Into<String>) -> Self {
+        self.url = url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Creates a new unpublished deposition with the metadata of the citation and reserves a DOI for it.
+    ///
+    /// The reserved DOI can be written to the `CITATION.cff` file before the release is uploaded and published.
+    pub fn create_deposition(&self, citation: &Citation) -> Result<Deposition, crate::Error> {
+        let mut metadata = metadata(citation);
+        metadata.insert("prereserve_doi".into(), true.into());
+
+        self.send(reqwest::Method::POST, format!("{}/deposit/depositions", self.url), metadata)
+    }
+
+    /// Replaces the metadata of an unpublished deposition with the metadata of the citation.
+    pub fn update_deposition(&self, id: u64, citation: &Citation) -> Result<Deposition, crate::Error> {
+        self.send(reqwest::Method::PUT, format!("{}/deposit/depositions/{id}", self.url), metadata(citation))
+    }
+
+    fn send(&self, method: reqwest::Method, url: String, metadata: Map<String, Value>) -> Result<Deposition, crate::Error> {
+        let response = crate::http::client()?
+            .request(method, url)
+            .bearer_auth(&self.token)
+            .header("Content-Type", "application/json")
+            .body(json!({ "metadata": metadata }).to_string())
+            .send()?
+            .error_for_status()?
+            .text()?;
+
+        let response: Value = serde_json::from_str(&response).map_err(|error| crate::ParseError::from_json(&error, None, &response))?;
+        let id = response["id"].as_u64().ok_or_else(|| crate::Error::schema("id", "the response contains no deposition ID"))?;
+        let doi = response["metadata"]["prereserve_doi"]["doi"].as_str().ok_or_else(|| crate::Error::schema("metadata.prereserve_doi.doi", "the response contains no reserved DOI"))?;
+
+        Ok(Deposition { id, doi: crate::Doi::new(doi).map_err(|error| crate::Error::schema("metadata.prereserve_doi.doi", error))? })
+    }
+}
@@ -1,10 +1,3200 @@
-use std::path::PathBuf;
+use std::borrow::Cow;
 
-use citation::Citation;
+use citation::{Author, BuildError, CffVersion, Citation, CitationFormatter, CitationRef, Code, Contact, Date, Document, Doi, Error, Identifier, IdentifierError, IntOrString, KeywordOptions, License, Licenses, MergeConflict, MergeStrategy, ObjectType, Orcid, OutputFormat, ParseOrcidError, ParseOptions, PartialCitation, Person, Reference, ReferenceType, Severity, Status, Style, Swhid, Type, ValidationReport, DEFAULT_MESSAGE};
 
 #[test]
 fn test() {
-    let citation = Citation::read("./tests/CITATION.cff".into());
+    let citation = Citation::read("./tests/CITATION.cff".into()).unwrap();
 
-    println!("{:?}", citation);
-}
\ No newline at end of file
+    assert_eq!(citation.title, "QED: mathematical proof assistant/database");
+    assert_eq!(citation.cff_version, "1.2.0");
+    assert_eq!(citation.authors.len(), 1);
+    let Author::Person(author) = &citation.authors[0] else { panic!("expected a person") };
+    assert_eq!(author.given_names.as_deref(), Some("Luca"));
+    assert_eq!(author.family_names.as_deref(), Some("Lewin"));
+    assert_eq!(citation.version.as_deref(), Some("0.0.0"));
+}
+
+#[test]
+fn builder() {
+    let author = Author::Person(Person {
+        given_names: Some("Luca".into()),
+        family_names: Some("Lewin".into()),
+        ..Default::default()
+    });
+
+    let citation = Citation::builder()
+        .cff_version("1.2.0")
+        .title("citation")
+        .author(author)
+        .build()
+        .unwrap();
+
+    assert_eq!(citation.message, DEFAULT_MESSAGE);
+    assert!(matches!(citation.r#type, Some(Type::Software)));
+
+    let missing = Citation::builder().cff_version("1.2.0").title("citation").build();
+    assert_eq!(missing.unwrap_err(), BuildError::MissingField("authors"));
+}
+
+#[test]
+fn round_trip() {
+    let citation = Citation::read("./tests/CITATION.cff".into()).unwrap();
+    let yaml = citation.to_yaml_string().unwrap();
+
+    assert!(yaml.contains("cff-version: 1.2.0"));
+    assert!(yaml.contains("repository-code: https://github.com/lucalewin/qed"));
+    assert!(yaml.contains("given-names: Luca"));
+    assert!(!yaml.contains("cff_version"));
+
+    let reparsed: Citation = serde_yaml::from_str(&yaml).unwrap();
+    assert_eq!(reparsed.title, citation.title);
+    assert_eq!(reparsed.repository_code, citation.repository_code);
+}
+
+#[test]
+fn references() {
+    let citation = Citation::read("./tests/references.cff".into()).unwrap();
+
+    assert_eq!(citation.references.len(), 2);
+
+    let article = &citation.references[0];
+    assert_eq!(article.r#type, ReferenceType::Article);
+    assert_eq!(article.authors.len(), 2);
+    assert_eq!(article.doi.as_ref().map(Doi::as_str), Some("10.7717/peerj-cs.86"));
+    assert_eq!(article.volume.as_deref(), Some("2"));
+    assert_eq!(article.publisher.as_ref().unwrap().name, "PeerJ");
+    assert!(matches!(article.status, Some(Status::InPress)));
+
+    let yaml = citation.to_yaml_string().unwrap();
+    assert!(yaml.contains("repository-code: https://github.com/serde-rs/serde"));
+    assert!(yaml.contains("status: in-press"));
+
+    let reparsed: Citation = serde_yaml::from_str(&yaml).unwrap();
+    assert_eq!(reparsed.references[1].keywords, ["serialization"]);
+
+    // integers stay integers and strings stay strings
+    assert!(yaml.contains("  volume: 2\n") && yaml.contains("  year: 2016\n") && yaml.contains("  start: e86\n"));
+    assert_eq!((article.year.as_ref().and_then(IntOrString::as_integer), article.start.as_ref().and_then(IntOrString::as_integer)), (Some(2016), None));
+    assert_eq!(reparsed, citation);
+
+    let mut reference = Reference { year: Some(2020i64.into()), month: Some(3u8.into()), volume: Some("12".into()), ..Reference::default() };
+    let yaml = serde_yaml::to_string(&reference).unwrap();
+    assert!(yaml.contains("volume: '12'\n") && yaml.contains("month: 3\n") && yaml.contains("year: 2020\n"), "{yaml}");
+    assert_eq!(serde_yaml::from_str::<Reference>(&yaml).unwrap(), reference);
+    reference.start = Some(1i64.into());
+    let json = serde_json::to_string(&reference).unwrap();
+    assert!(json.contains(r#""start":1"#) && json.contains(r#""volume":"12""#), "{json}");
+    assert_eq!(serde_json::from_str::<Reference>(&json).unwrap(), reference);
+}
+
+#[test]
+fn reference_types() {
+    let known: ReferenceType = serde_yaml::from_str("conference-paper").unwrap();
+    assert_eq!(known, ReferenceType::ConferencePaper);
+    assert_eq!(serde_yaml::to_string(&known).unwrap().trim(), "conference-paper");
+
+    let unknown: ReferenceType = serde_yaml::from_str("hologram").unwrap();
+    assert_eq!(unknown, ReferenceType::Other("hologram".into()));
+    assert_eq!(unknown.as_str(), "hologram");
+}
+
+#[test]
+fn identifiers() {
+    let yaml = r#"
+- type: doi
+  value: 10.5281/zenodo.1003150
+  description: The concept DOI of the work.
+- type: url
+  value: https://github.com/citation-file-format/citation-file-format
+- type: swh
+  value: swh:1:dir:bc286860f423ea7ced246ba7458eef4b4541cf2d
+- type: other
+  value: arXiv:2103.06681
+"#;
+    let identifiers: Vec<Identifier> = serde_yaml::from_str(yaml).unwrap();
+
+    assert_eq!(identifiers.len(), 4);
+    assert_eq!(identifiers[0].description(), Some("The concept DOI of the work."));
+    assert!(identifiers.iter().all(|identifier| identifier.validate().is_ok()));
+
+    let invalid = [
+        Identifier::Doi { value: "doi:10.5281".into(), description: None },
+        Identifier::Url { value: "github.com/lucalewin".into(), description: None },
+        Identifier::Swh { value: "swh:1:dir:abc".into(), description: None },
+    ];
+    assert!(matches!(invalid[0].validate(), Err(IdentifierError::InvalidDoi(_))));
+    assert!(matches!(invalid[1].validate(), Err(IdentifierError::InvalidUrl(_))));
+    assert!(matches!(invalid[2].validate(), Err(IdentifierError::InvalidSwhid(_))));
+
+    let serialized = serde_yaml::to_string(&identifiers[1]).unwrap();
+    assert!(serialized.starts_with("type: url\n"));
+}
+
+#[test]
+fn swhids() {
+    let value = "swh:1:cnt:4d99d2d18326621ccdd70f5ea66c2e2ac236ad8b;origin=https://gitorious.org/ocamlp3l/ocamlp3l_cvs.git;visit=swh:1:snp:d7f1b9eb7ccb596c2622c4780febaa02549830f9;anchor=swh:1:rev:2db189928c94d62a3b4757b3eec68f0a4d4113f0;path=/Examples/SimpleFarm/simplefarm.ml;lines=9-15";
+    let swhid: Swhid = value.parse().unwrap();
+    assert_eq!(swhid.object_type(), ObjectType::Content);
+    assert_eq!(swhid.hash(), "4d99d2d18326621ccdd70f5ea66c2e2ac236ad8b");
+    assert_eq!(swhid.qualifier("lines"), Some("9-15"));
+    assert_eq!(swhid.qualifiers().len(), 5);
+    assert!(!swhid.is_core());
+    assert_eq!(swhid.core().to_string(), "swh:1:cnt:4d99d2d18326621ccdd70f5ea66c2e2ac236ad8b");
+    assert_eq!(swhid.to_string(), value);
+
+    let error = "swh:1:dir:d198bc9d7a6bcf6db04f476d29314f157507d505;visit=swh:1:rev:2db189928c94d62a3b4757b3eec68f0a4d4113f0".parse::<Swhid>().unwrap_err();
+    assert_eq!(error.message, "`swh:1:rev:2db189928c94d62a3b4757b3eec68f0a4d4113f0` is not a valid value for the qualifier `visit`");
+    assert_eq!("swh:1:foo:d198bc9d7a6bcf6db04f476d29314f157507d505".parse::<Swhid>().unwrap_err().message, "the object type must be one of `snp`, `rel`, `rev`, `dir` and `cnt`");
+    assert_eq!("swh:2:dir:d198bc9d7a6bcf6db04f476d29314f157507d505".parse::<Swhid>().unwrap_err().message, "the scheme version must be `1`");
+    assert!("swh:1:dir:d198bc9d7a6bcf6db04f476d29314f157507d505;branch=main".parse::<Swhid>().is_err());
+
+    let qualified = Identifier::Swh { value: value.into(), description: None };
+    assert_eq!(qualified.validate(), Err(IdentifierError::QualifiedSwhid(value.into())));
+}
+
+#[test]
+fn contacts() {
+    let citation = Citation::read("./tests/references.cff".into()).unwrap();
+
+    assert_eq!(citation.contact.len(), 2);
+    assert!(matches!(&citation.contact[0], Contact::Person(person) if person.affiliation.as_deref() == Some("Example University")));
+    assert!(matches!(&citation.contact[1], Contact::Entity(entity) if entity.name == "The citation maintainers"));
+}
+
+#[test]
+fn entity_authors() {
+    let citation = Citation::read("./tests/references.cff".into()).unwrap();
+
+    assert_eq!(citation.authors.len(), 2);
+    assert!(matches!(&citation.authors[0], Author::Person(person) if person.family_names.as_deref() == Some("Lewin")));
+    assert!(matches!(&citation.authors[1], Author::Entity(entity) if entity.name == "The Rust Community"));
+
+    let yaml = citation.to_yaml_string().unwrap();
+    assert!(yaml.contains("- name: The Rust Community"));
+}
+
+#[test]
+fn person_fields() {
+    let yaml = r#"
+given-names: Alexander
+name-particle: von
+family-names: Humboldt
+name-suffix: Jr.
+alias: Alex
+affiliation: Prussian Academy of Sciences
+address: Unter den Linden 8
+city: Berlin
+region: Berlin
+post-code: "10117"
+country: DE
+tel: +49 30 000000
+fax: +49 30 000001
+website: https://example.org/humboldt
+"#;
+    let Author::Person(person) = serde_yaml::from_str(yaml).unwrap() else { panic!("expected a person") };
+
+    assert_eq!(person.name_particle.as_deref(), Some("von"));
+    assert_eq!(person.name_suffix.as_deref(), Some("Jr."));
+    assert_eq!(person.post_code.as_deref(), Some("10117"));
+    assert_eq!(person.country.as_deref(), Some("DE"));
+
+    let serialized = serde_yaml::to_string(&person).unwrap();
+    assert!(serialized.contains("name-particle: von"));
+    assert!(serialized.contains("post-code: '10117'"));
+    assert_eq!(person.display_name(), "Alexander von Humboldt Jr.");
+}
+
+#[test]
+fn mononyms() {
+    let yaml = r#"
+- given-names: Aristotle
+- family-names: Madonna
+- alias: the-anonymous-contributor
+- name: Plato
+"#;
+    let authors: Vec<Author> = serde_yaml::from_str(yaml).unwrap();
+
+    assert!(matches!(&authors[0], Author::Person(person) if person.family_names.is_none()));
+    assert!(matches!(&authors[1], Author::Person(person) if person.given_names.is_none()));
+
+    let names: Vec<_> = authors.iter().map(Author::display_name).collect();
+    assert_eq!(names, ["Aristotle", "Madonna", "the-anonymous-contributor", "Plato"]);
+}
+
+#[test]
+#[allow(deprecated)]
+fn licenses() {
+    let citation = Citation::read("./tests/references.cff".into()).unwrap();
+    assert_eq!(citation.licenses(), [License::Apache_2_0]);
+
+    assert_eq!(License::from_id("mit"), License::MIT);
+    assert_eq!(License::MIT.name(), Some("MIT License"));
+    assert!(License::MIT.is_osi_approved());
+
+    let deprecated: License = serde_yaml::from_str("GPL-3.0").unwrap();
+    assert_eq!(deprecated, License::GPL_3_0);
+    assert!(deprecated.is_deprecated());
+
+    let unknown = License::from_id("LicenseRef-Proprietary");
+    assert_eq!(unknown, License::Other("LicenseRef-Proprietary".into()));
+    assert!(!unknown.is_known());
+    assert_eq!(serde_yaml::to_string(&unknown).unwrap().trim(), "LicenseRef-Proprietary");
+}
+
+#[test]
+fn license_lists() {
+    let single: Licenses = serde_yaml::from_str("MIT").unwrap();
+    assert_eq!(single, Licenses::Single(License::MIT));
+    assert_eq!(single.as_slice(), [License::MIT]);
+
+    let multiple: Licenses = serde_yaml::from_str("[MIT, Apache-2.0]").unwrap();
+    assert_eq!(multiple.len(), 2);
+    assert!(multiple.contains(&License::Apache_2_0));
+    assert_eq!(multiple.iter().map(License::id).collect::<Vec<_>>(), ["MIT", "Apache-2.0"]);
+
+    let yaml = serde_yaml::to_string(&multiple).unwrap();
+    assert_eq!(yaml, "- MIT\n- Apache-2.0\n");
+}
+
+#[test]
+fn dois() {
+    let doi: Doi = "10.5281/zenodo.1003150".parse().unwrap();
+    assert_eq!(doi.prefix(), "10.5281");
+    assert_eq!(doi.suffix(), "zenodo.1003150");
+    assert_eq!(doi.to_url(), "https://doi.org/10.5281/zenodo.1003150");
+
+    assert!("https://doi.org/10.5281/zenodo.1003150".parse::<Doi>().is_err());
+    assert!("10.52/zenodo".parse::<Doi>().is_err());
+
+    let citation: Result<Citation, _> = serde_yaml::from_str(
+        "cff-version: 1.2.0\nmessage: cite me\ntitle: citation\nauthors: [{name: citation}]\ndoi: 10.5281/zenodo.1003150\n",
+    );
+    assert_eq!(citation.unwrap().doi, Some(doi));
+
+    let invalid: Result<Citation, _> = serde_yaml::from_str(
+        "cff-version: 1.2.0\nmessage: cite me\ntitle: citation\nauthors: [{name: citation}]\ndoi: not-a-doi\n",
+    );
+    assert!(invalid.unwrap_err().to_string().contains("`not-a-doi` is not a valid DOI"));
+}
+
+#[test]
+fn preferred_citation() {
+    let citation = Citation::read("./tests/references.cff".into()).unwrap();
+    let preferred = citation.preferred_citation.as_ref().unwrap();
+
+    assert_eq!(preferred.r#type, ReferenceType::Article);
+    assert_eq!(preferred.journal.as_deref(), Some("Journal of Open Source Software"));
+    assert_eq!(preferred.authors[0].display_name(), "Luca Lewin");
+
+    let yaml = citation.to_yaml_string().unwrap();
+    assert!(yaml.contains("preferred-citation:\n  authors:"));
+}
+
+#[test]
+fn orcids() {
+    let orcid: Orcid = "0000-0002-1825-0097".parse().unwrap();
+    assert_eq!(orcid.id(), "0000-0002-1825-0097");
+    assert_eq!(orcid.to_string(), "https://orcid.org/0000-0002-1825-0097");
+    assert_eq!("https://orcid.org/0000-0002-1825-0097".parse::<Orcid>().unwrap(), orcid);
+    assert_eq!("http://orcid.org/0000-0002-1694-233x".parse::<Orcid>().unwrap().id(), "0000-0002-1694-233X");
+
+    assert_eq!("0000-0002-1825-0098".parse::<Orcid>(), Err(ParseOrcidError::InvalidChecksum("0000-0002-1825-0098".into())));
+    assert_eq!("0000-0002-1825".parse::<Orcid>(), Err(ParseOrcidError::InvalidFormat("0000-0002-1825".into())));
+    assert!("0000-0002-X825-0097".parse::<Orcid>().is_err());
+
+    assert_eq!(serde_yaml::to_string(&orcid).unwrap(), "https://orcid.org/0000-0002-1825-0097\n");
+    assert_eq!(serde_yaml::from_str::<Orcid>("0000-0002-1825-0097").unwrap(), orcid);
+
+    let citation: Citation = serde_yaml::from_str(
+        "cff-version: 1.2.0\nmessage: cite me\ntitle: citation\nauthors: [{name: citation, orcid: 'https://orcid.org/0000-0002-1825-0098'}]\n",
+    )
+    .unwrap();
+    let issues: Vec<_> = citation.validate().errors().map(|issue| issue.message.clone()).collect();
+    assert_eq!(issues, ["`https://orcid.org/0000-0002-1825-0098` has an invalid check digit"]);
+
+    // bare identifiers are validated, but written as URLs
+    let mut citation: Citation = "cff-version: 1.2.0\nmessage: cite me\ntitle: citation\nauthors:\n  - given-names: Josiah\n    orcid: 0000-0002-1825-0097\n  - name: citation\n    orcid: 0000-0002-1694-233x\n".parse().unwrap();
+    assert_eq!(citation.validate().iter().filter(|issue| issue.code == Code::InvalidOrcid).count(), 2);
+    let yaml = citation.to_yaml_string().unwrap();
+    assert!(yaml.contains("orcid: https://orcid.org/0000-0002-1825-0097\n") && yaml.contains("orcid: https://orcid.org/0000-0002-1694-233X\n"));
+    assert!(yaml.parse::<Citation>().unwrap().validate().iter().all(|issue| issue.code != Code::InvalidOrcid));
+    if let Author::Person(person) = &mut citation.authors[0] {
+        person.orcid = Some("0000-0002-1825-0098".into());
+    }
+    assert!(citation.to_yaml_string().unwrap().contains("orcid: 0000-0002-1825-0098\n"));
+}
+
+#[test]
+fn dates() {
+    let date: Date = "2024-02-29".parse().unwrap();
+    assert_eq!((date.year(), date.month(), date.day()), (2024, 2, 29));
+    assert_eq!(date.to_string(), "2024-02-29");
+
+    assert!("2023-02-29".parse::<Date>().is_err());
+    assert!("2024-13-01".parse::<Date>().is_err());
+    assert!("2024-1-5".parse::<Date>().is_err());
+    assert!("24-01-05".parse::<Date>().is_err());
+
+    assert!(Date::new(2023, 12, 31).unwrap() < Date::new(2024, 1, 1).unwrap());
+
+    let citation: Citation = serde_yaml::from_str(
+        "cff-version: 1.2.0\nmessage: cite me\ntitle: citation\nauthors: [{name: citation}]\ndate-released: 2021-07-18\n",
+    )
+    .unwrap();
+    assert_eq!(citation.date_released, Some(Date::new(2021, 7, 18).unwrap()));
+    assert!(citation.to_yaml_string().unwrap().contains("date-released: 2021-07-18"));
+}
+
+#[test]
+fn validate() {
+    let valid = Citation::read("./tests/references.cff".into()).unwrap();
+    assert!(valid.validate().is_valid());
+
+    let invalid = Citation::read("./tests/invalid.cff".into()).unwrap();
+    let report = invalid.validate();
+
+    let issues: Vec<_> = report.errors().map(|issue| (issue.code.as_str(), issue.path.as_str())).collect();
+    assert_eq!(
+        issues,
+        [
+            ("invalid-cff-version", "cff-version"),
+            ("empty-title", "title"),
+            ("empty-authors", "authors"),
+            ("invalid-orcid", "contact[0].orcid"),
+            ("invalid-identifier", "identifiers[0].value"),
+            ("invalid-url", "repository-code"),
+            ("invalid-url", "references[0].authors[0].website"),
+            ("invalid-date", "references[0].month"),
+        ]
+    );
+}
+
+#[test]
+fn severities() {
+    let citation: Citation = serde_yaml::from_str(
+        "cff-version: 1.2.0\nmessage: ''\ntitle: citation\nauthors: [{name: citation}]\nlicense: [MIT, GPL-2.0+]\n",
+    )
+    .unwrap();
+    let report = citation.validate();
+
+    assert!(report.is_valid());
+    assert!(report.has_warnings());
+
+    let warnings: Vec<_> = report.warnings().map(|issue| (issue.code, issue.path.as_str())).collect();
+    assert_eq!(warnings, [(Code::EmptyMessage, "message"), (Code::DeprecatedLicense, "license[1]")]);
+
+    let hints: Vec<_> = report.hints().map(|issue| issue.code).collect();
+    assert_eq!(hints, [Code::MissingDoi]);
+
+    assert_eq!(report.at_least(Severity::Warning).count(), 2);
+    assert_eq!(report.at_least(Severity::Hint).count(), 3);
+}
+
+#[test]
+fn parse_errors() {
+    let error = Citation::read("./tests/malformed.cff".into()).unwrap_err();
+    let Error::Yaml(error) = error else { panic!("{error}") };
+
+    assert_eq!(error.path.as_deref(), Some("references[0].date-published"));
+    assert_eq!(error.line, Some(12));
+    assert_eq!(error.message, "`2016-02-30` is not a valid date");
+    assert_eq!(error.to_string(), "references[0].date-published: `2016-02-30` is not a valid date at line 12 column 21");
+
+    // errors inside authors and licenses are reported at the offending value, not at the enclosing one
+    let content = "cff-version: 1.2.0\nmessage: m\ntitle: t\nauthors:\n  - name: A\n  - given-names: B\n    email: [x]\n";
+    let error = content.parse::<Citation>().unwrap_err();
+    assert_eq!((error.path.as_deref(), error.line, error.message.as_str()), (Some("authors[1].email"), Some(7), "invalid type: sequence, expected a string"));
+
+    let content = "cff-version: 1.2.0\nmessage: m\ntitle: t\nauthors:\n  - name: A\nlicense: [MIT, {x: 1}]\n";
+    let error = content.parse::<Citation>().unwrap_err();
+    assert_eq!((error.path.as_deref(), error.line, error.column), (Some("license[1]"), Some(6), Some(16)));
+    let error = content.replace("[MIT, {x: 1}]", "\n  - MIT\n  - x: 1").parse::<Citation>().unwrap_err();
+    assert_eq!((error.path.as_deref(), error.line), (Some("license[1]"), Some(8)));
+    assert_eq!(content.replace("[MIT, {x: 1}]", "[MIT, Apache-2.0]").parse::<Citation>().unwrap().license.unwrap().len(), 2);
+}
+
+#[test]
+fn strict_parsing() {
+    let citation = Citation::read("./tests/unknown.cff".into()).unwrap();
+    assert_eq!(citation.authors.len(), 1);
+
+    let error = Citation::read_strict("./tests/unknown.cff".into()).unwrap_err();
+    let Error::Yaml(error) = error else { panic!("{error}") };
+    assert_eq!(error.path.as_deref(), Some("authors[0].familiy-names"));
+    assert_eq!(error.to_string(), "authors[0].familiy-names: unknown key `familiy-names`, did you mean `family-names`? at line 6 column 20");
+
+    let options = ParseOptions { strict: true };
+    assert!(Citation::read_with("./tests/CITATION.cff".into(), &options).is_ok());
+    assert!(Citation::read_with("./tests/references.cff".into(), &options).is_ok());
+
+    let duplicated = "cff-version: 1.2.0\nmessage: cite\ntitle: citation\nauthors:\n  - name: X\nfoo: 1\nfoo: 2\n";
+    assert!(duplicated.parse::<Citation>().is_ok());
+    let errors = Citation::parse_all(duplicated, &options).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].message.contains("duplicate entry with key \"foo\""), "{}", errors[0]);
+    assert!(errors[0].line.is_some());
+}
+
+#[test]
+fn extra_keys() {
+    let citation = Citation::read("./tests/unknown.cff".into()).unwrap();
+    assert_eq!(citation.extra["x-funding"]["grant"], 123456);
+
+    let Author::Person(person) = &citation.authors[0] else { panic!("expected a person") };
+    assert_eq!(person.extra["familiy-names"], "Mustermann");
+
+    let yaml = citation.to_yaml_string().unwrap();
+    assert!(yaml.contains("familiy-names: Mustermann"));
+    assert!(yaml.contains("x-funding:\n  agency: Deutsche Forschungsgemeinschaft\n  grant: 123456"));
+
+    let citation = Citation::read("./tests/CITATION.cff".into()).unwrap();
+    assert!(citation.extra.is_empty());
+
+    // an invalid entity is an error instead of a person with unknown keys
+    let content = "cff-version: 1.2.0\nmessage: cite\ntitle: t\nauthors: [{name: ACME Research, date-start: 2020-13-01}]\n";
+    let error = content.parse::<Citation>().unwrap_err();
+    assert_eq!((error.path.as_deref(), error.line), (Some("authors[0].date-start"), Some(4)));
+    assert!(Citation::parse_all(content, &ParseOptions::default()).is_err());
+
+    let citation: Citation = "cff-version: 1.2.0\nmessage: cite\ntitle: t\nauthors: [{name: ACME, given-names: X}, {given-names: Y, location: Z}]\n".parse().unwrap();
+    assert!(matches!(&citation.authors[0], Author::Entity(entity) if entity.extra["given-names"] == "X"));
+    assert!(matches!(&citation.authors[1], Author::Person(person) if person.extra["location"] == "Z"));
+    let citation: Citation = "cff-version: 1.2.0\nmessage: cite\ntitle: t\nauthors: [{name: null}]\n".parse().unwrap();
+    assert!(matches!(&citation.authors[0], Author::Entity(_)));
+}
+
+#[test]
+fn edit_in_place() {
+    let mut document = Document::read("./tests/edit.cff".into()).unwrap();
+    let mut citation = document.citation().unwrap();
+
+    citation.version = Some("0.2.0".into());
+    citation.date_released = Some(Date::new(2024, 1, 31).unwrap());
+    citation.keywords.push("yaml".into());
+    citation.r#abstract = None;
+    citation.doi = Some("10.5281/zenodo.1234".parse().unwrap());
+    citation.authors.push(Author::Person(Person { given_names: Some("Jane".into()), family_names: Some("Doe".into()), ..Default::default() }));
+    if let Author::Person(person) = &mut citation.authors[0] {
+        person.affiliation = None;
+        person.orcid = Some("https://orcid.org/0000-0000-0000-0000".into());
+    }
+
+    document.update(&citation).unwrap();
+    assert_eq!(
+        document.as_str(),
+        "# This CITATION.cff file was created by hand.
+cff-version: 1.2.0
+title: \"citation\"
+message: If you use this software, please cite it as below.
+version: '0.2.0' # bumped on every release
+date-released: 2024-01-31
+
+authors:
+  - given-names: Luca
+    family-names: Lewin
+    orcid: https://orcid.org/0000-0000-0000-0000
+  - family-names: Doe
+    given-names: Jane
+keywords: [citation, cff, yaml]
+license: MIT
+doi: 10.5281/zenodo.1234
+"
+    );
+
+    citation.authors.pop();
+    citation.r#abstract = Some("A library for reading\nand writing citation files.".into());
+    document.update(&citation).unwrap();
+    assert!(document.as_str().ends_with("abstract: |-\n  A library for reading\n  and writing citation files.\n"));
+    assert_eq!(document.citation().unwrap().authors.len(), 1);
+}
+
+#[test]
+fn comments() {
+    let mut document = Document::read("./tests/edit.cff".into()).unwrap();
+
+    assert_eq!(document.comment("cff-version").as_deref(), Some("This CITATION.cff file was created by hand."));
+    assert_eq!(document.comment("version").as_deref(), Some("bumped on every release"));
+    assert_eq!(document.comment("authors[0].affiliation").as_deref(), Some("the ORCID is still missing"));
+    assert_eq!(document.comment("title"), None);
+
+    assert!(document.set_comment("authors", "Sorted by contribution.\nSee CONTRIBUTORS.md"));
+    assert!(document.set_comment("authors[0].affiliation", ""));
+    assert!(!document.set_comment("doi", "missing"));
+    assert_eq!(document.comment("authors").as_deref(), Some("Sorted by contribution.\nSee CONTRIBUTORS.md"));
+
+    let mut citation = document.citation().unwrap();
+    citation.version = Some("0.3.0".into());
+    citation.keywords.clear();
+    document.update(&citation).unwrap();
+
+    let source = document.as_str();
+    assert!(source.starts_with("# This CITATION.cff file was created by hand.\n"));
+    assert!(source.contains("version: '0.3.0' # bumped on every release\n"));
+    assert!(source.contains("\n# Sorted by contribution.\n# See CONTRIBUTORS.md\nauthors:\n  - given-names: Luca\n    family-names: Lewin\n    affiliation"));
+    assert!(!source.contains("keywords"));
+}
+
+#[test]
+fn format_document() {
+    let mut document = Document::read("./tests/edit.cff".into()).unwrap();
+    assert!(!document.is_formatted());
+
+    document.format().unwrap();
+    assert!(document.is_formatted());
+    assert_eq!(
+        document.as_str(),
+        "# This CITATION.cff file was created by hand.
+cff-version: 1.2.0
+message: If you use this software, please cite it as below.
+title: citation
+abstract: A library for reading and writing citation files.
+authors:
+- family-names: Lewin
+  given-names: Luca
+  # the ORCID is still missing
+  affiliation: Example University
+# bumped on every release
+version: 0.1.0
+date-released: 2023-04-01
+license: MIT
+keywords:
+- citation
+- cff
+"
+    );
+
+    let mut document = Document::parse("# generated by hand\n\ntitle: x\nauthors:\n- orcid: 0000-0002-1825-0097\n  name: ACME\ncff-version: 1.2.0\nx-custom: 1\nmessage: m\n").unwrap();
+    document.format().unwrap();
+    assert_eq!(
+        document.as_str(),
+        "# generated by hand\n\ncff-version: 1.2.0\nmessage: m\ntitle: x\nauthors:\n- name: ACME\n  orcid: https://orcid.org/0000-0002-1825-0097\nx-custom: 1\n"
+    );
+}
+
+#[test]
+fn fix_document() {
+    let source = std::fs::read_to_string("./tests/broken.cff").unwrap();
+    assert!(source.parse::<Citation>().is_err());
+
+    let (document, fixes) = Document::fix(source).unwrap();
+    let fixes: Vec<_> = fixes.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        fixes,
+        [
+            "authors: renamed `author` to `authors`",
+            "authors[0].family-names: renamed `familiy-names` to `family-names`",
+            "title: quoted `Yes` so it is read as a string",
+            "version: quoted `1.10` so it is read as a string",
+            "authors[0].orcid: converted `0000-0002-1825-0097` to `https://orcid.org/0000-0002-1825-0097`",
+            "date-released: changed `2021/07/18` to `2021-07-18`",
+            "references[0].date-published: changed `18.07.2021` to `2021-07-18`",
+        ]
+    );
+    assert!(document.as_str().contains("    family-names: Doe # typo\n"));
+
+    let citation = document.citation().unwrap();
+    assert_eq!(citation.title, "Yes");
+    assert_eq!(citation.version.as_deref(), Some("1.10"));
+    assert_eq!(citation.date_released, Some(Date::new(2021, 7, 18).unwrap()));
+    assert!(citation.validate().is_valid());
+
+    let (_, fixes) = Document::fix(document.as_str()).unwrap();
+    assert!(fixes.is_empty());
+
+    // renaming `titel` would duplicate `title`
+    assert!(Document::fix("cff-version: 1.2.0\nmessage: m\ntitle: x\ntitel: z\nauthors:\n- name: ACME\n").unwrap().1.is_empty());
+    assert!(Document::fix("title: [").is_err());
+}
+
+#[test]
+fn json_input() {
+    let yaml = Citation::read("./tests/CITATION.cff".into()).unwrap();
+    let json = Citation::read("./tests/CITATION.json".into()).unwrap();
+    assert_eq!(json.to_yaml_string().unwrap(), yaml.to_yaml_string().unwrap());
+
+    let content = std::fs::read_to_string("./tests/CITATION.json").unwrap();
+    let citation = Citation::from_json_str(&content).unwrap();
+    assert_eq!(citation.title, "QED: mathematical proof assistant/database");
+    assert_eq!(content.parse::<Citation>().unwrap().version.as_deref(), Some("0.0.0"));
+
+    // JSON content is detected regardless of the extension
+    let path = std::env::temp_dir().join("citation-json-input.cff");
+    std::fs::write(&path, &content).unwrap();
+    assert_eq!(Citation::read(path.clone()).unwrap().title, citation.title);
+    std::fs::remove_file(path).unwrap();
+
+    let error = Citation::from_json_str("{\n  \"cff-version\": \"1.2.0\",\n  \"authors\": 1\n}").unwrap_err();
+    assert_eq!(error.path.as_deref(), Some("authors"));
+    assert_eq!((error.line, error.column), (Some(3), Some(14)));
+    assert_eq!(error.offset, Some(41));
+
+    let error = Citation::from_json_str("{\"title\": ").unwrap_err();
+    assert_eq!(error.line, Some(1));
+    assert!(Citation::from_json_str(&format!("{content} trailing")).is_err());
+
+    assert!(Citation::read_with("./tests/CITATION.json".into(), &ParseOptions::strict()).is_ok());
+    let duplicated = content.replacen('{', "{\"foo\": 1, \"foo\": 2,", 1);
+    let errors = Citation::parse_all(&duplicated, &ParseOptions::strict()).unwrap_err();
+    assert_eq!((errors[0].message.as_str(), errors[0].line), ("duplicate entry with key \"foo\"", Some(1)));
+    let path = std::env::temp_dir().join("citation-json-input.json");
+    std::fs::write(&path, content.replace("\"url\"", "\"uri\"")).unwrap();
+    assert!(Citation::read(path.clone()).is_ok());
+    let error = Citation::read_strict(path.clone()).unwrap_err();
+    assert!(matches!(error, Error::Yaml(error) if error.message == "unknown key `uri`, did you mean `url`?"));
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn schema_versions() {
+    let legacy = Citation::read("./tests/legacy.cff".into()).unwrap();
+    assert_eq!(legacy.schema_version(), Some(CffVersion::V1_0));
+    assert_eq!(legacy.version.as_deref(), Some("1.0"));
+    assert!(legacy.validate().is_empty());
+    assert!(Citation::read_strict("./tests/legacy.cff".into()).is_ok());
+
+    let citation = Citation::read("./tests/CITATION.cff".into()).unwrap();
+    assert_eq!(citation.schema_version(), Some(CffVersion::LATEST));
+    assert_eq!("1.1.0".parse::<CffVersion>(), Ok(CffVersion::V1_1));
+    assert_eq!("1.0.1".parse::<CffVersion>().map(|version| version.to_string()).as_deref(), Ok("1.0.3"));
+    assert!(CffVersion::V1_0 < CffVersion::V1_2);
+    assert_eq!("1.3.0".parse::<CffVersion>().unwrap_err().to_string(), "`1.3.0` is not a supported version of the schema (1.0.x, 1.1.0 or 1.2.0)");
+
+    // CITATION.cff uses `type`, which was added in 1.2.0, and has no `date-released`
+    let content = std::fs::read_to_string("./tests/CITATION.cff").unwrap().replace("cff-version: 1.2.0", "cff-version: 1.1.0");
+    let report = content.parse::<Citation>().unwrap().validate();
+    let codes: Vec<_> = report.iter().map(|issue| (issue.code, issue.path.as_str())).collect();
+    assert_eq!(codes, [(Code::MissingKey, "date-released"), (Code::UnavailableKey, "type"), (Code::MissingDoi, "doi")]);
+    assert_eq!(report.iter().next().unwrap().message, "`date-released` is required by cff-version 1.1.0");
+
+    let path = std::env::temp_dir().join("citation-schema-versions.cff");
+    std::fs::write(&path, &content).unwrap();
+    let error = Citation::read_strict(path.clone()).unwrap_err();
+    let Error::Yaml(error) = error else { panic!("{error}") };
+    assert_eq!(error.message, "key `type` is not defined by cff-version 1.1.0");
+    assert_eq!(error.line, Some(6));
+    std::fs::remove_file(path).unwrap();
+
+    let future = content.replace("cff-version: 1.1.0", "cff-version: 1.3.0").parse::<Citation>().unwrap();
+    assert_eq!(future.schema_version(), None);
+    let issue = future.validate().iter().next().cloned().unwrap();
+    assert_eq!((issue.code, issue.severity), (Code::UnsupportedCffVersion, Severity::Warning));
+}
+
+#[test]
+fn forward_compatibility() {
+    let citation = Citation::read("./tests/future.cff".into()).unwrap();
+    assert_eq!(citation.title, "Weather forecast model");
+    assert_eq!(citation.schema_version(), None);
+    assert!(citation.r#type.is_none());
+    assert_eq!(citation.identifiers.len(), 1);
+    assert!(Citation::read_strict("./tests/future.cff".into()).is_err());
+
+    let document = Document::read("./tests/future.cff".into()).unwrap();
+    let compatibility = document.compatibility();
+    assert_eq!(compatibility.declared, "1.3.0");
+    assert_eq!(compatibility.version, CffVersion::V1_2);
+    assert!(!compatibility.is_supported());
+    assert!(!compatibility.is_lossless());
+
+    let issues: Vec<_> = compatibility.issues.iter().map(|issue| (issue.code, issue.path.as_str(), issue.span.map(|span| span.line))).collect();
+    assert_eq!(issues, [(Code::UnknownKey, "authors[0].pronouns", Some(8)), (Code::IgnoredValue, "type", Some(4)), (Code::IgnoredValue, "identifiers[0]", Some(10))]);
+    assert_eq!(compatibility.issues[0].severity, Severity::Warning);
+
+    // the same problems are fatal for supported versions
+    let content = std::fs::read_to_string("./tests/future.cff").unwrap();
+    assert!(Document::parse(content.replace("1.3.0", "1.2.0")).is_err());
+
+    let compatibility = Document::read("./tests/CITATION.cff".into()).unwrap().compatibility();
+    assert!(compatibility.is_supported() && compatibility.is_complete());
+}
+
+#[test]
+fn migrate() {
+    let mut legacy = Citation::read("./tests/legacy.cff".into()).unwrap();
+    let report = legacy.migrate_to(CffVersion::V1_2);
+    assert!(report.is_lossless());
+    assert_eq!(report.changes.iter().map(ToString::to_string).collect::<Vec<_>>(), ["cff-version: changed `1.0.3` to `1.2.0`"]);
+    assert_eq!(legacy.schema_version(), Some(CffVersion::V1_2));
+    assert!(legacy.validate().is_empty());
+
+    let mut citation = Citation::read("./tests/references.cff".into()).unwrap();
+    citation.identifiers = vec![
+        Identifier::Doi { value: "10.5281/zenodo.1003150".into(), description: None },
+        Identifier::Url { value: "https://example.com".into(), description: None },
+    ];
+    citation.license = Some(Licenses::Multiple(vec![License::MIT, License::Apache_2_0]));
+    citation.r#type = Some(Type::Dataset);
+    citation.version = Some("1.0.0".into());
+    let references = citation.references.len();
+
+    let report = citation.migrate_to(CffVersion::V1_1);
+    assert_eq!(citation.cff_version, "1.1.0");
+    assert_eq!(citation.doi.as_ref().map(Doi::as_str), Some("10.5281/zenodo.1003150"));
+    assert!(citation.identifiers.is_empty() && citation.preferred_citation.is_none() && citation.r#type.is_none());
+    assert_eq!(citation.references.len(), references + 1);
+    assert_eq!(citation.references[0].title, "citation: a library to manipulate citation files");
+    assert_eq!(citation.license, Some(Licenses::Single(License::MIT)));
+
+    let lost: Vec<_> = report.lost.iter().map(|issue| (issue.code, issue.path.as_str())).collect();
+    assert_eq!(lost, [(Code::UnavailableKey, "identifiers[1]"), (Code::UnavailableKey, "preferred-citation"), (Code::UnavailableKey, "type"), (Code::UnavailableKey, "license[1]"), (Code::MissingKey, "date-released")]);
+    assert!(!report.is_lossless());
+
+    // the downgraded citation is valid for the target version, except for the missing release date
+    let codes: Vec<_> = citation.validate().iter().map(|issue| issue.code).collect();
+    assert_eq!(codes, [Code::MissingKey]);
+}
+
+#[cfg(feature = "url")]
+#[test]
+fn typed_urls() {
+    let citation = Citation::read("./tests/CITATION.cff".into()).unwrap();
+    let urls = citation.urls().unwrap();
+    assert_eq!(urls.repository_code.as_ref().map(|url| url.host_str()), Some(Some("github.com")));
+    assert_eq!(urls.url.unwrap().path(), "/projects/QED");
+    assert!(urls.license_url.is_none());
+
+    let mut reference = Reference { url: Some("ftp://ftp.example.com/data.csv".into()), ..Default::default() };
+    assert_eq!(reference.urls().unwrap().url.unwrap().scheme(), "ftp");
+    reference.repository = Some("git://example.com/repo.git".into());
+    let error = reference.urls().unwrap_err();
+    assert_eq!(error.path, "repository");
+    assert_eq!(error.to_string(), "repository: `git://example.com/repo.git` is not a valid URL with the scheme http, https, ftp or sftp");
+
+    // lenient parsing leaves broken URLs to validation, strict parsing rejects them
+    assert!(Citation::read("./tests/invalid.cff".into()).unwrap().urls().is_err());
+    let content = std::fs::read_to_string("./tests/references.cff").unwrap().replace("doi: 10.7717/peerj-cs.86", "url: https//doi.org/10.7717/peerj-cs.86");
+    let path = std::env::temp_dir().join("citation-typed-urls.cff");
+    std::fs::write(&path, content).unwrap();
+    assert!(Citation::read(path.clone()).is_ok());
+    let error = Citation::read_strict(path.clone()).unwrap_err();
+    let Error::Yaml(error) = error else { panic!("{error}") };
+    assert_eq!(error.path.as_deref(), Some("references[0].url"));
+    assert_eq!(error.line, Some(26));
+    assert!(Citation::read_strict("./tests/references.cff".into()).is_ok());
+    std::fs::remove_file(path).unwrap();
+}
+
+#[cfg(feature = "semver")]
+#[test]
+fn semantic_versions() {
+    use std::cmp::Ordering;
+
+    let mut citation = Citation::read("./tests/CITATION.cff".into()).unwrap();
+    assert_eq!(citation.semver(), Some(semver::Version::new(0, 0, 0)));
+
+    citation.version = Some("v1.2.0-beta.1".into());
+    let version = citation.semver().unwrap();
+    assert_eq!((version.major, version.minor, version.pre.as_str()), (1, 2, "beta.1"));
+    assert_eq!(citation.compare_version(&semver::Version::new(1, 2, 0)), Some(Ordering::Less));
+
+    let mut manifest = Citation::read("./tests/CITATION.cff".into()).unwrap();
+    manifest.version = Some("1.2.0".into());
+    assert_eq!(citation.is_behind(&manifest), Some(true));
+    assert_eq!(manifest.is_behind(&citation), Some(false));
+    assert_eq!(manifest.is_behind(&manifest), Some(false));
+
+    citation.version = Some("2024.05".into());
+    assert_eq!(citation.semver(), None);
+    assert_eq!(citation.is_behind(&manifest), None);
+}
+
+#[test]
+fn keywords() {
+    let mut citation = Citation::read("./tests/CITATION.cff".into()).unwrap();
+    citation.keywords = vec![" Rust".into(), "citation".into(), "".into(), "rust".into(), "CFF ".into()];
+    citation.references = vec![Reference { keywords: vec!["a".into(), "A".into()], ..Default::default() }];
+
+    let issues: Vec<_> = citation.validate().iter().map(|issue| (issue.code, issue.path.clone(), issue.message.clone())).collect();
+    assert_eq!(issues[1..3], [
+        (Code::EmptyKeyword, "keywords[2]".to_string(), "keyword must not be empty".to_string()),
+        (Code::DuplicateKeyword, "keywords[3]".to_string(), "`rust` is a duplicate of ` Rust`".to_string()),
+    ]);
+    assert!(issues.iter().any(|(code, path, _)| *code == Code::DuplicateKeyword && path == "references[0].keywords[1]"));
+
+    let fixes: Vec<_> = citation.normalize_keywords(&KeywordOptions::default()).iter().map(ToString::to_string).collect();
+    assert_eq!(fixes, [
+        "keywords[0]: changed ` Rust` to `Rust`",
+        "keywords[2]: removed empty keyword",
+        "keywords[3]: removed `rust`, a duplicate of `Rust`",
+        "keywords[4]: changed `CFF ` to `CFF`",
+        "references[0].keywords[1]: removed `A`, a duplicate of `a`",
+    ]);
+    assert_eq!(citation.keywords, ["Rust", "citation", "CFF"]);
+    assert!(!citation.validate().iter().any(|issue| matches!(issue.code, Code::EmptyKeyword | Code::DuplicateKeyword)));
+
+    citation.normalize_keywords(&KeywordOptions { lowercase: true });
+    assert_eq!(citation.keywords, ["rust", "citation", "cff"]);
+    assert!(citation.normalize_keywords(&KeywordOptions { lowercase: true }).is_empty());
+}
+
+#[test]
+fn normalize() {
+    let mut citation = Citation::read("./tests/references.cff".into()).unwrap();
+    citation.title = "  citation ".into();
+    citation.keywords = vec!["rust".into(), "CFF".into(), "Rust ".into(), "bibliography".into()];
+    citation.identifiers = vec![Identifier::Doi { value: "10.5281/ZENODO.1003150".into(), description: None }];
+    citation.doi = Some(Doi::new("10.5281/Zenodo.1003150").unwrap());
+    if let Author::Person(person) = &mut citation.authors[0] {
+        person.orcid = Some("0009-0005-1296-096X".into());
+        person.given_names = Some("Luca ".into());
+    }
+
+    let mut other = Citation::read("./tests/references.cff".into()).unwrap();
+    other.keywords = vec!["bibliography".into(), "cff".into(), "rust".into()];
+    other.identifiers = vec![Identifier::Doi { value: "10.5281/zenodo.1003150".into(), description: None }];
+    other.doi = Some(Doi::new("10.5281/zenodo.1003150").unwrap());
+    if let Author::Person(person) = &mut other.authors[0] {
+        person.orcid = Some("https://orcid.org/0009-0005-1296-096X".into());
+    }
+
+    citation.normalize();
+    other.normalize();
+    assert_eq!(citation.title, "citation");
+    assert_eq!(citation.keywords, ["bibliography", "CFF", "rust"]);
+    assert_eq!(citation.doi.as_ref().map(Doi::as_str), Some("10.5281/zenodo.1003150"));
+    assert_eq!(citation.identifiers[0].value(), "10.5281/zenodo.1003150");
+    assert!(matches!(&citation.authors[0], Author::Person(person) if person.orcid.as_deref() == Some("https://orcid.org/0009-0005-1296-096X")));
+
+    // `CFF` and `cff` only differ in case, so the first one is kept
+    other.keywords[1] = "CFF".into();
+    assert_eq!(citation.to_yaml_string().unwrap(), other.to_yaml_string().unwrap());
+
+    let normalized = citation.to_yaml_string().unwrap();
+    citation.normalize();
+    assert_eq!(citation.to_yaml_string().unwrap(), normalized);
+}
+
+#[test]
+fn diff() {
+    let old = Citation::read("./tests/references.cff".into()).unwrap();
+    assert!(old.diff(&old).is_empty());
+
+    let mut new = Citation::read("./tests/references.cff".into()).unwrap();
+    new.version = Some("1.1.0".into());
+    new.authors.insert(0, Author::Person(Person { given_names: Some("Jane".into()), family_names: Some("Doe".into()), ..Default::default() }));
+    if let Author::Person(person) = &mut new.authors[1] {
+        person.orcid = Some("https://orcid.org/0009-0005-1296-096X".into());
+    }
+    new.references[0].volume = Some("3".into());
+    new.references.remove(1);
+
+    let changes: Vec<String> = old.diff(&new).iter().map(ToString::to_string).collect();
+    assert_eq!(changes, [
+        r#"added authors[0]: {"family-names":"Doe","given-names":"Jane"}"#,
+        "added authors[1].orcid: https://orcid.org/0009-0005-1296-096X",
+        r#"removed references[1]: {"authors":[{"family-names":"Tolnay","given-names":"David"}],"keywords":["serialization"],"repository-code":"https://github.com/serde-rs/serde","title":"serde","type":"software","version":"1.0.0"}"#,
+        "changed references[0].volume from 2 to 3",
+        "added version: 1.1.0",
+    ]);
+    assert_eq!(old.diff(&new)[1].path(), "authors[1].orcid");
+
+    // reordering items is not a change
+    let mut reordered = Citation::read("./tests/references.cff".into()).unwrap();
+    reordered.authors.reverse();
+    assert!(old.diff(&reordered).is_empty());
+}
+
+#[test]
+fn merge() {
+    let read = || Citation::read("./tests/references.cff".into()).unwrap();
+
+    let mut ours = read();
+    ours.keywords = vec!["rust".into(), "cff".into()];
+    ours.version = Some("1.0.0".into());
+
+    let mut theirs = read();
+    theirs.title = "citation-rs".into();
+    theirs.keywords = vec!["cff".into(), "citation".into()];
+    theirs.version = Some("1.1.0".into());
+    theirs.date_released = Some("2024-05-01".parse().unwrap());
+    if let Author::Person(person) = &mut theirs.authors[0] {
+        person.orcid = Some("https://orcid.org/0009-0005-1296-096X".into());
+    }
+    theirs.authors.push(Author::Person(Person { given_names: Some("Jane".into()), family_names: Some("Doe".into()), ..Default::default() }));
+
+    let mut merged = read();
+    merged.keywords = ours.keywords.clone();
+    merged.version = ours.version.clone();
+    let conflict = merged.merge(theirs.to_yaml_string().unwrap().parse().unwrap(), MergeStrategy::Fail).unwrap_err();
+    assert_eq!(conflict, MergeConflict { path: "title".into(), ours: "citation".into(), theirs: "citation-rs".into() });
+    assert_eq!(conflict.to_string(), "conflicting values for title: citation and citation-rs");
+    // a failed merge leaves the citation unchanged
+    assert_eq!(merged.to_yaml_string().unwrap(), ours.to_yaml_string().unwrap());
+
+    merged.merge(theirs.to_yaml_string().unwrap().parse().unwrap(), MergeStrategy::Ours).unwrap();
+    assert_eq!(merged.title, "citation");
+    assert_eq!(merged.version.as_deref(), Some("1.0.0"));
+    assert_eq!(merged.keywords, ["rust", "cff", "citation"]);
+    assert_eq!(merged.date_released, theirs.date_released);
+    assert_eq!(merged.authors.len(), 3);
+    assert!(matches!(&merged.authors[0], Author::Person(person) if person.orcid.as_deref() == Some("https://orcid.org/0009-0005-1296-096X")));
+    assert!(matches!(&merged.authors[2], Author::Person(person) if person.given_names.as_deref() == Some("Jane")));
+
+    let mut merged = ours;
+    merged.merge(theirs.to_yaml_string().unwrap().parse().unwrap(), MergeStrategy::Theirs).unwrap();
+    assert_eq!(merged.title, "citation-rs");
+    assert_eq!(merged.version.as_deref(), Some("1.1.0"));
+}
+
+#[test]
+fn equality() {
+    let citation = Citation::read("./tests/references.cff".into()).unwrap();
+    let copy = citation.clone();
+    assert_eq!(citation, copy);
+
+    let mut changed = citation.clone();
+    changed.references[0].authors.pop();
+    assert_ne!(citation, changed);
+    assert_eq!(citation.authors[0], copy.authors[0]);
+    assert_ne!(citation.authors[0], citation.authors[1]);
+
+    let citations: std::collections::HashSet<Citation> = [citation, copy, changed].into_iter().collect();
+    assert_eq!(citations.len(), 2);
+}
+
+#[test]
+fn default() {
+    let citation = Citation {
+        title: "citation".into(),
+        authors: vec![Author::Person(Person { given_names: Some("Luca".into()), family_names: Some("Lewin".into()), ..Default::default() })],
+        ..Default::default()
+    };
+    assert_eq!(citation.cff_version, "1.2.0");
+    assert_eq!(citation.message, DEFAULT_MESSAGE);
+    assert!(matches!(citation.r#type, Some(Type::Software)));
+    assert_eq!(citation, Citation::builder().cff_version("1.2.0").title("citation").author(citation.authors[0].clone()).build().unwrap());
+    assert!(citation.validate().is_valid());
+}
+
+#[test]
+fn display() {
+    let citation = Citation::read("./tests/CITATION.cff".into()).unwrap();
+    let line = citation.to_string();
+    assert!(!line.contains('\n'));
+    assert_eq!(line, citation.format(Style::Apa));
+
+    // the preferred citation is not used
+    let citation = Citation::read("./tests/references.cff".into()).unwrap();
+    assert!(citation.to_string().starts_with("Lewin, L., & The Rust Community. (n.d.). citation [Computer software]."), "{citation}");
+    assert_ne!(citation.to_string(), citation.format(Style::Apa));
+}
+
+#[test]
+fn errors() {
+    let error = Citation::read("./tests/missing.cff".into()).unwrap_err();
+    assert!(matches!(&error, Error::Io(error) if error.kind() == std::io::ErrorKind::NotFound));
+    assert_eq!(error.location(), None);
+
+    let error = Citation::read("./tests/malformed.cff".into()).unwrap_err();
+    assert!(matches!(&error, Error::Yaml(error) if error.path.as_deref() == Some("references[0].date-published")));
+    assert_eq!(error.location(), Some((12, 21)));
+
+    #[cfg(feature = "cargo")]
+    {
+        let directory = std::env::temp_dir().join("citation-errors");
+        std::fs::create_dir_all(&directory).unwrap();
+        std::fs::write(directory.join("Cargo.toml"), "[workspace]\nmembers = []\n").unwrap();
+        let error = Citation::from_cargo_manifest(directory.clone()).unwrap_err();
+        assert!(matches!(&error, Error::Schema { field, .. } if field == "package"));
+        assert_eq!(error.to_string(), "package: the manifest has no [package] table");
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    let citation = Citation::read("./tests/invalid.cff".into()).unwrap();
+    let error = citation.validate().into_result().unwrap_err();
+    assert!(matches!(&error, Error::Validation(report) if report.has_errors()));
+    assert!(Citation::read("./tests/CITATION.cff".into()).unwrap().validate().into_result().is_ok());
+}
+
+#[test]
+fn all_parse_errors() {
+    let content = std::fs::read_to_string("./tests/malformed.cff").unwrap();
+    let content = content.replace("  - given-names: Luca", "  - given-names: Luca\n    email: []")
+        + "    doi: not-a-doi\nidentifiers:\n  - type: doi\n    value: 10.5281/zenodo.1234\n  - type: handle\n    value: 1\nlicens: MIT\n";
+
+    let errors = Citation::parse_all(&content, &ParseOptions::default()).unwrap_err();
+    let paths: Vec<Option<&str>> = errors.iter().map(|error| error.path.as_deref()).collect();
+    assert_eq!(paths, [Some("authors[0].email"), Some("references[0].date-published"), Some("references[0].doi"), Some("identifiers[1].type")]);
+    assert_eq!(errors[1].message, "`2016-02-30` is not a valid date");
+    assert_eq!(errors[1].line, Some(13));
+    assert_eq!(errors[3].message, "unknown variant `handle`, expected one of `doi`, `url`, `swh`, `other`");
+
+    let errors = Citation::parse_all(&content, &ParseOptions::strict()).unwrap_err();
+    assert_eq!(errors.last().and_then(|error| error.path.as_deref()), Some("licens"));
+    assert_eq!(errors.last().unwrap().message, "unknown key `licens`, did you mean `license`?");
+
+    // the first error is the one reported by regular parsing
+    let error = content.parse::<Citation>().unwrap_err();
+    assert_eq!(error.path.as_deref(), Some("authors[0].email"));
+
+    let errors = Citation::parse_all("title: [", &ParseOptions::default()).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    let errors = Citation::parse_all("cff-version: 1.2.0\nmessage: m\ntitle: []\nauthors: []\n", &ParseOptions::default()).unwrap_err();
+    assert_eq!(errors.iter().map(|error| error.path.clone()).collect::<Vec<_>>(), [Some("title".to_string()), None]);
+
+    let content = std::fs::read_to_string("./tests/CITATION.cff").unwrap();
+    assert!(Citation::parse_all(&content, &ParseOptions::strict()).is_ok());
+}
+
+#[test]
+fn lossy() {
+    let content = std::fs::read_to_string("./tests/malformed.cff").unwrap();
+    let content = content.replace("title: citation\n", "").replace("    family-names: Lewin", "    family-names: Lewin\n    email: []\n  - given-names: Jane\n    family-names: Doe");
+
+    let (partial, issues) = Citation::parse_lossy(&content).unwrap();
+    assert_eq!(partial.missing, ["title"]);
+    assert!(!partial.is_complete());
+    assert_eq!(partial.citation.title, "");
+    assert_eq!(partial.citation.authors.len(), 2);
+    assert_eq!(partial.citation.references.len(), 1);
+    assert!(partial.citation.references[0].date_published.is_none());
+
+    let issues: Vec<(Code, &str, Option<usize>)> = issues.iter().map(|issue| (issue.code, issue.path.as_str(), issue.span.map(|span| span.line))).collect();
+    assert_eq!(issues, [(Code::MissingKey, "title", None), (Code::IgnoredValue, "authors[0].email", Some(6)), (Code::IgnoredValue, "references[0].date-published", Some(14))]);
+
+    let (partial, issues) = Citation::read_lossy("./tests/CITATION.cff".into()).unwrap();
+    assert!(issues.is_empty());
+    assert_eq!(partial.complete(), Some(Citation::read("./tests/CITATION.cff".into()).unwrap()));
+
+    let (partial, issues) = Citation::parse_lossy("- a list\n").unwrap();
+    assert_eq!(partial, PartialCitation { citation: Citation::default(), missing: vec!["authors".into(), "cff-version".into(), "message".into(), "title".into()] });
+    assert_eq!(issues.len(), 1);
+    assert!(Citation::parse_lossy("title: [").is_err());
+}
+
+#[test]
+fn lint() {
+    use citation::lint::{self, Level, LintConfig, Linter};
+
+    assert_eq!(lint::rule(Code::MissingDoi).unwrap().id, "CFF001");
+    assert_eq!(lint::find("CFF002").unwrap().code, Code::FutureDate);
+    assert_eq!(lint::find("deprecated-license").unwrap().id, "CFF003");
+    assert!(lint::find("CFF999").is_none());
+
+    let mut citation = Citation::read("./tests/invalid.cff".into()).unwrap();
+    citation.date_released = Some(Date::new(2999, 1, 1).unwrap());
+    let report = citation.validate();
+    let future = report.iter().find(|issue| issue.code == Code::FutureDate).unwrap();
+    assert_eq!((future.path.as_str(), future.severity), ("date-released", Severity::Warning));
+
+    let config = LintConfig::new().disable("CFF002").severity("invalid-url", Severity::Hint).level("empty-title", Level::Warning);
+    let linted = Linter::new(config).unwrap().lint(&citation);
+    assert_eq!(linted.len(), report.len() - 1);
+    assert!(linted.iter().all(|issue| issue.code != Code::FutureDate));
+    assert!(linted.iter().filter(|issue| issue.code == Code::InvalidUrl).all(|issue| issue.severity == Severity::Hint));
+    assert_eq!(linted.iter().find(|issue| issue.code == Code::EmptyTitle).unwrap().severity, Severity::Warning);
+    assert_eq!(Linter::default().lint(&citation).issues(), report.issues());
+
+    let config = LintConfig::from_yaml_str("rules:\n  CFF002: off\n  empty-authors: hint\n").unwrap();
+    assert_eq!(config, LintConfig::new().disable("CFF002").level("empty-authors", Level::Hint));
+
+    let error = Linter::new(LintConfig::from_yaml_str("rules:\n  CFF999: off\n").unwrap()).unwrap_err();
+    assert!(matches!(&error, Error::Schema { field, .. } if field == "rules.CFF999"), "{error}");
+    assert!(Linter::new(LintConfig::new().disable("missing-dio")).is_err());
+    assert!(matches!(LintConfig::from_yaml_str("rules:\n  CFF002: loud\n"), Err(Error::Yaml(_))));
+}
+
+#[test]
+fn custom_lint_rules() {
+    use citation::lint::{LintConfig, LintRule, Linter};
+
+    struct AuthorOrcid;
+
+    impl LintRule for AuthorOrcid {
+        fn id(&self) -> &'static str {
+            "ACME001"
+        }
+
+        fn name(&self) -> &'static str {
+            "author-orcid"
+        }
+
+        fn check(&self, citation: &Citation) -> Vec<citation::Issue> {
+            let persons = citation.authors.iter().enumerate().filter_map(|(index, author)| match author {
+                Author::Person(person) => Some((index, person)),
+                Author::Entity(_) => None,
+            });
+            persons.filter(|(_, person)| person.orcid.is_none()).map(|(index, _)| citation::Issue::new(Code::Custom(self.name()), format!("authors[{index}]"), "the author has no ORCID")).collect()
+        }
+    }
+
+    struct Duplicate;
+
+    impl LintRule for Duplicate {
+        fn id(&self) -> &'static str {
+            "ACME002"
+        }
+
+        fn name(&self) -> &'static str {
+            "missing-doi"
+        }
+
+        fn check(&self, _: &Citation) -> Vec<citation::Issue> {
+            Vec::new()
+        }
+    }
+
+    let mut citation = Citation::read("./tests/CITATION.cff".into()).unwrap();
+    assert!(Linter::with_rules(LintConfig::new(), vec![Box::new(AuthorOrcid)]).unwrap().lint(&citation).iter().all(|issue| issue.code != Code::Custom("author-orcid")));
+    if let Author::Person(person) = &mut citation.authors[0] {
+        person.orcid = None;
+    }
+
+    let linter = Linter::with_rules(LintConfig::new(), vec![Box::new(AuthorOrcid)]).unwrap();
+    let report = linter.lint(&citation);
+    let issue = report.iter().find(|issue| issue.code == Code::Custom("author-orcid")).unwrap();
+    assert_eq!((issue.severity, issue.code.to_string(), issue.path.as_str()), (Severity::Warning, "author-orcid".to_string(), "authors[0]"));
+    assert_eq!(report.len(), citation.validate().len() + 1);
+
+    let config = LintConfig::from_yaml_str("rules:\n  ACME001: error\n").unwrap();
+    let report = Linter::with_rules(config, vec![Box::new(AuthorOrcid)]).unwrap().lint(&citation);
+    assert!(report.has_errors());
+
+    let config = LintConfig::new().disable("author-orcid");
+    let report = Linter::with_rules(config.clone(), vec![Box::new(AuthorOrcid)]).unwrap().lint(&citation);
+    assert_eq!(report.len(), citation.validate().len());
+
+    assert!(Linter::new(config).is_err());
+    assert!(Linter::with_rules(LintConfig::new(), vec![Box::new(Duplicate)]).is_err());
+    assert!(Linter::with_rules(LintConfig::new(), vec![Box::new(AuthorOrcid), Box::new(AuthorOrcid)]).is_err());
+}
+
+#[test]
+fn invalid_emails() {
+    let mut citation = Citation::read("./tests/CITATION.cff".into()).unwrap();
+    assert!(citation.validate().iter().all(|issue| issue.code != Code::InvalidEmail));
+
+    let emails = ["first.last+tag@sub.example.org", "contact@lucalewin.dev", "Luca Lewin <contact@lucalewin.dev>", "mailto:contact@lucalewin.dev", "contact.lucalewin.dev", "contact@lucalewin", "contact..me@lucalewin.dev", ".contact@lucalewin.dev", "contact@-lucalewin.dev", "contact@lucalewin..dev", "@lucalewin.dev"];
+    citation.authors = emails.iter().map(|email| Author::Person(Person { email: Some(email.to_string()), ..Person::from_name("Luca Lewin") })).collect();
+
+    let report = citation.validate();
+    let invalid: Vec<&str> = report.iter().filter(|issue| issue.code == Code::InvalidEmail).map(|issue| issue.path.as_str()).collect();
+    assert_eq!(invalid, ["authors[2].email", "authors[3].email", "authors[4].email", "authors[5].email", "authors[6].email", "authors[7].email", "authors[8].email", "authors[9].email", "authors[10].email"]);
+
+    let issue = report.iter().find(|issue| issue.path == "authors[4].email").unwrap();
+    assert_eq!((issue.severity, issue.message.as_str()), (Severity::Error, "`contact.lucalewin.dev` is not an email address: it has no `@`"));
+    assert_eq!(citation::lint::find("CFF021").unwrap().code, Code::InvalidEmail);
+}
+
+#[test]
+fn license_lints() {
+    assert_eq!(License::from_id("GPL-3.0").replacements(), ["GPL-3.0-only", "GPL-3.0-or-later"]);
+    assert_eq!(License::from_id("GPL-2.0+").replacements(), ["GPL-2.0-or-later"]);
+    assert!(License::from_id("MIT").replacements().is_empty());
+    assert_eq!(License::from_id("Apache 2.0").suggestion(), Some("Apache-2.0"));
+    assert_eq!(License::from_id("MIT").suggestion(), None);
+    assert_eq!(License::from_id("LicenseRef-Proprietary").suggestion(), None);
+
+    let mut citation = Citation::read("./tests/CITATION.cff".into()).unwrap();
+    citation.license = Some(Licenses::Multiple(["GPL-3.0", "Apache 2.0", "LicenseRef-Proprietary", "mit"].map(License::from_id).to_vec()));
+
+    let report = citation.validate();
+    let issues: Vec<(Code, Severity, &str, &str)> = report.iter().filter(|issue| issue.path.starts_with("license")).map(|issue| (issue.code, issue.severity, issue.path.as_str(), issue.message.as_str())).collect();
+    assert_eq!(issues, [
+        (Code::DeprecatedLicense, Severity::Warning, "license[0]", "`GPL-3.0` is a deprecated SPDX license identifier, use `GPL-3.0-only` or `GPL-3.0-or-later` instead"),
+        (Code::UnknownLicense, Severity::Error, "license[1]", "`Apache 2.0` is not an SPDX license identifier, did you mean `Apache-2.0`?"),
+        (Code::UnknownLicense, Severity::Error, "license[2]", "`LicenseRef-Proprietary` is not an SPDX license identifier"),
+    ]);
+}
+
+#[test]
+#[cfg(feature = "jsonschema")]
+fn json_schema() {
+    assert!(CffVersion::V1_2.json_schema().unwrap().contains("\"$id\": \"https://citation-file-format.github.io/1.2.0/schema.json\""));
+    assert!(CffVersion::V1_1.json_schema().is_none());
+
+    for file in ["./tests/CITATION.cff", "./tests/CITATION.json", "./tests/references.cff"] {
+        let report = Document::read(file.into()).unwrap().validate_against_schema().unwrap();
+        assert!(report.is_empty(), "{file}: {report}");
+    }
+
+    let content = std::fs::read_to_string("./tests/CITATION.cff").unwrap();
+    let content = content.replace("    email: contact@lucalewin.dev\n", "    email: contact@lucalewin.dev\n    country: XY\n") + "commit: ''\nkeywords: [rust, rust]\n";
+    let report = Document::parse(content.as_str()).unwrap().validate_against_schema().unwrap();
+    let issues: Vec<(Code, &str, usize)> = report.iter().map(|issue| (issue.code, issue.path.as_str(), issue.span.unwrap().line)).collect();
+    assert_eq!(issues, [(Code::SchemaViolation, "authors[0]", 8), (Code::SchemaViolation, "commit", 17), (Code::SchemaViolation, "keywords", 18)]);
+
+    let legacy = Document::read("./tests/legacy.cff".into()).unwrap();
+    assert!(matches!(legacy.validate_against_schema(), Err(Error::Schema { field, .. }) if field == "cff-version"));
+}
+
+#[test]
+#[cfg(feature = "schemars")]
+fn schemars() {
+    let schema = serde_json::to_value(schemars::schema_for!(Citation)).unwrap();
+    let upstream: serde_json::Value = serde_json::from_str(&std::fs::read_to_string("./schemas/1.2.0.json").unwrap()).unwrap();
+
+    // the model defines the same keys as the schema of the latest cff-version
+    let keys = |value: &serde_json::Value| value.as_object().unwrap().keys().cloned().collect::<Vec<_>>();
+    assert_eq!(keys(&schema["properties"]), keys(&upstream["properties"]));
+    assert_eq!(schema["required"], upstream["required"]);
+    for (name, definition) in [("Person", "person"), ("Entity", "entity"), ("Reference", "reference")] {
+        assert_eq!(keys(&schema["$defs"][name]["properties"]), keys(&upstream["definitions"][definition]["properties"]), "{name}");
+    }
+    assert_eq!(schema["$defs"]["Reference"]["required"], upstream["definitions"]["reference"]["required"]);
+
+    let types = &schema["$defs"]["ReferenceType"]["anyOf"][0]["enum"];
+    assert_eq!(types, &upstream["definitions"]["reference"]["properties"]["type"]["enum"]);
+    assert_eq!(schema["$defs"]["Date"]["pattern"], upstream["definitions"]["date"]["pattern"]);
+}
+
+#[test]
+#[cfg(feature = "arbitrary")]
+fn arbitrary() {
+    use arbitrary::{Arbitrary, Unstructured};
+
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    for _ in 0..200 {
+        let bytes: Vec<u8> = (0..4096)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state as u8
+            })
+            .collect();
+
+        let citation = Citation::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+        assert!(citation.schema_version().is_some());
+
+        let yaml = citation.to_yaml_string().unwrap();
+        assert_eq!(yaml.parse::<Citation>().unwrap(), citation, "{yaml}");
+        citation.validate();
+    }
+}
+
+#[test]
+#[cfg(feature = "proptest")]
+fn proptest_strategies() {
+    use proptest::test_runner::TestRunner;
+
+    let mut runner = TestRunner::default();
+    runner
+        .run(&citation::testing::citation(), |citation| {
+            let report = citation.validate();
+            assert!(!report.has_errors() && !report.has_warnings(), "{report}");
+
+            let yaml = citation.to_yaml_string().unwrap();
+            assert_eq!(yaml.parse::<Citation>().unwrap(), citation, "{yaml}");
+            Ok(())
+        })
+        .unwrap();
+
+    runner
+        .run(&citation::testing::near_valid_citation(), |(citation, code)| {
+            let report = citation.validate();
+            let problems: Vec<Code> = report.at_least(Severity::Warning).map(|issue| issue.code).collect();
+            assert_eq!(problems, [code], "{report}");
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn borrowed_citation() {
+    let content = std::fs::read_to_string("./tests/CITATION.cff").unwrap();
+    let citation: Citation = content.parse().unwrap();
+    let borrowed = CitationRef::parse(&content).unwrap();
+
+    assert!(matches!(borrowed.cff_version, Cow::Borrowed("1.2.0")));
+    assert!(matches!(borrowed.version, Some(Cow::Borrowed("0.0.0"))));
+    assert!(matches!(borrowed.authors[0].given_names, Some(Cow::Borrowed("Luca"))));
+    // folded scalars are assembled from several lines
+    assert!(matches!(borrowed.message, Cow::Owned(_)));
+
+    assert_eq!(borrowed.title, citation.title);
+    assert_eq!(borrowed.message, citation.message);
+    assert_eq!(borrowed.r#type.as_deref(), Some("software"));
+    assert_eq!(borrowed.r#abstract, citation.r#abstract.map(Cow::Owned));
+    assert_eq!(borrowed.repository_code.as_deref(), Some("https://github.com/lucalewin/qed"));
+    assert_eq!(borrowed.authors.len(), 1);
+    assert_eq!(borrowed.authors[0].display_name(), citation.authors[0].display_name());
+    assert_eq!(borrowed.authors[0].orcid.as_deref(), Some("https://orcid.org/0009-0005-1296-096X"));
+    assert!(!borrowed.authors[0].is_entity());
+
+    let content = std::fs::read_to_string("./tests/references.cff").unwrap();
+    let borrowed = CitationRef::parse(&content).unwrap();
+    assert_eq!(borrowed.license, vec!["Apache-2.0"]);
+    assert_eq!(borrowed.authors[1].display_name(), "The Rust Community");
+    assert!(borrowed.authors[1].is_entity());
+    assert_eq!(borrowed.doi(), None);
+
+    let content = r#"{"cff-version": "1.2.0", "title": "citation", "message": "Please cite", "license": ["MIT", "Apache-2.0"], "identifiers": [{"type": "doi", "value": "10.5281/zenodo.1003150"}], "keywords": ["rust", "cff\u00e9"]}"#;
+    let borrowed = CitationRef::parse(content).unwrap();
+    assert!(matches!(borrowed.title, Cow::Borrowed("citation")));
+    assert!(matches!(borrowed.keywords[0], Cow::Borrowed("rust")));
+    // escape sequences are decoded into a new string
+    assert!(matches!(&borrowed.keywords[1], Cow::Owned(keyword) if keyword == "cffé"));
+    assert_eq!(borrowed.license, vec!["MIT", "Apache-2.0"]);
+    assert_eq!(borrowed.doi(), Some("10.5281/zenodo.1003150"));
+
+    let error = CitationRef::parse("cff-version: 1.2.0\nmessage: Please cite\n").unwrap_err();
+    assert!(error.message.contains("missing field `title`"));
+}
+
+#[test]
+#[cfg(feature = "rayon")]
+fn batch_validation() {
+    use citation::{batch, lint::{LintConfig, Linter}};
+
+    let paths = ["./tests/invalid.cff", "./tests/CITATION.cff", "./tests/missing.cff", "./tests/references.cff", "./tests/malformed.cff"];
+    let paths: Vec<&str> = paths.iter().cycle().take(50).copied().collect();
+    let results = batch::validate_all(paths.clone());
+
+    assert_eq!(results.iter().map(|(path, _)| path.to_str().unwrap()).collect::<Vec<_>>(), paths);
+    for (path, outcome) in &results {
+        match Citation::read(path.clone()) {
+            Ok(citation) => assert_eq!(outcome.as_ref().unwrap().issues(), citation.validate().with_source(path.display().to_string(), std::fs::read_to_string(path).unwrap()).issues()),
+            Err(_) => assert!(outcome.is_err()),
+        }
+    }
+    assert!(matches!(results[2].1, Err(Error::Io(_))));
+    assert!(results[0].1.as_ref().unwrap().has_errors());
+
+    let linter = Linter::new(LintConfig::new().disable("missing-doi")).unwrap();
+    let results = batch::validate_all_with(["./tests/CITATION.cff"], &ParseOptions::default(), &linter);
+    assert!(results[0].1.as_ref().unwrap().iter().all(|issue| issue.code != Code::MissingDoi));
+}
+
+#[test]
+fn markdown_section() {
+    let mut citation = Citation::read("./tests/CITATION.cff".into()).unwrap();
+    let section = citation.to_markdown_section();
+    assert!(section.starts_with("<!-- citation:start -->\n## How to cite\n\nIf you use this software, please cite it as:\n\nLewin, L. (n.d.). *QED: mathematical proof assistant/database*"));
+    assert!(section.contains(&format!("```bibtex\n{}```\n", citation.to_bibtex())));
+    assert!(section.ends_with("<!-- citation:end -->\n"));
+
+    let readme = "# QED\n\nA proof assistant.\n";
+    let updated = citation.update_markdown_section(readme);
+    assert_eq!(updated, format!("# QED\n\nA proof assistant.\n\n{section}"));
+    assert_eq!(citation.update_markdown_section(&updated), updated);
+    assert_eq!(citation.update_markdown_section(""), section);
+
+    citation.version = Some("1.0.0".into());
+    let edited = format!("{updated}\n## License\n\nMIT\n");
+    let synced = citation.update_markdown_section(&edited);
+    assert_eq!(synced, format!("# QED\n\nA proof assistant.\n\n{}\n## License\n\nMIT\n", citation.to_markdown_section()));
+    assert!(synced.contains("(Version 1.0.0)"));
+}
+
+#[test]
+fn latex_snippet() {
+    use citation::LatexOptions;
+
+    let mut citation = Citation::read("./tests/CITATION.cff".into()).unwrap();
+    assert_eq!(citation.citation_key("{author}_{title}_{year}"), "lewin_qed");
+    citation.date_released = Some(Date::new(2024, 3, 1).unwrap());
+    assert_eq!(citation.citation_key("{author}_{title}_{year}"), "lewin_qed_2024");
+    assert_eq!(citation.citation_key("{author}{year}-v{version}"), "lewin2024-v0.0.0");
+    assert_eq!(citation.citation_key("{unknown}:{title}"), "qed");
+    assert_eq!(citation.citation_key(""), "citation");
+
+    let snippet = citation.to_latex_snippet(&LatexOptions::default());
+    assert_eq!(snippet.key, "lewin_qed_2024");
+    assert_eq!(snippet.cite, "\\cite{lewin_qed_2024}");
+    assert_eq!(snippet.entry, citation.to_bibtex().replace("@software{lewin2024,", "@software{lewin_qed_2024,"));
+    let snippet = citation.to_latex_snippet(&LatexOptions { key: "{title}{year}".into(), biblatex: true });
+    assert!(snippet.entry.starts_with("@software{qed2024,\n"));
+    assert!(snippet.entry.contains("  date = {2024-03-01},\n"));
+
+    citation.authors = vec![Author::Person(Person { given_names: Some("Jürgen".into()), name_particle: Some("von".into()), family_names: Some("Müller-Lüdenscheidt".into()), ..Person::default() })];
+    citation.title = "The Ökonomie of citations".into();
+    assert_eq!(citation.citation_key("{author}_{title}_{year}"), "vonmullerludenscheidt_okonomie_2024");
+
+    let citation = Citation::read("./tests/references.cff".into()).unwrap();
+    assert_eq!(citation.citation_key("{author}_{title}_{year}"), "lewin_citation_2024");
+}
+
+#[test]
+fn badge() {
+    use citation::Badge;
+
+    let mut citation = Citation::read("./tests/CITATION.cff".into()).unwrap();
+    citation.doi = None;
+    citation.identifiers.clear();
+    citation.repository_code = Some("https://github.com/lucalewin/citation".into());
+    let badge = citation.badge();
+    assert_eq!(badge.url(), "https://img.shields.io/badge/cite-this_software-blue");
+    assert_eq!(badge.to_markdown(), "[![cite: this software](https://img.shields.io/badge/cite-this_software-blue)](https://github.com/lucalewin/citation)");
+
+    citation.doi = Some("10.5281/zenodo.1003150".parse().unwrap());
+    let badge = citation.badge();
+    assert_eq!(badge.label, "DOI");
+    assert_eq!(badge.url(), "https://img.shields.io/badge/DOI-10.5281%2Fzenodo.1003150-blue");
+    assert_eq!(badge.link.as_deref(), Some("https://doi.org/10.5281/zenodo.1003150"));
+
+    let badge = Badge { label: "my-label".into(), message: "a_b & <c>".into(), color: "4c1".into(), link: None };
+    assert_eq!(badge.url(), "https://img.shields.io/badge/my--label-a__b_%26_%3Cc%3E-4c1");
+    let svg = badge.to_svg();
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    assert!(svg.contains("<title>my-label: a_b &amp; &lt;c&gt;</title>"));
+    assert!(svg.contains("fill=\"#4c1\""));
+    assert!(svg.ends_with("</svg>"));
+    assert!(citation.badge().to_svg().starts_with("<a href=\"https://doi.org/10.5281/zenodo.1003150\"><svg "));
+}
+
+#[cfg(feature = "wasm")]
+#[test]
+fn wasm() {
+    use citation::wasm;
+
+    let content = std::fs::read_to_string("./tests/CITATION.cff").unwrap();
+    let citation: Citation = content.parse().unwrap();
+
+    let report: serde_json::Value = serde_json::from_str(&wasm::validate(&content, false)).unwrap();
+    assert_eq!(report, serde_json::from_str::<serde_json::Value>(&citation.validate().with_source("CITATION.cff", content.as_str()).to_json()).unwrap());
+    let report: serde_json::Value = serde_json::from_str(&wasm::validate("cff-version: 1.2.0\ntitle: [", false)).unwrap();
+    assert_eq!(report["valid"], false);
+    assert_eq!(report["line"], 2);
+    assert!(report["error"].is_string());
+
+    assert_eq!(wasm::format(&content, "apa", "html").unwrap(), citation.format_as(Style::Apa, OutputFormat::Html));
+    assert_eq!(wasm::convert(&content, "bibtex").unwrap(), citation.to_bibtex());
+    assert_eq!(wasm::convert(&serde_json::to_string(&citation).unwrap(), "cff").unwrap(), citation.to_yaml_string().unwrap());
+}
+
+#[cfg(feature = "python")]
+#[test]
+fn python() {
+    use citation::python::PyCitation;
+
+    let content = std::fs::read_to_string("./tests/CITATION.cff").unwrap();
+    let citation: Citation = content.parse().unwrap();
+    let Ok(py) = PyCitation::read("./tests/CITATION.cff".into(), false) else { panic!("cannot read the citation") };
+    assert_eq!(py.title(), citation.title);
+    assert_eq!(py.version(), Some("0.0.0"));
+
+    let issues = py.validate();
+    assert_eq!(issues.len(), citation.validate().iter().count());
+    assert!(issues.iter().all(|issue| !issue.code.is_empty() && !issue.help.is_empty()));
+
+    assert_eq!(py.format("apa", "html").ok(), Some(citation.format_as(Style::Apa, OutputFormat::Html)));
+    assert_eq!(py.convert("bibtex").ok(), Some(citation.to_bibtex()));
+    assert_eq!(py.convert("cff").ok(), citation.to_yaml_string().ok());
+    assert!(PyCitation::parse(&content, true).is_ok());
+    assert!(PyCitation::parse("cff-version: 1.2.0\ntitle: [", false).is_err());
+}
+
+#[cfg(feature = "capi")]
+#[test]
+fn capi() {
+    use std::{ffi::{CStr, CString}, ptr};
+
+    use citation::capi::*;
+
+    let content = std::fs::read_to_string("./tests/CITATION.cff").unwrap();
+    let expected: Citation = content.parse().unwrap();
+    let take = |string: *mut std::ffi::c_char| unsafe {
+        assert!(!string.is_null());
+        let owned = CStr::from_ptr(string).to_str().unwrap().to_string();
+        citation_string_free(string);
+        owned
+    };
+
+    unsafe {
+        let citation = citation_parse(content.as_ptr(), content.len(), ptr::null_mut());
+        assert!(!citation.is_null());
+        assert_eq!(take(citation_to_bibtex(citation)), expected.to_bibtex());
+        assert_eq!(take(citation_validate(citation)), expected.validate().with_source("CITATION.cff", content.as_str()).to_json());
+        let (apa, html) = (CString::new("apa").unwrap(), CString::new("html").unwrap());
+        assert_eq!(take(citation_format(citation, apa.as_ptr(), html.as_ptr())), expected.format_as(Style::Apa, OutputFormat::Html));
+        let ris = CString::new("ris").unwrap();
+        assert_eq!(take(citation_convert(citation, ris.as_ptr())), expected.to_ris());
+        assert!(citation_convert(citation, html.as_ptr()).is_null());
+        assert!(citation_format(citation, html.as_ptr(), apa.as_ptr()).is_null());
+        citation_free(citation);
+
+        let mut error = ptr::null_mut();
+        let invalid = "cff-version: 1.2.0\ntitle: [";
+        assert!(citation_parse(invalid.as_ptr(), invalid.len(), &mut error).is_null());
+        assert!(take(error).ends_with("at line 2 column 8"));
+        assert!(citation_parse([0xff].as_ptr(), 1, &mut error).is_null());
+        assert!(take(error).starts_with("the content is not valid UTF-8"));
+        assert!(citation_to_bibtex(ptr::null()).is_null());
+        citation_free(ptr::null_mut());
+    }
+}
+
+#[test]
+fn from_str() {
+    let content = std::fs::read_to_string("./tests/CITATION.cff").unwrap();
+
+    let citation: Citation = content.parse().unwrap();
+    assert_eq!(citation.title, "QED: mathematical proof assistant/database");
+
+    let citation = Citation::from_reader(content.as_bytes()).unwrap();
+    assert_eq!(citation.title, "QED: mathematical proof assistant/database");
+
+    let error = "title: [".parse::<Citation>().unwrap_err();
+    assert_eq!(error.line, Some(1));
+    assert!(Citation::from_reader(&b"\xff\xfe"[..]).is_err());
+}
+
+#[cfg(feature = "async")]
+#[tokio::test]
+async fn read_async() {
+    let citation = Citation::read_async("./tests/CITATION.cff".into()).await.unwrap();
+    assert_eq!(citation.title, "QED: mathematical proof assistant/database");
+
+    let path = std::env::temp_dir().join("citation-read-async.cff");
+    citation.write_async(path.clone()).await.unwrap();
+    assert_eq!(Citation::read_async(path).await.unwrap().title, citation.title);
+
+    let error = Citation::read_async("./tests/malformed.cff".into()).await.unwrap_err();
+    assert!(matches!(error, Error::Yaml(_)));
+}
+
+#[cfg(feature = "scan")]
+#[test]
+fn scan() {
+    use citation::ScanOptions;
+
+    let root = std::env::temp_dir().join("citation-scan");
+    let _ = std::fs::remove_dir_all(&root);
+    for directory in ["crates/core", "crates/cli", "target/package", "vendor", ".git"] {
+        std::fs::create_dir_all(root.join(directory)).unwrap();
+    }
+    std::fs::copy("./tests/CITATION.cff", root.join("CITATION.cff")).unwrap();
+    std::fs::copy("./tests/references.cff", root.join("crates/core/CITATION.cff")).unwrap();
+    std::fs::copy("./tests/malformed.cff", root.join("crates/cli/CITATION.cff")).unwrap();
+    std::fs::copy("./tests/CITATION.cff", root.join("target/package/CITATION.cff")).unwrap();
+    std::fs::copy("./tests/CITATION.cff", root.join("vendor/CITATION.cff")).unwrap();
+    std::fs::copy("./tests/CITATION.cff", root.join(".git/CITATION.cff")).unwrap();
+    std::fs::write(root.join(".gitignore"), "/target\nvendor/\n").unwrap();
+    let citation = Citation::read("./tests/CITATION.cff".into()).unwrap();
+    std::fs::write(root.join("crates/core/.zenodo.json"), citation.to_zenodo_json()).unwrap();
+    std::fs::write(root.join("codemeta.json"), "{ \"name\": \"citation\" }").unwrap();
+
+    let scan = Citation::scan(root.clone(), &ScanOptions::default());
+    let paths: Vec<_> = scan.citations.iter().map(|(path, _)| path.strip_prefix(&root).unwrap().to_path_buf()).collect();
+    assert_eq!(paths, vec![std::path::PathBuf::from("CITATION.cff"), "crates/core/CITATION.cff".into()]);
+    assert_eq!(scan.citations[1].1.title, "citation");
+    assert_eq!(scan.errors.len(), 1);
+    assert_eq!(scan.errors[0].0, root.join("crates/cli/CITATION.cff"));
+    assert!(matches!(&scan.errors[0].1, Error::Yaml(error) if error.line.is_some()));
+
+    let scan = Citation::scan(root.clone(), &ScanOptions { zenodo: true, codemeta: true, ..Default::default() });
+    let paths: Vec<_> = scan.citations.iter().map(|(path, _)| path.strip_prefix(&root).unwrap().to_path_buf()).collect();
+    assert_eq!(paths, vec![std::path::PathBuf::from("CITATION.cff"), "crates/core/.zenodo.json".into(), "crates/core/CITATION.cff".into()]);
+    assert_eq!(scan.citations[1].1.title, citation.title);
+    let errors: Vec<_> = scan.errors.iter().map(|(path, _)| path.strip_prefix(&root).unwrap().to_path_buf()).collect();
+    assert_eq!(errors, vec![std::path::PathBuf::from("codemeta.json"), "crates/cli/CITATION.cff".into()]);
+    assert!(scan.errors[0].1.to_string().contains("authors"));
+}
+
+#[test]
+fn discover() {
+    let root = std::env::temp_dir().join("citation-discover");
+    let nested = root.join("crates/core/src");
+    let _ = std::fs::remove_dir_all(&root);
+    std::fs::create_dir_all(&nested).unwrap();
+    std::fs::create_dir_all(root.join(".github")).unwrap();
+    std::fs::copy("./tests/CITATION.cff", root.join(".github/CITATION.cff")).unwrap();
+
+    let (path, citation) = Citation::discover(nested.clone()).unwrap().unwrap();
+    assert_eq!(path, root.canonicalize().unwrap().join(".github/CITATION.cff"));
+    assert_eq!(citation.title, "QED: mathematical proof assistant/database");
+
+    std::fs::copy("./tests/references.cff", root.join("crates/CITATION.cff")).unwrap();
+    let (path, _) = Citation::discover(nested).unwrap().unwrap();
+    assert_eq!(path, root.canonicalize().unwrap().join("crates/CITATION.cff"));
+
+    let (path, _) = Citation::discover("./tests".into()).unwrap().unwrap();
+    assert!(path.ends_with("tests/CITATION.cff"));
+}
+
+#[cfg(feature = "cargo")]
+#[test]
+fn cargo_manifest() {
+    let citation = Citation::from_cargo_manifest(".".into()).unwrap();
+    assert_eq!(citation.title, "citation");
+    assert_eq!(citation.version.as_deref(), Some(env!("CARGO_PKG_VERSION")));
+    assert_eq!(citation.r#abstract.as_deref(), Some("library to manipulate citation files"));
+    assert_eq!(citation.licenses(), [License::MIT, License::Apache_2_0]);
+    assert_eq!(citation.repository_code.as_deref(), Some("https://github.com/lucalewin/citation"));
+    assert!(citation.validate().is_valid());
+
+    let Author::Person(author) = &citation.authors[0] else { panic!("expected a person") };
+    assert_eq!(author.given_names.as_deref(), Some("Luca"));
+    assert_eq!(author.family_names.as_deref(), Some("Lewin"));
+    assert_eq!(author.email.as_deref(), Some("contact@lucalewin.dev"));
+
+    let root = std::env::temp_dir().join("citation-cargo-workspace");
+    std::fs::create_dir_all(root.join("member")).unwrap();
+    std::fs::write(
+        root.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"member\"]\n\n[workspace.package]\nversion = \"2.1.0\"\nauthors = [\"Ferris\"]\nlicense = \"MIT AND Zlib\"\n",
+    )
+    .unwrap();
+    std::fs::write(root.join("member/Cargo.toml"), "[package]\nname = \"member\"\nversion.workspace = true\nauthors.workspace = true\nlicense.workspace = true\n").unwrap();
+
+    let citation = Citation::from_cargo_manifest(root.join("member/Cargo.toml")).unwrap();
+    assert_eq!(citation.version.as_deref(), Some("2.1.0"));
+    assert_eq!(citation.authors[0].display_name(), "Ferris");
+    assert_eq!(citation.license, None);
+
+    std::fs::write(
+        root.join("member/Cargo.toml"),
+        "[package]\nname = \"member\"\nversion.workspace = true\n\n[package.metadata.citation]\ntitle = \"Member\"\ndate-released = 2024-05-01\nauthors = [{ name = \"The Member Developers\" }]\n",
+    )
+    .unwrap();
+    let citation = Citation::from_cargo_manifest(root.join("member")).unwrap();
+    assert_eq!(citation.title, "Member");
+    assert_eq!(citation.version.as_deref(), Some("2.1.0"));
+    assert_eq!(citation.date_released, Some(Date::new(2024, 5, 1).unwrap()));
+    assert_eq!(citation.authors[0].display_name(), "The Member Developers");
+}
+
+#[cfg(feature = "cargo")]
+#[test]
+fn cargo_dependencies() {
+    use citation::{DependencyOptions, DependencySource};
+
+    let root = std::env::temp_dir().join("citation-cargo-dependencies");
+    let _ = std::fs::remove_dir_all(&root);
+    let package = |directory: &str, manifest: &str| {
+        std::fs::create_dir_all(root.join(directory).join("src")).unwrap();
+        std::fs::write(root.join(directory).join("src/lib.rs"), "").unwrap();
+        std::fs::write(root.join(directory).join("Cargo.toml"), manifest).unwrap();
+    };
+    package(".", "[package]\nname = \"app\"\nversion = \"1.0.0\"\nedition = \"2021\"\n\n[dependencies]\nalpha = { path = \"alpha\" }\nbeta = { path = \"beta\" }\nold = { path = \"epsilon-0.9\", package = \"epsilon\" }\nnew = { path = \"epsilon-0.10\", package = \"epsilon\" }\n\n[dev-dependencies]\ngamma = { path = \"gamma\" }\n");
+    package("alpha", "[package]\nname = \"alpha\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[dependencies]\ndelta = { path = \"../delta\" }\n");
+    package("beta", "[package]\nname = \"beta\"\nversion = \"0.2.0\"\nedition = \"2021\"\nauthors = [\"Jane Doe <jane@example.org>\"]\nrepository = \"https://github.com/example/beta\"\n");
+    package("gamma", "[package]\nname = \"gamma\"\nversion = \"0.3.0\"\nedition = \"2021\"\nauthors = [\"Ferris\"]\n");
+    package("delta", "[package]\nname = \"delta\"\nversion = \"0.4.0\"\nedition = \"2021\"\n");
+    package("epsilon-0.9", "[package]\nname = \"epsilon\"\nversion = \"0.9.0\"\nedition = \"2021\"\nauthors = [\"Ferris\"]\n");
+    package("epsilon-0.10", "[package]\nname = \"epsilon\"\nversion = \"0.10.0\"\nedition = \"2021\"\nauthors = [\"Ferris\"]\n");
+    std::fs::write(root.join("beta/README.md"), "# beta\n\n[![DOI](https://zenodo.org/badge/DOI/10.5281/zenodo.1003150.svg)](https://doi.org/10.5281/zenodo.1003150)\n").unwrap();
+    std::fs::copy("./tests/CITATION.cff", root.join("alpha/CITATION.cff")).unwrap();
+
+    let dependencies = Citation::from_cargo_dependencies(root.clone(), &DependencyOptions::default()).unwrap();
+    let names: Vec<&str> = dependencies.dependencies.iter().map(|dependency| dependency.name.as_str()).collect();
+    assert_eq!(names, ["alpha", "beta", "epsilon", "epsilon"]);
+    let versions: Vec<&str> = dependencies.dependencies.iter().map(|dependency| dependency.version.as_str()).collect();
+    assert_eq!(versions[2..], ["0.9.0", "0.10.0"]);
+    assert_eq!(dependencies.dependencies[0].source, DependencySource::File(root.join("alpha/CITATION.cff")));
+    assert_eq!(dependencies.dependencies[0].citation.title, "QED: mathematical proof assistant/database");
+    assert_eq!(dependencies.dependencies[1].source, DependencySource::Manifest(root.join("beta/Cargo.toml")));
+    assert_eq!(dependencies.dependencies[1].version, "0.2.0");
+    assert_eq!(dependencies.dependencies[1].citation.repository_code.as_deref(), Some("https://github.com/example/beta"));
+    assert_eq!(dependencies.dependencies[1].citation.doi.as_ref().map(Doi::as_str), Some("10.5281/zenodo.1003150"));
+    assert_eq!(dependencies.errors.len(), 1);
+    assert_eq!(dependencies.errors[0].0, "delta");
+
+    let bibtex = dependencies.to_bibtex();
+    assert!(bibtex.starts_with("@software{alpha,\n"), "{bibtex}");
+    assert!(bibtex.contains("@software{beta,\n  author = {Doe, Jane},\n"), "{bibtex}");
+    assert!(bibtex.find("@software{epsilon-0.9.0,").unwrap() < bibtex.find("@software{epsilon-0.10.0,").unwrap(), "{bibtex}");
+    assert_eq!(dependencies.format(Style::Apa).lines().count(), 4);
+
+    let options = DependencyOptions { dev_dependencies: true, ..Default::default() };
+    let dependencies = Citation::from_cargo_dependencies(root.join("Cargo.toml"), &options).unwrap();
+    let names: Vec<&str> = dependencies.dependencies.iter().map(|dependency| dependency.name.as_str()).collect();
+    assert_eq!(names, ["alpha", "beta", "epsilon", "epsilon", "gamma"]);
+
+    #[cfg(not(feature = "http"))]
+    assert!(Citation::from_cargo_dependencies(root.clone(), &DependencyOptions { fetch: true, ..Default::default() }).is_err());
+
+    #[cfg(feature = "cli")]
+    {
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_cargo-citation")).args(["citation", "deps", "--format", "apa"]).current_dir(&root).output().unwrap();
+        assert_eq!(output.status.code(), Some(1));
+        assert!(String::from_utf8(output.stdout).unwrap().starts_with("Lewin, L. (n.d.). QED"));
+        assert!(String::from_utf8(output.stderr).unwrap().contains("warning: delta: "));
+    }
+
+    assert!(Citation::from_cargo_dependencies(root.join("missing"), &DependencyOptions::default()).is_err());
+    std::fs::remove_dir_all(root).unwrap();
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn toml_input() {
+    let citation = Citation::read("./tests/Citation.toml".into()).unwrap();
+    assert_eq!(citation.title, "QED: mathematical proof assistant/database");
+    assert_eq!(citation.date_released, Some(Date::new(2021, 7, 18).unwrap()));
+    assert_eq!(citation.keywords, ["proof assistant", "mathematics"]);
+    assert_eq!(citation.authors[0].display_name(), "Luca Lewin");
+    assert!(citation.validate().is_valid());
+
+    let content = std::fs::read_to_string("./tests/Citation.toml").unwrap();
+    assert_eq!(Citation::from_toml_str(&content).unwrap().version.as_deref(), Some("0.0.0"));
+    let citation = Citation::parse_file(std::path::Path::new("Citation.toml"), &content, &ParseOptions::strict()).unwrap();
+    assert_eq!(citation.title, "QED: mathematical proof assistant/database");
+    assert!(Citation::parse_file(std::path::Path::new("CITATION.cff"), &content, &ParseOptions::default()).is_err());
+
+    let error = Citation::from_toml_str("title = \"QED\"\nauthors = [\n").unwrap_err();
+    assert_eq!(error.line, Some(3));
+
+    let error = Citation::from_toml_str(&content.replace("2021-07-18", "\"2021-13-18\"")).unwrap_err();
+    assert_eq!(error.path.as_deref(), Some("date-released"));
+
+    let path = std::env::temp_dir().join("citation-toml-input.toml");
+    std::fs::write(&path, content.replace("[[authors]]", "homepage = \"https://example.com\"\n\n[[authors]]")).unwrap();
+    assert!(Citation::read(path.clone()).is_ok());
+    let error = Citation::read_strict(path.clone()).unwrap_err();
+    assert!(matches!(error, Error::Yaml(error) if error.message == "unknown key `homepage`"));
+    std::fs::remove_file(path).unwrap();
+}
+
+#[cfg(feature = "git")]
+#[test]
+fn fill_from_git() {
+    use git2::{Repository, Signature, Time};
+
+    let root = std::env::temp_dir().join("citation-git");
+    let _ = std::fs::remove_dir_all(&root);
+    let repository = Repository::init(&root).unwrap();
+
+    let commit = |message: &str, seconds: i64, parents: &[&git2::Commit]| {
+        let signature = Signature::new("Luca Lewin", "contact@lucalewin.dev", &Time::new(seconds, 120)).unwrap();
+        let tree = repository.find_tree(repository.index().unwrap().write_tree().unwrap()).unwrap();
+        let id = repository.commit(Some("HEAD"), &signature, &signature, message, &tree, parents).unwrap();
+        repository.find_commit(id).unwrap()
+    };
+
+    let mut citation = Citation::read("./tests/CITATION.cff".into()).unwrap();
+    let first = commit("Initial commit", 1_700_000_000, &[]);
+    citation.fill_from_git(root.clone()).unwrap();
+    assert_eq!(citation.commit, Some(first.id().to_string()));
+    assert_eq!(citation.version.as_deref(), Some("0.0.0"));
+
+    // 2023-12-31T23:30:00Z is already 2024-01-01 in the time zone of the committer
+    let released = commit("Release", 1_704_065_400, &[&first]);
+    repository.tag_lightweight("v2.0.0", released.as_object(), false).unwrap();
+    let head = commit("Start next release", 1_710_000_000, &[&released]);
+
+    citation.fill_from_git(root.join(".git")).unwrap();
+    assert_eq!(citation.commit, Some(head.id().to_string()));
+    assert_eq!(citation.version.as_deref(), Some("2.0.0"));
+    assert_eq!(citation.date_released, Some(Date::new(2024, 1, 1).unwrap()));
+    assert!(citation.check_git_history(root.clone()).unwrap().is_empty());
+
+    citation.date_released = Some(Date::new(2023, 1, 1).unwrap());
+    let messages: Vec<String> = citation.check_git_history(root.clone()).unwrap().iter().map(|issue| format!("{}: {}", issue.code, issue.message)).collect();
+    assert_eq!(messages, [
+        "inconsistent-date: 2023-01-01 is older than the first commit on 2023-11-15",
+        "inconsistent-date: 2023-01-01 differs from the date of the tag `v2.0.0` of version 2.0.0, 2024-01-01",
+    ]);
+
+    citation.version = Some("3.0.0".into());
+    citation.date_released = Some(Date::new(2024, 2, 1).unwrap());
+    assert!(citation.check_git_history(root).unwrap().is_empty());
+}
+
+#[cfg(feature = "git")]
+#[test]
+fn authors_from_git() {
+    use git2::{Repository, Signature};
+
+    let root = std::env::temp_dir().join("citation-git-authors");
+    let _ = std::fs::remove_dir_all(&root);
+    let repository = Repository::init(&root).unwrap();
+    std::fs::write(root.join(".mailmap"), "Luca Lewin <contact@lucalewin.dev> <luca@old-laptop.local>\n").unwrap();
+
+    let mut parent = None;
+    for (name, email) in [
+        ("Luca Lewin", "contact@lucalewin.dev"),
+        ("luca", "luca@old-laptop.local"),
+        ("Jane Doe", "jane@example.com"),
+        ("dependabot[bot]", "49699333+dependabot[bot]@users.noreply.github.com"),
+        ("Luca Lewin", "contact@lucalewin.dev"),
+        ("Jane Doe", "jane@example.com"),
+        ("Drive-By", "drive-by@example.com"),
+    ] {
+        let signature = Signature::now(name, email).unwrap();
+        let tree = repository.find_tree(repository.index().unwrap().write_tree().unwrap()).unwrap();
+        let parents: Vec<_> = parent.iter().collect();
+        let id = repository.commit(Some("HEAD"), &signature, &signature, "commit", &tree, &parents).unwrap();
+        parent = Some(repository.find_commit(id).unwrap());
+    }
+
+    let authors = Author::from_git_history(root.clone(), 2).unwrap();
+    let names: Vec<_> = authors.iter().map(Author::display_name).collect();
+    assert_eq!(names, ["Luca Lewin", "Jane Doe"]);
+
+    let Author::Person(person) = &authors[0] else { panic!("expected a person") };
+    assert_eq!(person.family_names.as_deref(), Some("Lewin"));
+    assert_eq!(person.email.as_deref(), Some("contact@lucalewin.dev"));
+
+    assert_eq!(Author::from_git_history(root, 1).unwrap().len(), 3);
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn fetch_from_github() {
+    use std::io::{BufRead, BufReader, Write};
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    std::env::set_var("GITHUB_API_URL", format!("http://{}", listener.local_addr().unwrap()));
+    std::env::remove_var("GITHUB_TOKEN");
+
+    let server = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = String::new();
+        BufReader::new(&stream).read_line(&mut request).unwrap();
+
+        let body = std::fs::read_to_string("./tests/CITATION.cff").unwrap();
+        write!(stream, "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}", body.len()).unwrap();
+        request
+    });
+
+    let citation = Citation::fetch_from_github("lucalewin/citation@v1.0.0").unwrap();
+    assert_eq!(citation.title, "QED: mathematical proof assistant/database");
+    assert_eq!(server.join().unwrap(), "GET /repos/lucalewin/citation/contents/CITATION.cff?ref=v1.0.0 HTTP/1.1\r\n");
+
+    assert!(Citation::fetch_from_github("lucalewin").is_err());
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn zenodo_deposition() {
+    use std::io::{BufRead, BufReader, Read, Write};
+
+    use citation::{Deposition, ZenodoClient};
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let client = ZenodoClient::new("secret").with_api_url(format!("http://{}/api/", listener.local_addr().unwrap()));
+
+    let server = std::thread::spawn(move || {
+        let mut requests = Vec::new();
+        for _ in 0..2 {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(&stream);
+            let mut head = String::new();
+            while !head.ends_with("\r\n\r\n") {
+                reader.read_line(&mut head).unwrap();
+            }
+            let length = head.lines().find_map(|line| line.to_lowercase().strip_prefix("content-length: ").map(|length| length.parse().unwrap())).unwrap();
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+
+            let response = r#"{"id": 1234, "metadata": {"prereserve_doi": {"doi": "10.5281/zenodo.1234", "recid": 1234}}}"#;
+            write!(stream, "HTTP/1.1 201 Created\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{response}", response.len()).unwrap();
+            requests.push((head, String::from_utf8(body).unwrap()));
+        }
+        requests
+    });
+
+    let citation = Citation::read("./tests/CITATION.cff".into()).unwrap();
+    let deposition = client.create_deposition(&citation).unwrap();
+    assert_eq!(deposition, Deposition { id: 1234, doi: Doi::new("10.5281/zenodo.1234").unwrap() });
+    client.update_deposition(deposition.id, &citation).unwrap();
+
+    let requests = server.join().unwrap();
+    assert!(requests[0].0.starts_with("POST /api/deposit/depositions HTTP/1.1\r\n"));
+    assert!(requests[0].0.to_lowercase().contains("authorization: bearer secret"));
+    let body: serde_json::Value = serde_json::from_str(&requests[0].1).unwrap();
+    assert_eq!(body["metadata"]["title"], "QED: mathematical proof assistant/database");
+    assert_eq!(body["metadata"]["prereserve_doi"], true);
+    assert!(requests[1].0.starts_with("PUT /api/deposit/depositions/1234 HTTP/1.1\r\n"));
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn from_doi() {
+    use std::io::{BufRead, BufReader, Write};
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    std::env::set_var("DOI_RESOLVER_URL", format!("http://{}", listener.local_addr().unwrap()));
+
+    let server = std::thread::spawn(move || {
+        let mut requests = Vec::new();
+        for _ in 0..3 {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(&stream);
+            let mut head = String::new();
+            while !head.ends_with("\r\n\r\n") {
+                reader.read_line(&mut head).unwrap();
+            }
+
+            let body = r#"{
+  "type": "article-journal",
+  "title": "Software citation principles",
+  "author": [{ "given": "Arfon M.", "family": "Smith" }, { "given": "Daniel S.", "family": "Katz" }],
+  "container-title": "PeerJ Computer Science",
+  "volume": "2",
+  "page": "e86",
+  "issued": { "date-parts": [[2016, 9, 19]] },
+  "DOI": "10.7717/peerj-cs.86",
+  "URL": "https://doi.org/10.7717/peerj-cs.86"
+}"#;
+            write!(stream, "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}", body.len()).unwrap();
+            requests.push(head);
+        }
+        requests
+    });
+
+    let reference = Reference::from_doi("10.7717/peerj-cs.86").unwrap();
+    assert_eq!(reference.r#type, ReferenceType::Article);
+    assert_eq!(reference.title, "Software citation principles");
+    assert_eq!(reference.journal.as_deref(), Some("PeerJ Computer Science"));
+    assert_eq!(reference.date_published, Some(Date::new(2016, 9, 19).unwrap()));
+
+    let citation = Citation::from_doi("https://doi.org/10.7717/peerj-cs.86").unwrap();
+    assert_eq!(citation.authors.len(), 2);
+    assert_eq!(citation.doi.as_ref().map(Doi::as_str), Some("10.7717/peerj-cs.86"));
+
+    let mut citation = Citation::read("./tests/references.cff".into()).unwrap();
+    let reference = &mut citation.references[0];
+    reference.title = "Software Citation Principles (draft)".into();
+    reference.authors.push(Author::Person(Person { given_names: Some("Kyle E.".into()), family_names: Some("Niemeyer".into()), ..Default::default() }));
+    reference.year = Some("2015".into());
+    let report = citation.check_doi_metadata().unwrap();
+    let issues: Vec<_> = report.iter().map(|issue| (issue.code, issue.severity, issue.path.as_str(), issue.message.as_str())).collect();
+    assert_eq!(
+        issues,
+        [
+            (Code::DoiMismatch, Severity::Warning, "references[0].title", "`Software Citation Principles (draft)` differs from the registered title `Software citation principles`"),
+            (Code::DoiMismatch, Severity::Warning, "references[0].authors", "the authors `Niemeyer` are not registered"),
+            (Code::DoiMismatch, Severity::Warning, "references[0].year", "`2015` differs from the registered year `2016`"),
+        ]
+    );
+
+    let requests = server.join().unwrap();
+    assert!(requests[0].starts_with("GET /10.7717/peerj-cs.86 HTTP/1.1\r\n"));
+    assert!(requests[0].to_lowercase().contains("accept: application/vnd.citationstyles.csl+json"));
+    assert!(requests[1].starts_with("GET /10.7717/peerj-cs.86 HTTP/1.1\r\n"));
+
+    assert!(Reference::from_doi("not a doi").is_err());
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn enrich_from_orcid() {
+    use std::io::{BufRead, BufReader, Write};
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    std::env::set_var("ORCID_API_URL", format!("http://{}/v3.0", listener.local_addr().unwrap()));
+
+    let server = std::thread::spawn(move || {
+        let mut requests = Vec::new();
+        for _ in 0..2 {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(&stream);
+            let mut head = String::new();
+            while !head.ends_with("\r\n\r\n") {
+                reader.read_line(&mut head).unwrap();
+            }
+
+            let body = r#"{
+  "person": { "name": { "given-names": { "value": "Josiah" }, "family-name": { "value": "Carberry" } } },
+  "activities-summary": { "employments": { "affiliation-group": [
+    { "summaries": [{ "employment-summary": { "end-date": { "year": { "value": "2010" } }, "organization": { "name": "Wesleyan University" } } }] },
+    { "summaries": [{ "employment-summary": { "end-date": null, "organization": { "name": "Brown University" } } }] }
+  ] } }
+}"#;
+            write!(stream, "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}", body.len()).unwrap();
+            requests.push(head);
+        }
+        requests
+    });
+
+    let mut citation: Citation = "cff-version: 1.2.0
+message: Cite me
+title: citation
+authors:
+  - orcid: https://orcid.org/0000-0002-1825-0097
+  - given-names: Josiah S.
+    family-names: Carberry
+    affiliation: Psychoceramics
+    orcid: https://orcid.org/0000-0002-1825-0097
+  - name: The Rust Community"
+        .parse()
+        .unwrap();
+    let report = citation.enrich_from_orcid().unwrap();
+
+    let Author::Person(person) = &citation.authors[0] else { panic!("expected a person") };
+    assert_eq!(person.given_names.as_deref(), Some("Josiah"));
+    assert_eq!(person.family_names.as_deref(), Some("Carberry"));
+    assert_eq!(person.affiliation.as_deref(), Some("Brown University"));
+    let Author::Person(person) = &citation.authors[1] else { panic!("expected a person") };
+    assert_eq!(person.affiliation.as_deref(), Some("Psychoceramics"));
+
+    let issues: Vec<_> = report.iter().map(|issue| (issue.code, issue.path.as_str(), issue.message.as_str())).collect();
+    assert_eq!(issues, [(Code::OrcidMismatch, "authors[1].given-names", "`Josiah S.` differs from `Josiah` registered for https://orcid.org/0000-0002-1825-0097")]);
+
+    let requests = server.join().unwrap();
+    assert!(requests[0].starts_with("GET /v3.0/0000-0002-1825-0097/record HTTP/1.1\r\n"));
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn swhid_is_archived() {
+    use std::io::{BufRead, BufReader, Write};
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    std::env::set_var("SWH_API_URL", format!("http://{}/api/1", listener.local_addr().unwrap()));
+
+    let server = std::thread::spawn(move || {
+        let mut requests = Vec::new();
+        for status in ["200 OK", "404 Not Found"] {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = String::new();
+            BufReader::new(&stream).read_line(&mut request).unwrap();
+            write!(stream, "HTTP/1.1 {status}\r\nContent-Length: 2\r\nConnection: close\r\n\r\n{{}}").unwrap();
+            requests.push(request);
+        }
+        requests
+    });
+
+    let swhid: Swhid = "swh:1:dir:d198bc9d7a6bcf6db04f476d29314f157507d505;origin=https://github.com/python/cpython".parse().unwrap();
+    assert!(swhid.is_archived().unwrap());
+    assert!(!swhid.is_archived().unwrap());
+
+    let requests = server.join().unwrap();
+    assert_eq!(requests[0], "GET /api/1/resolve/swh:1:dir:d198bc9d7a6bcf6db04f476d29314f157507d505/ HTTP/1.1\r\n");
+}
+
+#[test]
+fn bibtex() {
+    let citation = Citation::read("./tests/CITATION.cff".into()).unwrap();
+    assert_eq!(
+        citation.to_bibtex(),
+        "@software{lewin,
+  author = {Lewin, Luca},
+  title = {{QED}: mathematical proof assistant/database},
+  version = {0.0.0},
+  url = {https://github.com/lucalewin/qed},
+}
+"
+    );
+
+    let citation = Citation::read("./tests/references.cff".into()).unwrap();
+    assert!(citation.to_bibtex().starts_with("@article{lewin2024,\n  author = {Lewin, Luca},\n"));
+    assert_eq!(
+        citation.references[0].to_bibtex(),
+        "@article{smith2016,
+  author = {Smith, Arfon M. and Katz, Daniel S.},
+  title = {Software citation principles},
+  journal = {PeerJ Computer Science},
+  publisher = {PeerJ},
+  volume = {2},
+  pages = {e86},
+  year = {2016},
+  doi = {10.7717/peerj-cs.86},
+}
+"
+    );
+
+    let author = Person {
+        given_names: Some("Ludwig".into()),
+        name_particle: Some("van".into()),
+        family_names: Some("Beethoven".into()),
+        ..Default::default()
+    };
+    let citation = Citation::builder()
+        .cff_version("1.2.0")
+        .title("A Faster Fast_Fourier Transform & more")
+        .author(Author::Person(author))
+        .author(Author::Person(Person::from_name("Plato")))
+        .date_released(Date::new(2024, 3, 1).unwrap())
+        .build()
+        .unwrap();
+    let bibtex = citation.to_bibtex();
+    assert!(bibtex.contains("author = {van Beethoven, Ludwig and {Plato}},"));
+    assert!(bibtex.contains("title = {A {Faster} {Fast\\_Fourier} {Transform} \\& more},"));
+    assert!(bibtex.contains("year = {2024},\n  month = mar,"));
+}
+
+#[test]
+fn biblatex() {
+    let mut citation = Citation::read("./tests/CITATION.cff".into()).unwrap();
+    citation.date_released = Some(Date::new(2024, 3, 1).unwrap());
+    citation.doi = Some("10.5281/zenodo.1234".parse().unwrap());
+    assert_eq!(
+        citation.to_biblatex(),
+        "@software{lewin2024,
+  author = {Lewin, Luca},
+  title = {{QED}: mathematical proof assistant/database},
+  date = {2024-03-01},
+  version = {0.0.0},
+  doi = {10.5281/zenodo.1234},
+  url = {https://github.com/lucalewin/qed},
+}
+"
+    );
+
+    let citation = Citation::read("./tests/references.cff".into()).unwrap();
+    assert_eq!(
+        citation.references[0].to_biblatex(),
+        "@article{smith2016,
+  author = {Smith, Arfon M. and Katz, Daniel S.},
+  title = {Software citation principles},
+  journaltitle = {PeerJ Computer Science},
+  publisher = {PeerJ},
+  volume = {2},
+  pages = {e86},
+  date = {2016},
+  doi = {10.7717/peerj-cs.86},
+}
+"
+    );
+    assert!(citation.references[1].to_biblatex().contains("keywords = {serialization},"));
+}
+
+#[test]
+fn bibtex_import() {
+    let input = std::fs::read_to_string("./tests/references.bib").unwrap();
+
+    let references = Reference::from_bibtex(&input).unwrap();
+    assert_eq!(references.len(), 3);
+
+    let article = &references[1];
+    assert_eq!(article.r#type, ReferenceType::Article);
+    assert_eq!(article.journal.as_deref(), Some("PeerJ Computer Science"));
+    assert_eq!((article.start.as_deref(), article.end.as_deref()), (Some("e86"), Some("e117")));
+    assert_eq!((article.year.as_deref(), article.month.as_deref()), (Some("2016"), Some("9")));
+    assert_eq!(article.doi.as_ref().map(Doi::as_str), Some("10.7717/peerj-cs.86"));
+
+    let names: Vec<_> = article.authors.iter().map(Author::display_name).collect();
+    assert_eq!(names, ["Arfon M. Smith", "Daniel S. Katz", "Ludwig van Beethoven", "Kurt Gödel"]);
+    let Author::Person(beethoven) = &article.authors[2] else { panic!("expected a person") };
+    assert_eq!(beethoven.name_particle.as_deref(), Some("van"));
+
+    let thesis = &references[2];
+    assert_eq!(thesis.r#type, ReferenceType::Thesis);
+    assert_eq!(thesis.title, "On the Citation of Software & Data");
+    assert_eq!(thesis.thesis_type.as_deref(), Some("PhD thesis"));
+    assert_eq!(thesis.institution.as_ref().map(|institution| institution.name.as_str()), Some("Example University"));
+    assert_eq!(thesis.authors[0].display_name(), "John Doe Jr.");
+
+    let citation = Citation::from_bibtex(&input).unwrap();
+    assert_eq!(citation.title, "citation: manipulate CITATION.cff files");
+    assert!(matches!(&citation.authors[1], Author::Entity(entity) if entity.name == "The Rust Community"));
+    assert_eq!(citation.date_released, Some(Date::new(2024, 3, 1).unwrap()));
+    assert_eq!(citation.repository_code.as_deref(), Some("https://github.com/lucalewin/citation"));
+    assert_eq!(citation.keywords, ["citation", "research software"]);
+    assert_eq!(citation.licenses(), [License::MIT]);
+    assert!(citation.validate().is_valid());
+
+    let error = Reference::from_bibtex("@article{broken,\n  title = {unbalanced\n}").unwrap_err();
+    assert_eq!(error.to_string(), "unterminated entry `broken` at line 3");
+    assert!(Citation::from_bibtex("@misc{x, title = {x}}").is_err());
+}
+
+#[test]
+fn ris() {
+    let citation = Citation::read("./tests/CITATION.cff".into()).unwrap();
+    assert_eq!(
+        citation.to_ris(),
+        "TY  - COMP
+AU  - Lewin, Luca
+TI  - QED: mathematical proof assistant/database
+ET  - 0.0.0
+UR  - https://github.com/lucalewin/qed
+AB  - work in progress
+ER  - 
+"
+    );
+
+    let citation = Citation::read("./tests/references.cff".into()).unwrap();
+    assert!(citation.to_ris().starts_with("TY  - JOUR\nAU  - Lewin, Luca\n"));
+    assert_eq!(
+        citation.references[0].to_ris(),
+        "TY  - JOUR
+AU  - Smith, Arfon M.
+AU  - Katz, Daniel S.
+TI  - Software citation principles
+T2  - PeerJ Computer Science
+PY  - 2016
+VL  - 2
+SP  - e86
+PB  - PeerJ
+CY  - San Diego
+DO  - 10.7717/peerj-cs.86
+ER  - 
+"
+    );
+
+    let input = std::fs::read_to_string("./tests/references.ris").unwrap();
+    let references = Reference::from_ris(&input).unwrap();
+    assert_eq!(references.len(), 3);
+
+    let article = &references[1];
+    assert_eq!(article.r#type, ReferenceType::Article);
+    assert_eq!(article.title, "Software citation principles");
+    assert_eq!(article.journal.as_deref(), Some("PeerJ Computer Science"));
+    assert_eq!(article.year.as_deref(), Some("2016"));
+    assert_eq!(article.issn.as_deref(), Some("2376-5992"));
+    assert_eq!(article.doi.as_ref().map(Doi::as_str), Some("10.7717/peerj-cs.86"));
+    let names: Vec<_> = article.authors.iter().map(Author::display_name).collect();
+    assert_eq!(names, ["Arfon M. Smith", "Daniel S. Katz", "Ludwig van Beethoven"]);
+
+    let thesis = &references[2];
+    assert_eq!(thesis.r#type, ReferenceType::Thesis);
+    assert_eq!(thesis.thesis_type.as_deref(), Some("PhD thesis"));
+    assert_eq!(thesis.authors[0].display_name(), "John Doe Jr.");
+    assert_eq!(Reference::from_ris(&thesis.to_ris()).unwrap()[0].title, thesis.title);
+
+    let citation = Citation::from_ris(&input).unwrap();
+    assert_eq!(citation.title, "citation: manipulate CITATION.cff files");
+    assert!(matches!(&citation.authors[1], Author::Entity(entity) if entity.name == "The Rust Community"));
+    assert_eq!(citation.date_released, Some(Date::new(2024, 3, 1).unwrap()));
+    assert_eq!(citation.version.as_deref(), Some("0.1.0"));
+    assert_eq!(citation.repository_code.as_deref(), Some("https://github.com/lucalewin/citation"));
+    assert_eq!(citation.keywords, ["citation", "research software"]);
+    assert!(citation.validate().is_valid());
+
+    let error = Reference::from_ris("TY  - JOUR\nTI  - unterminated\n").unwrap_err();
+    assert_eq!(error.to_string(), "the record is not terminated with ER at line 1");
+    assert!(Citation::from_ris("TY  - JOUR\nTI  - x\nER  - \n").is_err());
+}
+
+#[test]
+fn endnote_xml() {
+    let citation = Citation::read("./tests/CITATION.cff".into()).unwrap();
+    assert_eq!(
+        citation.to_endnote_xml(),
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<xml>
+  <records>
+    <record>
+      <ref-type name="Computer Program">9</ref-type>
+      <contributors>
+        <authors>
+          <author>Lewin, Luca</author>
+        </authors>
+      </contributors>
+      <titles>
+        <title>QED: mathematical proof assistant/database</title>
+      </titles>
+      <edition>0.0.0</edition>
+      <abstract>work in progress</abstract>
+      <urls>
+        <related-urls>
+          <url>https://github.com/lucalewin/qed</url>
+          <url>https://lucalewin.dev/projects/QED</url>
+        </related-urls>
+      </urls>
+    </record>
+  </records>
+</xml>
+"#
+    );
+
+    let mut citation = Citation::read("./tests/references.cff".into()).unwrap();
+    let reference = &mut citation.references[0];
+    reference.title = "Software <citation> & data".to_string();
+    reference.date_published = Some(Date::new(2016, 9, 19).unwrap());
+
+    let xml = reference.to_endnote_xml();
+    assert!(xml.contains("<ref-type name=\"Journal Article\">17</ref-type>"));
+    assert!(xml.contains("<author>Smith, Arfon M.</author>\n          <author>Katz, Daniel S.</author>"));
+    assert!(xml.contains("<title>Software &lt;citation&gt; &amp; data</title>"));
+    assert!(xml.contains("<year>2016</year>\n        <pub-dates>\n          <date>2016-09-19</date>"));
+    assert!(xml.contains("<electronic-resource-num>10.7717/peerj-cs.86</electronic-resource-num>"));
+}
+
+#[test]
+fn csl_json_import() {
+    let input = std::fs::read_to_string("./tests/references.json").unwrap();
+
+    let references = Reference::from_csl_json(&input).unwrap();
+    assert_eq!(references.len(), 3);
+
+    let article = &references[1];
+    assert_eq!(article.r#type, ReferenceType::Article);
+    assert_eq!(article.journal.as_deref(), Some("PeerJ Computer Science"));
+    assert_eq!(article.volume.as_deref(), Some("2"));
+    assert_eq!((article.start.as_deref(), article.end.as_deref()), (Some("e86"), Some("e117")));
+    assert_eq!((article.year.as_deref(), article.month.as_deref()), (Some("2016"), Some("9")));
+    assert_eq!(article.doi.as_ref().map(Doi::as_str), Some("10.7717/peerj-cs.86"));
+    assert_eq!(article.publisher.as_ref().and_then(|publisher| publisher.city.as_deref()), Some("San Diego"));
+    let names: Vec<_> = article.authors.iter().map(Author::display_name).collect();
+    assert_eq!(names, ["Arfon M. Smith", "Daniel S. Katz", "Ludwig van Beethoven"]);
+
+    let thesis = &references[2];
+    assert_eq!(thesis.r#type, ReferenceType::Thesis);
+    assert_eq!(thesis.thesis_type.as_deref(), Some("PhD thesis"));
+    assert_eq!(thesis.date_published, Some(Date::new(2020, 5, 4).unwrap()));
+    assert_eq!(thesis.authors[0].display_name(), "John Doe Jr.");
+
+    let citation = Citation::from_csl_json(&input).unwrap();
+    assert_eq!(citation.title, "citation: manipulate CITATION.cff files");
+    assert!(matches!(&citation.authors[1], Author::Entity(entity) if entity.name == "The Rust Community"));
+    assert_eq!(citation.date_released, Some(Date::new(2024, 3, 1).unwrap()));
+    assert_eq!(citation.version.as_deref(), Some("0.1.0"));
+    assert_eq!(citation.repository_code.as_deref(), Some("https://github.com/lucalewin/citation"));
+    assert_eq!(citation.keywords, ["citation", "research software"]);
+    assert!(citation.validate().is_valid());
+
+    let single = Reference::from_csl_json(r#"{ "type": "webpage", "title": "Home" }"#).unwrap();
+    assert_eq!(single[0].r#type, ReferenceType::Website);
+
+    let error = Reference::from_csl_json(r#"[{ "id": "untitled", "type": "book" }]"#).unwrap_err();
+    assert_eq!(error.to_string(), "item `untitled` has no title");
+    assert!(Reference::from_csl_json("[{").unwrap_err().is_eof());
+    assert!(Citation::from_csl_json(r#"[{ "type": "book", "title": "x" }]"#).is_err());
+}
+
+#[test]
+fn csl_json_export() {
+    let input = std::fs::read_to_string("./tests/references.json").unwrap();
+    let citation = Citation::from_csl_json(&input).unwrap();
+
+    let exported = Citation::from_csl_json(&citation.to_csl_json()).unwrap();
+    assert_eq!(exported.title, citation.title);
+    assert_eq!(exported.date_released, citation.date_released);
+    assert_eq!(exported.keywords, citation.keywords);
+    assert!(citation.to_csl_json().contains(r#""id": "lewin2024""#));
+
+    let article = &Reference::from_csl_json(&input).unwrap()[1];
+    let exported = &Reference::from_csl_json(&article.to_csl_json()).unwrap()[0];
+    assert_eq!(exported.r#type, ReferenceType::Article);
+    assert_eq!(exported.journal, article.journal);
+    assert_eq!((exported.start.as_deref(), exported.end.as_deref()), (Some("e86"), Some("e117")));
+    assert_eq!(exported.doi, article.doi);
+    assert!(article.to_csl_json().contains(r#""id": "smith2016""#));
+}
+
+#[test]
+fn format_apa() {
+    let citation = Citation::read("./tests/CITATION.cff".into()).unwrap();
+    assert_eq!(
+        citation.format(Style::Apa),
+        "Lewin, L. (n.d.). QED: mathematical proof assistant/database (Version 0.0.0) [Computer software]. GitHub. https://github.com/lucalewin/qed"
+    );
+
+    let citation = Citation::read("./tests/references.cff".into()).unwrap();
+    assert_eq!(
+        citation.references[0].format(Style::Apa),
+        "Smith, A. M., & Katz, D. S. (2016). Software citation principles. PeerJ Computer Science, 2, e86. https://doi.org/10.7717/peerj-cs.86"
+    );
+
+    let input = std::fs::read_to_string("./tests/references.bib").unwrap();
+    let software = Citation::from_bibtex(&input).unwrap();
+    assert_eq!(
+        software.format(Style::Apa),
+        "Lewin, L., & The Rust Community. (2024). citation: manipulate CITATION.cff files (Version 0.1.0) [Computer software]. GitHub. https://doi.org/10.5281/zenodo.1234"
+    );
+    let references = Reference::from_bibtex(&input).unwrap();
+    assert_eq!(
+        references[2].format(Style::Apa),
+        "Doe, J., Jr. (2020). On the Citation of Software & Data [PhD thesis, Example University]."
+    );
+
+    let mut reference = Reference { title: "Many hands".into(), ..Default::default() };
+    reference.authors = (1..=25).map(|n| Author::Person(Person::from_name(&format!("Jean-Paul Author{n}")))).collect();
+    let text = reference.format(Style::Apa);
+    assert!(text.starts_with("Author1, J.-P., Author2, J.-P., "));
+    assert!(text.contains("Author19, J.-P., . . . Author25, J.-P. (n.d.). Many hands."));
+}
+
+#[test]
+fn format_ieee() {
+    let citation = Citation::read("./tests/CITATION.cff".into()).unwrap();
+    assert_eq!(
+        citation.format(Style::Ieee),
+        "L. Lewin, “QED: mathematical proof assistant/database,” Version 0.0.0, Computer software, GitHub. [Online]. Available: https://github.com/lucalewin/qed"
+    );
+
+    let citation = Citation::read("./tests/references.cff".into()).unwrap();
+    assert_eq!(
+        citation.references[0].format(Style::Ieee),
+        "A. M. Smith and D. S. Katz, “Software citation principles,” PeerJ Computer Science, vol. 2, p. e86, 2016, doi: 10.7717/peerj-cs.86."
+    );
+
+    let references = Reference::from_bibtex(&std::fs::read_to_string("./tests/references.bib").unwrap()).unwrap();
+    assert_eq!(
+        references[1].format(Style::Ieee),
+        "A. M. Smith, D. S. Katz, L. van Beethoven, and K. Gödel, “Software citation principles,” PeerJ Computer Science, vol. 2, pp. e86–e117, 2016, doi: 10.7717/peerj-cs.86."
+    );
+    assert_eq!(references[2].format(Style::Ieee), "J. Doe, Jr., “On the Citation of Software & Data,” PhD thesis, Example University, 2020.");
+
+    let mut reference = Reference { title: "Untitled?".into(), ..Default::default() };
+    assert_eq!(reference.format(Style::Ieee), "“Untitled?”");
+    reference.authors = (1..=7).map(|n| Author::Person(Person::from_name(&format!("Jane Author{n}")))).collect();
+    reference.url = Some("https://example.com".into());
+    assert_eq!(reference.format(Style::Ieee), "J. Author1 et al., “Untitled?” [Online]. Available: https://example.com");
+}
+
+#[test]
+fn format_chicago_mla() {
+    let citation = Citation::read("./tests/CITATION.cff".into()).unwrap();
+    assert_eq!(
+        citation.format(Style::Chicago),
+        "Lewin, Luca. n.d. QED: mathematical proof assistant/database. Version 0.0.0. GitHub. https://github.com/lucalewin/qed."
+    );
+    assert_eq!(
+        citation.format(Style::Mla),
+        "Lewin, Luca. QED: mathematical proof assistant/database. Version 0.0.0, GitHub, github.com/lucalewin/qed."
+    );
+
+    let input = std::fs::read_to_string("./tests/references.bib").unwrap();
+    let software = Citation::from_bibtex(&input).unwrap();
+    assert_eq!(
+        software.format(Style::Chicago),
+        "Lewin, Luca, and The Rust Community. 2024. citation: manipulate CITATION.cff files. Version 0.1.0. GitHub. https://doi.org/10.5281/zenodo.1234."
+    );
+    assert_eq!(
+        software.format(Style::Mla),
+        "Lewin, Luca, and The Rust Community. citation: manipulate CITATION.cff files. Version 0.1.0, GitHub, 2024, doi:10.5281/zenodo.1234."
+    );
+
+    let references = Reference::from_bibtex(&input).unwrap();
+    assert_eq!(
+        references[1].format(Style::Chicago),
+        "Smith, Arfon M., Daniel S. Katz, Ludwig van Beethoven, and Kurt Gödel. 2016. “Software citation principles.” PeerJ Computer Science 2: e86–e117. https://doi.org/10.7717/peerj-cs.86."
+    );
+    assert_eq!(
+        references[1].format(Style::Mla),
+        "Smith, Arfon M., et al. “Software citation principles.” PeerJ Computer Science, vol. 2, 2016, pp. e86–e117, doi:10.7717/peerj-cs.86."
+    );
+    assert_eq!(references[2].format(Style::Chicago), "Doe, John, Jr. 2020. “On the Citation of Software & Data.” PhD thesis, Example University.");
+    assert_eq!(references[2].format(Style::Mla), "Doe, John, Jr. On the Citation of Software & Data. 2020. Example University, PhD thesis.");
+
+    let mut dataset = Citation::from_bibtex(&input).unwrap();
+    dataset.r#type = Some(Type::Dataset);
+    dataset.authors = (1..=11).map(|n| Author::Person(Person::from_name(&format!("Jane Author{n}")))).collect();
+    assert!(dataset.format(Style::Chicago).starts_with("Author1, Jane, Jane Author2, Jane Author3, Jane Author4, Jane Author5, Jane Author6, Jane Author7, et al. 2024."));
+    assert!(dataset.format(Style::Chicago).contains(" Version 0.1.0. Dataset. GitHub. "));
+}
+
+#[test]
+fn format_harvard_vancouver() {
+    let citation = Citation::read("./tests/CITATION.cff".into()).unwrap();
+    assert_eq!(
+        citation.format(Style::Harvard),
+        "Lewin, L. (no date) QED: mathematical proof assistant/database (Version 0.0.0) [Computer program]. GitHub. Available at: https://github.com/lucalewin/qed"
+    );
+    assert_eq!(
+        citation.format(Style::Vancouver),
+        "Lewin L. QED: mathematical proof assistant/database [Computer software]. Version 0.0.0. GitHub; [date unknown]. Available from: https://github.com/lucalewin/qed"
+    );
+
+    let citation = Citation::read("./tests/references.cff".into()).unwrap();
+    assert_eq!(
+        citation.references[0].format(Style::Harvard),
+        "Smith, A.M. and Katz, D.S. (2016) ‘Software citation principles’, PeerJ Computer Science, 2, p. e86. Available at: https://doi.org/10.7717/peerj-cs.86"
+    );
+    assert_eq!(
+        citation.references[0].format(Style::Vancouver),
+        "Smith AM, Katz DS. Software citation principles. PeerJ Computer Science. 2016;2:e86. doi:10.7717/peerj-cs.86"
+    );
+
+    let references = Reference::from_bibtex(&std::fs::read_to_string("./tests/references.bib").unwrap()).unwrap();
+    assert!(references[1].format(Style::Harvard).starts_with("Smith, A.M. et al. (2016) ‘Software citation principles’"));
+    assert!(references[1].format(Style::Vancouver).starts_with("Smith AM, Katz DS, van Beethoven L, Gödel K. Software citation principles."));
+    assert_eq!(references[2].format(Style::Harvard), "Doe, J., Jr. (2020) On the Citation of Software & Data. PhD thesis. Example University.");
+    assert_eq!(references[2].format(Style::Vancouver), "Doe J Jr. On the Citation of Software & Data [PhD thesis]. Example University; 2020.");
+
+    let mut reference = Reference { title: "Many hands".into(), year: Some("2020".into()), ..Default::default() };
+    reference.authors = (1..=7).map(|n| Author::Person(Person::from_name(&format!("Jean-Paul Author{n}")))).collect();
+    assert_eq!(
+        reference.format(Style::Vancouver),
+        "Author1 J-P, Author2 J-P, Author3 J-P, Author4 J-P, Author5 J-P, Author6 J-P, et al. Many hands. 2020."
+    );
+}
+
+#[test]
+fn citation_formatter() {
+    struct Bracketed(Style);
+
+    impl CitationFormatter for Bracketed {
+        fn format(&self, citation: &Citation, output: OutputFormat) -> String {
+            format!("[{}]", self.0.format(citation, output))
+        }
+    }
+
+    let citation = Citation::read("./tests/CITATION.cff".into()).unwrap();
+    assert_eq!(citation.format(Bracketed(Style::Vancouver)), format!("[{}]", citation.format(Style::Vancouver)));
+
+    let styles: Vec<Box<dyn CitationFormatter>> = vec![Box::new(Style::Apa), Box::new(Bracketed(Style::Apa))];
+    let rendered: Vec<String> = styles.iter().map(|style| citation.format(style)).collect();
+    assert_eq!(rendered[1], format!("[{}]", rendered[0]));
+}
+
+#[cfg(feature = "csl")]
+#[test]
+fn csl_style() {
+    use citation::CslStyle;
+
+    let citation = Citation::read("./tests/CITATION.cff".into()).unwrap();
+    let references = Reference::from_bibtex(&std::fs::read_to_string("./tests/references.bib").unwrap()).unwrap();
+
+    let style = CslStyle::read("./tests/minimal.csl".into()).unwrap();
+    assert_eq!(style.title(), "Minimal");
+    assert_eq!(citation.format(&style), "Lewin | QED: MATHEMATICAL PROOF ASSISTANT/DATABASE | v0.0.0.");
+    assert_eq!(
+        style.format_reference(&references[1], OutputFormat::Plain),
+        "Smith, Katz, van Beethoven, & Gödel | 2016 | SOFTWARE CITATION PRINCIPLES | doi:10.7717/peerj-cs.86."
+    );
+
+    let apa = CslStyle::bundled("apa").unwrap();
+    assert_eq!(
+        apa.format_reference(&references[2], OutputFormat::Plain),
+        "Doe, J., Jr. (2020). On the Citation of Software & Data [PhD thesis]. Example University."
+    );
+    let nature = CslStyle::bundled("nature").unwrap();
+    let article = &Citation::read("./tests/references.cff".into()).unwrap().references[0];
+    assert_eq!(citation.format(&nature), "Lewin, L. QED: mathematical proof assistant/database.");
+    assert_eq!(
+        nature.format_reference(article, OutputFormat::Plain),
+        "Smith, A. M. & Katz, D. S. Software citation principles. PeerJ Computer Science 2, e86 (2016)."
+    );
+
+    assert_eq!(
+        apa.format_reference(&references[2], OutputFormat::Html),
+        "Doe, J., Jr. (2020). <i>On the Citation of Software &amp; Data</i> [PhD thesis]. Example University."
+    );
+
+    assert!(CslStyle::bundled("no-such-style").is_none());
+    let error = CslStyle::from_xml("<style/>").unwrap_err();
+    assert!(error.to_string().contains("missing field `info`"));
+}
+
+#[test]
+fn output_formats() {
+    let citation = Citation::read("./tests/CITATION.cff".into()).unwrap();
+    assert_eq!(citation.format_as(Style::Apa, OutputFormat::Plain), citation.format(Style::Apa));
+    assert_eq!(
+        citation.format_as(Style::Apa, OutputFormat::Markdown),
+        "Lewin, L. (n.d.). *QED: mathematical proof assistant/database* (Version 0.0.0) \\[Computer software\\]. GitHub. <https://github.com/lucalewin/qed>"
+    );
+
+    let references = Reference::from_bibtex(&std::fs::read_to_string("./tests/references.bib").unwrap()).unwrap();
+    assert_eq!(
+        references[1].format_as(Style::Apa, OutputFormat::Html),
+        "Smith, A. M., Katz, D. S., van Beethoven, L., &amp; Gödel, K. (2016). Software citation principles. <cite>PeerJ Computer Science</cite>, <i>2</i>, e86–e117. <a href=\"https://doi.org/10.7717/peerj-cs.86\">https://doi.org/10.7717/peerj-cs.86</a>"
+    );
+    assert_eq!(
+        references[1].format_as(Style::Ieee, OutputFormat::Latex),
+        "A. M. Smith, D. S. Katz, L. van Beethoven, and K. Gödel, “Software citation principles,” \\emph{PeerJ Computer Science}, vol. 2, pp. e86–e117, 2016, doi: \\href{https://doi.org/10.7717/peerj-cs.86}{10.7717/peerj-cs.86}."
+    );
+    assert_eq!(
+        references[2].format_as(Style::Mla, OutputFormat::Latex),
+        "Doe, John, Jr. \\emph{On the Citation of Software \\& Data}. 2020. Example University, PhD thesis."
+    );
+    assert_eq!(
+        references[1].format_as(Style::Vancouver, OutputFormat::Markdown),
+        "Smith AM, Katz DS, van Beethoven L, Gödel K. Software citation principles. PeerJ Computer Science. 2016;2:e86–e117. [doi:10.7717/peerj-cs.86](https://doi.org/10.7717/peerj-cs.86)"
+    );
+
+    let reference = Reference { title: "snake_case *names*".into(), url: Some("https://example.com/a_b".into()), ..Default::default() };
+    assert_eq!(reference.format_as(Style::Chicago, OutputFormat::Markdown), "n.d. *snake\\_case \\*names\\**. <https://example.com/a_b>.");
+
+    let reference = Reference { title: "[click](https://evil.example) <b>now</b>".into(), url: Some("https://example.com/a%20b#c{d}".into()), ..Default::default() };
+    assert_eq!(
+        reference.format_as(Style::Chicago, OutputFormat::Markdown),
+        "n.d. *\\[click\\](https://evil.example) \\<b>now\\</b>*. <https://example.com/a%20b#c{d}>."
+    );
+    assert_eq!(
+        reference.format_as(Style::Chicago, OutputFormat::Latex),
+        "n.d. \\emph{[click](https://evil.example) <b>now</b>}. \\url{https://example.com/a\\%20b\\#c%7Bd%7D}."
+    );
+    assert!(reference.format_as(Style::Mla, OutputFormat::Latex).ends_with("\\href{https://example.com/a\\%20b\\#c%7Bd%7D}{example.com/a\\%20b\\#c\\{d\\}}."));
+}
+
+#[test]
+fn json_ld() {
+    let citation = Citation::read("./tests/CITATION.cff".into()).unwrap();
+    assert_eq!(
+        citation.to_json_ld(),
+        r#"{
+  "@context": "https://schema.org",
+  "@type": "SoftwareSourceCode",
+  "author": {
+    "@id": "https://orcid.org/0009-0005-1296-096X",
+    "@type": "Person",
+    "email": "contact@lucalewin.dev",
+    "familyName": "Lewin",
+    "givenName": "Luca"
+  },
+  "codeRepository": "https://github.com/lucalewin/qed",
+  "description": "work in progress",
+  "name": "QED: mathematical proof assistant/database",
+  "url": "https://lucalewin.dev/projects/QED",
+  "version": "0.0.0"
+}"#
+    );
+
+    let input = "cff-version: 1.2.0
+message: Cite me
+title: Data
+type: dataset
+doi: 10.5281/zenodo.1003150
+license: [MIT, Apache-2.0]
+keywords: [research]
+repository-code: https://github.com/lucalewin/data
+authors:
+  - name: The Research Software Project
+  - given-names: Ludwig
+    name-particle: van
+    family-names: Beethoven
+    affiliation: Vienna
+identifiers:
+  - type: doi
+    value: 10.5281/zenodo.1003150
+  - type: swh
+    value: swh:1:dir:bc286860f423ea7ced246ba7458eef4b4541cf2d";
+    let citation: Citation = input.parse().unwrap();
+    let json: serde_json::Value = serde_json::from_str(&citation.to_json_ld()).unwrap();
+    assert_eq!(json["@type"], "Dataset");
+    assert_eq!(json["codeRepository"], serde_json::Value::Null);
+    assert_eq!(json["author"][0], serde_json::json!({ "@type": "Organization", "name": "The Research Software Project" }));
+    assert_eq!(json["author"][1]["familyName"], "van Beethoven");
+    assert_eq!(json["author"][1]["affiliation"]["name"], "Vienna");
+    assert_eq!(json["license"], serde_json::json!(["https://spdx.org/licenses/MIT", "https://spdx.org/licenses/Apache-2.0"]));
+    assert_eq!(
+        json["identifier"],
+        serde_json::json!(["https://doi.org/10.5281/zenodo.1003150", "swh:1:dir:bc286860f423ea7ced246ba7458eef4b4541cf2d"])
+    );
+    assert_eq!(json["keywords"], serde_json::json!(["research"]));
+}
+
+#[test]
+fn codemeta() {
+    let input = std::fs::read_to_string("./tests/codemeta.json").unwrap();
+
+    let citation = Citation::from_codemeta(&input).unwrap();
+    assert_eq!(citation.title, "cffconvert");
+    assert_eq!(citation.r#abstract.as_deref(), Some("Command line program to validate and convert CITATION.cff files."));
+    assert_eq!(citation.version.as_deref(), Some("2"));
+    assert_eq!(citation.date_released, Some(Date::new(2021, 9, 22).unwrap()));
+    assert_eq!(citation.repository_code.as_deref(), Some("https://github.com/citation-file-format/cffconvert"));
+    assert_eq!(citation.repository_artifact.as_deref(), Some("https://pypi.org/project/cffconvert/"));
+    assert_eq!(citation.licenses(), [License::from_id("Apache-2.0")]);
+    assert_eq!(citation.doi.as_ref().map(Doi::as_str), Some("10.5281/zenodo.1162057"));
+    assert_eq!(citation.identifiers[0].value(), "swh:1:dir:bc286860f423ea7ced246ba7458eef4b4541cf2d");
+    assert_eq!(citation.keywords, ["citation", "CFF", "conversion"]);
+    let Author::Person(person) = &citation.authors[0] else { panic!("expected a person") };
+    assert_eq!(person.given_names.as_deref(), Some("Jurriaan H."));
+    assert_eq!(person.orcid.as_deref(), Some("https://orcid.org/0000-0002-7064-4069"));
+    assert_eq!(person.affiliation.as_deref(), Some("Netherlands eScience Center"));
+    assert_eq!(citation.authors[1].display_name(), "The Research Software Directory");
+
+    let json: serde_json::Value = serde_json::from_str(&citation.to_codemeta()).unwrap();
+    assert_eq!(json["@context"], "https://w3id.org/codemeta/3.0");
+    assert_eq!(json["license"], "https://spdx.org/licenses/Apache-2.0");
+    assert_eq!(json["downloadUrl"], "https://pypi.org/project/cffconvert/");
+
+    let original = Citation::read("./tests/CITATION.cff".into()).unwrap();
+    let converted = Citation::from_codemeta(&original.to_codemeta()).unwrap();
+    assert_eq!(converted.title, original.title);
+    assert_eq!(converted.version, original.version);
+    assert_eq!(converted.repository_code, original.repository_code);
+    assert_eq!(converted.url, original.url);
+    assert_eq!(converted.authors[0].display_name(), original.authors[0].display_name());
+
+    assert!(Citation::from_codemeta(r#"{ "name": "citation" }"#).unwrap_err().to_string().contains("authors"));
+}
+
+#[test]
+fn zenodo_json() {
+    let citation = Citation::read("./tests/CITATION.cff".into()).unwrap();
+    assert_eq!(
+        citation.to_zenodo_json(),
+        r#"{
+  "access_right": "open",
+  "creators": [
+    {
+      "name": "Lewin, Luca",
+      "orcid": "0009-0005-1296-096X"
+    }
+  ],
+  "description": "work in progress",
+  "related_identifiers": [
+    {
+      "identifier": "https://github.com/lucalewin/qed",
+      "relation": "isSupplementTo",
+      "scheme": "url"
+    },
+    {
+      "identifier": "https://lucalewin.dev/projects/QED",
+      "relation": "isDocumentedBy",
+      "scheme": "url"
+    }
+  ],
+  "title": "QED: mathematical proof assistant/database",
+  "upload_type": "software",
+  "version": "0.0.0"
+}"#
+    );
+
+    let citation = Citation::read("./tests/references.cff".into()).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&citation.to_zenodo_json()).unwrap();
+    assert_eq!(json["description"], json["title"]);
+    let related = json["related_identifiers"].as_array().unwrap();
+    assert!(related.contains(&serde_json::json!({ "identifier": "10.7717/peerj-cs.86", "relation": "references", "scheme": "doi" })));
+
+    let original = Citation::read("./tests/CITATION.cff".into()).unwrap();
+    let converted = Citation::from_zenodo_json(&original.to_zenodo_json()).unwrap();
+    assert_eq!(converted.title, original.title);
+    let Author::Person(person) = &original.authors[0] else { panic!("the author is a person") };
+    assert_eq!(converted.authors, vec![Author::Person(Person { email: None, ..person.clone() })]);
+    assert_eq!((converted.r#abstract, converted.version), (original.r#abstract, original.version));
+    assert_eq!((converted.repository_code, converted.url), (original.repository_code, original.url));
+
+    let converted = Citation::from_zenodo_json(&citation.to_zenodo_json()).unwrap();
+    assert_eq!(converted.r#abstract, None);
+    assert_eq!(converted.authors[1], Author::Entity(citation::Entity { name: "The Rust Community".into(), ..Default::default() }));
+    assert_eq!(converted.license, Some(Licenses::Single(License::from_id("Apache-2.0"))));
+    assert!(converted.references.is_empty());
+    assert!(Citation::from_zenodo_json(r#"{ "title": "citation" }"#).unwrap_err().to_string().contains("authors"));
+}
+
+#[test]
+fn datacite() {
+    let mut citation = Citation::read("./tests/CITATION.cff".into()).unwrap();
+    citation.doi = Some(Doi::new("10.5281/zenodo.1234").unwrap());
+    citation.date_released = Some(Date::new(2024, 3, 1).unwrap());
+    citation.license = Some(Licenses::Single(License::from_id("MIT")));
+    assert_eq!(
+        citation.to_datacite_xml(),
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<resource xmlns="http://datacite.org/schema/kernel-4" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="http://datacite.org/schema/kernel-4 https://schema.datacite.org/meta/kernel-4/metadata.xsd">
+  <identifier identifierType="DOI">10.5281/zenodo.1234</identifier>
+  <creators>
+    <creator>
+      <creatorName nameType="Personal">Lewin, Luca</creatorName>
+      <givenName>Luca</givenName>
+      <familyName>Lewin</familyName>
+      <nameIdentifier nameIdentifierScheme="ORCID" schemeURI="https://orcid.org">https://orcid.org/0009-0005-1296-096X</nameIdentifier>
+    </creator>
+  </creators>
+  <titles>
+    <title>QED: mathematical proof assistant/database</title>
+  </titles>
+  <publisher>GitHub</publisher>
+  <publicationYear>2024</publicationYear>
+  <resourceType resourceTypeGeneral="Software">Software</resourceType>
+  <dates>
+    <date dateType="Issued">2024-03-01</date>
+  </dates>
+  <relatedIdentifiers>
+    <relatedIdentifier relatedIdentifierType="URL" relationType="IsSupplementTo">https://github.com/lucalewin/qed</relatedIdentifier>
+    <relatedIdentifier relatedIdentifierType="URL" relationType="IsDocumentedBy">https://lucalewin.dev/projects/QED</relatedIdentifier>
+  </relatedIdentifiers>
+  <version>0.0.0</version>
+  <rightsList>
+    <rights rightsURI="https://spdx.org/licenses/MIT" rightsIdentifier="MIT" rightsIdentifierScheme="SPDX">MIT</rights>
+  </rightsList>
+  <descriptions>
+    <description descriptionType="Abstract">work in progress</description>
+  </descriptions>
+</resource>
+"#
+    );
+
+    let json: serde_json::Value = serde_json::from_str(&citation.to_datacite_json()).unwrap();
+    assert_eq!(json["doi"], "10.5281/zenodo.1234");
+    assert_eq!(json["publicationYear"], 2024);
+    assert_eq!(json["types"]["resourceTypeGeneral"], "Software");
+    assert_eq!(json["creators"][0]["nameIdentifiers"][0]["nameIdentifier"], "https://orcid.org/0009-0005-1296-096X");
+    assert_eq!(json["rightsList"][0]["rightsIdentifier"], "MIT");
+
+    let citation = Citation::read("./tests/references.cff".into()).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&citation.to_datacite_json()).unwrap();
+    assert_eq!(json["publisher"], "(:unav)");
+    assert_eq!(json["creators"][1], serde_json::json!({ "name": "The Rust Community", "nameType": "Organizational" }));
+    assert_eq!(json["relatedIdentifiers"][0]["relationType"], "References");
+}
+
+#[test]
+fn dublin_core() {
+    let citation = Citation::read("./tests/CITATION.cff".into()).unwrap();
+    assert_eq!(
+        citation.to_dublin_core(),
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<oai_dc:dc xmlns:oai_dc="http://www.openarchives.org/OAI/2.0/oai_dc/" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="http://www.openarchives.org/OAI/2.0/oai_dc/ http://www.openarchives.org/OAI/2.0/oai_dc.xsd">
+  <dc:title>QED: mathematical proof assistant/database</dc:title>
+  <dc:creator>Lewin, Luca</dc:creator>
+  <dc:description>work in progress</dc:description>
+  <dc:publisher>GitHub</dc:publisher>
+  <dc:type>Software</dc:type>
+  <dc:identifier>https://lucalewin.dev/projects/QED</dc:identifier>
+  <dc:identifier>https://github.com/lucalewin/qed</dc:identifier>
+</oai_dc:dc>
+"#
+    );
+
+    let mut citation = Citation::read("./tests/references.cff".into()).unwrap();
+    citation.doi = Some(Doi::new("10.5281/zenodo.1234").unwrap());
+    citation.identifiers.push(Identifier::Doi { value: "10.5281/zenodo.1234".into(), description: None });
+    let rdf = citation.to_dublin_core_rdf();
+    assert!(rdf.contains(r#"<rdf:Description rdf:about="https://doi.org/10.5281/zenodo.1234">"#));
+    assert_eq!(rdf.matches("<dc:identifier>").count(), 1);
+    assert!(rdf.contains("<dc:creator>The Rust Community</dc:creator>"));
+    assert!(rdf.contains("<dc:relation>https://doi.org/10.7717/peerj-cs.86</dc:relation>"));
+    assert!(rdf.contains("<dc:rights>Apache-2.0</dc:rights>"));
+}
+
+#[test]
+#[cfg(feature = "cli")]
+fn cli_validate() {
+    use std::process::Command;
+
+    let output = Command::new(env!("CARGO_BIN_EXE_citation")).args(["validate", "tests/CITATION.cff"]).output().unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("tests/CITATION.cff: 0 errors, 0 warnings, 1 hint"));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_citation")).args(["validate", "tests/CITATION.cff", "tests/invalid.cff"]).output().unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stdout.contains("tests/invalid.cff:2:8: error[empty-title] title: title must not be empty\n  help: set `title` to the name of the work"));
+    assert!(stdout.contains("tests/invalid.cff: 8 errors, 0 warnings, 0 hints"));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_citation")).args(["validate", "tests/missing.cff"]).output().unwrap();
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+#[cfg(feature = "cli")]
+fn cli_convert() {
+    use std::process::Command;
+
+    let output = Command::new(env!("CARGO_BIN_EXE_citation")).args(["convert", "tests/CITATION.cff", "--to", "bibtex"]).output().unwrap();
+    assert!(output.status.success());
+    let citation = Citation::read("./tests/CITATION.cff".into()).unwrap();
+    assert_eq!(String::from_utf8_lossy(&output.stdout), citation.to_bibtex());
+
+    let path = std::env::temp_dir().join("citation-cli-convert.cff");
+    let status = Command::new(env!("CARGO_BIN_EXE_citation")).args(["convert", "tests/references.json", "--to", "cff", "-o"]).arg(&path).status().unwrap();
+    assert!(status.success());
+    let imported = Citation::read(path.clone()).unwrap();
+    assert_eq!(imported.title, "citation: manipulate CITATION.cff files");
+    std::fs::remove_file(path).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_citation")).args(["convert", "tests/CITATION.cff", "--from", "json-ld", "--to", "cff"]).output().unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("cannot import from json-ld"));
+}
+
+#[test]
+#[cfg(feature = "cli")]
+fn cli_init() {
+    use std::{io::Write, process::{Command, Stdio}};
+
+    let directory = std::env::temp_dir().join("citation-cli-init");
+    std::fs::create_dir_all(&directory).unwrap();
+    std::fs::write(directory.join("Cargo.toml"), "[package]\nname = \"demo\"\nversion = \"1.0.0\"\nauthors = [\"Jane Doe <jane@example.com>\"]\nlicense = \"MIT\"\n").unwrap();
+    let path = directory.join("CITATION.cff");
+    let _ = std::fs::remove_file(&path);
+
+    let status = Command::new(env!("CARGO_BIN_EXE_citation")).arg("init").arg(&path).arg("--yes").status().unwrap();
+    assert!(status.success());
+    let citation = Citation::read(path.clone()).unwrap();
+    assert_eq!(citation.title, "demo");
+    assert_eq!(citation.authors[0].display_name(), "Jane Doe");
+    assert_eq!(citation.version.as_deref(), Some("1.0.0"));
+    assert!(citation.validate().is_valid());
+
+    let status = Command::new(env!("CARGO_BIN_EXE_citation")).arg("init").arg(&path).arg("--yes").stderr(Stdio::null()).status().unwrap();
+    assert!(!status.success());
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_citation")).arg("init").arg(&path).arg("--force").stdin(Stdio::piped()).stderr(Stdio::null()).stdout(Stdio::null()).spawn().unwrap();
+    child.stdin.take().unwrap().write_all(b"Demo\n\n0000-0002-1825-0098\n0000-0002-1825-0097\nJohn Smith\n\n\nApache-2.0\nhttps://github.com/jane/demo\n").unwrap();
+    assert!(child.wait().unwrap().success());
+    let citation = Citation::read(path).unwrap();
+    assert_eq!(citation.title, "Demo");
+    let names: Vec<_> = citation.authors.iter().map(Author::display_name).collect();
+    assert_eq!(names, ["Jane Doe", "John Smith"]);
+    assert!(matches!(&citation.authors[0], Author::Person(person) if person.orcid.as_deref() == Some("https://orcid.org/0000-0002-1825-0097")));
+    assert_eq!(citation.licenses()[0].id(), "Apache-2.0");
+    assert_eq!(citation.repository_code.as_deref(), Some("https://github.com/jane/demo"));
+    std::fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+#[cfg(feature = "cli")]
+fn cli_fmt() {
+    use std::process::Command;
+
+    let path = std::env::temp_dir().join("citation-cli-fmt.cff");
+    std::fs::copy("./tests/edit.cff", &path).unwrap();
+
+    let status = Command::new(env!("CARGO_BIN_EXE_citation")).args(["fmt", "--check"]).arg(&path).output().unwrap().status;
+    assert_eq!(status.code(), Some(1));
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), std::fs::read_to_string("./tests/edit.cff").unwrap());
+
+    let status = Command::new(env!("CARGO_BIN_EXE_citation")).arg("fmt").arg(&path).output().unwrap().status;
+    assert!(status.success());
+    assert!(Document::read(path.clone()).unwrap().is_formatted());
+
+    let status = Command::new(env!("CARGO_BIN_EXE_citation")).args(["fmt", "--check"]).arg(&path).output().unwrap().status;
+    assert!(status.success());
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+#[cfg(feature = "cli")]
+fn cli_bump() {
+    use std::process::Command;
+
+    let directory = std::env::temp_dir().join("citation-cli-bump");
+    std::fs::create_dir_all(&directory).unwrap();
+    let path = directory.join("CITATION.cff");
+    std::fs::copy("./tests/edit.cff", &path).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_citation")).args(["bump", "0.2.0", "--date", "2024-05-01", "-f"]).arg(&path).output().unwrap();
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("bumped version 0.1.0 to 0.2.0"));
+    let source = std::fs::read_to_string(&path).unwrap();
+    assert!(source.contains("version: '0.2.0' # bumped on every release\ndate-released: 2024-05-01\n"));
+
+    std::fs::write(directory.join("Cargo.toml"), "[package]\nname = \"demo\"\nversion = \"1.0.0\"\nauthors = [\"Jane Doe\"]\n").unwrap();
+    let status = Command::new(env!("CARGO_BIN_EXE_citation")).args(["bump", "--from-cargo", "-f"]).arg(&path).output().unwrap().status;
+    assert!(status.success());
+    let citation = Citation::read(path.clone()).unwrap();
+    assert_eq!(citation.version.as_deref(), Some("1.0.0"));
+    assert_eq!(citation.date_released, Some(Date::today()));
+
+    let status = Command::new(env!("CARGO_BIN_EXE_citation")).args(["bump", "-f"]).arg(&path).output().unwrap().status;
+    assert!(!status.success());
+    std::fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+#[cfg(feature = "cli")]
+fn cli_fix() {
+    use std::process::Command;
+
+    let path = std::env::temp_dir().join("citation-cli-fix.cff");
+    std::fs::copy("./tests/broken.cff", &path).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_citation")).args(["fix", "--dry-run"]).arg(&path).output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("authors[0].family-names: renamed `familiy-names` to `family-names`"));
+    assert!(stdout.contains("\n-date-released: 2021/07/18\n") && stdout.contains("\n+date-released: 2021-07-18\n"));
+    assert!(Citation::read(path.clone()).is_err());
+
+    let status = Command::new(env!("CARGO_BIN_EXE_citation")).arg("fix").arg(&path).output().unwrap().status;
+    assert!(status.success());
+    assert!(Citation::read(path.clone()).is_ok());
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+#[cfg(feature = "cli")]
+fn cli_validate_formats() {
+    use std::process::Command;
+
+    let output = Command::new(env!("CARGO_BIN_EXE_citation")).args(["validate", "--format", "json", "tests/CITATION.cff", "tests/broken.cff"]).output().unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json[0]["file"], "tests/CITATION.cff");
+    assert_eq!(json[0]["valid"], true);
+    assert_eq!(json[1]["valid"], false);
+    assert_eq!(json[1]["line"], 9);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_citation")).args(["validate", "--format", "sarif", "tests/invalid.cff", "tests/broken.cff"]).output().unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    let sarif: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let results = sarif["runs"][0]["results"].as_array().unwrap();
+    assert_eq!(results.len(), 9);
+    assert_eq!(results[8]["ruleId"], "parse-error");
+    assert_eq!(results[8]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"], "tests/broken.cff");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_citation")).args(["validate", "--format", "github", "tests/invalid.cff", "tests/broken.cff"]).output().unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 9);
+    assert_eq!(lines[1], "::error file=tests/invalid.cff,line=2,col=8,title=empty-title::title: title must not be empty");
+    assert!(lines[8].starts_with("::error file=tests/broken.cff,line=9,col=16,title=parse-error::date-released: "));
+}
+
+#[test]
+#[cfg(feature = "cli")]
+fn cargo_citation() {
+    use std::process::{Command, Stdio};
+
+    let directory = std::env::temp_dir().join("citation-cargo-citation");
+    let _ = std::fs::remove_dir_all(&directory);
+    for member in ["a", "b", "excluded"] {
+        std::fs::create_dir_all(directory.join("crates").join(member).join("src")).unwrap();
+        std::fs::write(directory.join("crates").join(member).join("src/lib.rs"), "").unwrap();
+        std::fs::write(directory.join("crates").join(member).join("Cargo.toml"), format!("[package]\nname = \"{member}\"\nversion.workspace = true\nauthors = [\"Jane Doe\"]\n")).unwrap();
+    }
+    std::fs::create_dir_all(directory.join("tools/x/cli/src")).unwrap();
+    std::fs::write(directory.join("tools/x/cli/src/lib.rs"), "").unwrap();
+    std::fs::write(directory.join("tools/x/cli/Cargo.toml"), "[package]\nname = \"x-cli\"\nversion.workspace = true\nauthors = [\"Jane Doe\"]\n").unwrap();
+    let members = "[\"crates/*\", \"tools/*/cli\", \"crates/a\"]";
+    std::fs::write(directory.join("Cargo.toml"), format!("[workspace]\nmembers = {members}\nexclude = [\"crates/excluded\"]\n\n[workspace.package]\nversion = \"1.0.0\"\n")).unwrap();
+    let cargo = |args: &[&str]| Command::new(env!("CARGO_BIN_EXE_cargo-citation")).arg("citation").args(args).current_dir(&directory).stdout(Stdio::null()).stderr(Stdio::null()).status().unwrap().code();
+
+    let workspace = citation::CargoWorkspace::read(directory.clone()).unwrap();
+    let mut names: Vec<&str> = workspace.members.iter().map(|member| member.name.as_str()).collect();
+    names.sort_unstable();
+    assert_eq!(names, ["a", "b", "x-cli"]);
+
+    assert_eq!(cargo(&["generate"]), Some(0));
+    assert!(directory.join("crates/a/CITATION.cff").is_file());
+    assert!(directory.join("crates/b/CITATION.cff").is_file());
+    assert!(directory.join("tools/x/cli/CITATION.cff").is_file());
+    assert!(!directory.join("crates/excluded/CITATION.cff").exists());
+    assert_eq!(cargo(&["generate"]), Some(2));
+    assert_eq!(cargo(&["validate"]), Some(0));
+
+    let manifest = std::fs::read_to_string(directory.join("Cargo.toml")).unwrap();
+    std::fs::write(directory.join("Cargo.toml"), manifest.replace("1.0.0", "1.1.0")).unwrap();
+    let path = directory.join("crates/a/CITATION.cff");
+    std::fs::write(&path, format!("# keep me\n{}", std::fs::read_to_string(&path).unwrap())).unwrap();
+
+    assert_eq!(cargo(&["validate"]), Some(1));
+    assert_eq!(cargo(&["sync", "--check"]), Some(1));
+    assert_eq!(cargo(&["sync", "--package", "a"]), Some(0));
+    let content = std::fs::read_to_string(&path).unwrap();
+    assert!(content.starts_with("# keep me\n"));
+    assert_eq!(Citation::read(path).unwrap().version.as_deref(), Some("1.1.0"));
+    assert_eq!(cargo(&["sync", "--check", "--package", "a"]), Some(0));
+    assert_eq!(cargo(&["sync", "--check", "--package", "b"]), Some(1));
+    assert_eq!(cargo(&["validate", "--package", "missing"]), Some(2));
+
+    std::fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+#[cfg(feature = "cli")]
+fn cli_typo_suggestions() {
+    use std::process::Command;
+
+    let output = Command::new(env!("CARGO_BIN_EXE_citation")).args(["validate", "--strict", "tests/unknown.cff"]).output().unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("unknown key `familiy-names`, did you mean `family-names`?"), "{stdout}");
+
+    let compatibility = Document::read("./tests/unknown.cff".into()).unwrap().compatibility();
+    let messages: Vec<&str> = compatibility.issues.iter().map(|issue| issue.message.as_str()).collect();
+    assert_eq!(messages, ["`familiy-names` is not defined by cff-version 1.2.0 and is kept as is, did you mean `family-names`?", "`x-funding` is not defined by cff-version 1.2.0 and is kept as is"]);
+}
+
+#[test]
+#[cfg(feature = "cli")]
+fn cli_lint_config() {
+    use std::process::Command;
+
+    let config = std::env::temp_dir().join(format!("citation-lint-{}.yml", std::process::id()));
+    std::fs::write(&config, "rules:\n  invalid-cff-version: off\n  CFF004: off\n  CFF005: off\n  CFF009: off\n  CFF010: warning\n  invalid-orcid: hint\n  invalid-date: hint\n").unwrap();
+
+    let run = |config: &std::path::Path| Command::new(env!("CARGO_BIN_EXE_citation")).args(["validate", "--config"]).arg(config).arg("tests/invalid.cff").output().unwrap();
+
+    let output = run(&config);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(output.status.code(), Some(1), "{stdout}");
+    assert!(stdout.contains("warning[invalid-identifier]"), "{stdout}");
+    assert!(!stdout.contains("empty-title"), "{stdout}");
+
+    std::fs::write(&config, "rules:\n  CFF404: off\n").unwrap();
+    let output = run(&config);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8(output.stderr).unwrap().contains("rules.CFF404"));
+
+    std::fs::remove_file(config).unwrap();
+}
+
+#[test]
+#[cfg(feature = "cli")]
+fn cli_readme() {
+    use std::process::Command;
+
+    let readme = std::env::temp_dir().join(format!("citation-readme-{}.md", std::process::id()));
+    std::fs::write(&readme, "# QED\n").unwrap();
+    let run = |check: bool| {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_citation"));
+        command.arg("readme").arg(&readme).args(["--citation", "tests/CITATION.cff"]);
+        if check {
+            command.arg("--check");
+        }
+        command.output().unwrap().status.code()
+    };
+
+    assert_eq!(run(true), Some(1));
+    assert_eq!(std::fs::read_to_string(&readme).unwrap(), "# QED\n");
+    assert_eq!(run(false), Some(0));
+    assert!(std::fs::read_to_string(&readme).unwrap().contains("## How to cite"));
+    assert_eq!(run(true), Some(0));
+
+    std::fs::remove_file(readme).unwrap();
+}
+
+#[test]
+fn issue_spans() {
+    let source = std::fs::read_to_string("./tests/invalid.cff").unwrap();
+    let citation = Citation::read("./tests/invalid.cff".into()).unwrap();
+    let report = citation.validate().with_source("invalid.cff", source.as_str());
+
+    let located = |path: &str| {
+        let span = report.iter().find(|issue| issue.path == path).unwrap().span.unwrap();
+        (span.line, span.column, &source[span.offset..span.offset + span.length])
+    };
+
+    assert_eq!(located("cff-version"), (1, 14, "'1.2'"));
+    assert_eq!(located("authors"), (4, 10, "[]"));
+    assert_eq!(located("contact[0].orcid"), (8, 12, "0009-0005-1296-096X"));
+    assert_eq!(located("identifiers[0].value"), (11, 12, "zenodo.1003150"));
+    assert_eq!(located("references[0].authors[0].website"), (18, 18, "force11.org"));
+    assert_eq!(located("references[0].month"), (19, 12, "13"));
+    assert_eq!(located("title"), (2, 8, "''"));
+}
+
+#[test]
+fn report_formats() {
+    let source = std::fs::read_to_string("./tests/invalid.cff").unwrap();
+    let citation = Citation::read("./tests/invalid.cff".into()).unwrap();
+    let report = citation.validate().with_source("invalid.cff", source.as_str());
+
+    let json: serde_json::Value = serde_json::from_str(&report.to_json()).unwrap();
+    assert_eq!(json["file"], "invalid.cff");
+    assert_eq!(json["valid"], false);
+    assert_eq!(json["errors"], 8);
+    assert_eq!(json["issues"][1], serde_json::json!({ "code": "empty-title", "severity": "error", "path": "title", "message": "title must not be empty", "help": "set `title` to the name of the work", "line": 2, "column": 8 }));
+
+    let sarif: serde_json::Value = serde_json::from_str(&report.to_sarif()).unwrap();
+    assert_eq!(sarif["version"], "2.1.0");
+    let run = &sarif["runs"][0];
+    assert_eq!(run["tool"]["driver"]["name"], "citation");
+    assert_eq!(run["tool"]["driver"]["rules"][1]["id"], "empty-title");
+    assert_eq!(run["results"].as_array().unwrap().len(), 8);
+    assert_eq!(run["results"][1]["ruleId"], "empty-title");
+    assert_eq!(run["results"][1]["ruleIndex"], 1);
+    assert_eq!(run["results"][1]["level"], "error");
+    assert_eq!(run["results"][1]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"], "invalid.cff");
+    assert_eq!(run["results"][1]["locations"][0]["physicalLocation"]["region"]["startLine"], 2);
+
+    let valid = Citation::read("./tests/CITATION.cff".into()).unwrap().validate();
+    let sarif: serde_json::Value = serde_json::from_str(&ValidationReport::to_sarif_combined([&report, &valid])).unwrap();
+    assert_eq!(sarif["runs"][0]["results"][8]["level"], "note");
+    assert_eq!(sarif["runs"][0]["results"][8]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"], "CITATION.cff");
+
+    let annotations = report.to_github_annotations();
+    let lines: Vec<&str> = annotations.lines().collect();
+    assert_eq!(lines.len(), 8);
+    assert_eq!(lines[1], "::error file=invalid.cff,line=2,col=8,title=empty-title::title: title must not be empty");
+    assert_eq!(valid.to_github_annotations(), "::notice file=CITATION.cff,title=missing-doi::doi: consider adding a DOI, so the work can be cited persistently\n");
+
+    let escaped = citation.validate().with_source("a:b,c.cff", source.as_str()).to_github_annotations();
+    assert!(escaped.starts_with("::error file=a%3Ab%2Cc.cff,line=1,col=14,"));
+}
+
+#[cfg(feature = "diagnostics")]
+#[test]
+fn diagnostics() {
+    use miette::{Diagnostic, NarratableReportHandler};
+
+    let error = Citation::read("./tests/malformed.cff".into()).unwrap_err();
+    let Error::Yaml(error) = error else { panic!("{error}") };
+
+    let label = error.labels().unwrap().next().unwrap();
+    assert_eq!(label.offset(), 300);
+    assert_eq!(label.len(), 10);
+
+    let mut rendered = String::new();
+    NarratableReportHandler::new().render_report(&mut rendered, &error).unwrap();
+    assert!(rendered.contains("./tests/malformed.cff"));
+    assert!(rendered.contains("2016-02-30"));
+
+    let citation = Citation::read("./tests/invalid.cff".into()).unwrap();
+    let source = std::fs::read_to_string("./tests/invalid.cff").unwrap();
+    let report = citation.validate().with_source("invalid.cff", source);
+
+    assert_eq!(report.related().unwrap().count(), report.len());
+    assert_eq!(report.severity(), Some(miette::Severity::Error));
+
+    let mut rendered = String::new();
+    NarratableReportHandler::new().render_report(&mut rendered, &report).unwrap();
+    assert!(rendered.contains("invalid-orcid"));
+}